@@ -0,0 +1,47 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use criterion::{BenchmarkId, Criterion, Throughput};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Server -> all clients broadcast.
+fn broadcast_fan_out(c: &mut Criterion)
+{
+    let mut group = c.benchmark_group("broadcast_fan_out");
+
+    for num_clients in [10usize, 100usize]
+    {
+        let (server, mut clients) = connected_clients(num_clients);
+
+        group.throughput(Throughput::Elements(num_clients as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(num_clients), &num_clients, |b, _|
+            {
+                b.iter(||
+                    {
+                        for client in clients.iter()
+                        {
+                            server.send(client.id(), BenchServerMsg(0)).unwrap();
+                        }
+                        for client in clients.iter_mut()
+                        {
+                            wait_until(|| matches!(client.next(), Some(BenchClientEvent::Msg(_))));
+                        }
+                    });
+            });
+    }
+
+    group.finish();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+criterion::criterion_group!(benches, broadcast_fan_out);
+
+//-------------------------------------------------------------------------------------------------------------------
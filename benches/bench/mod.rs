@@ -0,0 +1,143 @@
+//module tree
+mod fan_out;
+mod reconnect_storm;
+mod round_trip;
+mod throughput;
+
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::time::{Duration, Instant};
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BenchServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BenchClientMsg(pub u64);
+
+/// request from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BenchClientRequest(pub u64);
+
+/// response from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BenchServerResponse(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct BenchChannel;
+impl bevy_simplenet::ChannelPack for BenchChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = BenchClientMsg;
+    type ClientRequest = BenchClientRequest;
+    type ServerMsg = BenchServerMsg;
+    type ServerResponse = BenchServerResponse;
+    type RejectReason = ();
+}
+
+pub type BenchServer = bevy_simplenet::Server<BenchChannel>;
+pub type BenchClient = bevy_simplenet::Client<BenchChannel>;
+pub type BenchClientEvent = bevy_simplenet::ClientEventFrom<BenchChannel>;
+pub type BenchServerEvent = bevy_simplenet::ServerEventFrom<BenchChannel>;
+pub type BenchServerReport = bevy_simplenet::ServerReport<<BenchChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+/// Max time to wait for expected events before panicking.
+const WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a server listening on an ephemeral localhost port.
+pub fn bench_server() -> BenchServer
+{
+    bevy_simplenet::ServerFactory::<BenchChannel>::new("bench").new_server(
+            enfync::builtin::native::TokioHandle::default(),
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                rate_limit_config: bevy_simplenet::RateLimitConfig{
+                    period    : Duration::from_millis(1),
+                    max_count : u32::MAX,
+                },
+                ..Default::default()
+            }
+        )
+}
+
+/// Makes a client connected to the server.
+///
+/// Does not wait for the connection to be established.
+pub fn bench_client(server: &BenchServer, client_id: u128) -> BenchClient
+{
+    bevy_simplenet::ClientFactory::<BenchChannel>::new("bench").new_client(
+            enfync::builtin::Handle::default(),
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id },
+            bevy_simplenet::ClientConfig{
+                reconnect_on_disconnect   : false,
+                reconnect_on_server_close : false,
+                ..Default::default()
+            },
+            ()
+        )
+}
+
+/// Spins until the condition is true.
+///
+/// Panics if the condition is not met within the wait timeout.
+pub fn wait_until(mut condition: impl FnMut() -> bool)
+{
+    let start = Instant::now();
+    while !condition()
+    {
+        if start.elapsed() > WAIT_TIMEOUT { panic!("timed out waiting for benchmark condition"); }
+        std::hint::spin_loop();
+    }
+}
+
+/// Makes a server with connected clients.
+///
+/// Blocks until all connection reports have been consumed.
+pub fn connected_clients(num_clients: usize) -> (BenchServer, Vec<BenchClient>)
+{
+    let mut server = bench_server();
+    let mut clients: Vec<BenchClient> = (0..num_clients)
+        .map(|client_id| bench_client(&server, client_id as u128))
+        .collect();
+
+    let mut connected = 0;
+    wait_until(||
+        {
+            while let Some((_, BenchServerEvent::Report(BenchServerReport::Connected(..)))) = server.next()
+            { connected += 1; }
+            connected == num_clients
+        });
+    for client in clients.iter_mut()
+    {
+        wait_until(||
+            matches!(client.next(), Some(BenchClientEvent::Report(bevy_simplenet::ClientReport::Connected)))
+        );
+    }
+
+    (server, clients)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+criterion::criterion_main!(
+    throughput::benches,
+    fan_out::benches,
+    round_trip::benches,
+    reconnect_storm::benches,
+);
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,62 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use criterion::{BenchmarkId, Criterion, Throughput};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Many clients connecting at once (e.g. after a server restart or network blip).
+fn reconnect_storm(c: &mut Criterion)
+{
+    let mut group = c.benchmark_group("reconnect_storm");
+    group.sample_size(10);
+    let mut server = bench_server();
+
+    for num_clients in [10usize, 100usize]
+    {
+        group.throughput(Throughput::Elements(num_clients as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(num_clients), &num_clients, |b, &num_clients|
+            {
+                b.iter(||
+                    {
+                        // connect all clients at once
+                        let clients: Vec<BenchClient> = (0..num_clients)
+                            .map(|client_id| bench_client(&server, client_id as u128))
+                            .collect();
+
+                        let mut connected = 0;
+                        wait_until(||
+                            {
+                                while let Some((_, event)) = server.next()
+                                {
+                                    if let BenchServerEvent::Report(BenchServerReport::Connected(..)) = event
+                                    { connected += 1; }
+                                }
+                                connected == num_clients
+                            });
+
+                        // disconnect all clients so their ids can be reused
+                        std::mem::drop(clients);
+                        wait_until(||
+                            {
+                                while let Some(_) = server.next() {}
+                                server.num_connections() == 0
+                            });
+                    });
+            });
+    }
+
+    group.finish();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+criterion::criterion_group!(benches, reconnect_storm);
+
+//-------------------------------------------------------------------------------------------------------------------
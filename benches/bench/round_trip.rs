@@ -0,0 +1,45 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use criterion::Criterion;
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Client request -> server response latency.
+fn request_round_trip(c: &mut Criterion)
+{
+    let (mut server, mut clients) = connected_clients(1);
+    let client = &mut clients[0];
+
+    c.bench_function("request_round_trip", |b|
+        {
+            b.iter(||
+                {
+                    let signal = client.request(BenchClientRequest(0));
+
+                    wait_until(||
+                        {
+                            let Some((_, BenchServerEvent::Request(token, BenchClientRequest(val)))) = server.next()
+                            else { return false; };
+                            server.respond(token, BenchServerResponse(val)).unwrap();
+                            true
+                        });
+                    wait_until(||
+                        {
+                            matches!(client.next(), Some(BenchClientEvent::Response(_, id)) if id == signal.id())
+                        });
+                });
+        });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+criterion::criterion_group!(benches, request_round_trip);
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,47 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use criterion::{BenchmarkId, Criterion, Throughput};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Client -> server message throughput.
+fn client_to_server_throughput(c: &mut Criterion)
+{
+    let mut group = c.benchmark_group("client_to_server_throughput");
+    let (mut server, clients) = connected_clients(1);
+    let client = &clients[0];
+
+    for num_msgs in [100u64, 1_000u64]
+    {
+        group.throughput(Throughput::Elements(num_msgs));
+        group.bench_with_input(BenchmarkId::from_parameter(num_msgs), &num_msgs, |b, &num_msgs|
+            {
+                b.iter(||
+                    {
+                        for i in 0..num_msgs { let _ = client.send(BenchClientMsg(i)); }
+
+                        let mut received = 0;
+                        wait_until(||
+                            {
+                                while let Some((_, BenchServerEvent::Msg(_))) = server.next() { received += 1; }
+                                received == num_msgs
+                            });
+                    });
+            });
+    }
+
+    group.finish();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+criterion::criterion_group!(benches, client_to_server_throughput);
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -216,11 +216,14 @@ fn handle_client_events(
         {
             DemoClientEvent::Report(connection_report) => match connection_report
             {
-                bevy_simplenet::ClientReport::Connected         => next_status = ConnectionStatus::Connected,
-                bevy_simplenet::ClientReport::Disconnected      |
-                bevy_simplenet::ClientReport::ClosedByServer(_) |
-                bevy_simplenet::ClientReport::ClosedBySelf      => next_status = ConnectionStatus::Connecting,
-                bevy_simplenet::ClientReport::IsDead(aborted_reqs) =>
+                bevy_simplenet::ClientReport::Connected           => next_status = ConnectionStatus::Connected,
+                bevy_simplenet::ClientReport::InstanceChanged{..} |
+                bevy_simplenet::ClientReport::IdChanged{..}       => (),
+                bevy_simplenet::ClientReport::Connecting{..}      |
+                bevy_simplenet::ClientReport::Disconnected        |
+                bevy_simplenet::ClientReport::ClosedByServer(_)   |
+                bevy_simplenet::ClientReport::ClosedBySelf        => next_status = ConnectionStatus::Connecting,
+                bevy_simplenet::ClientReport::IsDead(_, aborted_reqs) =>
                 {
                     for aborted_req in aborted_reqs
                     {
@@ -250,7 +253,8 @@ fn handle_client_events(
                 owner.predicted_id = None;
                 pending_select.get_mut(&mut c).0 = None;
             }
-            DemoClientEvent::Reject(request_id) =>
+            DemoClientEvent::Reject(request_id) |
+            DemoClientEvent::RejectWith((), request_id) =>
             {
                 if !pending_select.equals_request(request_id) { continue; }
 
@@ -266,6 +270,12 @@ fn handle_client_events(
                 // an error occurred, roll back the predicted input
                 c.react().broadcast(DeselectButton);
             }
+            DemoClientEvent::AckWith(..)      |
+            DemoClientEvent::ResponsePart(..) |
+            DemoClientEvent::Text(_)          |
+            DemoClientEvent::TopicMsg(..)     |
+            DemoClientEvent::Unauthorized(..) |
+            DemoClientEvent::Control(_)       => continue,
         }
     }
 
@@ -296,6 +306,7 @@ fn main()
             bevy_simplenet::ClientConfig{
                 reconnect_on_disconnect   : true,
                 reconnect_on_server_close : true,
+                disambiguate_id           : true,  //timestamp ids may collide
                 ..Default::default()
             },
             ()
@@ -37,6 +37,7 @@ impl bevy_simplenet::ChannelPack for DemoChannel
     type ClientRequest = DemoClientRequest;
     type ServerMsg = DemoServerMsg;
     type ServerResponse = ();
+    type RejectReason = ();
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -42,7 +42,7 @@ fn send_new_button_state(
 ){
     for client_id in clients.0.iter()
     {
-        server.send(*client_id, DemoServerMsg::Current(state.0));
+        let _ = server.send(*client_id, DemoServerMsg::Current(state.0));
     }
 }
 
@@ -63,7 +63,8 @@ fn handle_server_events(
         {
             DemoServerEvent::Report(connection_report) => match connection_report
             {
-                bevy_simplenet::ServerReport::Connected(_, _) =>
+                bevy_simplenet::ServerReport::Connected(_, _) |
+                bevy_simplenet::ServerReport::Reconnected(_, _) =>
                 {
                     tracing::info!("client {:?} connected", client_id);
 
@@ -73,9 +74,10 @@ fn handle_server_events(
                     // send current server state to client
                     // - we must use new_button_state to ensure the order of events is preserved
                     let current_state = new_button_state;
-                    server.send(client_id, DemoServerMsg::Current(current_state));
+                    let _ = server.send(client_id, DemoServerMsg::Current(current_state));
                 }
-                bevy_simplenet::ServerReport::Disconnected =>
+                bevy_simplenet::ServerReport::Disconnected |
+                bevy_simplenet::ServerReport::DisconnectedWithReason(_) =>
                 {
                     tracing::info!("client {:?} disconnected", client_id);
 
@@ -85,8 +87,25 @@ fn handle_server_events(
                     // clear the state if disconnected client held the button
                     if state.0 == Some(client_id) { new_button_state = None; }
                 }
+                bevy_simplenet::ServerReport::FlappingClient(_, reconnects) =>
+                {
+                    tracing::warn!("client {:?} reconnected {} times recently", client_id, reconnects);
+                }
+                bevy_simplenet::ServerReport::IdCollision =>
+                {
+                    tracing::warn!("another client tried to connect with id {:?}", client_id);
+                }
+            }
+            DemoServerEvent::Msg(())               |
+            DemoServerEvent::Text(_)               |
+            DemoServerEvent::MailboxExpired(_)     |
+            DemoServerEvent::Control(_)            |
+            DemoServerEvent::RateLimitViolation(_) => continue,
+            DemoServerEvent::RequestBatch(token, _) =>
+            {
+                // the demo client doesn't send request batches
+                server.reject(token);
             }
-            DemoServerEvent::Msg(()) => continue,
             DemoServerEvent::Request(token, request) => match request
             {
                 DemoClientRequest::Select =>
@@ -94,7 +113,7 @@ fn handle_server_events(
                     tracing::info!("received {:?} from client {:?}", request, client_id);
 
                     // acknowldge selection
-                    server.ack(token);
+                    let _ = server.ack(token);
 
                     // update button
                     new_button_state = Some(client_id);
@@ -70,16 +70,16 @@ fn authenticate_secret(expected_secret: &[u8; SECRET_AUTH_BYTES], request: &Auth
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
-fn authenticate_token(pubkey: &[u8; AUTH_PUBKEY_BYTES], request: &AuthRequest) -> bool
+fn authenticate_token(pubkey: &[u8; AUTH_PUBKEY_BYTES], request: &AuthRequest, now: Duration) -> bool
 {
     let AuthRequest::Token{token} = request else { return false; };
     let AuthToken{ protocol_version, expiry, client_id, signature } = token;
 
     // Check token expiration.
-    if token.is_expired() {
+    if token.is_expired_at(now) {
         tracing::debug!("failed verifying auth request {request:?}, token is expired \
             (current time: {:?}, expiration time: {:?}",
-            format_date(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()),
+            format_date(now),
             format_date(token.expiration_time()),
         );
     }
@@ -163,6 +163,12 @@ impl Authenticator
 {
     /// Authenticates an auth request.
     pub fn authenticate(&self, request: &AuthRequest) -> bool
+    {
+        self.authenticate_at(request, SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default())
+    }
+
+    /// Authenticates an auth request at a given duration since `UNIX_EPOCH`.
+    pub fn authenticate_at(&self, request: &AuthRequest, now: Duration) -> bool
     {
         match self
         {
@@ -176,7 +182,7 @@ impl Authenticator
             }
             Authenticator::Token{pubkey} =>
             {
-                authenticate_token(pubkey, request)
+                authenticate_token(pubkey, request, now)
             }
         }
     }
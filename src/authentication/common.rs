@@ -47,18 +47,25 @@ impl AuthToken
     /// Checks if the token has expired.
     pub fn is_expired(&self) -> bool
     {
-        self.time_until_expiry() == Duration::default()
+        self.is_expired_at(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default())
+    }
+
+    /// Checks if the token has expired at a given duration since `UNIX_EPOCH`.
+    pub fn is_expired_at(&self, now: Duration) -> bool
+    {
+        self.time_until_expiry_at(now) == Duration::default()
     }
 
     /// Gets the time remaining before the token expires.
     pub fn time_until_expiry(&self) -> Duration
     {
-        self.expiration_time()
-            .saturating_sub(
-                SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or_default()
-            )
+        self.time_until_expiry_at(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default())
+    }
+
+    /// Gets the time remaining before the token expires, measured from a given duration since `UNIX_EPOCH`.
+    pub fn time_until_expiry_at(&self, now: Duration) -> Duration
+    {
+        self.expiration_time().saturating_sub(now)
     }
 
     /// Gets the duration after `UNIX_EPOCH` when the token will expire.
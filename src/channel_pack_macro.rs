@@ -0,0 +1,149 @@
+//local shortcuts
+
+//third-party shortcuts
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Defines a [`ChannelPack`](crate::ChannelPack) with type aliases and factory helpers.
+///
+/// For a prefix `Demo`, this generates:
+/// - `DemoChannel`: a unit struct that implements [`ChannelPack`](crate::ChannelPack).
+/// - `DemoClient`, `DemoClientEvent` (requires the `client` feature).
+/// - `DemoServer`, `DemoServerEvent`, `DemoServerReport` (requires the `server` feature).
+/// - `demo_client_factory()` and `demo_server_factory()`, which make factories with the specified protocol version.
+///
+/// ```ignore
+/// bevy_simplenet::channel_pack!{
+///     /// Channel for the demo app.
+///     pub Demo
+///     {
+///         protocol_version = env!("CARGO_PKG_VERSION");
+///         type ConnectMsg     = ();
+///         type ClientMsg      = DemoClientMsg;
+///         type ClientRequest  = DemoClientRequest;
+///         type ServerMsg      = DemoServerMsg;
+///         type ServerResponse = DemoServerResponse;
+///         type RejectReason   = DemoRejectReason;  //optional, defaults to ()
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! channel_pack
+{
+    (
+        $(#[$meta:meta])*
+        $vis:vis $prefix:ident
+        {
+            protocol_version = $version:expr;
+            type ConnectMsg = $connect_msg:ty;
+            type ClientMsg = $client_msg:ty;
+            type ClientRequest = $client_request:ty;
+            type ServerMsg = $server_msg:ty;
+            type ServerResponse = $server_response:ty;
+            $(type RejectReason = $reject_reason:ty;)?
+        }
+    ) =>
+    {
+        $crate::paste::paste!{
+            $(#[$meta])*
+            #[derive(Debug, Clone)]
+            $vis struct [<$prefix Channel>];
+
+            impl $crate::ChannelPack for [<$prefix Channel>]
+            {
+                type ConnectMsg = $connect_msg;
+                type ClientMsg = $client_msg;
+                type ClientRequest = $client_request;
+                type ServerMsg = $server_msg;
+                type ServerResponse = $server_response;
+                type RejectReason = $crate::__channel_pack_reject_reason!($($reject_reason)?);
+            }
+        }
+
+        $crate::__channel_pack_client!{ $vis $prefix, $version }
+        $crate::__channel_pack_server!{ $vis $prefix, $version }
+    };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __channel_pack_reject_reason
+{
+    () => { () };
+    ($reject_reason:ty) => { $reject_reason };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "client")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __channel_pack_client
+{
+    ($vis:vis $prefix:ident, $version:expr) =>
+    {
+        $crate::paste::paste!{
+            #[allow(dead_code)]
+            $vis type [<$prefix Client>] = $crate::Client<[<$prefix Channel>]>;
+            #[allow(dead_code)]
+            $vis type [<$prefix ClientEvent>] = $crate::ClientEventFrom<[<$prefix Channel>]>;
+
+            #[allow(dead_code)]
+            $vis fn [<$prefix:snake _client_factory>]() -> $crate::ClientFactory<[<$prefix Channel>]>
+            {
+                $crate::ClientFactory::<[<$prefix Channel>]>::new($version)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "client"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __channel_pack_client
+{
+    ($vis:vis $prefix:ident, $version:expr) => {};
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "server")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __channel_pack_server
+{
+    ($vis:vis $prefix:ident, $version:expr) =>
+    {
+        $crate::paste::paste!{
+            #[allow(dead_code)]
+            $vis type [<$prefix Server>] = $crate::Server<[<$prefix Channel>]>;
+            #[allow(dead_code)]
+            $vis type [<$prefix ServerEvent>] = $crate::ServerEventFrom<[<$prefix Channel>]>;
+            #[allow(dead_code)]
+            $vis type [<$prefix ServerReport>] = $crate::ServerReport<
+                <[<$prefix Channel>] as $crate::ChannelPack>::ConnectMsg
+            >;
+
+            #[allow(dead_code)]
+            $vis fn [<$prefix:snake _server_factory>]() -> $crate::ServerFactory<[<$prefix Channel>]>
+            {
+                $crate::ServerFactory::<[<$prefix Channel>]>::new($version)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "server"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __channel_pack_server
+{
+    ($vis:vis $prefix:ident, $version:expr) => {};
+}
+
+//-------------------------------------------------------------------------------------------------------------------
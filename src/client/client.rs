@@ -2,13 +2,12 @@
 use crate::*;
 
 //third-party shortcuts
-use bincode::Options;
 
 //standard shortcuts
 use core::fmt::Debug;
 use std::marker::PhantomData;
 use std::sync::{Arc, Mutex};
-use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU16, AtomicU64, Ordering};
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -27,11 +26,18 @@ use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
 pub struct Client<Channel: ChannelPack>
 {
     /// this client's id
-    client_id: u128,
+    /// - may change if [`ClientConfig::disambiguate_id`] is enabled
+    client_id: Arc<Mutex<u128>>,
+    /// protocol version baked into this client
+    protocol_version: &'static str,
     /// core websockets client
     client: ezsockets::Client<ClientHandler<Channel>>,
+    /// encodes messages sent to the server
+    codec: WireCodec,
+    /// config: allow text messages
+    text_messages: bool,
     /// sender for client events
-    client_event_sender: crossbeam::channel::Sender<ClientEventFrom<Channel>>,
+    client_event_sender: ClientEventSender<Channel>,
     /// receiver for client events
     client_event_receiver: crossbeam::channel::Receiver<ClientEventFrom<Channel>>,
     /// synchronized tracker for pending requests
@@ -42,6 +48,14 @@ pub struct Client<Channel: ChannelPack>
     client_closed_signal: Arc<AtomicBool>,
     /// flag indicating the client closed itself
     closed_by_self: Arc<AtomicBool>,
+    /// flag indicating the client aborted connecting
+    connect_aborted: Arc<AtomicBool>,
+    /// connect message sent when connecting to the server
+    connect_msg: Arc<Mutex<Channel::ConnectMsg>>,
+    /// totals accumulated by the client backend
+    stats: ClientStatsCounters,
+    /// mirrors the server's rate limit
+    rate_limit_guard: RateLimitGuard,
 }
 
 impl<Channel: ChannelPack> Client<Channel>
@@ -69,22 +83,31 @@ impl<Channel: ChannelPack> Client<Channel>
             return MessageSignal::new(MessageStatus::Failed);
         }
 
+        // check the server's rate limit
+        if !self.rate_limit_guard.try_count()
+        {
+            tracing::warn!("tried to send message but it would exceed the server's rate limit");
+            return MessageSignal::new(MessageStatus::Failed);
+        }
+
         // forward message to server
-        let Ok(ser_msg) = bincode::DefaultOptions::new().serialize(&ServerMetaEventFrom::<Channel>::Msg(msg))
+        let frame = ServerMetaEventFrom::<Channel>::Msg(msg);
+        let Ok(result) = self.codec.send(&frame, |ser_msg| (ser_msg.len(), self.client.binary(ser_msg)))
         else
         {
             tracing::error!("failed serializing client message");
             return MessageSignal::new(MessageStatus::Failed);
         };
 
-        match self.client.binary(ser_msg)
+        match result
         {
-            Ok(signal) =>
+            (len, Ok(signal)) =>
             {
                 tracing::trace!("sending message to server");
+                self.stats.record_sent(len);
                 signal
             }
-            Err(_) =>
+            (_, Err(_)) =>
             {
                 tracing::warn!("tried to send message to dead client");
                 MessageSignal::new(MessageStatus::Failed)
@@ -92,6 +115,162 @@ impl<Channel: ChannelPack> Client<Channel>
         }
     }
 
+    /// Sends a one-shot message to the server in a latest-only slot.
+    ///
+    /// If the slot's previous message hasn't been flushed to the socket yet, then this message will wait until it is
+    /// flushed. Waiting messages are replaced by newer messages sent to the same slot, and the replaced messages
+    /// become [`LatestMsgStatus::Superseded`]. This is useful for rapid-fire messages like cursor positions, where
+    /// only the newest message is worth sending.
+    ///
+    /// Latest-only messages are not ordered with messages sent by other methods. Waiting messages will fail if the
+    /// underlying client becomes disconnected.
+    pub fn send_latest(&self, slot: u64, msg: Channel::ClientMsg) -> LatestMsgSignal
+    {
+        // lock pending requests
+        let Ok(_pending_requests) = self.pending_requests.lock()
+        else
+        {
+            tracing::error!("the client experienced a critical internal error");
+            return LatestMsgSignal::failed();
+        };
+
+        // check if connected
+        // - We do this after locking the pending requests cache in order to synchronize with dropping the internal
+        //   client handler, and to synchronize with disconnect events in the client backend.
+        if !self.is_connected()
+        {
+            tracing::warn!("tried to send latest-only message to disconnected client");
+            return LatestMsgSignal::failed();
+        }
+
+        // forward message to the client backend
+        let Ok(ser_msg) = self.codec.serialize(&ServerMetaEventFrom::<Channel>::Msg(msg))
+        else
+        {
+            tracing::error!("failed serializing client message");
+            return LatestMsgSignal::failed();
+        };
+
+        let signal = LatestMsgSignal::new();
+        if let Err(_) = self.client.call(ClientHandlerCall::SendLatest(slot, ser_msg, signal.clone()))
+        {
+            tracing::warn!("tried to send latest-only message to dead client");
+            return LatestMsgSignal::failed();
+        }
+
+        tracing::trace!(slot, "sending latest-only message to server");
+        signal
+    }
+
+    /// Sends a text message to the server.
+    ///
+    /// Returns `Ok(MessageSignal)` on success. Text messages will fail if [`ClientConfig::text_messages`] is
+    /// disabled or if the underlying client becomes disconnected.
+    pub fn send_text(&self, text: impl Into<String>) -> MessageSignal
+    {
+        if !self.text_messages
+        {
+            tracing::warn!("tried to send text message but text messages are disabled");
+            return MessageSignal::new(MessageStatus::Failed);
+        }
+
+        // lock pending requests
+        let Ok(_pending_requests) = self.pending_requests.lock()
+        else
+        {
+            tracing::error!("the client experienced a critical internal error");
+            return MessageSignal::new(MessageStatus::Failed);
+        };
+
+        // check if connected
+        // - We do this after locking the pending requests cache in order to synchronize with dropping the internal
+        //   client handler, and to synchronize with disconnect events in the client backend.
+        if !self.is_connected()
+        {
+            tracing::warn!("tried to send text message to disconnected client");
+            return MessageSignal::new(MessageStatus::Failed);
+        }
+
+        // check the server's rate limit
+        if !self.rate_limit_guard.try_count()
+        {
+            tracing::warn!("tried to send text message but it would exceed the server's rate limit");
+            return MessageSignal::new(MessageStatus::Failed);
+        }
+
+        // forward text to server
+        let text = text.into();
+        let len = text.len();
+        match self.client.text(format!("{}{}", TEXT_MSG_PREFIX, text))
+        {
+            Ok(signal) =>
+            {
+                tracing::trace!("sending text message to server");
+                self.stats.record_sent(len);
+                signal
+            }
+            Err(_) =>
+            {
+                tracing::warn!("tried to send text message to dead client");
+                MessageSignal::new(MessageStatus::Failed)
+            }
+        }
+    }
+
+    /// Sends a control message to the server.
+    ///
+    /// Returns `MessageSignal`. Control messages will fail if the underlying client is or becomes disconnected.
+    pub fn send_control(&self, msg: ControlMsg) -> MessageSignal
+    {
+        // lock pending requests
+        let Ok(_pending_requests) = self.pending_requests.lock()
+        else
+        {
+            tracing::error!("the client experienced a critical internal error");
+            return MessageSignal::new(MessageStatus::Failed);
+        };
+
+        // check if connected
+        // - We do this after locking the pending requests cache in order to synchronize with dropping the internal
+        //   client handler, and to synchronize with disconnect events in the client backend.
+        if !self.is_connected()
+        {
+            tracing::warn!("tried to send control message to disconnected client");
+            return MessageSignal::new(MessageStatus::Failed);
+        }
+
+        // check the server's rate limit
+        if !self.rate_limit_guard.try_count()
+        {
+            tracing::warn!("tried to send control message but it would exceed the server's rate limit");
+            return MessageSignal::new(MessageStatus::Failed);
+        }
+
+        // forward control message to server
+        let frame = ServerMetaEventFrom::<Channel>::Control(msg);
+        let Ok(result) = self.codec.send(&frame, |ser_msg| (ser_msg.len(), self.client.binary(ser_msg)))
+        else
+        {
+            tracing::error!("failed serializing client control message");
+            return MessageSignal::new(MessageStatus::Failed);
+        };
+
+        match result
+        {
+            (len, Ok(signal)) =>
+            {
+                tracing::trace!("sending control message to server");
+                self.stats.record_sent(len);
+                signal
+            }
+            (_, Err(_)) =>
+            {
+                tracing::warn!("tried to send control message to dead client");
+                MessageSignal::new(MessageStatus::Failed)
+            }
+        }
+    }
+
     /// Sends a request to the server.
     ///
     /// Returns `RequestSignal`. The signal can be used to track the message status.
@@ -99,13 +278,125 @@ impl<Channel: ChannelPack> Client<Channel>
     ///
     /// Failed requests will always emit a client event unless the client has a critical internal error.
     pub fn request(&self, request: Channel::ClientRequest) -> RequestSignal
+    {
+        self.request_impl(None, move |request_id| ServerMetaEventFrom::<Channel>::Request(request, request_id, None))
+    }
+
+    /// Sends a request to the server with a trace id.
+    ///
+    /// The trace id is exposed to the server with [`RequestToken::trace_id()`](crate::RequestToken::trace_id), and
+    /// echoed back in the request's response, ack, or rejection. This can be used to correlate client actions with
+    /// server work in distributed-tracing systems. The trace id can be read from [`RequestSignal::trace_id()`].
+    ///
+    /// See [`Self::request()`].
+    pub fn request_traced(&self, request: Channel::ClientRequest, trace_id: impl Into<String>) -> RequestSignal
+    {
+        let trace_id = Some(trace_id.into());
+        let trace_id_clone = trace_id.clone();
+        self.request_impl(
+                trace_id,
+                move |request_id| ServerMetaEventFrom::<Channel>::Request(request, request_id, trace_id_clone)
+            )
+    }
+
+    /// Sends a batch of requests to the server.
+    ///
+    /// Returns `BatchSignal`. The server receives the batch as one
+    /// [`ServerEvent::RequestBatch`](crate::ServerEvent::RequestBatch) with a single request token, so the batch is
+    /// responded to, acknowledged, or rejected as a whole. This is useful for operations that must succeed or fail
+    /// together.
+    ///
+    /// See [`Self::request()`].
+    pub fn request_batch(&self, requests: Vec<Channel::ClientRequest>) -> BatchSignal
+    {
+        self.request_impl(
+                None,
+                move |request_id| ServerMetaEventFrom::<Channel>::RequestBatch(requests, request_id, None)
+            )
+    }
+
+    /// Subscribes to a topic published by the server (see [`Server::publish()`](crate::Server::publish)).
+    ///
+    /// Returns `RequestSignal`. The subscription will be acknowledged by the server, or rejected if the server's
+    /// [`TopicPermissionFn`](crate::TopicPermissionFn) denies it. Messages published to the topic are received as
+    /// [`ClientEvent::TopicMsg`].
+    ///
+    /// Subscriptions are per-session, so they must be renewed after every [`ClientReport::Connected`].
+    pub fn subscribe(&self, topic: impl Into<String>) -> RequestSignal
+    {
+        let topic = topic.into();
+        self.request_impl(None, move |request_id| ServerMetaEventFrom::<Channel>::Subscribe(topic, request_id))
+    }
+
+    /// Unsubscribes from a topic.
+    ///
+    /// Returns `MessageSignal`. Unsubscribing will fail if the underlying client is or becomes disconnected.
+    pub fn unsubscribe(&self, topic: impl Into<String>) -> MessageSignal
+    {
+        // lock pending requests
+        let Ok(_pending_requests) = self.pending_requests.lock()
+        else
+        {
+            tracing::error!("the client experienced a critical internal error");
+            return MessageSignal::new(MessageStatus::Failed);
+        };
+
+        // check if connected
+        // - We do this after locking the pending requests cache in order to synchronize with dropping the internal
+        //   client handler, and to synchronize with disconnect events in the client backend.
+        if !self.is_connected()
+        {
+            tracing::warn!("tried to unsubscribe with disconnected client");
+            return MessageSignal::new(MessageStatus::Failed);
+        }
+
+        // check the server's rate limit
+        if !self.rate_limit_guard.try_count()
+        {
+            tracing::warn!("tried to unsubscribe but it would exceed the server's rate limit");
+            return MessageSignal::new(MessageStatus::Failed);
+        }
+
+        // forward unsubscription to server
+        let frame = ServerMetaEventFrom::<Channel>::Unsubscribe(topic.into());
+        let Ok(result) = self.codec.send(&frame, |ser_msg| (ser_msg.len(), self.client.binary(ser_msg)))
+        else
+        {
+            tracing::error!("failed serializing client unsubscription");
+            return MessageSignal::new(MessageStatus::Failed);
+        };
+
+        match result
+        {
+            (len, Ok(signal)) =>
+            {
+                tracing::trace!("sending unsubscription to server");
+                self.stats.record_sent(len);
+                signal
+            }
+            (_, Err(_)) =>
+            {
+                tracing::warn!("tried to unsubscribe with dead client");
+                MessageSignal::new(MessageStatus::Failed)
+            }
+        }
+    }
+
+    /// Sends a request-like frame to the server with an optional trace id.
+    ///
+    /// The frame is produced from the request's reserved id.
+    fn request_impl(
+        &self,
+        trace_id : Option<String>,
+        frame    : impl FnOnce(u64) -> ServerMetaEventFrom<Channel>
+    ) -> RequestSignal
     {
         // lock pending requests
         let Ok(mut pending_requests) = self.pending_requests.lock()
         else
         {
             tracing::error!("the client experienced a critical internal error");
-            return RequestSignal::new(u64::MAX, MessageSignal::new(MessageStatus::Failed));
+            return RequestSignal::new(u64::MAX, MessageSignal::new(MessageStatus::Failed)).with_trace_id(trace_id);
         };
 
         // prep request id
@@ -117,34 +408,76 @@ impl<Channel: ChannelPack> Client<Channel>
         if !self.is_connected()
         {
             tracing::warn!("tried to send request to disconnected client");
-            return pending_requests.add_request(request_id, MessageSignal::new(MessageStatus::Failed));
+            return pending_requests.add_request(request_id, trace_id, MessageSignal::new(MessageStatus::Failed));
         };
 
+        // check the server's rate limit
+        if !self.rate_limit_guard.try_count()
+        {
+            tracing::warn!("tried to send request but it would exceed the server's rate limit");
+            return pending_requests.add_request(request_id, trace_id, MessageSignal::new(MessageStatus::Failed));
+        }
+
         // forward message to server
-        let Ok(ser_msg) = bincode::DefaultOptions::new().serialize(
-                &ServerMetaEventFrom::<Channel>::Request(request, request_id)
-            )
+        let request_frame = (frame)(request_id);
+        let Ok(result) = self.codec.send(&request_frame, |ser_msg| (ser_msg.len(), self.client.binary(ser_msg)))
         else
         {
             tracing::error!("failed serializing client request");
-            return pending_requests.add_request(request_id, MessageSignal::new(MessageStatus::Failed));
+            return pending_requests.add_request(request_id, trace_id, MessageSignal::new(MessageStatus::Failed));
         };
 
-        match self.client.binary(ser_msg)
+        match result
         {
-            Ok(signal) =>
+            (len, Ok(signal)) =>
             {
                 tracing::trace!("sending request to server");
-                pending_requests.add_request(request_id, signal)
+                self.stats.record_sent(len);
+                pending_requests.add_request(request_id, trace_id, signal)
             }
-            Err(_) =>
+            (_, Err(_)) =>
             {
                 tracing::warn!("tried to send request to dead client");
-                pending_requests.add_request(request_id, MessageSignal::new(MessageStatus::Failed))
+                pending_requests.add_request(request_id, trace_id, MessageSignal::new(MessageStatus::Failed))
             }
         }
     }
 
+    /// Gets the ids and statuses of requests that are waiting for a result from the server, sorted by request id.
+    ///
+    /// Requests are removed once they are responded to, acknowledged, rejected, or fail. Note that failed requests
+    /// may linger here until the client backend observes the failure.
+    pub fn pending_requests(&self) -> Vec<(u64, RequestStatus)>
+    {
+        let Ok(pending_requests) = self.pending_requests.lock()
+        else
+        {
+            tracing::error!("the client experienced a critical internal error");
+            return Vec::default();
+        };
+
+        pending_requests.snapshot()
+    }
+
+    /// Sets the connect message that will be sent the next time the client connects to the server.
+    ///
+    /// Connect messages are sent every time the client connects, including reconnects. Use this to keep connect
+    /// messages that contain client state up to date (e.g. the client's current scene). Changing the connect message
+    /// does not affect the current connection.
+    pub fn set_connect_msg(&self, connect_msg: Channel::ConnectMsg)
+    {
+        let Ok(mut current) = self.connect_msg.lock()
+        else { tracing::error!("the client experienced a critical internal error"); return; };
+
+        *current = connect_msg;
+    }
+
+    /// Gets the connect message that will be sent the next time the client connects to the server.
+    pub(crate) fn current_connect_msg(&self) -> Channel::ConnectMsg
+    {
+        self.connect_msg.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+
     /// Tries to get the next client event.
     ///
     /// When the client dies, the last event emitted will be `ClientEvent::Report(ClientReport::IsDead))`.
@@ -156,6 +489,8 @@ impl<Channel: ChannelPack> Client<Channel>
     ///
     /// Note: This method is mutable so that message sending synchronizes with setting the connection signal. We
     ///       expect the caller will handle connection events atomically without interleaving unrelated messages.
+    ///
+    /// Always returns `None` if the client was made with [`ClientFactory::with_event_callback()`].
     pub fn next(&mut self) -> Option<ClientEventFrom<Channel>>
     {
         let Ok(msg) = self.client_event_receiver.try_recv() else { return None; };
@@ -174,9 +509,47 @@ impl<Channel: ChannelPack> Client<Channel>
     }
 
     /// Access this client's id.
+    ///
+    /// The id may change if [`ClientConfig::disambiguate_id`] is enabled (see [`ClientReport::IdChanged`]).
     pub fn id(&self) -> u128
     {
-        self.client_id
+        *self.client_id.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Gets the protocol version of this client (see [`ClientFactory::new()`]).
+    ///
+    /// Servers reject clients with a different protocol version, so if [`ClientReport::Connected`] was emitted then
+    /// the server is using this protocol version.
+    pub fn protocol_version(&self) -> &'static str
+    {
+        self.protocol_version
+    }
+
+    /// Gets the environment type of this client (the server receives this when the client connects).
+    pub fn env_type(&self) -> EnvType
+    {
+        env_type()
+    }
+
+    /// Gets statistics about frames received from the server.
+    pub fn frame_stats(&self) -> FrameStats
+    {
+        self.codec.counters().stats()
+    }
+
+    /// Gets totals accumulated over the client's lifetime (see [`ClientStats`]).
+    pub fn stats(&self) -> ClientStats
+    {
+        self.stats.stats()
+    }
+
+    /// Gets the rate limit of the client's current server session.
+    ///
+    /// Returns `None` until the server's rate limit is received after connecting (see
+    /// [`ClientConfig::rate_limit_guard`]).
+    pub fn server_rate_limit(&self) -> Option<RateLimitConfig>
+    {
+        self.rate_limit_guard.config()
     }
 
     /// Tests if the client is connected.
@@ -214,17 +587,52 @@ impl<Channel: ChannelPack> Client<Channel>
     ///
     /// The client will eventually emit [`ClientReport::IsDead`] once this method has been called.
     pub fn close(&self)
+    {
+        self.close_impl(
+                ezsockets::CloseFrame{
+                    code   : ezsockets::CloseCode::Normal,
+                    reason : String::from(CLIENT_DONE_CLOSE_REASON),
+                }
+            );
+    }
+
+    /// Closes the client with an application-defined close frame (e.g. [`CloseCode::Normal`](ezsockets::CloseCode)
+    /// with "logging out", or [`CloseCode::Away`](ezsockets::CloseCode) with "switching servers").
+    ///
+    /// The server will report the client's disconnect as
+    /// [`ServerReport::DisconnectedWithReason`](crate::ServerReport::DisconnectedWithReason), which lets the server
+    /// distinguish voluntary closes from crashes. Reasons that don't fit in a websocket close frame (123 bytes) are
+    /// truncated. Codes that can't be sent in a close frame (e.g. 1005 or 1006) are replaced with
+    /// [`CloseCode::Normal`](ezsockets::CloseCode).
+    ///
+    /// See [`Self::close()`].
+    pub fn close_with(&self, mut frame: CloseFrame)
+    {
+        if !frame.code.is_allowed()
+        {
+            tracing::warn!(code = %frame.code, "replacing close code that can't be sent");
+            frame.code = ezsockets::CloseCode::Normal;
+        }
+        if frame.reason.len() > MAX_CLOSE_REASON_BYTES
+        {
+            let mut end = MAX_CLOSE_REASON_BYTES;
+            while !frame.reason.is_char_boundary(end) { end -= 1; }
+            frame.reason.truncate(end);
+        }
+        self.close_impl(frame);
+    }
+
+    fn close_impl(&self, closure_frame: CloseFrame)
     {
         // sanity check
         if self.is_closed() { tracing::warn!("tried to close an already closed client"); return; }
         tracing::info!("client closing self");
 
+        // mark the client as closed
+        // - We do this before closing the internal client so the client backend will see it when the client dies.
+        self.closed_by_self.store(true, Ordering::Release);
+
         // close the client
-        let closure_frame =
-            ezsockets::CloseFrame{
-                code   : ezsockets::CloseCode::Normal,
-                reason : String::from("client done")
-            };
         if self.client.close(Some(closure_frame)).is_err()
         {
             tracing::warn!("tried to close an already dead client");
@@ -238,9 +646,48 @@ impl<Channel: ChannelPack> Client<Channel>
         }
 
         // note: request failures will be emitted for all pending requests when the internal client is dropped
+    }
 
-        // mark the client as closed
-        self.closed_by_self.store(true, Ordering::Release);
+    /// Aborts the client's in-progress connection attempt.
+    ///
+    /// This is intended for 'cancel' buttons on connection screens. The client dies immediately and emits
+    /// [`ClientReport::IsDead`] with [`DeathReason::ConnectAborted`], without waiting for the in-flight connection
+    /// attempt to time out. The client backend shuts down once the attempt resolves, and if the attempt succeeds then
+    /// the socket is closed without authenticating.
+    ///
+    /// Does nothing if the client is connected (use [`Client::close()`] instead) or closed.
+    pub fn abort_connect(&self)
+    {
+        // sanity checks
+        if self.is_closed() { tracing::warn!("tried to abort connecting on a closed client"); return; }
+        if self.is_connected() { tracing::warn!("tried to abort connecting on a connected client"); return; }
+        tracing::info!("client aborting connection attempt");
+
+        // lock the pending requests cache
+        // - We do this to synchronize with the client backend, which emits events within the lock.
+        let Ok(mut pending_requests) = self.pending_requests.lock()
+        else { tracing::error!("the client experienced a critical internal error"); return; };
+
+        // tell the client backend to stop
+        self.connect_aborted.store(true, Ordering::Release);
+        let _ = self.client.close(None);
+
+        // kill the client
+        // - Events from the client backend are discarded after this, so `IsDead` will be the last event.
+        let aborted_reqs = ClientHandler::<Channel>::final_request_cleanup(
+                &mut pending_requests,
+                &self.client_event_sender,
+                &self.stats,
+            );
+        if let Err(err) = self.client_event_sender.send(
+                ClientEventFrom::<Channel>::Report(ClientReport::IsDead(DeathReason::ConnectAborted, aborted_reqs))
+            )
+        {
+            tracing::error!(?err, "failed to forward connection event to client");
+        }
+        self.client_event_sender.close();
+        self.client_disconnected_count.fetch_add(1u16, Ordering::Release);
+        self.client_closed_signal.store(true, Ordering::Release);
     }
 }
 
@@ -261,6 +708,7 @@ impl<Channel: ChannelPack> Drop for Client<Channel>
 pub struct ClientFactory<Channel: ChannelPack>
 {
     protocol_version : &'static str,
+    event_callback   : Option<Arc<dyn ClientEventCallback<Channel>>>,
     _phantom         : PhantomData<Channel>,
 }
 
@@ -269,7 +717,29 @@ impl<Channel: ChannelPack> ClientFactory<Channel>
     /// Makes a new server factory with a given protocol version.
     pub fn new(protocol_version: &'static str) -> Self
     {
-        ClientFactory{ protocol_version, _phantom: PhantomData }
+        ClientFactory{ protocol_version, event_callback: None, _phantom: PhantomData }
+    }
+
+    /// Sets a callback that receives client events as soon as they are produced.
+    ///
+    /// Clients made by this factory will pass events to the callback instead of returning them from
+    /// [`Client::next()`]. See [`ClientEventCallback`].
+    pub fn with_event_callback(mut self, callback: impl ClientEventCallback<Channel>) -> Self
+    {
+        self.event_callback = Some(Arc::new(callback));
+        self
+    }
+
+    /// Checks if clients made by this factory pass events to an event callback.
+    pub(crate) fn has_event_callback(&self) -> bool
+    {
+        self.event_callback.is_some()
+    }
+
+    /// Makes a [`ClientBuilder`] for configuring and validating a new client.
+    pub fn builder(&self) -> ClientBuilder<Channel>
+    {
+        ClientBuilder::new(self.clone())
     }
 
     /// Makes a new client.
@@ -281,6 +751,12 @@ impl<Channel: ChannelPack> ClientFactory<Channel>
         connect_msg    : Channel::ConnectMsg,
     ) -> Client<Channel>
     {
+        assert!(config.wire_format.is_supported(), "wire format {:?} isn't supported by the enabled features",
+            config.wire_format);
+        assert!(!(config.text_messages && config.cipher.is_some()), "text messages can't be used with a cipher");
+        #[cfg(feature = "netsim")]
+        assert!(!(config.netsim.is_some() && config.cipher.is_some()), "netsim can't be used with a cipher");
+
         // prepare to make client connection
         // note: urls cannot contain raw bytes so we must serialize as json
         let client_config = ezsockets::ClientConfig::new(url)
@@ -288,23 +764,88 @@ impl<Channel: ChannelPack> ClientFactory<Channel>
             .max_initial_connect_attempts(config.max_initial_connect_attempts)
             .max_reconnect_attempts(config.max_reconnect_attempts)
             .query_parameter(VERSION_MSG_KEY, self.protocol_version)
-            .query_parameter(TYPE_MSG_KEY, env_type_as_str(env_type()));
+            .query_parameter(TYPE_MSG_KEY, env_type_as_str(env_type()))
+            .query_parameter(FORMAT_MSG_KEY, wire_format_as_str(config.wire_format));
+        let client_config = match Channel::fingerprint()
+        {
+            Some(fingerprint) => client_config.query_parameter(HASH_MSG_KEY, &fingerprint_as_str(fingerprint)),
+            None              => client_config,
+        };
 
         // prepare client's socket config
         let mut socket_config = ezsockets::SocketConfig::default();
         socket_config.heartbeat = config.heartbeat_interval;
         socket_config.timeout   = config.keepalive_timeout;
 
-        #[cfg(target_family = "wasm")]
+        // on WASM we need custom Ping/Pong protocol
+        // - we also use it on native if the user wants to customize heartbeats, since websocket pongs are consumed by
+        //   the backend
+        if env_type() == EnvType::Wasm
+            || config.heartbeat_ping_fn.is_some()
+            || config.heartbeat_callback.is_some()
+            || config.quality_callback.is_some()
+            || config.keepalive_miss_callback.is_some()
+        {
+            socket_config.heartbeat_ping_msg_fn = make_text_ping_fn(config.heartbeat_ping_fn.clone());
+        }
+
+        // replace heartbeat pings with unsolicited pongs if messages were sent since the last heartbeat
+        // - websocket control frames are not available on WASM
+        let stats = ClientStatsCounters::default();
+        if config.suppress_active_heartbeats && env_type() != EnvType::Wasm
         {
-            // on WASM we need custom Ping/Pong protocol
-            socket_config.heartbeat_ping_msg_fn = Arc::new(text_ping_fn);
+            let ping_fn = socket_config.heartbeat_ping_msg_fn.clone();
+            let stats = stats.clone();
+            let prev_msgs_sent = AtomicU64::new(0u64);
+            socket_config.heartbeat_ping_msg_fn = Arc::new(
+                    move |timestamp|
+                    {
+                        let msgs_sent = stats.msgs_sent();
+                        if prev_msgs_sent.swap(msgs_sent, Ordering::Relaxed) != msgs_sent
+                        { return ezsockets::RawMessage::Pong(Vec::default()); }
+                        (ping_fn)(timestamp)
+                    }
+                );
+        }
+
+        // report connection quality and missed heartbeats whenever a heartbeat ping is sent
+        let quality_tracker = Arc::new(Mutex::new(ConnectionQualityTracker::default()));
+        if config.quality_callback.is_some() || config.keepalive_miss_callback.is_some()
+        {
+            let ping_fn = socket_config.heartbeat_ping_msg_fn.clone();
+            let tracker = quality_tracker.clone();
+            let heartbeat_interval = config.heartbeat_interval;
+            let miss_threshold = config.keepalive_miss_threshold;
+            let quality_callback = config.quality_callback.clone();
+            let miss_callback = config.keepalive_miss_callback.clone();
+            socket_config.heartbeat_ping_msg_fn = Arc::new(
+                    move |timestamp|
+                    {
+                        let (report, missed) = match tracker.lock()
+                        {
+                            Ok(mut tracker) => (
+                                    tracker.report(heartbeat_interval),
+                                    tracker.check_missed(heartbeat_interval, miss_threshold)
+                                ),
+                            Err(_) => return (ping_fn)(timestamp),
+                        };
+                        if let Some(callback) = &quality_callback { (callback)(report); }
+                        if let (Some(callback), Some(missed)) = (&miss_callback, missed) { (callback)(missed); }
+                        (ping_fn)(timestamp)
+                    }
+                );
         }
 
         let client_config = client_config.socket_config(socket_config);
 
         // prepare message channel that points out of our client
+        let client_disconnected_count = Arc::new(AtomicU16::new(1u16));  //start at 1 for 'starting disconnected'
         let (client_event_sender, client_event_receiver) = crossbeam::channel::unbounded::<ClientEventFrom<Channel>>();
+        let client_event_sender = ClientEventSender::new(
+                client_event_sender,
+                self.event_callback.clone(),
+                client_disconnected_count.clone()
+            );
 
         // prepare client connector
         let client_connector = {
@@ -317,27 +858,64 @@ impl<Channel: ChannelPack> ClientFactory<Channel>
 
         // prep auth
         let client_id = auth.client_id();
-        let auth = ClientAuthMsg{ auth, msg: connect_msg };
+        let connect_msg = Arc::new(Mutex::new(connect_msg));
+        let codec = WireCodec::new(
+                config.wire_format,
+                config.decode_limits,
+                config.cipher.clone(),
+                config.checksum,
+                FrameDirection::ClientToServer,
+            );
+        let text_messages = config.text_messages;
 
         // make client core with our handler
         let client_event_sender_clone = client_event_sender.clone();
+        let codec_clone = codec.clone();
         let pending_requests = Arc::new(Mutex::new(PendingRequestTracker::default()));
         let pending_requests_clone = pending_requests.clone();
-        let client_disconnected_count = Arc::new(AtomicU16::new(1u16));  //start at 1 for 'starting disconnected'
         let client_closed_signal = Arc::new(AtomicBool::new(false));
         let client_disconnected_count_clone = client_disconnected_count.clone();
         let client_closed_signal_clone = client_closed_signal.clone();
+        let closed_by_self = Arc::new(AtomicBool::new(false));
+        let closed_by_self_clone = closed_by_self.clone();
+        let connect_aborted = Arc::new(AtomicBool::new(false));
+        let connect_aborted_clone = connect_aborted.clone();
+        let shared_client_id = Arc::new(Mutex::new(client_id));
+        let shared_client_id_clone = shared_client_id.clone();
+        let stats_clone = stats.clone();
+        let rate_limit_guard = RateLimitGuard::new(config.rate_limit_guard);
+        let rate_limit_guard_clone = rate_limit_guard.clone();
+        let connect_msg_clone = connect_msg.clone();
+        #[cfg(feature = "netsim")]
+        let netsim = config.netsim.map(|netsim_config| NetworkSimulator::new(netsim_config, client_id as u64));
         let (client, _client_task_handle) = ezsockets::connect_with(
                 move |client|
                 {
                     ClientHandler::<Channel>{
                             config,
+                            codec                     : codec_clone,
                             auth,
+                            client_id                 : shared_client_id_clone,
+                            connect_msg               : connect_msg_clone,
                             client,
                             client_event_sender       : client_event_sender_clone,
                             pending_requests          : pending_requests_clone,
                             client_disconnected_count : client_disconnected_count_clone,
                             client_closed_signal      : client_closed_signal_clone,
+                            closed_by_self            : closed_by_self_clone,
+                            connect_aborted           : connect_aborted_clone,
+                            death_reason              : DeathReason::ConnectFailed,
+                            failed_initial_attempts   : Some(0usize),
+                            quality_tracker,
+                            latest_slots              : ClientLatestSlots::default(),
+                            server_instance           : None,
+                            stats                     : stats_clone,
+                            rate_limit_guard          : rate_limit_guard_clone,
+                            runtime_handle,
+                            #[cfg(feature = "netsim")]
+                            netsim,
+                            #[cfg(feature = "netsim")]
+                            connection_epoch          : 0u64,
                         }
                 },
                 client_config,
@@ -348,14 +926,21 @@ impl<Channel: ChannelPack> ClientFactory<Channel>
         tracing::info!("created new client");
 
         Client{
-                client_id,
+                client_id: shared_client_id,
+                protocol_version: self.protocol_version,
                 client,
+                codec,
+                text_messages,
                 client_event_sender,
                 client_event_receiver,
                 pending_requests,
                 client_disconnected_count,
                 client_closed_signal,
-                closed_by_self: Arc::new(AtomicBool::new(false)),
+                closed_by_self,
+                connect_aborted,
+                connect_msg,
+                stats,
+                rate_limit_guard,
             }
     }
 }
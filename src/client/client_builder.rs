@@ -0,0 +1,144 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Builder for a [`Client`]. Make one with [`ClientFactory::builder()`].
+///
+/// The url, auth, and connect message are required. Other options have defaults:
+/// - runtime: `enfync::builtin::Handle::default()`
+/// - config: [`ClientConfig::default()`]
+///
+/// [`Self::build()`] validates the client's options, so misconfigurations are reported up front instead of
+/// appearing as connection failures or disconnects at runtime.
+#[derive(Debug)]
+pub struct ClientBuilder<Channel: ChannelPack>
+{
+    factory        : ClientFactory<Channel>,
+    runtime_handle : Option<enfync::builtin::Handle>,
+    url            : Option<url::Url>,
+    auth           : Option<AuthRequest>,
+    config         : ClientConfig,
+    connect_msg    : Option<Channel::ConnectMsg>,
+    require_tls    : bool,
+}
+
+impl<Channel: ChannelPack> ClientBuilder<Channel>
+{
+    pub(crate) fn new(factory: ClientFactory<Channel>) -> Self
+    {
+        Self{
+            factory,
+            runtime_handle : None,
+            url            : None,
+            auth           : None,
+            config         : ClientConfig::default(),
+            connect_msg    : None,
+            require_tls    : false,
+        }
+    }
+
+    /// Sets the runtime the client runs on.
+    pub fn runtime(mut self, runtime_handle: enfync::builtin::Handle) -> Self
+    {
+        self.runtime_handle = Some(runtime_handle);
+        self
+    }
+
+    /// Sets the server url (e.g. from [`Server::url()`](crate::Server::url)).
+    pub fn url(mut self, url: url::Url) -> Self
+    {
+        self.url = Some(url);
+        self
+    }
+
+    /// Sets the client's auth request.
+    pub fn auth(mut self, auth: AuthRequest) -> Self
+    {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Sets the client config.
+    pub fn config(mut self, config: ClientConfig) -> Self
+    {
+        self.config = config;
+        self
+    }
+
+    /// Sets the message sent to the server when connecting.
+    pub fn connect_msg(mut self, connect_msg: Channel::ConnectMsg) -> Self
+    {
+        self.connect_msg = Some(connect_msg);
+        self
+    }
+
+    /// Requires the server url to use TLS (`wss`). Defaults to `false`.
+    ///
+    /// This is useful for catching deployments that accidentally point production clients at unencrypted servers.
+    pub fn require_tls(mut self, require_tls: bool) -> Self
+    {
+        self.require_tls = require_tls;
+        self
+    }
+
+    /// Validates the client's options and builds the client.
+    pub fn build(self) -> Result<Client<Channel>, ClientBuildError>
+    {
+        let url = self.url.ok_or(ClientBuildError::MissingUrl)?;
+        let auth = self.auth.ok_or(ClientBuildError::MissingAuth)?;
+        let connect_msg = self.connect_msg.ok_or(ClientBuildError::MissingConnectMsg)?;
+
+        match url.scheme()
+        {
+            "wss" => (),
+            "ws"  => if self.require_tls { return Err(ClientBuildError::TlsRequired); },
+            scheme => return Err(ClientBuildError::InvalidScheme(String::from(scheme))),
+        }
+        validate_client_config(&self.config)?;
+
+        Ok(self.factory.new_client(
+                self.runtime_handle.unwrap_or_default(),
+                url,
+                auth,
+                self.config,
+                connect_msg,
+            ))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Checks a client config for options that can't work.
+fn validate_client_config(config: &ClientConfig) -> Result<(), ClientBuildError>
+{
+    // heartbeats must be sent before the connection times out
+    if config.heartbeat_interval >= config.keepalive_timeout
+    { return Err(ClientBuildError::HeartbeatNotBeforeKeepalive); }
+
+    // the client must try to connect at least once
+    if config.max_initial_connect_attempts == 0
+    { return Err(ClientBuildError::NoConnectAttempts); }
+
+    // the wire format must be compiled in
+    if !config.wire_format.is_supported()
+    { return Err(ClientBuildError::UnsupportedWireFormat(config.wire_format)); }
+
+    // text frames can't be encrypted
+    if config.text_messages && config.cipher.is_some()
+    { return Err(ClientBuildError::TextWithCipher); }
+
+    // simulated delays can reorder encrypted frames
+    #[cfg(feature = "netsim")]
+    if config.netsim.is_some() && config.cipher.is_some()
+    { return Err(ClientBuildError::NetsimWithCipher); }
+
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
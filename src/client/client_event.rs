@@ -5,6 +5,45 @@ use crate::*;
 
 //standard shortcuts
 use core::fmt::Debug;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The reason a client died.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DeathReason
+{
+    /// The client was closed with [`Client::close()`] or dropped.
+    ClosedBySelf,
+    /// The client's [`AuthToken`] expired.
+    ///
+    /// Request a new auth token before recreating the client.
+    AuthExpired,
+    /// The server rejected the client's authentication.
+    AuthRejected,
+    /// The server rejected the client's connection request (e.g. because of a protocol version or wire format
+    /// mismatch).
+    ///
+    /// Only detected on native targets, since browsers don't expose the HTTP status of failed websocket
+    /// connections. On WASM targets this is reported as [`DeathReason::ConnectFailed`].
+    ConnectRejected,
+    /// The client failed to connect and either ran out of connection attempts or
+    /// [`ClientConfig::reconnect_on_disconnect`] is disabled.
+    ConnectFailed,
+    /// The client disconnected and [`ClientConfig::reconnect_on_disconnect`] is disabled.
+    Disconnected,
+    /// The client was closed by the server and [`ClientConfig::reconnect_on_server_close`] is disabled.
+    ClosedByServer,
+    /// The client's connection attempts were aborted with [`Client::abort_connect()`].
+    ConnectAborted,
+    /// The client reconnected to a different server instance and [`ClientConfig::pin_instance`] is enabled.
+    InstanceChanged,
+    /// The server closed the client because another client is connected with the same id, and
+    /// [`ClientConfig::reconnect_on_server_close`] and [`ClientConfig::disambiguate_id`] are disabled.
+    IdCollision,
+    /// The client experienced an internal error.
+    InternalError,
+}
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -12,11 +51,41 @@ use core::fmt::Debug;
 #[derive(Debug, Clone)]
 pub enum ClientReport
 {
+    /// The client failed a connection attempt while initially connecting, and will try again.
+    ///
+    /// `attempt` is the number of the next connection attempt (starting at 2), and `next_retry_in` is the delay
+    /// before that attempt starts (see [`ClientConfig::reconnect_interval`]). This is not emitted for the final
+    /// attempt allowed by [`ClientConfig::max_initial_connect_attempts`], or when reconnecting after the client has
+    /// connected.
+    Connecting
+    {
+        attempt: usize,
+        next_retry_in: Duration,
+    },
     /// The client connected to the server.
     ///
     /// This event synchronizes with the request/response pattern. All requests sent before the client became connected
     /// will receive a result event (Response/Ack/Reject/SendFailed/ResponseLost) before `Connected` is emitted.
     Connected,
+    /// The client reconnected to a different server instance than it was previously connected to.
+    ///
+    /// Emitted after [`ClientReport::Connected`] if the server has an instance id (see `ServerConfig::instance_id`).
+    /// Any state that only exists on the previous instance (e.g. in-progress work for requests) should be considered
+    /// lost.
+    InstanceChanged
+    {
+        previous: u64,
+        current: u64,
+    },
+    /// The client picked a new id after the server closed it because another client is connected with the same id.
+    ///
+    /// Emitted after [`ClientReport::ClosedByServer`] if [`ClientConfig::disambiguate_id`] is enabled. The client
+    /// will reconnect with the new id.
+    IdChanged
+    {
+        previous: u128,
+        current: u128,
+    },
     /// The client disconnected from the server.
     Disconnected,
     /// The client was closed by the server.
@@ -25,11 +94,12 @@ pub enum ClientReport
     ClosedBySelf,
     /// The client has died and will not try to reconnect.
     ///
-    /// Stores the pending request ids for requests that were [`RequestStatus::Sending`] at the time the client died.
+    /// Stores the reason the client died, and the pending request ids for requests that were
+    /// [`RequestStatus::Sending`] at the time the client died.
     /// The requests will eventually transition to either [`RequestStatus::SendFailed`] or [`RequestStatus::ResponseLost`].
     ///
     /// No more events will be emitted after this event appears.
-    IsDead(Vec<u64>),
+    IsDead(DeathReason, Vec<u64>),
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -49,7 +119,7 @@ pub enum ClientReport
 /// - Between an unexpected internal error and a [`ClientReport::IsDead`] report.
 /// - Between dropping the client and a [`ClientReport::IsDead`] report. In this case the events will not be readable.
 #[derive(Debug, Clone)]
-pub enum ClientEvent<ServerMsg, ServerResponse>
+pub enum ClientEvent<ServerMsg, ServerResponse, RejectReason = ()>
 {
     /// A connection report.
     Report(ClientReport),
@@ -57,16 +127,43 @@ pub enum ClientEvent<ServerMsg, ServerResponse>
     Msg(ServerMsg),
     /// A response to a client request.
     Response(ServerResponse, u64),
+    /// A partial response to a client request.
+    ///
+    /// Partial responses are sent with [`Server::respond_partial()`](crate::Server::respond_partial), and are
+    /// followed by a final response, ack, or rejection (or a `SendFailed`/`ResponseLost` event if the request fails).
+    ResponsePart(ServerResponse, u64),
     /// The sever acknowledged receiving a client request.
     ///
     /// This will not be followed by a subsequent response (you either get a response, ack, or rejection).
     Ack(u64),
+    /// The server acknowledged receiving a client request, and attached a small payload to the ack.
+    ///
+    /// Acks with payloads are sent with [`Server::ack_with()`](crate::Server::ack_with). The payload's encoding is
+    /// defined by the application. This will not be followed by a subsequent response.
+    AckWith(Vec<u8>, u64),
     /// The server rejected a client request.
     Reject(u64),
+    /// The server rejected a client request with a reason.
+    ///
+    /// Rejections with reasons are sent with [`Server::reject_with()`](crate::Server::reject_with).
+    RejectWith(RejectReason, u64),
     /// Sending a request failed.
     SendFailed(u64),
     /// The server received a request but the client failed to receive a response.
     ResponseLost(u64),
+    /// A text message from the server.
+    ///
+    /// Only emitted if [`ClientConfig::text_messages`] is enabled.
+    Text(String),
+    /// A message published to a topic the client subscribed to (see [`Client::subscribe()`]).
+    TopicMsg(String, ServerMsg),
+    /// The server rejected a client message or request because the client lacks the required scopes.
+    ///
+    /// Includes the request id if a request was rejected (the request's status becomes
+    /// [`RequestStatus::Rejected`], and no [`Self::Reject`] event is emitted), and the missing scopes.
+    Unauthorized(Option<u64>, Scopes),
+    /// An application-defined control message from the server (see [`ControlMsg`]).
+    Control(ControlMsg),
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -74,7 +171,8 @@ pub enum ClientEvent<ServerMsg, ServerResponse>
 /// Get a [`ClientEvent`] from a [`ChannelPack`].
 pub type ClientEventFrom<Channel> = ClientEvent<
     <Channel as ChannelPack>::ServerMsg,
-    <Channel as ChannelPack>::ServerResponse
+    <Channel as ChannelPack>::ServerResponse,
+    <Channel as ChannelPack>::RejectReason
 >;
 
 //-------------------------------------------------------------------------------------------------------------------
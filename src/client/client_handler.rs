@@ -2,77 +2,173 @@
 use crate::*;
 
 //third-party shortcuts
-use bincode::Options;
+use enfync::Handle;
 
 //standard shortcuts
 use core::fmt::Debug;
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
 use std::vec::Vec;
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Checks if a connection attempt failed because the server rejected the connection request.
+///
+/// Servers reject invalid connection requests with 4xx HTTP responses (see `prevalidate_connection_request()`).
+/// Browsers don't expose HTTP statuses of failed websocket connections, so this always returns false on WASM.
+fn is_connect_rejection(error: &ezsockets::WSError) -> bool
+{
+    #[cfg(not(target_family = "wasm"))]
+    { error.to_string().starts_with("HTTP error: 4") }
+
+    #[cfg(target_family = "wasm")]
+    { let _ = error; false }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Picks a new random client id that differs from the previous id.
+fn disambiguated_client_id(previous: u128) -> u128
+{
+    let random_u64 = |salt: u64|
+    {
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        (previous, salt, SystemTimeSource.now()).hash(&mut hasher);
+        hasher.finish()
+    };
+
+    let client_id = ((random_u64(0) as u128) << 64) | random_u64(1) as u128;
+    match client_id == previous
+    {
+        true  => client_id.wrapping_add(1),
+        false => client_id,
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Calls into the client handler (i.e. `ezsockets::Client::call()`).
+#[derive(Debug)]
+pub(crate) enum ClientHandlerCall
+{
+    /// A binary frame delayed by the network simulator, tagged with the connection it arrived on.
+    #[cfg(feature = "netsim")]
+    DelayedBinary(u64, Vec<u8>),
+    /// Send a serialized message in a latest-only slot.
+    SendLatest(u64, Vec<u8>, LatestMsgSignal),
+    /// Sends the message held in a latest-only slot once the slot's previous message was flushed.
+    FlushLatest(u64),
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Debug)]
 pub(crate) struct ClientHandler<Channel: ChannelPack>
 {
     /// config
     pub(crate) config: ClientConfig,
-    /// authentication message
-    pub(crate) auth: ClientAuthMsg<Channel::ConnectMsg>,
+    /// encodes and decodes messages
+    pub(crate) codec: WireCodec,
+    /// authentication request
+    pub(crate) auth: AuthRequest,
+    /// the client's current id
+    /// - updated if the client picks a new id after an id collision
+    pub(crate) client_id: Arc<Mutex<u128>>,
+    /// connect message sent with the authentication request
+    /// - read on every connection attempt so it can be updated with [`Client::set_connect_msg()`]
+    pub(crate) connect_msg: Arc<Mutex<Channel::ConnectMsg>>,
     /// core websockets client
     pub(crate) client: ezsockets::Client<ClientHandler<Channel>>,
     /// send client events to the client
-    pub(crate) client_event_sender: crossbeam::channel::Sender<ClientEventFrom<Channel>>,
+    pub(crate) client_event_sender: ClientEventSender<Channel>,
     /// synchronized tracker for pending requests
     pub(crate) pending_requests: Arc<Mutex<PendingRequestTracker>>,
     /// signal to communicate how many disconnects have occurred; synchronizes with connection events
     pub(crate) client_disconnected_count: Arc<AtomicU16>,
     /// signal to communicate when the client handler is dead; synchronizes with draining the pending request cache
     pub(crate) client_closed_signal: Arc<AtomicBool>,
+    /// signal set when the client closes itself
+    pub(crate) closed_by_self: Arc<AtomicBool>,
+    /// signal set when the client owner aborts connecting
+    pub(crate) connect_aborted: Arc<AtomicBool>,
+    /// reason to report if the client dies
+    /// - updated whenever the client backend decides whether to keep running
+    pub(crate) death_reason: DeathReason,
+    /// number of failed connection attempts while initially connecting
+    /// - `None` once the client has connected
+    pub(crate) failed_initial_attempts: Option<usize>,
+    /// tracks heartbeat statistics for connection quality reports
+    pub(crate) quality_tracker: Arc<Mutex<ConnectionQualityTracker>>,
+    /// latest-only send slots
+    pub(crate) latest_slots: ClientLatestSlots,
+    /// id of the server instance the client is connected to, if the server has one
+    pub(crate) server_instance: Option<u64>,
+    /// totals exposed by [`Client::stats()`]
+    pub(crate) stats: ClientStatsCounters,
+    /// mirrors the server's rate limit
+    pub(crate) rate_limit_guard: RateLimitGuard,
+
+    /// runtime for spawning background tasks
+    pub(crate) runtime_handle: enfync::builtin::Handle,
+    /// simulated network conditions
+    #[cfg(feature = "netsim")]
+    pub(crate) netsim: Option<NetworkSimulator>,
+    /// counts connections so delayed frames can't leak into later connections
+    #[cfg(feature = "netsim")]
+    pub(crate) connection_epoch: u64,
 }
 
 #[async_trait::async_trait]
 impl<Channel: ChannelPack> ezsockets::ClientExt for ClientHandler<Channel>
 {
-    type Call = ();
+    type Call = ClientHandlerCall;
 
     /// Text from server.
-    /// - Does nothing on native.
-    /// - Echoes the text back to the server on WASM for custom Ping/Pong protocol.
+    /// - Forwards text messages to the client owner.
+    /// - Echoes pings back to the server for the custom Ping/Pong protocol.
     async fn on_text(&mut self, text: String) -> Result<(), ezsockets::Error>
     {
-        match env_type()
+        if let Ok(mut tracker) = self.quality_tracker.lock() { tracker.record_recv(); }
+
+        // received text message
+        if let Some(text) = text.strip_prefix(TEXT_MSG_PREFIX)
         {
-            EnvType::Native =>
+            self.stats.record_received(text.len());
+
+            if !self.config.text_messages
+            { tracing::warn!("ignoring text message from server (text messages are disabled)"); return Ok(()); }
+
+            // forward to client owner
+            if let Err(err) = self.client_event_sender.send(ClientEventFrom::<Channel>::Text(String::from(text)))
             {
-                // ignore text received
-                tracing::warn!("received text from server (not handled)");
+                tracing::debug!(?err, "failed to forward server text message to client");
+                return Err(Box::new(ClientError::SendError));
             }
-            EnvType::Wasm =>
-            {
-                // received Ping or Pong
-                let Some((var, value)) = text.as_str().split_once(':')
-                else { tracing::warn!("ignoring invalid text from server..."); return Ok(()); };
 
-                // try to deserialize timestamp
-                let Ok(timestamp) = u128::from_str_radix(value, 10u32)
-                else { tracing::warn!("ignoring invalid ping/pong from server..."); return Ok(()); };
+            return Ok(());
+        }
 
-                match var
-                {
-                    "ping" =>
-                    {
-                        // received Ping, send Pong back
-                        let _ = self.client.text(format!("pong:{}", value))?;
-                    }
-                    "pong" =>
-                    {
-                        // received Pong, log latency
-                        log_ping_pong_latency(timestamp);
-                    }
-                    _ => tracing::warn!("ignoring invalid ping/pong from server...")
-                }
+        // received Ping or Pong
+        // - native clients only receive text pings if the server customizes its heartbeats
+        let Some((var, value)) = text.as_str().split_once(':')
+        else { tracing::warn!("ignoring invalid text from server..."); return Ok(()); };
+
+        match var
+        {
+            "ping" =>
+            {
+                // received Ping, send Pong back
+                let _ = self.client.text(format!("pong:{}", value))?;
             }
+            "pong" =>
+            {
+                // received Pong, report it
+                let pong = read_pong(value, self.config.heartbeat_ping_fn.is_none());
+                if let Ok(mut tracker) = self.quality_tracker.lock() { tracker.record_pong(&pong); }
+                if let Some(callback) = &self.config.heartbeat_callback { (callback)(pong); }
+            }
+            _ => tracing::warn!("ignoring invalid ping/pong from server...")
         }
 
         Ok(())
@@ -82,91 +178,74 @@ impl<Channel: ChannelPack> ezsockets::ClientExt for ClientHandler<Channel>
     async fn on_binary(&mut self, bytes: Vec<u8>) -> Result<(), ezsockets::Error>
     {
         tracing::trace!("received binary from server");
+        if let Ok(mut tracker) = self.quality_tracker.lock() { tracker.record_recv(); }
 
-        // deserialize message
-        let Ok(server_msg) = bincode::DefaultOptions::new().deserialize(&bytes[..])
-        else
-        {
-            tracing::warn!("received server msg that failed to deserialize");
-            return Ok(());
-        };
-
-        // decide how to handle the message
-        let client_event = match server_msg
+        // apply simulated network conditions
+        #[cfg(feature = "netsim")]
+        if let Some(netsim) = &mut self.netsim
         {
-            ClientMetaEventFrom::<Channel>::Msg(msg) =>
-            {
-                // msg
-                ClientEventFrom::<Channel>::Msg(msg)
-            }
-            ClientMetaEventFrom::<Channel>::Response(response, request_id) =>
+            match netsim.next_action()
             {
-                // discard message if request id is unknown
-                // - this should never happen
-                let Ok(mut pending_requests) = self.pending_requests.lock()
-                else { tracing::error!("Response lock error"); return Ok(()); };
-
-                if !pending_requests.set_status_and_remove(request_id, RequestStatus::Responded)
+                NetsimAction::Deliver => (),
+                NetsimAction::Drop =>
                 {
-                    tracing::error!(request_id, "ignoring server response for unknown request");
+                    tracing::trace!("netsim: dropping frame from server");
                     return Ok(());
                 }
-
-                // response
-                ClientEventFrom::<Channel>::Response(response, request_id)
-            }
-            ClientMetaEventFrom::<Channel>::Ack(request_id) =>
-            {
-                // discard message if request id is unknown
-                // - this should never happen
-                let Ok(mut pending_requests) = self.pending_requests.lock()
-                else { tracing::error!("Ack lock error"); return Ok(()); };
-
-                if !pending_requests.set_status_and_remove(request_id, RequestStatus::Acknowledged)
+                NetsimAction::Delay(delay) =>
                 {
-                    tracing::error!(request_id, "ignoring server ack for unknown request");
+                    let client = self.client.clone();
+                    let epoch = self.connection_epoch;
+                    self.runtime_handle.spawn(async move {
+                            let _ = wasm_timer::Delay::new(delay).await;
+                            let _ = client.call(ClientHandlerCall::DelayedBinary(epoch, bytes));
+                        });
                     return Ok(());
                 }
-
-                // ack
-                ClientEventFrom::<Channel>::Ack(request_id)
             }
-            ClientMetaEventFrom::<Channel>::Reject(request_id) =>
-            {
-                // discard message if request id is unknown
-                // - this should never happen
-                let Ok(mut pending_requests) = self.pending_requests.lock()
-                else { tracing::error!("Reject lock error"); return Ok(()); };
-
-                if !pending_requests.set_status_and_remove(request_id, RequestStatus::Rejected)
-                {
-                    tracing::error!(request_id, "ignoring server rejection for unknown request");
-                    return Ok(());
-                }
-
-                // rejection
-                ClientEventFrom::<Channel>::Reject(request_id)
-            }
-        };
-
-        // forward to client owner
-        if let Err(err) = self.client_event_sender.send(client_event)
-        {
-            tracing::debug!(?err, "failed to forward server message to client");
-            return Err(Box::new(ClientError::SendError));
         }
 
-        Ok(())
+        self.handle_binary(bytes)
     }
 
     /// Call from associated client.
-    ///
-    /// Does nothing.
-    async fn on_call(&mut self, _msg: ()) -> Result<(), ezsockets::Error>
+    async fn on_call(&mut self, msg: ClientHandlerCall) -> Result<(), ezsockets::Error>
     {
-        // ignore call
-        tracing::error!("on_call() invocation (not handled)");
-        Ok(())
+        match msg
+        {
+            #[cfg(feature = "netsim")]
+            ClientHandlerCall::DelayedBinary(epoch, bytes) =>
+            {
+                if epoch != self.connection_epoch
+                {
+                    tracing::trace!("netsim: discarding delayed frame from old connection");
+                    return Ok(());
+                }
+                self.handle_binary(bytes)
+            }
+            ClientHandlerCall::SendLatest(slot, ser_msg, signal) =>
+            {
+                // forward the message, or hold it until the slot's previous message is flushed
+                let Some(in_flight) =
+                    self.latest_slots.send(&self.client, &self.codec, &self.stats, slot, ser_msg, signal)
+                else { return Ok(()); };
+
+                let client = self.client.clone();
+                self.runtime_handle.spawn(async move {
+                        while ClientLatestSlots::is_in_flight(&in_flight)
+                        {
+                            let _ = wasm_timer::Delay::new(CLIENT_LATEST_SLOT_POLL_INTERVAL).await;
+                        }
+                        let _ = client.call(ClientHandlerCall::FlushLatest(slot));
+                    });
+                Ok(())
+            }
+            ClientHandlerCall::FlushLatest(slot) =>
+            {
+                self.latest_slots.flush(&self.client, &self.codec, &self.stats, slot);
+                Ok(())
+            }
+        }
     }
 
     /// Respond to the client acquiring a connection.
@@ -174,13 +253,29 @@ impl<Channel: ChannelPack> ezsockets::ClientExt for ClientHandler<Channel>
     {
         tracing::info!("connected");
 
+        // don't authenticate if connecting was aborted
+        // - the client owner already closed the client, so the socket will be closed shortly
+        if self.connect_aborted.load(Ordering::Acquire)
+        { tracing::info!("connection was aborted, not authenticating"); return Ok(()); }
+
+        // connected clients can only die from closing, disconnecting, or an error
+        self.death_reason = DeathReason::InternalError;
+        if self.failed_initial_attempts.is_none() { self.stats.record_reconnect(); }
+        self.failed_initial_attempts = None;
+        if let Ok(mut tracker) = self.quality_tracker.lock() { tracker.reset(); }
+        self.rate_limit_guard.reset();
+        self.codec.reset_peer_stream();
+
+        #[cfg(feature = "netsim")]
+        { self.connection_epoch += 1; }
+
         // lock the pending requests cache
         let Ok(mut pending_requests) = self.pending_requests.lock()
         else { tracing::error!("on_connect() lock error"); return Ok(()); };
 
         // clean up existing requests
         // - do this before sending connection event so the event stream is synchronized
-        let aborted_sends = Self::final_request_cleanup(&mut pending_requests, &self.client_event_sender);
+        let aborted_sends = Self::final_request_cleanup(&mut pending_requests, &self.client_event_sender, &self.stats);
 
         // convert aborted sends to `SendFailed`
         // - `aborted_sends` may not be empty if the socket for the previous connection did not fully shut down yet.
@@ -189,6 +284,7 @@ impl<Channel: ChannelPack> ezsockets::ClientExt for ClientHandler<Channel>
         //   server's old session completely shutting down, so we can treat them as such here.
         for aborted_send in aborted_sends
         {
+            self.stats.record_send_failed();
             if let Err(err) = self.client_event_sender.send(ClientEventFrom::<Channel>::SendFailed(aborted_send))
             {
                 tracing::debug!(?err, "failed to forward client event to client");
@@ -202,16 +298,21 @@ impl<Channel: ChannelPack> ezsockets::ClientExt for ClientHandler<Channel>
         //   initial connection message.
         // - Sending this within the `pending_requests` lock ensures it is always the first message to be received on
         //   the server.
-        let Ok(ser_msg) = bincode::DefaultOptions::new().serialize(
-                &ServerMetaEventFrom::<Channel>::Authenticate(self.auth.clone())
-            )
-        else
+        let Ok(connect_msg) = self.connect_msg.lock()
+        else { tracing::error!("on_connect() connect msg lock error"); return Err(Box::new(ClientError::SendError)); };
+        let auth = ClientAuthMsg{
+                auth   : self.auth.clone(),
+                tenant : self.config.tenant.clone(),
+                msg    : connect_msg.clone(),
+            };
+        drop(connect_msg);
+
+        let auth_msg = ServerMetaEventFrom::<Channel>::Authenticate(auth);
+        if let Err(_) = self.codec.send(&auth_msg, |ser_msg| self.client.binary(ser_msg))
         {
             tracing::error!("failed serializing client connection request");
             return Err(Box::new(ClientError::SendError));
-        };
-
-        let _ = self.client.binary(ser_msg);
+        }
 
         // forward connection event to client owner
         if let Err(err) = self.client_event_sender.send(ClientEventFrom::<Channel>::Report(ClientReport::Connected))
@@ -226,9 +327,21 @@ impl<Channel: ChannelPack> ezsockets::ClientExt for ClientHandler<Channel>
     /// Respond to the client failing a connection attempt.
     async fn on_connect_fail(
         &mut self,
-        _error: ezsockets::WSError
+        error: ezsockets::WSError
     ) -> Result<ezsockets::client::ClientCloseMode, ezsockets::Error>
     {
+        // stop connecting if connecting was aborted
+        if self.connect_aborted.load(Ordering::Acquire) { return Ok(ezsockets::client::ClientCloseMode::Close); }
+
+        // record the failure
+        // - if the backend runs out of connection attempts then this will be the death reason
+        tracing::info!(%error, "connection attempt failed");
+        self.death_reason = match is_connect_rejection(&error)
+        {
+            true  => DeathReason::ConnectRejected,
+            false => DeathReason::ConnectFailed,
+        };
+
         // lock the pending requests cache
         let Ok(mut pending_requests) = self.pending_requests.lock()
         else { tracing::error!("on_connect_fail() lock error");  return Ok(ezsockets::client::ClientCloseMode::Close); };
@@ -237,19 +350,26 @@ impl<Channel: ChannelPack> ezsockets::ClientExt for ClientHandler<Channel>
         //       disconnected.
 
         // clean up pending requests
-        Self::clean_pending_requests(&mut pending_requests, &self.client_event_sender);
+        Self::clean_pending_requests(&mut pending_requests, &self.client_event_sender, &self.stats);
+
+        // don't reconnect if the client is leaving its server instance
+        if self.death_reason == DeathReason::InstanceChanged { return Ok(ezsockets::client::ClientCloseMode::Close); }
 
         // check auth token expiry
-        if let AuthRequest::Token{token} = self.auth.auth {
-            if token.is_expired() {
-                return Ok(ezsockets::client::ClientCloseMode::Close);
-            }
+        if self.auth_token_expired()
+        {
+            self.death_reason = DeathReason::AuthExpired;
+            return Ok(ezsockets::client::ClientCloseMode::Close);
         }
 
         // choose response
         match self.config.reconnect_on_disconnect
         {
-            true  => Ok(ezsockets::client::ClientCloseMode::Reconnect),
+            true  =>
+            {
+                self.report_initial_connect_retry()?;
+                Ok(ezsockets::client::ClientCloseMode::Reconnect)
+            }
             false => Ok(ezsockets::client::ClientCloseMode::Close),
         }
     }
@@ -266,6 +386,7 @@ impl<Channel: ChannelPack> ezsockets::ClientExt for ClientHandler<Channel>
         // mark the client as disconnected
         // - We do this within the pending requests lock in order to synchronize with the client API.
         self.client_disconnected_count.fetch_add(1u16, Ordering::Release);
+        self.latest_slots.clear();
 
         // forward event to client owner
         if let Err(err) = self.client_event_sender.send(ClientEventFrom::<Channel>::Report(ClientReport::Disconnected))
@@ -277,20 +398,31 @@ impl<Channel: ChannelPack> ezsockets::ClientExt for ClientHandler<Channel>
         // clean up pending requests
         // - do this after sending the client report so request failures appear between client disconnected and client
         //   connected reports (except when the client is dying)
-        Self::clean_pending_requests(&mut pending_requests, &self.client_event_sender);
+        Self::clean_pending_requests(&mut pending_requests, &self.client_event_sender, &self.stats);
+
+        // don't reconnect if the client is leaving its server instance
+        if self.death_reason == DeathReason::InstanceChanged { return Ok(ezsockets::client::ClientCloseMode::Close); }
 
         // check auth token expiry
-        if let AuthRequest::Token{token} = self.auth.auth {
-            if token.is_expired() {
-                return Ok(ezsockets::client::ClientCloseMode::Close);
-            }
+        if self.auth_token_expired()
+        {
+            self.death_reason = DeathReason::AuthExpired;
+            return Ok(ezsockets::client::ClientCloseMode::Close);
         }
 
         // choose response
         match self.config.reconnect_on_disconnect
         {
-            true  => Ok(ezsockets::client::ClientCloseMode::Reconnect),
-            false => Ok(ezsockets::client::ClientCloseMode::Close),
+            true  =>
+            {
+                self.death_reason = DeathReason::ConnectFailed;
+                Ok(ezsockets::client::ClientCloseMode::Reconnect)
+            }
+            false =>
+            {
+                self.death_reason = DeathReason::Disconnected;
+                Ok(ezsockets::client::ClientCloseMode::Close)
+            }
         }
     }
 
@@ -302,6 +434,16 @@ impl<Channel: ChannelPack> ezsockets::ClientExt for ClientHandler<Channel>
     {
         tracing::info!(?close_frame, "closed by server");
 
+        // check if the server rejected our authentication or our id
+        let auth_rejected = close_frame
+            .as_ref()
+            .map(|frame| frame.reason == AUTH_REJECTED_CLOSE_REASON)
+            .unwrap_or(false);
+        let id_collision = close_frame
+            .as_ref()
+            .map(|frame| frame.reason == ID_COLLISION_CLOSE_REASON)
+            .unwrap_or(false);
+
         // lock the pending requests cache
         let Ok(mut pending_requests) = self.pending_requests.lock()
         else { tracing::error!("on_close() lock error"); return Ok(ezsockets::client::ClientCloseMode::Close); };
@@ -309,6 +451,7 @@ impl<Channel: ChannelPack> ezsockets::ClientExt for ClientHandler<Channel>
         // mark the client as disconnected
         // - We do this within the pending requests lock in order to synchronize with the client API.
         self.client_disconnected_count.fetch_add(1u16, Ordering::Release);
+        self.latest_slots.clear();
 
         // forward event to client owner
         if let Err(err) = self.client_event_sender.send(
@@ -322,29 +465,346 @@ impl<Channel: ChannelPack> ezsockets::ClientExt for ClientHandler<Channel>
         // clean up pending requests
         // - do this after sending the client report so request failures appear between client disconnected and client
         //   connected reports (except when the client is dying)
-        Self::clean_pending_requests(&mut pending_requests, &self.client_event_sender);
+        Self::clean_pending_requests(&mut pending_requests, &self.client_event_sender, &self.stats);
+
+        // don't reconnect if the client is leaving its server instance
+        if self.death_reason == DeathReason::InstanceChanged { return Ok(ezsockets::client::ClientCloseMode::Close); }
 
         // check auth token expiry
-        if let AuthRequest::Token{token} = self.auth.auth {
-            if token.is_expired() {
-                return Ok(ezsockets::client::ClientCloseMode::Close);
+        if self.auth_token_expired()
+        {
+            self.death_reason = DeathReason::AuthExpired;
+            return Ok(ezsockets::client::ClientCloseMode::Close);
+        }
+
+        // pick a new id if another client has our id
+        if id_collision && self.config.disambiguate_id
+        {
+            if let AuthRequest::None{ client_id: previous } = self.auth
+            {
+                self.change_client_id(previous)?;
+                self.death_reason = DeathReason::ConnectFailed;
+                return Ok(ezsockets::client::ClientCloseMode::Reconnect);
             }
         }
 
         // choose response
         match self.config.reconnect_on_server_close
         {
-            true  => Ok(ezsockets::client::ClientCloseMode::Reconnect),
-            false => Ok(ezsockets::client::ClientCloseMode::Close),
+            true  =>
+            {
+                self.death_reason = DeathReason::ConnectFailed;
+                Ok(ezsockets::client::ClientCloseMode::Reconnect)
+            }
+            false =>
+            {
+                self.death_reason = match (auth_rejected, id_collision)
+                {
+                    (true, _)      => DeathReason::AuthRejected,
+                    (false, true)  => DeathReason::IdCollision,
+                    (false, false) => DeathReason::ClosedByServer,
+                };
+                Ok(ezsockets::client::ClientCloseMode::Close)
+            }
         }
     }
 }
 
 impl<Channel: ChannelPack> ClientHandler<Channel>
 {
+    /// Checks if the client's auth token has expired.
+    fn auth_token_expired(&self) -> bool
+    {
+        let AuthRequest::Token{ token } = &self.auth else { return false; };
+        token.is_expired()
+    }
+
+    /// Reports progress while initially connecting if another connection attempt will be made.
+    fn report_initial_connect_retry(&mut self) -> Result<(), ezsockets::Error>
+    {
+        let Some(failed_attempts) = &mut self.failed_initial_attempts else { return Ok(()); };
+        *failed_attempts += 1;
+        if *failed_attempts >= self.config.max_initial_connect_attempts { return Ok(()); }
+
+        let report = ClientReport::Connecting{
+                attempt       : *failed_attempts + 1,
+                next_retry_in : self.config.reconnect_interval,
+            };
+        if let Err(err) = self.client_event_sender.send(ClientEventFrom::<Channel>::Report(report))
+        {
+            tracing::debug!(?err, "failed to forward connection event to client");
+            return Err(Box::new(ClientError::SendError));
+        }
+
+        Ok(())
+    }
+
+    /// Picks a new client id after the server reported that another client has the current id.
+    fn change_client_id(&mut self, previous: u128) -> Result<(), ezsockets::Error>
+    {
+        let current = disambiguated_client_id(previous);
+        tracing::info!(previous, current, "client id collision, picking a new id");
+        self.auth = AuthRequest::None{ client_id: current };
+        if let Ok(mut client_id) = self.client_id.lock() { *client_id = current; }
+
+        let report = ClientReport::IdChanged{ previous, current };
+        if let Err(err) = self.client_event_sender.send(ClientEventFrom::<Channel>::Report(report))
+        {
+            tracing::debug!(?err, "failed to forward connection event to client");
+            return Err(Box::new(ClientError::SendError));
+        }
+
+        Ok(())
+    }
+
+    /// Handles a server instance id received after authenticating.
+    fn handle_instance(&mut self, instance_id: u64) -> Result<(), ezsockets::Error>
+    {
+        let Some(previous) = self.server_instance.replace(instance_id) else { return Ok(()); };
+        if previous == instance_id { return Ok(()); }
+
+        // close the client if it must stay on its original instance
+        if self.config.pin_instance
+        {
+            tracing::info!(previous, current = instance_id, "server instance changed, closing client");
+            self.server_instance = Some(previous);
+            self.death_reason = DeathReason::InstanceChanged;
+            let _ = self.client.close(Some(
+                ezsockets::CloseFrame{
+                    code   : ezsockets::CloseCode::Normal,
+                    reason : String::from("server instance changed")
+                }
+            ));
+            return Ok(());
+        }
+
+        // report the new instance
+        tracing::info!(previous, current = instance_id, "server instance changed");
+        let report = ClientReport::InstanceChanged{ previous, current: instance_id };
+        if let Err(err) = self.client_event_sender.send(ClientEventFrom::<Channel>::Report(report))
+        {
+            tracing::debug!(?err, "failed to forward connection event to client");
+            return Err(Box::new(ClientError::SendError));
+        }
+
+        Ok(())
+    }
+
+    /// Handles a binary frame from the server.
+    fn handle_binary(&mut self, bytes: Vec<u8>) -> Result<(), ezsockets::Error>
+    {
+        self.stats.record_received(bytes.len());
+
+        // ignore frames from a server instance the client is leaving
+        if self.death_reason == DeathReason::InstanceChanged { return Ok(()); }
+
+        // deserialize message
+        let server_msg = match self.codec.decode::<ClientMetaEventFrom<Channel>>(&bytes[..])
+        {
+            Ok(server_msg) => server_msg,
+            Err(err) =>
+            {
+                tracing::warn!(?err, "received server msg that failed to deserialize");
+                return Ok(());
+            }
+        };
+
+        // decide how to handle the message
+        let client_event = match server_msg
+        {
+            ClientMetaEventFrom::<Channel>::Msg(msg) =>
+            {
+                // msg
+                ClientEventFrom::<Channel>::Msg(msg)
+            }
+            ClientMetaEventFrom::<Channel>::ReliableMsg(msg, msg_id) =>
+            {
+                // acknowledge the message
+                // - the ack tells the server the message reached this session, not that the message was handled
+                // - acks count toward the server's rate limit, but they are never held back
+                let _ = self.rate_limit_guard.try_count();
+                let ack = ServerMetaEventFrom::<Channel>::MsgAck(msg_id);
+                match self.codec.send(&ack, |ser_ack| self.client.binary(ser_ack))
+                {
+                    Ok(Ok(_))  => (),
+                    Ok(Err(_)) => tracing::debug!(msg_id, "failed sending reliable message ack"),
+                    Err(_)     => tracing::error!(msg_id, "failed serializing reliable message ack"),
+                }
+
+                // msg
+                ClientEventFrom::<Channel>::Msg(msg)
+            }
+            ClientMetaEventFrom::<Channel>::Response(response, request_id, trace_id) =>
+            {
+                // discard message if request id is unknown
+                // - this should never happen
+                let Ok(mut pending_requests) = self.pending_requests.lock()
+                else { tracing::error!("Response lock error"); return Ok(()); };
+
+                if !pending_requests.set_status_and_remove(request_id, RequestStatus::Responded)
+                {
+                    tracing::error!(request_id, ?trace_id, "ignoring server response for unknown request");
+                    return Ok(());
+                }
+
+                // response
+                ClientEventFrom::<Channel>::Response(response, request_id)
+            }
+            ClientMetaEventFrom::<Channel>::ResponsePart(response, request_id) =>
+            {
+                // discard message if request id is unknown
+                // - this can happen if a partial response is sent after the final response
+                let Ok(pending_requests) = self.pending_requests.lock()
+                else { tracing::error!("ResponsePart lock error"); return Ok(()); };
+
+                if !pending_requests.is_pending(request_id)
+                {
+                    tracing::warn!(request_id, "ignoring server partial response for unknown request");
+                    return Ok(());
+                }
+
+                // partial response
+                ClientEventFrom::<Channel>::ResponsePart(response, request_id)
+            }
+            ClientMetaEventFrom::<Channel>::Progress(request_id, progress) =>
+            {
+                // discard invalid progress values
+                if !(0.0..=1.0).contains(&progress)
+                {
+                    tracing::warn!(request_id, progress, "ignoring invalid server progress update");
+                    return Ok(());
+                }
+
+                // update the request signal
+                // - this can fail if a progress update is sent after the final response
+                let Ok(mut pending_requests) = self.pending_requests.lock()
+                else { tracing::error!("Progress lock error"); return Ok(()); };
+
+                if !pending_requests.set_progress(request_id, progress)
+                { tracing::warn!(request_id, "ignoring server progress update for unknown request"); }
+
+                // progress updates are only exposed on request signals
+                return Ok(());
+            }
+            ClientMetaEventFrom::<Channel>::Instance(instance_id) =>
+            {
+                return self.handle_instance(instance_id);
+            }
+            ClientMetaEventFrom::<Channel>::TopicMsg(topic, msg) =>
+            {
+                // topic msg
+                ClientEventFrom::<Channel>::TopicMsg(topic, msg)
+            }
+            ClientMetaEventFrom::<Channel>::Unauthorized(request_id, missing) =>
+            {
+                // discard message if request id is unknown
+                // - this should never happen
+                if let Some(request_id) = request_id
+                {
+                    let Ok(mut pending_requests) = self.pending_requests.lock()
+                    else { tracing::error!("Unauthorized lock error"); return Ok(()); };
+
+                    if !pending_requests.set_status_and_remove(request_id, RequestStatus::Rejected)
+                    {
+                        tracing::error!(request_id, "ignoring server unauthorized rejection for unknown request");
+                        return Ok(());
+                    }
+                }
+
+                // unauthorized
+                ClientEventFrom::<Channel>::Unauthorized(request_id, missing)
+            }
+            ClientMetaEventFrom::<Channel>::Control(msg) =>
+            {
+                // control msg
+                ClientEventFrom::<Channel>::Control(msg)
+            }
+            ClientMetaEventFrom::<Channel>::RateLimit(rate_limit_config) =>
+            {
+                tracing::debug!(?rate_limit_config, "received server rate limit");
+                self.rate_limit_guard.set(rate_limit_config);
+                return Ok(());
+            }
+            ClientMetaEventFrom::<Channel>::Ack(request_id, trace_id) =>
+            {
+                // discard message if request id is unknown
+                // - this should never happen
+                let Ok(mut pending_requests) = self.pending_requests.lock()
+                else { tracing::error!("Ack lock error"); return Ok(()); };
+
+                if !pending_requests.set_status_and_remove(request_id, RequestStatus::Acknowledged)
+                {
+                    tracing::error!(request_id, ?trace_id, "ignoring server ack for unknown request");
+                    return Ok(());
+                }
+
+                // ack
+                ClientEventFrom::<Channel>::Ack(request_id)
+            }
+            ClientMetaEventFrom::<Channel>::AckWith(request_id, payload, trace_id) =>
+            {
+                // discard message if request id is unknown
+                // - this should never happen
+                let Ok(mut pending_requests) = self.pending_requests.lock()
+                else { tracing::error!("AckWith lock error"); return Ok(()); };
+
+                if !pending_requests.set_status_and_remove(request_id, RequestStatus::Acknowledged)
+                {
+                    tracing::error!(request_id, ?trace_id, "ignoring server ack for unknown request");
+                    return Ok(());
+                }
+
+                // ack with payload
+                ClientEventFrom::<Channel>::AckWith(payload, request_id)
+            }
+            ClientMetaEventFrom::<Channel>::Reject(request_id, trace_id) =>
+            {
+                // discard message if request id is unknown
+                // - this should never happen
+                let Ok(mut pending_requests) = self.pending_requests.lock()
+                else { tracing::error!("Reject lock error"); return Ok(()); };
+
+                if !pending_requests.set_status_and_remove(request_id, RequestStatus::Rejected)
+                {
+                    tracing::error!(request_id, ?trace_id, "ignoring server rejection for unknown request");
+                    return Ok(());
+                }
+
+                // rejection
+                ClientEventFrom::<Channel>::Reject(request_id)
+            }
+            ClientMetaEventFrom::<Channel>::RejectWith(request_id, reason, trace_id) =>
+            {
+                // discard message if request id is unknown
+                // - this should never happen
+                let Ok(mut pending_requests) = self.pending_requests.lock()
+                else { tracing::error!("RejectWith lock error"); return Ok(()); };
+
+                if !pending_requests.set_status_and_remove(request_id, RequestStatus::Rejected)
+                {
+                    tracing::error!(request_id, ?trace_id, "ignoring server rejection for unknown request");
+                    return Ok(());
+                }
+
+                // rejection with reason
+                ClientEventFrom::<Channel>::RejectWith(reason, request_id)
+            }
+        };
+
+        // forward to client owner
+        if let Err(err) = self.client_event_sender.send(client_event)
+        {
+            tracing::debug!(?err, "failed to forward server message to client");
+            return Err(Box::new(ClientError::SendError));
+        }
+
+        Ok(())
+    }
+
     fn clean_pending_requests(
         pending_requests    : &mut PendingRequestTracker,
-        client_event_sender : &crossbeam::channel::Sender<ClientEventFrom<Channel>>
+        client_event_sender : &ClientEventSender<Channel>,
+        stats               : &ClientStatsCounters,
     ){
         for failed_req in pending_requests.drain_failed_requests()
         {
@@ -352,6 +812,7 @@ impl<Channel: ChannelPack> ClientHandler<Channel>
             {
                 RequestStatus::SendFailed =>
                 {
+                    stats.record_send_failed();
                     if let Err(err) = client_event_sender.send(ClientEventFrom::<Channel>::SendFailed(failed_req.id()))
                     {
                         tracing::debug!(?err, "failed to forward client report to client");
@@ -359,6 +820,7 @@ impl<Channel: ChannelPack> ClientHandler<Channel>
                 }
                 RequestStatus::ResponseLost =>
                 {
+                    stats.record_response_lost();
                     if let Err(err) = client_event_sender.send(ClientEventFrom::<Channel>::ResponseLost(failed_req.id()))
                     {
                         tracing::debug!(?err, "failed to forward client report to client");
@@ -372,9 +834,10 @@ impl<Channel: ChannelPack> ClientHandler<Channel>
         }
     }
 
-    fn final_request_cleanup(
+    pub(crate) fn final_request_cleanup(
         pending_requests    : &mut PendingRequestTracker,
-        client_event_sender : &crossbeam::channel::Sender<ClientEventFrom<Channel>>
+        client_event_sender : &ClientEventSender<Channel>,
+        stats               : &ClientStatsCounters,
     ) -> Vec<u64>
     {
         let mut aborted_reqs = Vec::new();
@@ -385,6 +848,7 @@ impl<Channel: ChannelPack> ClientHandler<Channel>
             {
                 RequestStatus::SendFailed =>
                 {
+                    stats.record_send_failed();
                     if let Err(err) = client_event_sender.send(ClientEventFrom::<Channel>::SendFailed(failed_req.id()))
                     {
                         tracing::debug!(?err, "failed to forward client report to client");
@@ -392,6 +856,7 @@ impl<Channel: ChannelPack> ClientHandler<Channel>
                 }
                 RequestStatus::ResponseLost =>
                 {
+                    stats.record_response_lost();
                     if let Err(err) = client_event_sender.send(ClientEventFrom::<Channel>::ResponseLost(failed_req.id()))
                     {
                         tracing::debug!(?err, "failed to forward client report to client");
@@ -418,6 +883,7 @@ impl<Channel: ChannelPack> Drop for ClientHandler<Channel>
     fn drop(&mut self)
     {
         tracing::info!("dropping client");
+        self.latest_slots.clear();
 
         // lock the pending requests cache
         let Ok(mut pending_requests) = self.pending_requests.lock()
@@ -425,11 +891,20 @@ impl<Channel: ChannelPack> Drop for ClientHandler<Channel>
 
         // abort all pending requests
         // - do this before the client report so IsDead is the last event emitted
-        let aborted_reqs = Self::final_request_cleanup(&mut pending_requests, &self.client_event_sender);
+        let aborted_reqs = Self::final_request_cleanup(&mut pending_requests, &self.client_event_sender, &self.stats);
+
+        // decide why the client died
+        // - closing the client takes precedence, since the backend may be mid-reconnect when the client is closed
+        let death_reason = match self.closed_by_self.load(Ordering::Acquire)
+        {
+            true  => DeathReason::ClosedBySelf,
+            false => self.death_reason.clone(),
+        };
+        tracing::info!(?death_reason, "client died");
 
         // forward event to client owner
         if let Err(err) = self.client_event_sender.send(
-                ClientEventFrom::<Channel>::Report(ClientReport::IsDead(aborted_reqs))
+                ClientEventFrom::<Channel>::Report(ClientReport::IsDead(death_reason, aborted_reqs))
             )
         {
             // failing may not be an error since the owning client could have been dropped
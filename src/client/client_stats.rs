@@ -0,0 +1,108 @@
+//local shortcuts
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Totals accumulated by a [`Client`](crate::Client) over its lifetime.
+///
+/// These are intended for analytics, e.g. to measure real-world connection quality across many clients.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct ClientStats
+{
+    /// Number of times the client reconnected after its first connection.
+    pub reconnects: u64,
+    /// Number of [`ClientEvent::SendFailed`](crate::ClientEvent::SendFailed) events emitted.
+    pub send_failed: u64,
+    /// Number of [`ClientEvent::ResponseLost`](crate::ClientEvent::ResponseLost) events emitted.
+    pub response_lost: u64,
+    /// Number of messages, text messages, control messages, and requests forwarded to the socket.
+    ///
+    /// Internal frames (e.g. authentication and acknowledgements) are not counted.
+    pub msgs_sent: u64,
+    /// Number of bytes in [`Self::msgs_sent`].
+    pub bytes_sent: u64,
+    /// Number of binary frames and text messages received from the server.
+    ///
+    /// Text heartbeats are not counted.
+    pub msgs_received: u64,
+    /// Number of bytes in [`Self::msgs_received`].
+    pub bytes_received: u64,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct ClientStatsInner
+{
+    reconnects     : AtomicU64,
+    send_failed    : AtomicU64,
+    response_lost  : AtomicU64,
+    msgs_sent      : AtomicU64,
+    bytes_sent     : AtomicU64,
+    msgs_received  : AtomicU64,
+    bytes_received : AtomicU64,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Shared counters for [`ClientStats`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ClientStatsCounters
+{
+    inner: Arc<ClientStatsInner>,
+}
+
+impl ClientStatsCounters
+{
+    pub(crate) fn record_reconnect(&self)
+    {
+        self.inner.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_send_failed(&self)
+    {
+        self.inner.send_failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_response_lost(&self)
+    {
+        self.inner.response_lost.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_sent(&self, bytes: usize)
+    {
+        self.inner.msgs_sent.fetch_add(1, Ordering::Relaxed);
+        self.inner.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_received(&self, bytes: usize)
+    {
+        self.inner.msgs_received.fetch_add(1, Ordering::Relaxed);
+        self.inner.bytes_received.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn msgs_sent(&self) -> u64
+    {
+        self.inner.msgs_sent.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn stats(&self) -> ClientStats
+    {
+        ClientStats{
+            reconnects     : self.inner.reconnects.load(Ordering::Relaxed),
+            send_failed    : self.inner.send_failed.load(Ordering::Relaxed),
+            response_lost  : self.inner.response_lost.load(Ordering::Relaxed),
+            msgs_sent      : self.inner.msgs_sent.load(Ordering::Relaxed),
+            bytes_sent     : self.inner.bytes_sent.load(Ordering::Relaxed),
+            msgs_received  : self.inner.msgs_received.load(Ordering::Relaxed),
+            bytes_received : self.inner.bytes_received.load(Ordering::Relaxed),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
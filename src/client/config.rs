@@ -1,15 +1,17 @@
 //local shortcuts
+use crate::*;
 
 //third-party shortcuts
 
 //standard shortcuts
 use core::fmt::Debug;
+use std::sync::Arc;
 use std::time::Duration;
 
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Config for the [`Client`](crate::Client).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ClientConfig
 {
     /// Try to reconnect if the client is disconnected or fails to connect. Defaults to `true`.
@@ -39,6 +41,98 @@ pub struct ClientConfig
     pub heartbeat_interval: Duration,
     /// Duration after which a socket will shut down if the connection is inactive. Defaults to 10 seconds
     pub keepalive_timeout: Duration,
+    /// Replaces the payload of heartbeat pings sent to the server. Defaults to `None`.
+    ///
+    /// Native clients normally send websocket ping frames. If this, [`Self::heartbeat_callback`],
+    /// [`Self::quality_callback`], or [`Self::keepalive_miss_callback`] is set, then text pings will be sent instead
+    /// (see the 'Wire format' section of the README).
+    pub heartbeat_ping_fn: Option<Arc<dyn HeartbeatPingFn>>,
+    /// Called whenever a heartbeat pong is received from the server. Defaults to `None`.
+    ///
+    /// The callback is invoked on the client's internal task, so it should not block.
+    pub heartbeat_callback: Option<Arc<dyn HeartbeatCallback>>,
+    /// Called with a [`ConnectionQuality`] report once per [`Self::heartbeat_interval`] while connected. Defaults to
+    /// `None`.
+    ///
+    /// The callback is invoked on the client's internal task, so it should not block.
+    pub quality_callback: Option<Arc<dyn ConnectionQualityCallback>>,
+    /// Number of consecutive unanswered heartbeats that triggers [`Self::keepalive_miss_callback`]. Defaults to 1.
+    ///
+    /// This should be less than `keepalive_timeout / heartbeat_interval`, otherwise the connection will be closed
+    /// before the callback is invoked. Setting this to zero disables the callback.
+    pub keepalive_miss_threshold: u32,
+    /// Called with the number of missed heartbeats when [`Self::keepalive_miss_threshold`] heartbeats have gone
+    /// unanswered. Defaults to `None`.
+    ///
+    /// The callback is invoked at most once until the next pong is received. It is invoked on the client's internal
+    /// task, so it should not block.
+    pub keepalive_miss_callback: Option<Arc<dyn KeepaliveMissCallback>>,
+    /// Replace heartbeat pings with unsolicited websocket pong frames if messages were sent to the server since the
+    /// previous heartbeat. Defaults to `false`.
+    ///
+    /// Outgoing messages already keep the connection alive, and the server does not answer unsolicited pongs, so this
+    /// avoids waking up the client to receive pongs while application traffic is flowing (e.g. on battery-constrained
+    /// devices). Pongs are only received for heartbeats sent while idle, so [`Self::heartbeat_callback`] and
+    /// round-trip times are only updated while idle, and [`Self::keepalive_miss_callback`] may report missed
+    /// heartbeats while traffic is flowing. Ignored on WASM, since browsers can't send websocket control frames.
+    pub suppress_active_heartbeats: bool,
+    /// Encoding of messages sent to and received from the server. Defaults to [`WireFormat::Bincode`].
+    ///
+    /// This must match the server's wire format, otherwise connection attempts will be rejected. Making a client with
+    /// a format that isn't [supported](WireFormat::is_supported) by the enabled features will panic (or fail with
+    /// [`ClientBuildError::UnsupportedWireFormat`] if using a [`ClientBuilder`]).
+    pub wire_format: WireFormat,
+    /// Limits for decoding messages from the server. See [`DecodeLimits`] for defaults.
+    pub decode_limits: DecodeLimits,
+    /// Cipher for encrypting message payloads. Defaults to `None`.
+    ///
+    /// This must be equivalent to the server's cipher.
+    pub cipher: Option<Arc<dyn PayloadCipher>>,
+    /// Checksum for detecting corrupted frames. Defaults to `None`.
+    ///
+    /// This must match the server's checksum.
+    pub checksum: Option<FrameChecksum>,
+    /// Allow text messages to be sent to and received from the server (see [`ClientEvent::Text`]). Defaults to
+    /// `false`.
+    ///
+    /// Text messages are sent as raw websocket text frames, so they bypass the [`Self::wire_format`] and
+    /// [`Self::checksum`]. They can't be encrypted, so making a client with text messages and a [`Self::cipher`] will
+    /// panic (or fail with [`ClientBuildError::TextWithCipher`] if using a [`ClientBuilder`]). The server must also
+    /// allow text messages.
+    pub text_messages: bool,
+    /// Tenant key sent to the server when authenticating. Defaults to `None`.
+    ///
+    /// Servers with a tenant registry (see `ServerConfig::tenants`) will reject clients that don't have a registered
+    /// tenant key.
+    pub tenant: Option<String>,
+    /// Only stay connected to the server instance the client first connected to. Defaults to `false`.
+    ///
+    /// Servers identify themselves with `ServerConfig::instance_id`. If this is set and the client reconnects to a
+    /// different instance, then the client will close itself and die with [`DeathReason::InstanceChanged`] instead
+    /// of emitting [`ClientReport::InstanceChanged`].
+    pub pin_instance: bool,
+    /// Pick a new random client id and reconnect if the server closes the client because another client is connected
+    /// with the same id. Defaults to `false`.
+    ///
+    /// Only applies to [`AuthRequest::None`], since other auth requests can't change the client's id. The new id is
+    /// reported with [`ClientReport::IdChanged`] and read from [`Client::id()`]. If this is not set and the client
+    /// does not reconnect, then it dies with [`DeathReason::IdCollision`].
+    pub disambiguate_id: bool,
+    /// Fail sends that would exceed the server's rate limit instead of sending them. Defaults to `false`.
+    ///
+    /// Servers tell clients their rate limit (see `ServerConfig::rate_limit_config`) after they authenticate, so
+    /// clients can avoid being disconnected for violating it. If enabled, messages, text messages, control messages,
+    /// requests, subscriptions, and unsubscriptions that would exceed the rate limit fail immediately with a warning.
+    /// The guard is approximate since it can't observe network delays. Latest-only messages
+    /// (see [`Client::send_latest()`]) are not guarded or counted. See [`Client::server_rate_limit()`].
+    pub rate_limit_guard: bool,
+    /// Simulated network conditions applied to frames received by the client. Defaults to `None`.
+    ///
+    /// Simulated delays can reorder frames, which a [`Self::cipher`] would reject as replays, so making a client with a
+    /// simulator and a cipher will panic (or fail with [`ClientBuildError::NetsimWithCipher`] if using a
+    /// [`ClientBuilder`]).
+    #[cfg(feature = "netsim")]
+    pub netsim: Option<NetsimConfig>,
 }
 
 impl Default for ClientConfig
@@ -53,6 +147,23 @@ impl Default for ClientConfig
                 max_reconnect_attempts       : usize::MAX,
                 heartbeat_interval           : Duration::from_secs(5),
                 keepalive_timeout            : Duration::from_secs(10),
+                heartbeat_ping_fn            : None,
+                heartbeat_callback           : None,
+                quality_callback             : None,
+                keepalive_miss_threshold     : 1,
+                keepalive_miss_callback      : None,
+                suppress_active_heartbeats   : false,
+                wire_format                  : WireFormat::default(),
+                decode_limits                : DecodeLimits::default(),
+                cipher                       : None,
+                checksum                     : None,
+                text_messages                : false,
+                tenant                       : None,
+                pin_instance                 : false,
+                disambiguate_id              : false,
+                rate_limit_guard             : false,
+                #[cfg(feature = "netsim")]
+                netsim                       : None,
             }
     }
 }
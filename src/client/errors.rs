@@ -1,4 +1,5 @@
 //local shortcuts
+use crate::*;
 
 //third-party shortcuts
 
@@ -30,3 +31,56 @@ impl std::fmt::Display for ClientError
 impl std::error::Error for ClientError {}
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Errors returned by [`ClientBuilder::build()`](crate::ClientBuilder::build).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ClientBuildError
+{
+    /// No server url was set.
+    MissingUrl,
+    /// No auth request was set.
+    MissingAuth,
+    /// No connect message was set.
+    MissingConnectMsg,
+    /// The server url's scheme is not `ws` or `wss`.
+    InvalidScheme(String),
+    /// TLS is required but the server url's scheme is `ws`.
+    TlsRequired,
+    /// The heartbeat interval is not shorter than the keepalive timeout, so idle connections would time out.
+    HeartbeatNotBeforeKeepalive,
+    /// The max number of initial connection attempts is zero.
+    NoConnectAttempts,
+    /// The configured [`WireFormat`](crate::WireFormat) requires a crate feature that is disabled.
+    UnsupportedWireFormat(WireFormat),
+    /// Text messages are enabled along with a cipher. Text frames can't be encrypted, so they would bypass the cipher.
+    TextWithCipher,
+    /// A network simulator is enabled along with a cipher. Simulated delays can reorder frames, which the cipher
+    /// rejects as replays.
+    #[cfg(feature = "netsim")]
+    NetsimWithCipher,
+}
+
+impl std::fmt::Display for ClientBuildError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let _ = write!(f, "ClientBuildError::");
+        match self
+        {
+            ClientBuildError::MissingUrl                  => write!(f, "MissingUrl"),
+            ClientBuildError::MissingAuth                 => write!(f, "MissingAuth"),
+            ClientBuildError::MissingConnectMsg           => write!(f, "MissingConnectMsg"),
+            ClientBuildError::InvalidScheme(scheme)       => write!(f, "InvalidScheme({scheme})"),
+            ClientBuildError::TlsRequired                 => write!(f, "TlsRequired"),
+            ClientBuildError::HeartbeatNotBeforeKeepalive => write!(f, "HeartbeatNotBeforeKeepalive"),
+            ClientBuildError::NoConnectAttempts           => write!(f, "NoConnectAttempts"),
+            ClientBuildError::UnsupportedWireFormat(wire) => write!(f, "UnsupportedWireFormat({wire:?})"),
+            ClientBuildError::TextWithCipher              => write!(f, "TextWithCipher"),
+            #[cfg(feature = "netsim")]
+            ClientBuildError::NetsimWithCipher            => write!(f, "NetsimWithCipher"),
+        }
+    }
+}
+impl std::error::Error for ClientBuildError {}
+
+//-------------------------------------------------------------------------------------------------------------------
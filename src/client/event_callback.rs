@@ -0,0 +1,102 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(ClientEventFrom<Channel>)`.
+///
+/// Receives client events as soon as they are produced, for consumers that don't run a polling loop (e.g. headless
+/// tokio services). When a callback is set, events are passed to the callback *instead of* being returned by
+/// [`Client::next()`].
+///
+/// The callback is invoked inside the client backend (and inside [`Client::close()`]), so it should not block. It
+/// should also not call methods on the [`Client`] directly, since some events are emitted while the client's internal
+/// locks are held.
+///
+/// See [`ClientFactory::with_event_callback()`].
+pub trait ClientEventCallback<Channel: ChannelPack>: Fn(ClientEventFrom<Channel>) + Send + Sync + 'static {}
+impl<Channel, F> ClientEventCallback<Channel> for F
+where
+    Channel: ChannelPack,
+    F: Fn(ClientEventFrom<Channel>) + Send + Sync + 'static
+{}
+
+impl<Channel: ChannelPack> Debug for dyn ClientEventCallback<Channel>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("ClientEventCallback") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Forwards client events to the client owner, either via the client's event channel or via an event callback.
+#[derive(Debug)]
+pub(crate) struct ClientEventSender<Channel: ChannelPack>
+{
+    sender: crossbeam::channel::Sender<ClientEventFrom<Channel>>,
+    callback: Option<Arc<dyn ClientEventCallback<Channel>>>,
+    client_disconnected_count: Arc<AtomicU16>,
+    /// set when the client owner stops accepting events from the client backend
+    closed: Arc<AtomicBool>,
+}
+
+impl<Channel: ChannelPack> ClientEventSender<Channel>
+{
+    pub(crate) fn new(
+        sender                    : crossbeam::channel::Sender<ClientEventFrom<Channel>>,
+        callback                  : Option<Arc<dyn ClientEventCallback<Channel>>>,
+        client_disconnected_count : Arc<AtomicU16>,
+    ) -> Self
+    {
+        Self{ sender, callback, client_disconnected_count, closed: Arc::new(AtomicBool::new(false)) }
+    }
+
+    /// Discards all events forwarded after this is called.
+    pub(crate) fn close(&self)
+    {
+        self.closed.store(true, Ordering::Release);
+    }
+
+    /// Forwards an event.
+    ///
+    /// Events are silently discarded if the sender is closed.
+    pub(crate) fn send(
+        &self,
+        event: ClientEventFrom<Channel>
+    ) -> Result<(), crossbeam::channel::SendError<ClientEventFrom<Channel>>>
+    {
+        if self.closed.load(Ordering::Acquire) { return Ok(()); }
+        let Some(callback) = &self.callback else { return self.sender.send(event); };
+
+        // events passed to the callback are consumed immediately, so we mark the client as connected here instead
+        // of in `Client::next()`
+        if let ClientEventFrom::<Channel>::Report(ClientReport::Connected) = &event
+        {
+            self.client_disconnected_count.fetch_sub(1u16, Ordering::Release);
+        }
+
+        (callback)(event);
+        Ok(())
+    }
+}
+
+impl<Channel: ChannelPack> Clone for ClientEventSender<Channel>
+{
+    fn clone(&self) -> Self
+    {
+        Self{
+            sender                    : self.sender.clone(),
+            callback                  : self.callback.clone(),
+            client_disconnected_count : self.client_disconnected_count.clone(),
+            closed                    : self.closed.clone(),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,245 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const WAITING    : u8 = 0u8;
+const FORWARDED  : u8 = 1u8;
+const FAILED     : u8 = 2u8;
+const SUPERSEDED : u8 = 3u8;
+
+/// How often a slot's in-flight message is checked when a newer message is waiting behind it.
+pub(crate) const CLIENT_LATEST_SLOT_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The status of a message sent with [`Client::send_latest()`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LatestMsgStatus
+{
+    /// The message is waiting for the slot's previous message to be sent.
+    Waiting,
+    /// The message is sending.
+    Sending,
+    /// The message was sent.
+    Sent,
+    /// The message failed to send.
+    Failed,
+    /// The message was replaced by a newer message in the same slot before it could be sent.
+    Superseded,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct LatestMsgSignalInner
+{
+    status: AtomicU8,
+    message_signal: Mutex<Option<MessageSignal>>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the status of a message sent with [`Client::send_latest()`].
+#[derive(Debug, Clone)]
+pub struct LatestMsgSignal
+{
+    inner: Arc<LatestMsgSignalInner>,
+}
+
+impl LatestMsgSignal
+{
+    /// Makes a new waiting signal.
+    pub(crate) fn new() -> Self
+    {
+        Self{
+            inner: Arc::new(LatestMsgSignalInner{ status: AtomicU8::new(WAITING), message_signal: Mutex::new(None) })
+        }
+    }
+
+    /// Makes a new failed signal.
+    pub(crate) fn failed() -> Self
+    {
+        let signal = Self::new();
+        signal.set_status(FAILED);
+        signal
+    }
+
+    /// Gets the current status of the message.
+    pub fn status(&self) -> LatestMsgStatus
+    {
+        match self.inner.status.load(Ordering::Acquire)
+        {
+            WAITING    => LatestMsgStatus::Waiting,
+            FORWARDED  =>
+            {
+                let Ok(message_signal) = self.inner.message_signal.lock() else { return LatestMsgStatus::Failed; };
+                let Some(message_signal) = &*message_signal else { return LatestMsgStatus::Waiting; };
+
+                match message_signal.status()
+                {
+                    MessageStatus::Sending => LatestMsgStatus::Sending,
+                    MessageStatus::Sent    => LatestMsgStatus::Sent,
+                    MessageStatus::Failed  => LatestMsgStatus::Failed,
+                }
+            }
+            SUPERSEDED => LatestMsgStatus::Superseded,
+            _          => LatestMsgStatus::Failed,
+        }
+    }
+
+    /// Marks the message as forwarded to the client's socket.
+    fn set_forwarded(&self, message_signal: MessageSignal)
+    {
+        let Ok(mut slot) = self.inner.message_signal.lock() else { return; };
+        if self.inner.status.load(Ordering::Acquire) != WAITING { return; }
+        *slot = Some(message_signal);
+        self.inner.status.store(FORWARDED, Ordering::Release);
+    }
+
+    /// Sets the status of a waiting message.
+    fn set_status(&self, status: u8)
+    {
+        let _ = self.inner.status.compare_exchange(WAITING, status, Ordering::AcqRel, Ordering::Acquire);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct ClientLatestSlot
+{
+    /// the most recent message forwarded to the socket
+    in_flight: Option<LatestMsgSignal>,
+    /// the newest message waiting for the in-flight message to be sent
+    pending: Option<(Vec<u8>, LatestMsgSignal)>,
+    /// set when a flush has been scheduled for the pending message
+    flush_scheduled: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Latest-only send slots for a client connection.
+///
+/// Each slot holds at most one message that hasn't been flushed to the client's socket. Newer messages replace
+/// older ones that are still waiting.
+#[derive(Debug, Default)]
+pub(crate) struct ClientLatestSlots
+{
+    slots: HashMap<u64, ClientLatestSlot>,
+}
+
+impl ClientLatestSlots
+{
+    /// Sends a message in a slot, or holds it if the slot's previous message hasn't been flushed yet.
+    ///
+    /// Returns the in-flight message's signal if a flush needs to be scheduled for the held message.
+    pub(crate) fn send<E: ezsockets::ClientExt>(
+        &mut self,
+        client  : &ezsockets::Client<E>,
+        codec   : &WireCodec,
+        stats   : &ClientStatsCounters,
+        slot    : u64,
+        ser_msg : Vec<u8>,
+        signal  : LatestMsgSignal,
+    ) -> Option<LatestMsgSignal>
+    {
+        let entry = self.slots.entry(slot).or_default();
+
+        if let Some(in_flight) = entry.in_flight.as_ref().filter(|in_flight| Self::is_in_flight(in_flight))
+        {
+            if let Some((_, superseded)) = entry.pending.replace((ser_msg, signal))
+            {
+                tracing::trace!(slot, "replacing unsent latest-only message");
+                superseded.set_status(SUPERSEDED);
+            }
+            if entry.flush_scheduled { return None; }
+            entry.flush_scheduled = true;
+            return Some(in_flight.clone());
+        }
+
+        entry.in_flight = Self::forward(client, codec, stats, ser_msg, signal);
+        None
+    }
+
+    /// Sends the message held in a slot if the slot's previous message was flushed.
+    pub(crate) fn flush<E: ezsockets::ClientExt>(
+        &mut self,
+        client : &ezsockets::Client<E>,
+        codec  : &WireCodec,
+        stats  : &ClientStatsCounters,
+        slot   : u64
+    )
+    {
+        let Some(entry) = self.slots.get_mut(&slot) else { return; };
+
+        // a flush scheduled for a previous connection may arrive while this connection's message is in flight
+        if entry.in_flight.as_ref().map(Self::is_in_flight).unwrap_or(false) { return; }
+
+        entry.flush_scheduled = false;
+        let Some((ser_msg, signal)) = entry.pending.take() else { return; };
+        entry.in_flight = Self::forward(client, codec, stats, ser_msg, signal);
+    }
+
+    /// Fails all held messages and resets the slots.
+    pub(crate) fn clear(&mut self)
+    {
+        for (_, slot) in self.slots.drain()
+        {
+            let Some((_, signal)) = slot.pending else { continue; };
+            signal.set_status(FAILED);
+        }
+    }
+
+    /// Checks if a message is still being sent.
+    pub(crate) fn is_in_flight(signal: &LatestMsgSignal) -> bool
+    {
+        matches!(signal.status(), LatestMsgStatus::Waiting | LatestMsgStatus::Sending)
+    }
+
+    /// Encrypts a serialized message and forwards it to the socket, returning the message's signal if it was
+    /// forwarded.
+    fn forward<E: ezsockets::ClientExt>(
+        client  : &ezsockets::Client<E>,
+        codec   : &WireCodec,
+        stats   : &ClientStatsCounters,
+        ser_msg : Vec<u8>,
+        signal  : LatestMsgSignal,
+    ) -> Option<LatestMsgSignal>
+    {
+        let Ok(result) = codec.send_serialized(ser_msg, |ser_msg| (ser_msg.len(), client.binary(ser_msg)))
+        else
+        {
+            tracing::error!("failed encrypting latest-only message");
+            signal.set_status(FAILED);
+            return None;
+        };
+
+        match result
+        {
+            (len, Ok(message_signal)) =>
+            {
+                stats.record_sent(len);
+                signal.set_forwarded(message_signal);
+                Some(signal)
+            }
+            (_, Err(_)) =>
+            {
+                tracing::warn!("tried to send latest-only message to dead client");
+                signal.set_status(FAILED);
+                None
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,17 +1,29 @@
 //module tree
 mod client;
+mod client_builder;
 mod client_event;
 mod client_handler;
+mod client_stats;
 mod config;
+mod event_callback;
 mod errors;
+mod latest_slots;
 mod pending_request_tracker;
+mod rate_limit_guard;
+mod reconnector;
 mod request_signal;
 
 //API exports
 pub use client::*;
+pub use client_builder::*;
 pub use client_event::*;
 pub(crate) use client_handler::*;
+pub use client_stats::*;
 pub use config::*;
+pub use event_callback::*;
 pub use errors::*;
+pub use latest_slots::*;
 pub(crate) use pending_request_tracker::*;
+pub(crate) use rate_limit_guard::*;
+pub use reconnector::*;
 pub use request_signal::*;
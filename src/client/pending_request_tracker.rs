@@ -30,13 +30,44 @@ impl PendingRequestTracker
     }
 
     /// Add a new pending request.
-    pub(crate) fn add_request(&mut self, id: u64, message_signal: MessageSignal) -> RequestSignal
+    pub(crate) fn add_request(
+        &mut self,
+        id             : u64,
+        trace_id       : Option<String>,
+        message_signal : MessageSignal
+    ) -> RequestSignal
     {
-        let signal = RequestSignal::new(id, message_signal);
+        let signal = RequestSignal::new(id, message_signal).with_trace_id(trace_id);
         self.pending_requests.insert(id, signal.clone());
         signal
     }
 
+    /// Check if a request is pending.
+    pub(crate) fn is_pending(&self, request_id: u64) -> bool
+    {
+        self.pending_requests.contains_key(&request_id)
+    }
+
+    /// Set the progress of a pending request.
+    pub(crate) fn set_progress(&mut self, request_id: u64, progress: f32) -> bool
+    {
+        let Some(signal) = self.pending_requests.get(&request_id) else { return false; };
+        signal.inner().set(RequestStatus::InProgress(progress));
+
+        true
+    }
+
+    /// Get the ids and statuses of all pending requests, sorted by request id.
+    pub(crate) fn snapshot(&self) -> Vec<(u64, RequestStatus)>
+    {
+        let mut snapshot: Vec<(u64, RequestStatus)> = self.pending_requests
+            .iter()
+            .map(|(id, signal)| (*id, signal.status()))
+            .collect();
+        snapshot.sort_unstable_by_key(|(id, _)| *id);
+        snapshot
+    }
+
     /// Set the status of a pending request and remove it from the tracker.
     pub(crate) fn set_status_and_remove(&mut self, request_id: u64, status: RequestStatus) -> bool
     {
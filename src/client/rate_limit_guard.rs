@@ -0,0 +1,68 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::sync::{Arc, Mutex};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Mirrors the rate limit of the client's server session (see [`ClientConfig::rate_limit_guard`]).
+///
+/// The server sends the session's rate limit after the client authenticates, and again if the rate limit changes.
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimitGuard
+{
+    /// config: reject sends that would exceed the rate limit
+    enabled: bool,
+    /// tracks frames sent in the current session
+    /// - `None` until the server's rate limit is received
+    tracker: Arc<Mutex<Option<RateLimitTracker>>>,
+}
+
+impl RateLimitGuard
+{
+    pub(crate) fn new(enabled: bool) -> Self
+    {
+        Self{ enabled, tracker: Arc::new(Mutex::new(None)) }
+    }
+
+    /// Sets the server's rate limit.
+    ///
+    /// The client's authentication message is counted, since the server counts it toward the session's first rate
+    /// limit. This makes the guard conservative if the rate limit changes mid-session.
+    pub(crate) fn set(&self, config: RateLimitConfig)
+    {
+        let Ok(mut tracker) = self.tracker.lock() else { tracing::error!("rate limit guard lock error"); return; };
+        let mut new_tracker = RateLimitTracker::new(config);
+        new_tracker.try_count_msg();
+        *tracker = Some(new_tracker);
+    }
+
+    /// Forgets the server's rate limit (e.g. when the client disconnects).
+    pub(crate) fn reset(&self)
+    {
+        let Ok(mut tracker) = self.tracker.lock() else { tracing::error!("rate limit guard lock error"); return; };
+        *tracker = None;
+    }
+
+    /// Gets the server's rate limit.
+    pub(crate) fn config(&self) -> Option<RateLimitConfig>
+    {
+        let Ok(tracker) = self.tracker.lock() else { tracing::error!("rate limit guard lock error"); return None; };
+        tracker.as_ref().map(RateLimitTracker::config)
+    }
+
+    /// Counts a frame that will be sent to the server.
+    ///
+    /// Returns `false` if the guard is enabled and sending the frame would exceed the server's rate limit.
+    pub(crate) fn try_count(&self) -> bool
+    {
+        let Ok(mut tracker) = self.tracker.lock() else { tracing::error!("rate limit guard lock error"); return true; };
+        let Some(tracker) = tracker.as_mut() else { return true; };
+        tracker.try_count_msg() || !self.enabled
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,182 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::sync::Arc;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(&DeathReason) -> Option<AuthRequest>`.
+///
+/// Provides auth for the replacement client when a [`ClientReconnector`]'s client dies. Return `None` if auth is not
+/// available yet (e.g. because a new [`AuthToken`] is being requested). The provider will be called again the next
+/// time [`ClientReconnector::next()`] is called.
+pub trait ReconnectorAuthProvider: Fn(&DeathReason) -> Option<AuthRequest> + Send + Sync + 'static {}
+impl<F> ReconnectorAuthProvider for F where F: Fn(&DeathReason) -> Option<AuthRequest> + Send + Sync + 'static {}
+
+impl Debug for dyn ReconnectorAuthProvider
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("ReconnectorAuthProvider") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// An event emitted by a [`ClientReconnector`].
+#[derive(Debug, Clone)]
+pub enum ClientReconnectorEvent<Channel: ChannelPack>
+{
+    /// An event from the current client.
+    Event(ClientEventFrom<Channel>),
+    /// The previous client died and was replaced by a new client.
+    ///
+    /// This is emitted after the previous client's [`ClientReport::IsDead`] event, and before any events from the
+    /// new client. Request ids from the previous client should be discarded, since the new client will reuse them.
+    ClientRecreated,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wraps a [`Client`] and automatically replaces it with a new client when it dies.
+///
+/// Dead clients cannot be revived, so this stores everything needed to make a fresh client. When the current client
+/// emits [`ClientReport::IsDead`], the next call to [`Self::next()`] will make a new client and emit
+/// [`ClientReconnectorEvent::ClientRecreated`].
+/// - Clients that die with [`DeathReason::ClosedBySelf`] or [`DeathReason::ConnectAborted`] are not replaced.
+/// - Clients are replaced at most once per [`ClientConfig::reconnect_interval`], to avoid spinning in a reconnect loop
+///   when the server is rejecting connections.
+///
+/// The current client can be accessed by deref, e.g. `reconnector.send(msg)`.
+///
+/// Factories made with [`ClientFactory::with_event_callback()`] are not supported, since the reconnector needs to
+/// observe [`ClientReport::IsDead`].
+#[derive(Debug)]
+#[cfg_attr(feature = "bevy", derive(bevy_ecs::system::Resource))]
+pub struct ClientReconnector<Channel: ChannelPack>
+{
+    /// factory for making clients
+    factory: ClientFactory<Channel>,
+    /// runtime for making clients
+    runtime_handle: enfync::builtin::Handle,
+    /// server url
+    url: url::Url,
+    /// config for making clients
+    config: ClientConfig,
+    /// provides auth for replacement clients
+    auth_provider: Arc<dyn ReconnectorAuthProvider>,
+
+    /// the current client
+    client: Client<Channel>,
+    /// reason the current client died, if it has died
+    death_reason: Option<DeathReason>,
+    /// when the current client was made
+    created_at: wasm_timer::Instant,
+}
+
+impl<Channel: ChannelPack> ClientReconnector<Channel>
+{
+    /// Makes a new reconnector and its first client.
+    ///
+    /// The `auth` is used for the first client. Replacement clients get auth from the `auth_provider`, and reuse the
+    /// dead client's connect message (see [`Client::set_connect_msg()`]).
+    pub fn new(
+        factory        : ClientFactory<Channel>,
+        runtime_handle : enfync::builtin::Handle,
+        url            : url::Url,
+        auth           : AuthRequest,
+        config         : ClientConfig,
+        connect_msg    : Channel::ConnectMsg,
+        auth_provider  : impl ReconnectorAuthProvider,
+    ) -> Self
+    {
+        if factory.has_event_callback()
+        { tracing::warn!("client reconnectors do not support factories with event callbacks"); }
+
+        let client = factory.new_client(
+                runtime_handle.clone(),
+                url.clone(),
+                auth,
+                config.clone(),
+                connect_msg,
+            );
+
+        Self{
+            factory,
+            runtime_handle,
+            url,
+            config,
+            auth_provider : Arc::new(auth_provider),
+            client,
+            death_reason  : None,
+            created_at    : wasm_timer::Instant::now(),
+        }
+    }
+
+    /// Tries to get the next event.
+    ///
+    /// Events from the current client are emitted until it dies, then this will try to replace the client.
+    pub fn next(&mut self) -> Option<ClientReconnectorEvent<Channel>>
+    {
+        // drain the current client
+        if let Some(event) = self.client.next()
+        {
+            if let ClientEventFrom::<Channel>::Report(ClientReport::IsDead(death_reason, _)) = &event
+            {
+                self.death_reason = Some(death_reason.clone());
+            }
+
+            return Some(ClientReconnectorEvent::Event(event));
+        }
+
+        // try to replace the client
+        // - we only do this after the dead client is fully drained so events from the old and new clients can't
+        //   interleave
+        match self.try_recreate()
+        {
+            true  => Some(ClientReconnectorEvent::ClientRecreated),
+            false => None,
+        }
+    }
+
+    /// Access the current client.
+    pub fn client(&self) -> &Client<Channel>
+    {
+        &self.client
+    }
+
+    /// Makes a new client if the current client is dead and should be replaced.
+    fn try_recreate(&mut self) -> bool
+    {
+        let Some(death_reason) = &self.death_reason else { return false; };
+        if matches!(death_reason, DeathReason::ClosedBySelf | DeathReason::ConnectAborted) { return false; }
+        if self.created_at.elapsed() < self.config.reconnect_interval { return false; }
+        let Some(auth) = (self.auth_provider)(death_reason) else { return false; };
+
+        tracing::info!(?death_reason, "recreating dead client");
+        self.client = self.factory.new_client(
+                self.runtime_handle.clone(),
+                self.url.clone(),
+                auth,
+                self.config.clone(),
+                self.client.current_connect_msg(),
+            );
+        self.death_reason = None;
+        self.created_at = wasm_timer::Instant::now();
+
+        true
+    }
+}
+
+impl<Channel: ChannelPack> std::ops::Deref for ClientReconnector<Channel>
+{
+    type Target = Client<Channel>;
+
+    fn deref(&self) -> &Client<Channel>
+    {
+        &self.client
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
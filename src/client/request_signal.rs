@@ -4,8 +4,11 @@
 
 //standard shortcuts
 use core::fmt::Debug;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicU8, Ordering};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+use std::task::{Context, Poll, Waker};
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -18,7 +21,7 @@ pub type MessageStatus = ezsockets::MessageStatus;
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Indicates the current status of a client request.
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum RequestStatus
 {
     /// The request is sending.
@@ -27,6 +30,10 @@ pub enum RequestStatus
     ///
     /// If disconnected while in this state, the request status will change to `ResponseLost`.
     Waiting,
+    /// The request was sent and the server reported progress in the range `[0.0, 1.0]`.
+    ///
+    /// See [`Server::report_progress()`](crate::Server::report_progress). This behaves like `Waiting`.
+    InProgress(f32),
     /// The server responded to the request.
     Responded,
     /// The server acknowledged the request and will not respond.
@@ -43,12 +50,49 @@ pub enum RequestStatus
     ResponseLost,
 }
 
+/// The final status of a client request.
+///
+/// See [`RequestSignal::completed()`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RequestStatusFinal
+{
+    /// The server responded to the request.
+    Responded,
+    /// The server acknowledged the request and will not respond.
+    Acknowledged,
+    /// The server rejected the request.
+    Rejected,
+    /// The request failed to send.
+    SendFailed,
+    /// The request was sent but the client disconnected from the server before we could receive a response.
+    ResponseLost,
+}
+
+impl From<RequestStatusFinal> for RequestStatus
+{
+    fn from(status: RequestStatusFinal) -> Self
+    {
+        match status
+        {
+            RequestStatusFinal::Responded    => RequestStatus::Responded,
+            RequestStatusFinal::Acknowledged => RequestStatus::Acknowledged,
+            RequestStatusFinal::Rejected     => RequestStatus::Rejected,
+            RequestStatusFinal::SendFailed   => RequestStatus::SendFailed,
+            RequestStatusFinal::ResponseLost => RequestStatus::ResponseLost,
+        }
+    }
+}
+
 //-------------------------------------------------------------------------------------------------------------------
 
 #[derive(Clone, Debug)]
 pub(crate) struct RequestSignalInner
 {
     signal: Arc<AtomicU8>,
+    /// Latest progress reported by the server (as `f32` bits).
+    progress: Arc<AtomicU32>,
+    /// Wakers waiting for the request to complete.
+    wakers: Arc<Mutex<Vec<Waker>>>,
 }
 
 impl RequestSignalInner {
@@ -58,6 +102,7 @@ impl RequestSignalInner {
             1u8 => RequestStatus::Responded,
             2u8 => RequestStatus::Acknowledged,
             3u8 => RequestStatus::Rejected,
+            5u8 => RequestStatus::InProgress(f32::from_bits(self.progress.load(Ordering::Acquire))),
             _   => RequestStatus::ResponseLost,
         }
     }
@@ -69,14 +114,43 @@ impl RequestSignalInner {
             RequestStatus::Acknowledged => self.signal.store(2u8, Ordering::Release),
             RequestStatus::Rejected     => self.signal.store(3u8, Ordering::Release),
             RequestStatus::ResponseLost => self.signal.store(4u8, Ordering::Release),
+            RequestStatus::InProgress(progress) =>
+            {
+                // progress doesn't complete the request, so there is no need to wake waiters
+                // - progress is stored first so readers that see the status also see the progress value
+                self.progress.store(progress.to_bits(), Ordering::Release);
+                self.signal.store(5u8, Ordering::Release);
+                return;
+            }
             _ => panic!("invalid request status sent to RequestSignalInner"),
         }
+
+        // wake tasks waiting for completion
+        // - We lock after storing the status so waiters that registered before the store are always woken.
+        let wakers = match self.wakers.lock()
+        {
+            Ok(mut wakers) => std::mem::take(&mut *wakers),
+            Err(_) => return,
+        };
+        for waker in wakers { waker.wake(); }
+    }
+
+    /// Registers a waker if `is_complete` returns `None`.
+    fn register<T>(&self, waker: &Waker, is_complete: impl Fn() -> Option<T>) -> Option<T> {
+        let Ok(mut wakers) = self.wakers.lock() else { return is_complete(); };
+        let result = is_complete();
+        if result.is_none() && !wakers.iter().any(|w| w.will_wake(waker)) { wakers.push(waker.clone()); }
+        result
     }
 }
 
 impl Default for RequestSignalInner { fn default() -> Self
 {
-    Self { signal: Arc::new(AtomicU8::new(0u8)) } }
+    Self {
+        signal   : Arc::new(AtomicU8::new(0u8)),
+        progress : Arc::new(AtomicU32::new(0u32)),
+        wakers   : Arc::new(Mutex::new(Vec::default())),
+    } }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -86,6 +160,7 @@ impl Default for RequestSignalInner { fn default() -> Self
 pub struct RequestSignal
 {
     request_id     : u64,
+    trace_id       : Option<Arc<str>>,
     message_signal : MessageSignal,
     request_signal : RequestSignalInner,
 }
@@ -97,6 +172,7 @@ impl RequestSignal
     {
         Self{
             request_id,
+            trace_id: None,
             message_signal,
             request_signal: RequestSignalInner::default(),
         }
@@ -108,6 +184,14 @@ impl RequestSignal
         self.request_id
     }
 
+    /// Get the trace id attached to the request, if any.
+    ///
+    /// See [`Client::request_traced()`](crate::Client::request_traced).
+    pub fn trace_id(&self) -> Option<&str>
+    {
+        self.trace_id.as_deref()
+    }
+
     /// Get the request status.
     pub fn status(&self) -> RequestStatus
     {
@@ -119,6 +203,42 @@ impl RequestSignal
         }
     }
 
+    /// Waits until the request has completed.
+    ///
+    /// The returned future can be awaited on any executor, including in WASM. Note that a response's contents must
+    /// still be read from the client's event stream.
+    ///
+    /// If a request fails to send, the future may not resolve until the client backend observes the failure (i.e.
+    /// when the client disconnects).
+    pub fn completed(&self) -> RequestCompletion
+    {
+        RequestCompletion{ signal: self.clone() }
+    }
+
+    /// Attaches a trace id to the signal.
+    pub(crate) fn with_trace_id(mut self, trace_id: Option<String>) -> Self
+    {
+        self.trace_id = trace_id.map(Arc::from);
+        self
+    }
+
+    /// Get the final request status if the request has completed.
+    ///
+    /// We read the inner status directly in case a response arrives before the message signal is marked as sent.
+    fn final_status(&self) -> Option<RequestStatusFinal>
+    {
+        if let MessageStatus::Failed = self.message_signal.status() { return Some(RequestStatusFinal::SendFailed); }
+
+        match self.inner().status()
+        {
+            RequestStatus::Responded    => Some(RequestStatusFinal::Responded),
+            RequestStatus::Acknowledged => Some(RequestStatusFinal::Acknowledged),
+            RequestStatus::Rejected     => Some(RequestStatusFinal::Rejected),
+            RequestStatus::ResponseLost => Some(RequestStatusFinal::ResponseLost),
+            _                           => None,
+        }
+    }
+
     /// Access the inner request signal tracker.
     pub(crate) fn inner(&self) -> &RequestSignalInner
     {
@@ -127,3 +247,33 @@ impl RequestSignal
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Future returned by [`RequestSignal::completed()`].
+#[derive(Debug)]
+pub struct RequestCompletion
+{
+    signal: RequestSignal,
+}
+
+impl Future for RequestCompletion
+{
+    type Output = RequestStatusFinal;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output>
+    {
+        match self.signal.inner().register(cx.waker(), || self.signal.final_status())
+        {
+            Some(status) => Poll::Ready(status),
+            None         => Poll::Pending,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the current status of a client request batch (see [`Client::request_batch()`](crate::Client::request_batch)).
+///
+/// A batch is tracked as one request, so it is responded to, acknowledged, or rejected as a whole.
+pub type BatchSignal = RequestSignal;
+
+//-------------------------------------------------------------------------------------------------------------------
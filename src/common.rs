@@ -1,4 +1,5 @@
 //local shortcuts
+use crate::*;
 
 //third-party shortcuts
 use serde::{Serialize, Deserialize};
@@ -23,8 +24,8 @@ pub trait ChannelPack: Clone + Debug + 'static
 {
     /// A client sends this to a server as part of connection requests.
     ///
-    /// Note that a client's connect message is defined when creating the client and can't be modified for
-    /// reconnect attempts.
+    /// Note that a client's connect message is defined when creating the client, and is resent on every reconnect
+    /// attempt. Use [`Client::set_connect_msg()`](crate::Client::set_connect_msg) to update it.
     type ConnectMsg: Clone + Debug + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static;
 
     /// A server one-shot message.
@@ -36,6 +37,59 @@ pub trait ChannelPack: Clone + Debug + 'static
     type ClientMsg: Clone + Debug + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static;
     /// A client request. The server may respond to it, acknowledge it, or reject it.
     type ClientRequest: Clone + Debug + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static;
+    /// A reason attached to a request rejection (see `Server::reject_with()`).
+    ///
+    /// Use `()` if rejections don't need reasons ([`channel_pack!`](crate::channel_pack) defaults to `()`).
+    type RejectReason: Clone + Debug + Send + Sync + Serialize + for<'de> Deserialize<'de> + 'static;
+
+    /// A fingerprint of the message types, which clients send to servers when connecting.
+    ///
+    /// Servers with a fingerprint reject clients that send a different fingerprint or don't send one, so client and
+    /// server binaries with mismatched message types are caught at connect time instead of failing to deserialize
+    /// messages.
+    ///
+    /// Defaults to `None`, which disables the check. Override this with [`shape_fingerprint()`] to fingerprint the
+    /// serde shapes of the message types, or with a hash of the message definitions (e.g. a schema file). Servers
+    /// that accept non-Rust clients must either disable the check or have those clients send the fingerprint.
+    fn fingerprint() -> Option<u32>
+    {
+        None
+    }
+
+    /// The scopes a client needs to send a message. Defaults to [`Scopes::NONE`].
+    ///
+    /// Servers reject messages from clients without the required scopes (see
+    /// [`ClientEvent::Unauthorized`](crate::ClientEvent::Unauthorized)).
+    fn client_msg_scopes(_msg: &Self::ClientMsg) -> Scopes
+    {
+        Scopes::NONE
+    }
+
+    /// Whether a client message may be dropped as a duplicate of a recent message. Defaults to `true`.
+    ///
+    /// Only applies if the server deduplicates client messages (see `ServerConfig::msg_dedup`). Return `false` for
+    /// messages that are legitimately repeated (e.g. inputs).
+    fn dedup_client_msg(_msg: &Self::ClientMsg) -> bool
+    {
+        true
+    }
+
+    /// The scopes a client needs to send a request. Defaults to [`Scopes::NONE`].
+    ///
+    /// Servers reject requests from clients without the required scopes (see
+    /// [`ClientEvent::Unauthorized`](crate::ClientEvent::Unauthorized)).
+    fn client_request_scopes(_request: &Self::ClientRequest) -> Scopes
+    {
+        Scopes::NONE
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Convert a fingerprint to a string.
+pub fn fingerprint_as_str(fingerprint: u32) -> String
+{
+    format!("{:08x}", fingerprint)
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -11,6 +11,25 @@ use std::fmt::Debug;
 
 pub(crate) const VERSION_MSG_KEY : &'static str = "v";
 pub(crate) const TYPE_MSG_KEY    : &'static str = "t";
+pub(crate) const FORMAT_MSG_KEY  : &'static str = "f";
+pub(crate) const HASH_MSG_KEY    : &'static str = "h";
+
+/// Prefix of text frames that carry [`ServerEvent::Text`]/[`ClientEvent::Text`] messages.
+///
+/// Other text frames are reserved for WASM ping/pong.
+pub(crate) const TEXT_MSG_PREFIX : &'static str = "text:";
+
+/// Reason in the close frame sent by clients that are closed with `Client::close()`.
+pub(crate) const CLIENT_DONE_CLOSE_REASON : &'static str = "client done";
+
+/// Max length of a websocket close frame's reason in bytes.
+pub(crate) const MAX_CLOSE_REASON_BYTES : usize = 123;
+
+/// Reason in the close frame sent to clients that fail authentication.
+pub(crate) const AUTH_REJECTED_CLOSE_REASON : &'static str = "invalid auth";
+
+/// Reason in the close frame sent to clients that authenticate with the id of an already-connected client.
+pub(crate) const ID_COLLISION_CLOSE_REASON : &'static str = "client already connected";
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -18,37 +37,58 @@ pub(crate) const TYPE_MSG_KEY    : &'static str = "t";
 pub(crate) struct ClientAuthMsg<ConnectMsg>
 {
     pub(crate) auth: AuthRequest,
+    pub(crate) tenant: Option<String>,
     pub(crate) msg: ConnectMsg,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
 /// A meta event that may be received by a client.
+///
+/// This is the top-level type of server-to-client frames (see the 'Wire format' section of the README).
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub(crate) enum ClientMetaEvent<ServerMsg, ServerResponse>
+pub(crate) enum ClientMetaEvent<ServerMsg, ServerResponse, RejectReason>
 {
     Msg(ServerMsg),
-    Response(ServerResponse, u64),
-    Ack(u64),
-    Reject(u64),
+    Response(ServerResponse, u64, Option<String>),
+    Ack(u64, Option<String>),
+    Reject(u64, Option<String>),
+    ReliableMsg(ServerMsg, u64),
+    ResponsePart(ServerResponse, u64),
+    Progress(u64, f32),
+    Instance(u64),
+    TopicMsg(String, ServerMsg),
+    Unauthorized(Option<u64>, Scopes),
+    Control(ControlMsg),
+    RateLimit(RateLimitConfig),
+    AckWith(u64, Vec<u8>, Option<String>),
+    RejectWith(u64, RejectReason, Option<String>),
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
 pub(crate) type ClientMetaEventFrom<Channel> = ClientMetaEvent<
     <Channel as ChannelPack>::ServerMsg,
-    <Channel as ChannelPack>::ServerResponse
+    <Channel as ChannelPack>::ServerResponse,
+    <Channel as ChannelPack>::RejectReason
 >;
 
 //-------------------------------------------------------------------------------------------------------------------
 
 /// A meta event that may be received by a server.
+///
+/// This is the top-level type of client-to-server frames (see the 'Wire format' section of the README).
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) enum ServerMetaEvent<ConnectMsg, ClientMsg, ClientRequest>
 {
     Authenticate(ClientAuthMsg<ConnectMsg>),
     Msg(ClientMsg),
-    Request(ClientRequest, u64),
+    Request(ClientRequest, u64, Option<String>),
+    MsgAck(u64),
+    Subscribe(String, u64),
+    Unsubscribe(String),
+    Control(ControlMsg),
+    RequestBatch(Vec<ClientRequest>, u64, Option<String>),
 }
 
 //-------------------------------------------------------------------------------------------------------------------
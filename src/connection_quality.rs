@@ -0,0 +1,184 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use wasm_timer::Instant;
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A snapshot of a connection's quality, derived from heartbeats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionQuality
+{
+    /// Smoothed round-trip time of heartbeats.
+    ///
+    /// Only available once a pong with the default ping payload has been received.
+    pub rtt: Option<Duration>,
+    /// Smoothed round-trip time variation of heartbeats.
+    ///
+    /// Only available once a pong with the default ping payload has been received.
+    pub rtt_var: Option<Duration>,
+    /// Number of heartbeat intervals that have elapsed since the last pong (or since the connection was made if no
+    /// pongs have been received).
+    pub missed_heartbeats: u32,
+    /// Time since the last message was received from the peer.
+    pub last_recv: Duration,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(ConnectionQuality)`.
+///
+/// Called by clients once per heartbeat interval while connected.
+pub trait ConnectionQualityCallback: Fn(ConnectionQuality) + Send + Sync + 'static {}
+impl<F> ConnectionQualityCallback for F where F: Fn(ConnectionQuality) + Send + Sync + 'static {}
+
+impl Debug for dyn ConnectionQualityCallback
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("ConnectionQualityCallback") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(ClientId, ConnectionQuality)`.
+///
+/// Called by servers once per heartbeat interval for each authenticated client.
+pub trait ServerConnectionQualityCallback: Fn(ClientId, ConnectionQuality) + Send + Sync + 'static {}
+impl<F> ServerConnectionQualityCallback for F where F: Fn(ClientId, ConnectionQuality) + Send + Sync + 'static {}
+
+impl Debug for dyn ServerConnectionQualityCallback
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.write_str("ServerConnectionQualityCallback")
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(u32)`.
+///
+/// Called by clients with the number of missed heartbeats when the server stops answering heartbeats (see
+/// `ClientConfig::keepalive_miss_threshold`).
+pub trait KeepaliveMissCallback: Fn(u32) + Send + Sync + 'static {}
+impl<F> KeepaliveMissCallback for F where F: Fn(u32) + Send + Sync + 'static {}
+
+impl Debug for dyn KeepaliveMissCallback
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("KeepaliveMissCallback") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(ClientId, u32)`.
+///
+/// Called by servers with the number of missed heartbeats when a client stops answering heartbeats (see
+/// `ServerConfig::keepalive_miss_threshold`).
+pub trait ServerKeepaliveMissCallback: Fn(ClientId, u32) + Send + Sync + 'static {}
+impl<F> ServerKeepaliveMissCallback for F where F: Fn(ClientId, u32) + Send + Sync + 'static {}
+
+impl Debug for dyn ServerKeepaliveMissCallback
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("ServerKeepaliveMissCallback") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks heartbeat statistics for a connection.
+///
+/// Round-trip times are smoothed as in RFC 6298.
+#[derive(Debug)]
+pub(crate) struct ConnectionQualityTracker
+{
+    rtt: Option<Duration>,
+    rtt_var: Option<Duration>,
+    last_pong: Instant,
+    last_recv: Instant,
+    /// set when missed heartbeats were reported, cleared when a pong arrives
+    miss_reported: bool,
+}
+
+impl ConnectionQualityTracker
+{
+    /// Resets the tracker for a new connection.
+    pub(crate) fn reset(&mut self)
+    {
+        *self = Self::default();
+    }
+
+    /// Records that a message was received from the peer.
+    pub(crate) fn record_recv(&mut self)
+    {
+        self.last_recv = Instant::now();
+    }
+
+    /// Records a heartbeat pong received from the peer.
+    pub(crate) fn record_pong(&mut self, pong: &HeartbeatPong)
+    {
+        let now = Instant::now();
+        self.last_pong     = now;
+        self.last_recv     = now;
+        self.miss_reported = false;
+
+        let Some(sample) = pong.rtt else { return; };
+        let (rtt, rtt_var) = match (self.rtt, self.rtt_var)
+        {
+            (Some(rtt), Some(rtt_var)) =>
+            {
+                let deviation = if rtt > sample { rtt - sample } else { sample - rtt };
+                ((rtt * 7 + sample) / 8, (rtt_var * 3 + deviation) / 4)
+            }
+            _ => (sample, sample / 2),
+        };
+        self.rtt     = Some(rtt);
+        self.rtt_var = Some(rtt_var);
+    }
+
+    /// Makes a quality report for the current time.
+    pub(crate) fn report(&self, heartbeat_interval: Duration) -> ConnectionQuality
+    {
+        ConnectionQuality{
+                rtt               : self.rtt,
+                rtt_var           : self.rtt_var,
+                missed_heartbeats : self.missed_heartbeats(heartbeat_interval),
+                last_recv         : self.last_recv.elapsed(),
+            }
+    }
+
+    /// Returns the number of missed heartbeats if it reached `threshold` and hasn't been reported since the last pong.
+    pub(crate) fn check_missed(&mut self, heartbeat_interval: Duration, threshold: u32) -> Option<u32>
+    {
+        if self.miss_reported || threshold == 0 { return None; }
+
+        let missed_heartbeats = self.missed_heartbeats(heartbeat_interval);
+        if missed_heartbeats < threshold { return None; }
+
+        self.miss_reported = true;
+        Some(missed_heartbeats)
+    }
+
+    /// Number of heartbeat intervals since the last pong.
+    fn missed_heartbeats(&self, heartbeat_interval: Duration) -> u32
+    {
+        match heartbeat_interval.as_millis()
+        {
+            0        => 0,
+            interval => (self.last_pong.elapsed().as_millis() / interval).min(u32::MAX as u128) as u32,
+        }
+    }
+}
+
+impl Default for ConnectionQualityTracker
+{
+    fn default() -> Self
+    {
+        let now = Instant::now();
+        Self{ rtt: None, rtt_var: None, last_pong: now, last_recv: now, miss_reported: false }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,38 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// An application-defined control message.
+///
+/// Control messages carry out-of-band metadata (e.g. feature flags or capability negotiation) that doesn't belong in
+/// a [`ChannelPack`](crate::ChannelPack). They are sent with `Client::send_control()`/`Server::send_control()` and
+/// received as `ServerEvent::Control`/`ClientEvent::Control`.
+///
+/// The serialization of control messages does not depend on the channel pack, so adding new control messages does
+/// not require changing the protocol version. Use [`Self::kind`] to tell control messages apart, and ignore unknown
+/// kinds so older peers stay compatible.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ControlMsg
+{
+    /// Application-defined kind of the control message (e.g. `"feature_flags"`).
+    pub kind: String,
+    /// The message's payload. The payload's encoding is defined by the application.
+    pub payload: Vec<u8>,
+}
+
+impl ControlMsg
+{
+    /// Makes a new control message.
+    pub fn new(kind: impl Into<String>, payload: impl Into<Vec<u8>>) -> Self
+    {
+        Self{ kind: kind.into(), payload: payload.into() }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
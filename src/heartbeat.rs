@@ -0,0 +1,65 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A heartbeat pong received from a peer.
+#[derive(Debug, Clone)]
+pub struct HeartbeatPong
+{
+    /// The ping payload echoed back by the peer.
+    pub payload: String,
+    /// Round-trip time of the heartbeat.
+    ///
+    /// Only available if pings use the default payload (a millisecond timestamp), i.e. if no custom
+    /// `heartbeat_ping_fn` is set. Custom payloads are never interpreted as timestamps.
+    pub rtt: Option<Duration>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(Duration) -> String`.
+///
+/// Makes the payload of heartbeat pings from the current time (as a duration since `UNIX_EPOCH`). The default payload
+/// is the time in milliseconds.
+pub trait HeartbeatPingFn: Fn(Duration) -> String + Send + Sync + 'static {}
+impl<F> HeartbeatPingFn for F where F: Fn(Duration) -> String + Send + Sync + 'static {}
+
+impl Debug for dyn HeartbeatPingFn
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("HeartbeatPingFn") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(HeartbeatPong)`.
+///
+/// Called by clients whenever a heartbeat pong is received from the server.
+pub trait HeartbeatCallback: Fn(HeartbeatPong) + Send + Sync + 'static {}
+impl<F> HeartbeatCallback for F where F: Fn(HeartbeatPong) + Send + Sync + 'static {}
+
+impl Debug for dyn HeartbeatCallback
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("HeartbeatCallback") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(ClientId, HeartbeatPong)`.
+///
+/// Called by servers whenever a heartbeat pong is received from an authenticated client.
+pub trait ServerHeartbeatCallback: Fn(ClientId, HeartbeatPong) + Send + Sync + 'static {}
+impl<F> ServerHeartbeatCallback for F where F: Fn(ClientId, HeartbeatPong) + Send + Sync + 'static {}
+
+impl Debug for dyn ServerHeartbeatCallback
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("ServerHeartbeatCallback") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
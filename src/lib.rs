@@ -7,10 +7,25 @@ use crate as bevy_simplenet;
 
 //module tree
 mod authentication;
+mod channel_pack_macro;
 mod common;
 mod common_internal;
+mod connection_quality;
+mod control_msg;
+mod heartbeat;
+#[cfg(feature = "lobby")]
+mod lobby;
+mod message_shape;
+#[cfg(feature = "netsim")]
+mod netsim;
+mod payload_cipher;
 mod rate_limiter;
+mod scopes;
 mod text_ping_pong;
+mod time_source;
+#[cfg(feature = "transport")]
+mod transport;
+mod wire;
 
 #[cfg(feature = "client")]
 mod client;
@@ -18,15 +33,42 @@ mod client;
 #[cfg(feature = "server")]
 mod server;
 
+#[cfg(feature = "test_utils")]
+mod mock_server;
+#[cfg(feature = "test_utils")]
+mod test_utils;
+
 //API exports
 pub use authentication::*;
 pub use common::*;
 pub(crate) use common_internal::*;
+pub use connection_quality::*;
+pub use control_msg::*;
+pub use heartbeat::*;
+#[cfg(feature = "lobby")]
+pub use lobby::*;
+pub use message_shape::*;
+#[cfg(feature = "netsim")]
+pub use netsim::*;
+pub use payload_cipher::*;
 pub use rate_limiter::*;
+pub use scopes::*;
 pub(crate) use text_ping_pong::*;
+pub use time_source::*;
+#[cfg(feature = "transport")]
+pub use transport::*;
+pub use wire::*;
 
 #[cfg(feature = "client")]
 pub use client::*;
 
 #[cfg(feature = "server")]
 pub use server::*;
+
+#[cfg(feature = "test_utils")]
+pub use mock_server::*;
+#[cfg(feature = "test_utils")]
+pub use test_utils::*;
+
+#[doc(hidden)]
+pub use paste;
@@ -0,0 +1,337 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use serde::{Deserialize, Serialize};
+
+//standard shortcuts
+#[cfg(feature = "server")]
+use std::collections::HashMap;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Id of a lobby.
+pub type LobbyId = u64;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A lobby request sent by a client.
+///
+/// Add this to your [`ChannelPack::ClientRequest`] (e.g. as an enum variant with a `From` impl), and forward it to
+/// [`Lobbies::handle_request()`] on the server.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub enum LobbyRequest
+{
+    /// Creates a lobby with room for `capacity` members. The requesting client becomes the lobby's host.
+    Create{ capacity: u32 },
+    /// Joins a lobby.
+    Join(LobbyId),
+    /// Leaves the client's current lobby.
+    Leave,
+    /// Sets the client's ready state in its current lobby.
+    SetReady(bool),
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A member of a lobby.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct LobbyMember
+{
+    /// The member's client id.
+    pub client_id: ClientId,
+    /// Indicates if the member is ready.
+    pub ready: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The state of a lobby.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct LobbyInfo
+{
+    /// The lobby's id.
+    pub id: LobbyId,
+    /// The lobby's host.
+    pub host: ClientId,
+    /// The maximum number of members.
+    pub capacity: u32,
+    /// The lobby's members, ordered by when they joined.
+    pub members: Vec<LobbyMember>,
+}
+
+impl LobbyInfo
+{
+    /// Checks if the lobby has no room for more members.
+    pub fn is_full(&self) -> bool
+    {
+        self.members.len() >= self.capacity as usize
+    }
+
+    /// Checks if all members are ready.
+    pub fn all_ready(&self) -> bool
+    {
+        self.members.iter().all(|member| member.ready)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Errors returned in response to [`LobbyRequest`]s.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LobbyError
+{
+    /// The client is already in a lobby.
+    AlreadyInLobby,
+    /// The client is not in a lobby.
+    NotInLobby,
+    /// The lobby does not exist.
+    UnknownLobby,
+    /// The lobby is full.
+    LobbyFull,
+    /// Lobbies must have room for at least one member.
+    InvalidCapacity,
+}
+
+impl std::fmt::Display for LobbyError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let _ = write!(f, "LobbyError::");
+        match self
+        {
+            LobbyError::AlreadyInLobby  => write!(f, "AlreadyInLobby"),
+            LobbyError::NotInLobby      => write!(f, "NotInLobby"),
+            LobbyError::UnknownLobby    => write!(f, "UnknownLobby"),
+            LobbyError::LobbyFull       => write!(f, "LobbyFull"),
+            LobbyError::InvalidCapacity => write!(f, "InvalidCapacity"),
+        }
+    }
+}
+impl std::error::Error for LobbyError {}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The server's response to a [`LobbyRequest`].
+///
+/// Add this to your [`ChannelPack::ServerResponse`].
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub enum LobbyResponse
+{
+    /// The client's lobby after the request was applied.
+    Lobby(LobbyInfo),
+    /// The client left its lobby.
+    Left,
+    /// The request failed.
+    Error(LobbyError),
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A lobby message sent by the server.
+///
+/// Add this to your [`ChannelPack::ServerMsg`].
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub enum LobbyMsg
+{
+    /// The client's lobby changed because of another member (e.g. a member joined, left, or changed its ready
+    /// state, or the host migrated).
+    Updated(LobbyInfo),
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Server-side lobby manager.
+///
+/// Lobbies are driven by the server owner:
+/// - Forward [`LobbyRequest`]s to [`Self::handle_request()`].
+/// - Call [`Self::handle_disconnect()`] when a [`ServerReport::Disconnected`] report is consumed. If the host
+///   disconnects, then the lobby's oldest remaining member becomes the host. Empty lobbies are removed.
+///
+/// Members receive [`LobbyMsg::Updated`] when their lobby is changed by other members.
+#[cfg(feature = "server")]
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "bevy", derive(bevy_ecs::system::Resource))]
+pub struct Lobbies
+{
+    /// counter for assigning lobby ids
+    lobby_counter: LobbyId,
+    /// active lobbies
+    lobbies: HashMap<LobbyId, LobbyInfo>,
+    /// lobby of each member
+    client_lobbies: HashMap<ClientId, LobbyId>,
+}
+
+#[cfg(feature = "server")]
+impl Lobbies
+{
+    /// Applies a lobby request and responds to it.
+    ///
+    /// Other members of the affected lobby are sent [`LobbyMsg::Updated`]. Returns an error if the response could not
+    /// be sent (the request is still applied).
+    pub fn handle_request<Channel>(
+        &mut self,
+        server  : &Server<Channel>,
+        token   : RequestToken,
+        request : LobbyRequest
+    ) -> Result<(), ServerError>
+    where
+        Channel: ChannelPack,
+        Channel::ServerMsg: From<LobbyMsg>,
+        Channel::ServerResponse: From<LobbyResponse>,
+    {
+        let client_id = token.client_id();
+        let (response, changed_lobby) = match self.apply(client_id, request)
+        {
+            Ok(result) => result,
+            Err(err)   => (LobbyResponse::Error(err), None),
+        };
+
+        if let Some(lobby_id) = changed_lobby { self.notify(server, lobby_id, Some(client_id)); }
+        server.respond(token, response.into())
+    }
+
+    /// Removes a disconnected client from its lobby.
+    ///
+    /// The lobby's remaining members are sent [`LobbyMsg::Updated`].
+    pub fn handle_disconnect<Channel>(&mut self, server: &Server<Channel>, client_id: ClientId)
+    where
+        Channel: ChannelPack,
+        Channel::ServerMsg: From<LobbyMsg>,
+    {
+        let Some(lobby_id) = self.leave(client_id) else { return; };
+        self.notify(server, lobby_id, None);
+    }
+
+    /// Sends a message to all members of a lobby.
+    ///
+    /// Returns the send signal of each member. Returns nothing if the lobby does not exist.
+    pub fn broadcast<Channel: ChannelPack>(
+        &self,
+        server   : &Server<Channel>,
+        lobby_id : LobbyId,
+        msg      : Channel::ServerMsg
+    ) -> Result<Vec<(ClientId, SendSignal)>, ServerError>
+    {
+        let Some(lobby) = self.lobbies.get(&lobby_id) else { return Ok(Vec::default()); };
+        lobby.members
+            .iter()
+            .map(|member| Ok((member.client_id, server.send(member.client_id, msg.clone())?)))
+            .collect()
+    }
+
+    /// Gets a lobby.
+    pub fn lobby(&self, lobby_id: LobbyId) -> Option<&LobbyInfo>
+    {
+        self.lobbies.get(&lobby_id)
+    }
+
+    /// Gets a client's lobby.
+    pub fn client_lobby(&self, client_id: ClientId) -> Option<&LobbyInfo>
+    {
+        self.lobbies.get(self.client_lobbies.get(&client_id)?)
+    }
+
+    /// Iterates over all lobbies.
+    pub fn iter(&self) -> impl Iterator<Item = &LobbyInfo> + '_
+    {
+        self.lobbies.values()
+    }
+
+    /// Applies a lobby request.
+    ///
+    /// Returns the response and the lobby whose other members should be notified.
+    fn apply(
+        &mut self,
+        client_id : ClientId,
+        request   : LobbyRequest
+    ) -> Result<(LobbyResponse, Option<LobbyId>), LobbyError>
+    {
+        match request
+        {
+            LobbyRequest::Create{ capacity } =>
+            {
+                if self.client_lobbies.contains_key(&client_id) { return Err(LobbyError::AlreadyInLobby); }
+                if capacity == 0 { return Err(LobbyError::InvalidCapacity); }
+
+                self.lobby_counter = self.lobby_counter.checked_add(1).expect("ran out of lobby ids");
+                let lobby = LobbyInfo{
+                        id       : self.lobby_counter,
+                        host     : client_id,
+                        capacity,
+                        members  : vec![LobbyMember{ client_id, ready: false }],
+                    };
+                self.client_lobbies.insert(client_id, lobby.id);
+                self.lobbies.insert(lobby.id, lobby.clone());
+
+                Ok((LobbyResponse::Lobby(lobby), None))
+            }
+            LobbyRequest::Join(lobby_id) =>
+            {
+                if self.client_lobbies.contains_key(&client_id) { return Err(LobbyError::AlreadyInLobby); }
+                let Some(lobby) = self.lobbies.get_mut(&lobby_id) else { return Err(LobbyError::UnknownLobby); };
+                if lobby.is_full() { return Err(LobbyError::LobbyFull); }
+
+                lobby.members.push(LobbyMember{ client_id, ready: false });
+                self.client_lobbies.insert(client_id, lobby_id);
+
+                Ok((LobbyResponse::Lobby(lobby.clone()), Some(lobby_id)))
+            }
+            LobbyRequest::Leave =>
+            {
+                let Some(lobby_id) = self.leave(client_id) else { return Err(LobbyError::NotInLobby); };
+
+                Ok((LobbyResponse::Left, Some(lobby_id)))
+            }
+            LobbyRequest::SetReady(ready) =>
+            {
+                let Some(lobby_id) = self.client_lobbies.get(&client_id).copied()
+                else { return Err(LobbyError::NotInLobby); };
+                let Some(lobby) = self.lobbies.get_mut(&lobby_id) else { return Err(LobbyError::NotInLobby); };
+                let Some(member) = lobby.members.iter_mut().find(|member| member.client_id == client_id)
+                else { return Err(LobbyError::NotInLobby); };
+
+                member.ready = ready;
+
+                Ok((LobbyResponse::Lobby(lobby.clone()), Some(lobby_id)))
+            }
+        }
+    }
+
+    /// Removes a client from its lobby, migrating the host if needed.
+    ///
+    /// Returns the lobby the client left.
+    fn leave(&mut self, client_id: ClientId) -> Option<LobbyId>
+    {
+        let lobby_id = self.client_lobbies.remove(&client_id)?;
+        let lobby = self.lobbies.get_mut(&lobby_id)?;
+        lobby.members.retain(|member| member.client_id != client_id);
+
+        match lobby.members.first()
+        {
+            Some(member) => { if lobby.host == client_id { lobby.host = member.client_id; } }
+            None         => { self.lobbies.remove(&lobby_id); }
+        }
+
+        Some(lobby_id)
+    }
+
+    /// Sends the state of a lobby to its members.
+    fn notify<Channel>(&self, server: &Server<Channel>, lobby_id: LobbyId, skip: Option<ClientId>)
+    where
+        Channel: ChannelPack,
+        Channel::ServerMsg: From<LobbyMsg>,
+    {
+        let Some(lobby) = self.lobbies.get(&lobby_id) else { return; };
+
+        for member in lobby.members.iter().filter(|member| Some(member.client_id) != skip)
+        {
+            if let Err(err) = server.send(member.client_id, LobbyMsg::Updated(lobby.clone()).into())
+            {
+                tracing::debug!(?err, member.client_id, lobby_id, "failed sending lobby update");
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
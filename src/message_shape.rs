@@ -0,0 +1,547 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use serde::{Deserialize, Deserializer};
+use serde::de::{DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor};
+
+//standard shortcuts
+use std::collections::{BTreeMap, BTreeSet};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Max number of times a type is traced while exploring its enum variants.
+const MAX_SHAPE_TRACES: usize = 1000;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Errors returned by [`shape_fingerprint()`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ShapeError
+{
+    /// The shape of a type can't be traced.
+    ///
+    /// This happens for types that need a self-describing format (e.g. untagged or internally tagged enums and
+    /// flattened fields), and types whose `Deserialize` impls reject placeholder values (e.g. `url::Url`).
+    Unsupported(String),
+    /// A variant chosen to end the trace of a recursive enum led back to the same enum.
+    ///
+    /// This is handled internally by tracing again with a different variant.
+    Recursion,
+}
+
+impl std::fmt::Display for ShapeError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let _ = write!(f, "ShapeError::");
+        match self
+        {
+            ShapeError::Unsupported(reason) => write!(f, "Unsupported({reason})"),
+            ShapeError::Recursion           => write!(f, "Recursion"),
+        }
+    }
+}
+impl std::error::Error for ShapeError {}
+
+impl serde::de::Error for ShapeError
+{
+    fn custom<T: std::fmt::Display>(msg: T) -> Self
+    {
+        ShapeError::Unsupported(msg.to_string())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The traced shape of an enum.
+#[derive(Debug)]
+struct EnumShape
+{
+    variants: &'static [&'static str],
+    /// shapes of the variants traced so far, by variant index
+    contents: BTreeMap<u32, String>,
+    /// variants that can't be used to end the trace of a recursive enum
+    non_terminating: BTreeSet<u32>,
+}
+
+impl EnumShape
+{
+    fn is_explored(&self) -> bool
+    {
+        self.contents.len() == self.variants.len()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Traces the serde shape of types by deserializing placeholder values.
+///
+/// Each named container (struct or enum) is traced once into a definition, and referred to by name elsewhere. If a
+/// container contains itself, the nested copy is traced in 'minimal' mode: options are `None`, sequences and maps are
+/// empty, and enums use a variant that doesn't lead back to the same enum. Since deserializing a value only visits
+/// one variant of each enum, types are traced repeatedly until all variants of all enums are explored.
+#[derive(Debug, Default)]
+struct ShapeTracer
+{
+    /// definitions of named structs
+    containers: BTreeMap<&'static str, String>,
+    /// definitions of named enums
+    enums: BTreeMap<&'static str, EnumShape>,
+
+    /// shape of the value currently being traced
+    out: String,
+    /// named containers currently being traced, from outermost to innermost
+    in_progress: Vec<&'static str>,
+    /// indicates the current value is nested in a copy of itself
+    minimal: bool,
+    /// enum variants chosen in minimal mode, from outermost to innermost
+    minimal_choices: Vec<(&'static str, u32)>,
+}
+
+impl ShapeTracer
+{
+    /// Traces a type, returning its shape.
+    fn trace<T: for<'de> Deserialize<'de>>(&mut self) -> Result<String, ShapeError>
+    {
+        for _ in 0..MAX_SHAPE_TRACES
+        {
+            self.out.clear();
+            self.in_progress.clear();
+            self.minimal = false;
+            self.minimal_choices.clear();
+
+            match T::deserialize(&mut *self)
+            {
+                Ok(_) if self.enums.values().all(EnumShape::is_explored) => return Ok(std::mem::take(&mut self.out)),
+                Ok(_) | Err(ShapeError::Recursion) => (),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(ShapeError::Unsupported(String::from("too many enum variants")))
+    }
+
+    /// Describes the definitions of all containers traced so far.
+    fn definitions(&self) -> String
+    {
+        let mut definitions = String::default();
+        for (name, body) in self.containers.iter()
+        {
+            definitions.push_str(&format!("{name}={body};"));
+        }
+        for (name, shape) in self.enums.iter()
+        {
+            definitions.push_str(&format!("{name}=enum{{"));
+            for (index, variant) in shape.variants.iter().enumerate()
+            {
+                let content = shape.contents.get(&(index as u32)).map(String::as_str).unwrap_or_default();
+                definitions.push_str(&format!("{variant}:{content},"));
+            }
+            definitions.push_str("};");
+        }
+        definitions
+    }
+
+    /// Marks the innermost variant chosen in minimal mode as non-terminating.
+    ///
+    /// Returns [`ShapeError::Unsupported`] if no variants were chosen in minimal mode.
+    fn mark_non_terminating(&mut self, name: &'static str) -> ShapeError
+    {
+        let Some((choice_name, choice_index)) = self.minimal_choices.last().copied()
+        else { return ShapeError::Unsupported(format!("enum {name} has no terminating variant")); };
+        let Some(shape) = self.enums.get_mut(choice_name)
+        else { return ShapeError::Unsupported(format!("enum {choice_name} was not traced")); };
+        shape.non_terminating.insert(choice_index);
+        ShapeError::Recursion
+    }
+
+    /// Traces a named struct.
+    fn trace_container<'de, V, F>(&mut self, name: &'static str, visitor: V, trace: F) -> Result<V::Value, ShapeError>
+    where
+        V: Visitor<'de>,
+        F: FnOnce(&mut Self, V) -> Result<V::Value, ShapeError>,
+    {
+        self.out.push('@');
+        self.out.push_str(name);
+
+        let parent_out = std::mem::take(&mut self.out);
+        let parent_minimal = self.minimal;
+        self.minimal = self.minimal || self.in_progress.contains(&name);
+        self.in_progress.push(name);
+
+        let result = trace(self, visitor);
+
+        self.in_progress.pop();
+        let body = std::mem::replace(&mut self.out, parent_out);
+        if result.is_ok() && !self.minimal { self.containers.insert(name, body); }
+        self.minimal = parent_minimal;
+
+        result
+    }
+
+    /// Traces a sequence of values.
+    fn trace_seq<'de, V: Visitor<'de>>(
+        &mut self,
+        len     : usize,
+        fields  : &'static [&'static str],
+        visitor : V
+    ) -> Result<V::Value, ShapeError>
+    {
+        visitor.visit_seq(ShapeSeq{ tracer: self, len, fields, index: 0 })
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+macro_rules! trace_primitive
+{
+    ($method:ident, $visit:ident, $name:literal, $value:expr) =>
+    {
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ShapeError>
+        {
+            self.out.push_str($name);
+            visitor.$visit($value)
+        }
+    };
+}
+
+impl<'de> Deserializer<'de> for &mut ShapeTracer
+{
+    type Error = ShapeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, ShapeError>
+    {
+        Err(ShapeError::Unsupported(String::from("self-describing types")))
+    }
+
+    trace_primitive!(deserialize_bool, visit_bool, "bool", false);
+    trace_primitive!(deserialize_i8, visit_i8, "i8", 1);
+    trace_primitive!(deserialize_i16, visit_i16, "i16", 1);
+    trace_primitive!(deserialize_i32, visit_i32, "i32", 1);
+    trace_primitive!(deserialize_i64, visit_i64, "i64", 1);
+    trace_primitive!(deserialize_i128, visit_i128, "i128", 1);
+    trace_primitive!(deserialize_u8, visit_u8, "u8", 1);
+    trace_primitive!(deserialize_u16, visit_u16, "u16", 1);
+    trace_primitive!(deserialize_u32, visit_u32, "u32", 1);
+    trace_primitive!(deserialize_u64, visit_u64, "u64", 1);
+    trace_primitive!(deserialize_u128, visit_u128, "u128", 1);
+    trace_primitive!(deserialize_f32, visit_f32, "f32", 0.0);
+    trace_primitive!(deserialize_f64, visit_f64, "f64", 0.0);
+    trace_primitive!(deserialize_char, visit_char, "char", 'a');
+    trace_primitive!(deserialize_str, visit_str, "str", "");
+    trace_primitive!(deserialize_string, visit_string, "str", String::default());
+    trace_primitive!(deserialize_bytes, visit_bytes, "bytes", &[]);
+    trace_primitive!(deserialize_byte_buf, visit_byte_buf, "bytes", Vec::default());
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ShapeError>
+    {
+        self.out.push_str("unit");
+        visitor.visit_unit()
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ShapeError>
+    {
+        if self.minimal { return visitor.visit_none(); }
+
+        self.out.push_str("option(");
+        let value = visitor.visit_some(&mut *self)?;
+        self.out.push(')');
+        Ok(value)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, name: &'static str, visitor: V) -> Result<V::Value, ShapeError>
+    {
+        self.trace_container(name, visitor, |tracer, visitor| { tracer.out.push_str("unit"); visitor.visit_unit() })
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, name: &'static str, visitor: V)
+        -> Result<V::Value, ShapeError>
+    {
+        self.trace_container(name, visitor, |tracer, visitor| visitor.visit_newtype_struct(tracer))
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ShapeError>
+    {
+        let len = if self.minimal { 0 } else { 1 };
+        self.out.push_str("seq(");
+        let value = self.trace_seq(len, &[], visitor)?;
+        self.out.push(')');
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, ShapeError>
+    {
+        self.out.push('(');
+        let value = self.trace_seq(len, &[], visitor)?;
+        self.out.push(')');
+        Ok(value)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, name: &'static str, len: usize, visitor: V)
+        -> Result<V::Value, ShapeError>
+    {
+        self.trace_container(name, visitor, |tracer, visitor| tracer.deserialize_tuple(len, visitor))
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ShapeError>
+    {
+        let len = if self.minimal { 0 } else { 1 };
+        self.out.push_str("map(");
+        let value = visitor.visit_map(ShapeMap{ tracer: &mut *self, len })?;
+        self.out.push(')');
+        Ok(value)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name    : &'static str,
+        fields  : &'static [&'static str],
+        visitor : V
+    ) -> Result<V::Value, ShapeError>
+    {
+        self.trace_container(name, visitor,
+                |tracer, visitor|
+                {
+                    tracer.out.push('{');
+                    let value = tracer.trace_seq(fields.len(), fields, visitor)?;
+                    tracer.out.push('}');
+                    Ok(value)
+                }
+            )
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name     : &'static str,
+        variants : &'static [&'static str],
+        visitor  : V
+    ) -> Result<V::Value, ShapeError>
+    {
+        self.out.push('@');
+        self.out.push_str(name);
+        if variants.is_empty() { return Err(ShapeError::Unsupported(format!("enum {name} has no variants"))); }
+
+        // choose a variant
+        // - in minimal mode, choose a variant that doesn't lead back to this enum
+        // - otherwise, choose a variant that hasn't been explored yet
+        let minimal = self.minimal || self.in_progress.contains(&name);
+        let shape = self.enums.entry(name).or_insert_with(
+                || EnumShape{ variants, contents: BTreeMap::default(), non_terminating: BTreeSet::default() }
+            );
+        let num_variants = variants.len() as u32;
+        let index = match minimal
+        {
+            true =>
+            {
+                let terminating = (0..num_variants).find(|index| !shape.non_terminating.contains(index));
+                let recursed = self.minimal_choices.iter().any(|(choice_name, _)| *choice_name == name);
+                match terminating
+                {
+                    Some(index) if !recursed => index,
+                    _ => return Err(self.mark_non_terminating(name)),
+                }
+            }
+            false => (0..num_variants).find(|index| !shape.contents.contains_key(index)).unwrap_or(0),
+        };
+
+        // trace the variant
+        let parent_out = std::mem::take(&mut self.out);
+        let parent_minimal = self.minimal;
+        self.minimal = minimal;
+        self.in_progress.push(name);
+        if minimal { self.minimal_choices.push((name, index)); }
+
+        let result = visitor.visit_enum(ShapeEnum{ tracer: &mut *self, index });
+
+        if minimal { self.minimal_choices.pop(); }
+        self.in_progress.pop();
+        let content = std::mem::replace(&mut self.out, parent_out);
+        if result.is_ok() && !minimal
+        {
+            if let Some(shape) = self.enums.get_mut(name) { shape.contents.insert(index, content); }
+        }
+        self.minimal = parent_minimal;
+
+        result
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, ShapeError>
+    {
+        Err(ShapeError::Unsupported(String::from("identifiers")))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, ShapeError>
+    {
+        Err(ShapeError::Unsupported(String::from("ignored values")))
+    }
+
+    fn is_human_readable(&self) -> bool
+    {
+        false
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Traces the elements of a sequence, tuple, or struct.
+struct ShapeSeq<'a>
+{
+    tracer: &'a mut ShapeTracer,
+    len: usize,
+    /// field names if the elements are struct fields
+    fields: &'static [&'static str],
+    index: usize,
+}
+
+impl<'de> SeqAccess<'de> for ShapeSeq<'_>
+{
+    type Error = ShapeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, ShapeError>
+    {
+        if self.index >= self.len { return Ok(None); }
+
+        if self.index > 0 { self.tracer.out.push(','); }
+        if let Some(field) = self.fields.get(self.index)
+        {
+            self.tracer.out.push_str(field);
+            self.tracer.out.push(':');
+        }
+        self.index += 1;
+
+        seed.deserialize(&mut *self.tracer).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize>
+    {
+        Some(self.len - self.index)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Traces the entries of a map.
+struct ShapeMap<'a>
+{
+    tracer: &'a mut ShapeTracer,
+    len: usize,
+}
+
+impl<'de> MapAccess<'de> for ShapeMap<'_>
+{
+    type Error = ShapeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, ShapeError>
+    {
+        if self.len == 0 { return Ok(None); }
+        seed.deserialize(&mut *self.tracer).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, ShapeError>
+    {
+        self.len -= 1;
+        self.tracer.out.push(',');
+        seed.deserialize(&mut *self.tracer)
+    }
+
+    fn size_hint(&self) -> Option<usize>
+    {
+        Some(self.len)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Traces the chosen variant of an enum.
+struct ShapeEnum<'a>
+{
+    tracer: &'a mut ShapeTracer,
+    index: u32,
+}
+
+impl<'de> EnumAccess<'de> for ShapeEnum<'_>
+{
+    type Error = ShapeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), ShapeError>
+    {
+        let index: serde::de::value::U32Deserializer<ShapeError> = self.index.into_deserializer();
+        Ok((seed.deserialize(index)?, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for ShapeEnum<'_>
+{
+    type Error = ShapeError;
+
+    fn unit_variant(self) -> Result<(), ShapeError>
+    {
+        self.tracer.out.push_str("unit");
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, ShapeError>
+    {
+        seed.deserialize(&mut *self.tracer)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, ShapeError>
+    {
+        self.tracer.out.push('(');
+        let value = self.tracer.trace_seq(len, &[], visitor)?;
+        self.tracer.out.push(')');
+        Ok(value)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, fields: &'static [&'static str], visitor: V)
+        -> Result<V::Value, ShapeError>
+    {
+        self.tracer.out.push('{');
+        let value = self.tracer.trace_seq(fields.len(), fields, visitor)?;
+        self.tracer.out.push('}');
+        Ok(value)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes a fingerprint from the serde shapes of a [`ChannelPack`]'s message types.
+///
+/// The shape of a type includes the names of its structs, enums, fields, and variants, and the order and types of
+/// its fields. Adding, removing, renaming, or reordering fields or variants changes the fingerprint, but moving a
+/// type to a different module does not. The fingerprint does not depend on the compiler version, so it can be
+/// compared between binaries built with different toolchains.
+///
+/// Shapes are traced by deserializing placeholder values. Types that need a self-describing format (e.g. untagged or
+/// internally tagged enums and flattened fields), or whose `Deserialize` impls reject placeholder values, can't be
+/// traced (see [`ShapeError::Unsupported`]).
+///
+/// Use this to implement [`ChannelPack::fingerprint()`]:
+/// ```ignore
+/// fn fingerprint() -> Option<u32> { bevy_simplenet::shape_fingerprint::<Self>().ok() }
+/// ```
+pub fn shape_fingerprint<Channel: ChannelPack>() -> Result<u32, ShapeError>
+{
+    let mut tracer = ShapeTracer::default();
+    let shapes = [
+            tracer.trace::<Channel::ConnectMsg>()?,
+            tracer.trace::<Channel::ServerMsg>()?,
+            tracer.trace::<Channel::ServerResponse>()?,
+            tracer.trace::<Channel::ClientMsg>()?,
+            tracer.trace::<Channel::ClientRequest>()?,
+            tracer.trace::<Channel::RejectReason>()?,
+        ];
+
+    let mut hasher = crc32fast::Hasher::new();
+    for shape in shapes.iter()
+    {
+        hasher.update(shape.as_bytes());
+        hasher.update(&[0u8]);
+    }
+    hasher.update(tracer.definitions().as_bytes());
+    Ok(hasher.finalize())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,476 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use enfync::Handle;
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Default time to wait for an expected event before panicking.
+const DEFAULT_EXPECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+type MockSession = ezsockets::Session<SessionId, ()>;
+
+/// Events forwarded from the mock server's backend.
+#[derive(Debug)]
+enum MockEvent<Channel: ChannelPack>
+{
+    Meta(ServerMetaEventFrom<Channel>),
+    Disconnected,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Session handler for the mock server.
+struct MockSessionHandler<Channel: ChannelPack>
+{
+    id: SessionId,
+    session: MockSession,
+    codec: WireCodec,
+    event_sender: crossbeam::channel::Sender<MockEvent<Channel>>,
+}
+
+#[async_trait::async_trait]
+impl<Channel: ChannelPack> ezsockets::SessionExt for MockSessionHandler<Channel>
+{
+    type ID   = SessionId;
+    type Call = ();
+
+    fn id(&self) -> &SessionId
+    {
+        &self.id
+    }
+
+    async fn on_text(&mut self, text: String) -> Result<(), ezsockets::Error>
+    {
+        // respond to text pings so clients with custom heartbeats stay connected
+        if let Some(value) = text.strip_prefix("ping:")
+        {
+            let _ = self.session.text(format!("pong:{}", value));
+        }
+        Ok(())
+    }
+
+    async fn on_binary(&mut self, bytes: Vec<u8>) -> Result<(), ezsockets::Error>
+    {
+        match self.codec.decode::<ServerMetaEventFrom<Channel>>(&bytes[..])
+        {
+            Ok(event) => { let _ = self.event_sender.send(MockEvent::Meta(event)); }
+            Err(err)  => tracing::warn!(?err, "mock server received a frame that failed to decode"),
+        }
+        Ok(())
+    }
+
+    async fn on_call(&mut self, _call: ()) -> Result<(), ezsockets::Error>
+    {
+        Ok(())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Connection handler for the mock server.
+struct MockConnectionHandler<Channel: ChannelPack>
+{
+    session_counter: SessionId,
+    session: Arc<Mutex<Option<MockSession>>>,
+    codec: WireCodec,
+    event_sender: crossbeam::channel::Sender<MockEvent<Channel>>,
+}
+
+#[async_trait::async_trait]
+impl<Channel: ChannelPack> ezsockets::ServerExt for MockConnectionHandler<Channel>
+{
+    type Session = MockSessionHandler<Channel>;
+    type Call    = ();
+
+    async fn on_connect(
+        &mut self,
+        socket   : ezsockets::Socket,
+        _request : ezsockets::Request,
+        _address : SocketAddr,
+    ) -> Result<MockSession, Option<ezsockets::CloseFrame>>
+    {
+        self.session_counter += 1;
+        let session_id = self.session_counter;
+        let codec = self.codec.clone();
+        let event_sender = self.event_sender.clone();
+        let session = ezsockets::Session::create(
+                move |session| MockSessionHandler::<Channel>{ id: session_id, session, codec, event_sender },
+                session_id,
+                socket
+            );
+
+        // the most recent session replaces any older session
+        if let Ok(mut current) = self.session.lock() { *current = Some(session.clone()); }
+
+        Ok(session)
+    }
+
+    async fn on_disconnect(
+        &mut self,
+        id      : SessionId,
+        _reason : Result<Option<ezsockets::CloseFrame>, ezsockets::Error>
+    ) -> Result<(), ezsockets::Error>
+    {
+        // only the most recent session is tracked
+        if id == self.session_counter
+        {
+            if let Ok(mut current) = self.session.lock() { *current = None; }
+        }
+        let _ = self.event_sender.send(MockEvent::Disconnected);
+        Ok(())
+    }
+
+    async fn on_call(&mut self, _call: ()) -> Result<(), ezsockets::Error>
+    {
+        Ok(())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+async fn mock_websocket_handler<Channel: ChannelPack>(
+    axum::Extension(server) : axum::Extension<ezsockets::Server<MockConnectionHandler<Channel>>>,
+    ezsocket_upgrade        : ezsockets::axum::Upgrade,
+) -> impl axum::response::IntoResponse
+{
+    ezsocket_upgrade.on_upgrade_with_config(server, ezsockets::SocketConfig::default())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A client request received by a [`MockServer`].
+///
+/// Use [`Self::respond()`], [`Self::ack()`], or [`Self::reject()`] to script the server's reply.
+pub struct MockRequest<Channel: ChannelPack>
+{
+    request: Channel::ClientRequest,
+    request_id: u64,
+    trace_id: Option<String>,
+    session: Option<MockSession>,
+    codec: WireCodec,
+}
+
+impl<Channel: ChannelPack> MockRequest<Channel>
+{
+    /// Gets the request.
+    pub fn request(&self) -> &Channel::ClientRequest
+    {
+        &self.request
+    }
+
+    /// Gets the request id.
+    pub fn request_id(&self) -> u64
+    {
+        self.request_id
+    }
+
+    /// Gets the request's trace id, if any.
+    pub fn trace_id(&self) -> Option<&str>
+    {
+        self.trace_id.as_deref()
+    }
+
+    /// Sends a response to the client.
+    pub fn respond(self, response: Channel::ServerResponse)
+    {
+        let (request_id, trace_id) = (self.request_id, self.trace_id.clone());
+        self.send(ClientMetaEventFrom::<Channel>::Response(response, request_id, trace_id));
+    }
+
+    /// Sends a partial response to the client.
+    ///
+    /// The request can still be responded to, acknowledged, or rejected afterward.
+    pub fn respond_partial(&self, response: Channel::ServerResponse)
+    {
+        let request_id = self.request_id;
+        send_mock_event::<Channel>(
+                self.session.as_ref(),
+                &self.codec,
+                &ClientMetaEventFrom::<Channel>::ResponsePart(response, request_id)
+            );
+    }
+
+    /// Reports progress for the request.
+    pub fn report_progress(&self, progress: f32)
+    {
+        let request_id = self.request_id;
+        send_mock_event::<Channel>(
+                self.session.as_ref(),
+                &self.codec,
+                &ClientMetaEventFrom::<Channel>::Progress(request_id, progress)
+            );
+    }
+
+    /// Acknowledges the request without responding.
+    pub fn ack(self)
+    {
+        let (request_id, trace_id) = (self.request_id, self.trace_id.clone());
+        self.send(ClientMetaEventFrom::<Channel>::Ack(request_id, trace_id));
+    }
+
+    /// Acknowledges the request with a small payload.
+    pub fn ack_with(self, payload: impl Into<Vec<u8>>)
+    {
+        let (request_id, trace_id) = (self.request_id, self.trace_id.clone());
+        self.send(ClientMetaEventFrom::<Channel>::AckWith(request_id, payload.into(), trace_id));
+    }
+
+    /// Rejects the request.
+    pub fn reject(self)
+    {
+        let (request_id, trace_id) = (self.request_id, self.trace_id.clone());
+        self.send(ClientMetaEventFrom::<Channel>::Reject(request_id, trace_id));
+    }
+
+    /// Rejects the request with a reason.
+    pub fn reject_with(self, reason: Channel::RejectReason)
+    {
+        let (request_id, trace_id) = (self.request_id, self.trace_id.clone());
+        self.send(ClientMetaEventFrom::<Channel>::RejectWith(request_id, reason, trace_id));
+    }
+
+    fn send(self, event: ClientMetaEventFrom<Channel>)
+    {
+        send_mock_event::<Channel>(self.session.as_ref(), &self.codec, &event);
+    }
+}
+
+impl<Channel: ChannelPack> Debug for MockRequest<Channel>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.debug_struct("MockRequest")
+            .field("request", &self.request)
+            .field("request_id", &self.request_id)
+            .field("trace_id", &self.trace_id)
+            .finish()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn send_mock_event<Channel: ChannelPack>(
+    session : Option<&MockSession>,
+    codec   : &WireCodec,
+    event   : &ClientMetaEventFrom<Channel>
+){
+    let Some(session) = session else { panic!("mock server has no connected client"); };
+    let Ok(result) = codec.send(event, |bytes| session.binary(bytes))
+    else { panic!("mock server failed to encode message"); };
+    if result.is_err() { panic!("mock server failed to send message"); }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A scripted server for testing client-side logic without a real [`Server`].
+///
+/// The mock server speaks the client wire protocol over a real socket, but it doesn't validate connection requests,
+/// authenticate clients, or rate limit. Tests drive it directly:
+/// - [`Self::expect_connect()`] waits for a client's auth message.
+/// - [`Self::expect_msg()`] and [`Self::expect_request()`] wait for client messages and requests. Requests can be
+///   answered with [`MockRequest::respond()`], [`MockRequest::ack()`], or [`MockRequest::reject()`].
+/// - [`Self::push_msg()`] sends a message to the client.
+///
+/// Only one client is served at a time, and the most recent connection replaces older connections. The mock server
+/// uses the default [`WireFormat`] without a cipher or checksum. The `expect_*` methods panic if the expected event
+/// doesn't arrive in time, or if a different event arrives first.
+pub struct MockServer<Channel: ChannelPack>
+{
+    /// The server's address.
+    server_address: SocketAddr,
+    /// the current session
+    session: Arc<Mutex<Option<MockSession>>>,
+    /// encodes messages sent to the client
+    codec: WireCodec,
+    /// receives events from the backend
+    event_receiver: crossbeam::channel::Receiver<MockEvent<Channel>>,
+    /// how long to wait for expected events
+    expect_timeout: Duration,
+}
+
+impl<Channel: ChannelPack> MockServer<Channel>
+{
+    /// Makes a new mock server listening on an ephemeral localhost port.
+    pub fn new() -> Self
+    {
+        let runtime_handle = enfync::builtin::native::TokioHandle::default();
+        let codec = WireCodec::new(
+                WireFormat::default(),
+                DecodeLimits::default(),
+                None,
+                None,
+                FrameDirection::ServerToClient,
+            );
+        let session = Arc::new(Mutex::new(None));
+        let (event_sender, event_receiver) = crossbeam::channel::unbounded();
+
+        // make server core with our connection handler
+        // note: ezsockets::Server::create() must be called from within a tokio runtime
+        let handler = MockConnectionHandler::<Channel>{
+                session_counter : 0u64,
+                session         : session.clone(),
+                codec           : codec.clone(),
+                event_sender,
+            };
+        let (server, server_worker) = enfync::blocking::extract(runtime_handle.spawn(async move {
+                ezsockets::Server::create(move |_server| handler)
+            })).unwrap();
+        runtime_handle.spawn(
+                async move {
+                    if let Err(err) = server_worker.await { tracing::error!(?err, "mock server closed with error"); }
+                }
+            );
+
+        // launch the server
+        let router = axum::Router::new()
+            .route("/ws", axum::routing::get(mock_websocket_handler::<Channel>))
+            .layer(axum::Extension(server));
+        let connection_listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let server_address = connection_listener.local_addr().unwrap();
+        runtime_handle.spawn(
+                async move {
+                    run_server(router, connection_listener, AcceptorConfig::Default, axum_server::Handle::new()).await
+                }
+            );
+
+        Self{ server_address, session, codec, event_receiver, expect_timeout: DEFAULT_EXPECT_TIMEOUT }
+    }
+
+    /// Gets the url clients should connect to.
+    pub fn url(&self) -> url::Url
+    {
+        make_websocket_url(false, self.server_address).unwrap()
+    }
+
+    /// Sets how long the `expect_*` methods wait before panicking. Defaults to 1 second.
+    pub fn set_expect_timeout(&mut self, timeout: Duration)
+    {
+        self.expect_timeout = timeout;
+    }
+
+    /// Tests if a client is connected.
+    pub fn is_connected(&self) -> bool
+    {
+        self.session.lock().map(|session| session.is_some()).unwrap_or(false)
+    }
+
+    /// Waits for a client to authenticate. Returns the client's auth request and connect message.
+    pub fn expect_connect(&mut self) -> (AuthRequest, Channel::ConnectMsg)
+    {
+        match self.expect_event()
+        {
+            MockEvent::Meta(ServerMetaEvent::Authenticate(auth_msg)) => (auth_msg.auth, auth_msg.msg),
+            event => panic!("expected client auth, received {event:?}"),
+        }
+    }
+
+    /// Waits for a client message.
+    pub fn expect_msg(&mut self) -> Channel::ClientMsg
+    {
+        match self.expect_event()
+        {
+            MockEvent::Meta(ServerMetaEvent::Msg(msg)) => msg,
+            event => panic!("expected client message, received {event:?}"),
+        }
+    }
+
+    /// Waits for a client request.
+    pub fn expect_request(&mut self) -> MockRequest<Channel>
+    {
+        match self.expect_event()
+        {
+            MockEvent::Meta(ServerMetaEvent::Request(request, request_id, trace_id)) =>
+            {
+                MockRequest{
+                    request,
+                    request_id,
+                    trace_id,
+                    session : self.current_session(),
+                    codec   : self.codec.clone(),
+                }
+            }
+            event => panic!("expected client request, received {event:?}"),
+        }
+    }
+
+    /// Waits for the client to disconnect.
+    pub fn expect_disconnect(&mut self)
+    {
+        match self.expect_event()
+        {
+            MockEvent::Disconnected => (),
+            event => panic!("expected client disconnect, received {event:?}"),
+        }
+    }
+
+    /// Sends a message to the client.
+    ///
+    /// Panics if no client is connected.
+    pub fn push_msg(&self, msg: Channel::ServerMsg)
+    {
+        send_mock_event::<Channel>(
+                self.current_session().as_ref(),
+                &self.codec,
+                &ClientMetaEventFrom::<Channel>::Msg(msg)
+            );
+    }
+
+    /// Closes the client's connection.
+    pub fn disconnect_client(&self, close_frame: Option<ezsockets::CloseFrame>)
+    {
+        let Some(session) = self.current_session() else { return; };
+        let _ = session.close(close_frame);
+    }
+
+    fn current_session(&self) -> Option<MockSession>
+    {
+        self.session.lock().ok()?.clone()
+    }
+
+    /// Waits for the next event, skipping message acks.
+    fn expect_event(&mut self) -> MockEvent<Channel>
+    {
+        let deadline = std::time::Instant::now() + self.expect_timeout;
+        loop
+        {
+            let timeout = deadline.saturating_duration_since(std::time::Instant::now());
+            match self.event_receiver.recv_timeout(timeout)
+            {
+                Ok(MockEvent::Meta(ServerMetaEvent::MsgAck(_))) => continue,
+                Ok(event) => return event,
+                Err(_) => panic!("timed out waiting for mock server event"),
+            }
+        }
+    }
+}
+
+impl<Channel: ChannelPack> Default for MockServer<Channel>
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
+
+impl<Channel: ChannelPack> Debug for MockServer<Channel>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.debug_struct("MockServer")
+            .field("server_address", &self.server_address)
+            .field("is_connected", &self.is_connected())
+            .finish()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,94 @@
+//local shortcuts
+
+//third-party shortcuts
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Configuration for simulating poor network conditions.
+///
+/// Faults are applied to frames when they are received. Delayed frames are re-injected into the receiver after the
+/// delay elapses, so frames with different delays may be reordered. Delayed frames are discarded if the connection
+/// they arrived on is closed before they are delivered.
+///
+/// Defaults to a perfect network.
+#[derive(Debug, Copy, Clone)]
+pub struct NetsimConfig
+{
+    /// Latency added to every received frame. Defaults to zero.
+    pub latency: Duration,
+    /// Max random latency added on top of [`Self::latency`]. Defaults to zero.
+    pub jitter: Duration,
+    /// Probability in `[0.0, 1.0]` that a received frame will be dropped. Defaults to zero.
+    pub drop_chance: f64,
+    /// Seed for the simulator's RNG. Defaults to zero.
+    pub seed: u64,
+}
+
+impl Default for NetsimConfig
+{
+    fn default() -> NetsimConfig
+    {
+        NetsimConfig{
+                latency     : Duration::default(),
+                jitter      : Duration::default(),
+                drop_chance : 0.0,
+                seed        : 0u64,
+            }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// What to do with a received frame.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum NetsimAction
+{
+    Deliver,
+    Delay(Duration),
+    Drop,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Decides the fate of received frames.
+#[derive(Debug)]
+pub(crate) struct NetworkSimulator
+{
+    config: NetsimConfig,
+    rng: StdRng,
+}
+
+impl NetworkSimulator
+{
+    /// Makes a new simulator.
+    ///
+    /// The `stream` is mixed into the seed so different connections sharing a config get different faults.
+    pub(crate) fn new(config: NetsimConfig, stream: u64) -> Self
+    {
+        Self{ config, rng: StdRng::seed_from_u64(config.seed.wrapping_add(stream)) }
+    }
+
+    /// Gets the action for the next received frame.
+    pub(crate) fn next_action(&mut self) -> NetsimAction
+    {
+        if self.rng.gen_bool(self.config.drop_chance.clamp(0.0, 1.0)) { return NetsimAction::Drop; }
+
+        let jitter = match self.config.jitter.is_zero()
+        {
+            true  => Duration::default(),
+            false => self.config.jitter.mul_f64(self.rng.gen_range(0.0..1.0)),
+        };
+        let delay = self.config.latency.saturating_add(jitter);
+        if delay.is_zero() { return NetsimAction::Deliver; }
+
+        NetsimAction::Delay(delay)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,166 @@
+//local shortcuts
+
+//third-party shortcuts
+#[cfg(feature = "encryption")]
+use chacha20poly1305::{AeadInPlace, KeyInit, XChaCha20Poly1305, XNonce};
+#[cfg(feature = "encryption")]
+use rand::RngCore;
+
+//standard shortcuts
+use core::fmt::Debug;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Direction of a frame sent over a connection.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FrameDirection
+{
+    /// The frame was sent by a client to a server.
+    ClientToServer,
+    /// The frame was sent by a server to a client.
+    ServerToClient,
+}
+
+impl FrameDirection
+{
+    /// Gets a byte that identifies the direction.
+    pub fn as_byte(&self) -> u8
+    {
+        match self
+        {
+            FrameDirection::ClientToServer => 0u8,
+            FrameDirection::ServerToClient => 1u8,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Identifies an encrypted frame within its connection.
+///
+/// Each side of a connection sends frames in a stream with a random id, and numbers its frames with a counter that
+/// starts at zero. Receivers reject frames whose counter is not greater than the previous frame's counter, and frames
+/// from a different stream than the first frame received on the connection.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct CipherFrame
+{
+    /// The direction the frame was sent in.
+    pub direction: FrameDirection,
+    /// Id of the sender's frame stream (see [`PayloadCipher::new_stream_id()`]).
+    pub stream_id: u128,
+    /// Position of the frame in its stream.
+    pub counter: u64,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Encrypts and decrypts message payloads.
+///
+/// Ciphers are applied to each frame after it is serialized and before it is deserialized. They are intended for
+/// deployments where TLS is terminated at an untrusted edge, so the client and server must be configured with
+/// equivalent ciphers (e.g. using a key exchanged out of band).
+///
+/// Encrypted frames are prefixed with their [`CipherFrame`] stream id and counter, which are checked by the receiver
+/// before the frame is decrypted. Ciphers should bind the whole [`CipherFrame`] to the ciphertext (e.g. in the nonce
+/// and associated data), so frames can't be replayed, reordered, or reflected back to their sender.
+///
+/// See [`ChaChaPayloadCipher`] (requires the `encryption` feature).
+pub trait PayloadCipher: Debug + Send + Sync + 'static
+{
+    /// Makes an id for a new frame stream.
+    ///
+    /// Ids must be unique for each key (e.g. random), since ciphers may derive nonces from them.
+    fn new_stream_id(&self) -> u128;
+    /// Encrypts a serialized frame.
+    fn encrypt(&self, plaintext: Vec<u8>, frame: &CipherFrame) -> Result<Vec<u8>, ()>;
+    /// Decrypts a received frame.
+    ///
+    /// Frames that fail to decrypt are treated as malformed.
+    fn decrypt(&self, ciphertext: &[u8], frame: &CipherFrame) -> Result<Vec<u8>, ()>;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Number of bytes in a [`ChaChaPayloadCipher`] key.
+#[cfg(feature = "encryption")]
+pub const PAYLOAD_CIPHER_KEY_BYTES: usize = 32;
+
+/// A [`PayloadCipher`] using XChaCha20-Poly1305.
+///
+/// Each frame is encrypted with a 192-bit nonce made from its stream id (128 random bits) and counter, so nonces are
+/// never reused with the same key. The frame's direction and stream id are authenticated as associated data.
+///
+/// Streams are bound to a connection by the first frame received on it, so a recorded connection can still be replayed
+/// from its start into a new connection (including its auth message). Use expiring auth tokens if that matters.
+///
+/// On WASM targets this requires `getrandom`'s `js` feature.
+#[cfg(feature = "encryption")]
+pub struct ChaChaPayloadCipher
+{
+    cipher: XChaCha20Poly1305,
+}
+
+#[cfg(feature = "encryption")]
+impl ChaChaPayloadCipher
+{
+    /// Makes a new cipher from a secret key.
+    pub fn new(key: &[u8; PAYLOAD_CIPHER_KEY_BYTES]) -> Self
+    {
+        Self{ cipher: XChaCha20Poly1305::new(key.into()) }
+    }
+
+    fn nonce(frame: &CipherFrame) -> XNonce
+    {
+        let mut nonce = XNonce::default();
+        nonce[..16].copy_from_slice(&frame.stream_id.to_le_bytes());
+        nonce[16..].copy_from_slice(&frame.counter.to_le_bytes());
+        nonce
+    }
+
+    fn associated_data(frame: &CipherFrame) -> [u8; 17]
+    {
+        let mut associated_data = [0u8; 17];
+        associated_data[0] = frame.direction.as_byte();
+        associated_data[1..].copy_from_slice(&frame.stream_id.to_le_bytes());
+        associated_data
+    }
+}
+
+#[cfg(feature = "encryption")]
+impl Debug for ChaChaPayloadCipher
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.debug_struct("ChaChaPayloadCipher").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "encryption")]
+impl PayloadCipher for ChaChaPayloadCipher
+{
+    fn new_stream_id(&self) -> u128
+    {
+        let mut stream_id = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut stream_id);
+        u128::from_le_bytes(stream_id)
+    }
+
+    fn encrypt(&self, mut plaintext: Vec<u8>, frame: &CipherFrame) -> Result<Vec<u8>, ()>
+    {
+        self.cipher
+            .encrypt_in_place(&Self::nonce(frame), &Self::associated_data(frame), &mut plaintext)
+            .map_err(|_| ())?;
+        Ok(plaintext)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8], frame: &CipherFrame) -> Result<Vec<u8>, ()>
+    {
+        let mut plaintext = ciphertext.to_vec();
+        self.cipher
+            .decrypt_in_place(&Self::nonce(frame), &Self::associated_data(frame), &mut plaintext)
+            .map_err(|_| ())?;
+        Ok(plaintext)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,15 +1,17 @@
 //local shortcuts
+use crate::*;
 
 //third-party shortcuts
-use wasm_timer::Instant;
+use serde::{Serialize, Deserialize};
 
 //standard shortcuts
+use std::sync::Arc;
 use std::time::Duration;
 
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Configuration for rate limiter. Defaults to 10 messages per 100 millisconds.
-#[derive(Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
 pub struct RateLimitConfig
 {
     /// Length of time to count messages. Defaults to 100 milliseconds.
@@ -39,8 +41,10 @@ pub struct RateLimitTracker
 {
     /// rate limit configuration
     config: RateLimitConfig,
-    /// timer
-    timer: Instant,
+    /// source of the current time
+    time_source: Arc<dyn TimeSource>,
+    /// time when the tracker was created
+    start_time: Duration,
 
     /// time of last checkpoint message (first message that appeared after end of last tracking period)
     next_checkpoint_time: Duration,
@@ -52,22 +56,42 @@ impl RateLimitTracker
 {
     /// Make a new rate limit tracker.
     pub fn new(config: RateLimitConfig) -> RateLimitTracker
+    {
+        Self::new_with_time_source(config, Arc::new(SystemTimeSource))
+    }
+
+    /// Make a new rate limit tracker that reads time from a custom [`TimeSource`].
+    pub fn new_with_time_source(config: RateLimitConfig, time_source: Arc<dyn TimeSource>) -> RateLimitTracker
     {
         let next_checkpoint_time = config.period;
+        let start_time = time_source.now();
         RateLimitTracker{
                 config,
-                timer: Instant::now(),
+                time_source,
+                start_time,
                 next_checkpoint_time,
                 count: 0u64
             }
     }
 
+    /// Get the tracker's rate limit configuration.
+    pub fn config(&self) -> RateLimitConfig
+    {
+        self.config
+    }
+
+    /// Get the number of messages counted in the current tracking period.
+    pub fn count(&self) -> u64
+    {
+        self.count
+    }
+
     /// Try to add a message to the tracker.
     /// - Fails if adding the message violates the rate limit.
     pub fn try_count_msg(&mut self) -> bool
     {
         // check if we are in a new period
-        let msg_time = self.timer.elapsed();
+        let msg_time = self.time_source.now().saturating_sub(self.start_time);
 
         if msg_time >= self.next_checkpoint_time
         {
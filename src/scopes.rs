@@ -0,0 +1,71 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Deserialize, Serialize};
+
+//standard shortcuts
+use core::fmt::Debug;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A set of permission scopes, stored as a bitset.
+///
+/// Servers assign scopes to each client connection (see `ServerFactory::with_scopes_fn()`), and channel packs declare
+/// the scopes required to send each message or request (see [`ChannelPack::client_msg_scopes()`](crate::ChannelPack)
+/// and [`ChannelPack::client_request_scopes()`](crate::ChannelPack)).
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Scopes(pub u64);
+
+impl Scopes
+{
+    /// No scopes.
+    pub const NONE: Scopes = Scopes(0u64);
+    /// All scopes.
+    pub const ALL: Scopes = Scopes(u64::MAX);
+
+    /// Makes a set with only the scope at bit `index`.
+    ///
+    /// Panics if `index >= 64`.
+    pub const fn bit(index: u32) -> Self
+    {
+        Scopes(1u64 << index)
+    }
+
+    /// Checks if this set contains all scopes in `required`.
+    pub fn contains(self, required: Scopes) -> bool
+    {
+        self.0 & required.0 == required.0
+    }
+
+    /// Gets the scopes in `required` that are not in this set.
+    pub fn missing(self, required: Scopes) -> Scopes
+    {
+        Scopes(required.0 & !self.0)
+    }
+
+    /// Checks if this set is empty.
+    pub fn is_empty(self) -> bool
+    {
+        self.0 == 0u64
+    }
+}
+
+impl std::ops::BitOr for Scopes
+{
+    type Output = Scopes;
+
+    fn bitor(self, other: Scopes) -> Scopes
+    {
+        Scopes(self.0 | other.0)
+    }
+}
+
+impl std::ops::BitOrAssign for Scopes
+{
+    fn bitor_assign(&mut self, other: Scopes)
+    {
+        self.0 |= other.0;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
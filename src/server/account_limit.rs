@@ -0,0 +1,59 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::sync::Arc;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(ClientId) -> u128`.
+///
+/// Maps client ids to account ids. See [`AccountLimitConfig`].
+pub trait AccountIdFn: Fn(ClientId) -> u128 + Send + Sync + 'static {}
+impl<F> AccountIdFn for F where F: Fn(ClientId) -> u128 + Send + Sync + 'static {}
+
+impl Debug for dyn AccountIdFn
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("AccountIdFn") }
+}
+
+/// Gets the account id stored in the high 64 bits of a client id.
+pub fn account_id_from_high_bits(client_id: ClientId) -> u128
+{
+    client_id >> 64
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Limits the number of concurrent connections per account.
+///
+/// Client ids are unique per connection, but multiple clients may belong to the same account (e.g. a user with
+/// several devices). If your auth backend encodes an account in each client id (e.g. in the client ids of the
+/// [`AuthTokens`](AuthToken) it produces), then this prevents one account from opening an unbounded number of
+/// sessions.
+///
+/// Sessions for accounts at capacity are closed after they authenticate.
+#[derive(Debug, Clone)]
+pub struct AccountLimitConfig
+{
+    /// Max number of concurrent client connections per account. Defaults to 4.
+    pub max_connections: u32,
+    /// Maps client ids to account ids. Defaults to [`account_id_from_high_bits()`].
+    pub account_id_fn: Arc<dyn AccountIdFn>,
+}
+
+impl Default for AccountLimitConfig
+{
+    fn default() -> AccountLimitConfig
+    {
+        AccountLimitConfig{
+                max_connections : 4u32,
+                account_id_fn   : Arc::new(account_id_from_high_bits),
+            }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
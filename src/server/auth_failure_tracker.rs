@@ -0,0 +1,140 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Max number of tracked addresses before stale records are pruned.
+const AUTH_FAILURE_PRUNE_THRESHOLD: usize = 1_000;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct AuthFailureRecord
+{
+    /// start of the current counting period
+    period_start: Duration,
+    /// number of failures in the current counting period
+    count: u32,
+    /// address is locked out until this time
+    locked_until: Option<Duration>,
+}
+
+impl AuthFailureRecord
+{
+    fn is_stale(&self, now: Duration, config: &AuthFailureConfig) -> bool
+    {
+        if let Some(locked_until) = self.locked_until
+        {
+            if now < locked_until { return false; }
+        }
+        now.saturating_sub(self.period_start) >= config.period
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks failed authentication attempts per source address.
+///
+/// Addresses that fail authentication more than [`AuthFailureConfig::max_failures`] times in one
+/// [`AuthFailureConfig::period`] are locked out for [`AuthFailureConfig::lockout`].
+#[derive(Debug, Clone)]
+pub(crate) struct AuthFailureTracker
+{
+    config: AuthFailureConfig,
+    time_source: Arc<dyn TimeSource>,
+    records: Arc<Mutex<HashMap<IpAddr, AuthFailureRecord>>>,
+}
+
+impl AuthFailureTracker
+{
+    pub(crate) fn new(config: AuthFailureConfig, time_source: Arc<dyn TimeSource>) -> Self
+    {
+        Self{ config, time_source, records: Arc::new(Mutex::new(HashMap::default())) }
+    }
+
+    /// Checks if an address is currently locked out.
+    pub(crate) fn is_locked_out(&self, address: IpAddr) -> bool
+    {
+        let Ok(records) = self.records.lock()
+        else { tracing::error!("auth failure tracker lock error"); return false; };
+        let Some(record) = records.get(&address) else { return false; };
+        let Some(locked_until) = record.locked_until else { return false; };
+
+        self.time_source.now() < locked_until
+    }
+
+    /// Records a failed authentication attempt from an address.
+    pub(crate) fn record_failure(&self, address: IpAddr)
+    {
+        let Ok(mut records) = self.records.lock()
+        else { tracing::error!("auth failure tracker lock error"); return; };
+        let now = self.time_source.now();
+
+        // clean up old records so the map can't grow without bound
+        if records.len() >= AUTH_FAILURE_PRUNE_THRESHOLD
+        {
+            let config = self.config;
+            records.retain(|_, record| !record.is_stale(now, &config));
+        }
+
+        let record = records
+            .entry(address)
+            .or_insert(AuthFailureRecord{ period_start: now, count: 0, locked_until: None });
+
+        // reset the record if its counting period has elapsed
+        if now.saturating_sub(record.period_start) >= self.config.period
+        {
+            record.period_start = now;
+            record.count = 0;
+        }
+
+        record.count = record.count.saturating_add(1);
+
+        if record.count > self.config.max_failures
+        {
+            tracing::debug!(?address, "too many auth failures, locking out address");
+            record.locked_until = Some(now.saturating_add(self.config.lockout));
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Configuration for locking out addresses that repeatedly fail authentication.
+///
+/// Defaults to a 60 second lockout after 10 failures in 60 seconds.
+#[derive(Debug, Copy, Clone)]
+pub struct AuthFailureConfig
+{
+    /// Length of time to count failures. Defaults to 60 seconds.
+    pub period: Duration,
+    /// Max number of failures allowed in a counting period. Defaults to 10 failures.
+    ///
+    /// Set this to `u32::MAX` to disable lockouts.
+    pub max_failures: u32,
+    /// Length of time an address is locked out after exceeding the failure limit. Defaults to 60 seconds.
+    pub lockout: Duration,
+}
+
+impl Default for AuthFailureConfig
+{
+    fn default() -> AuthFailureConfig
+    {
+        AuthFailureConfig{
+                period       : Duration::from_secs(60),
+                max_failures : 10u32,
+                lockout      : Duration::from_secs(60),
+            }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,65 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::collections::{HashMap, HashSet};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the members of named client groups.
+///
+/// Groups are owned by the server API, so their membership only changes in step with consumed connection events.
+#[derive(Debug, Default)]
+pub(crate) struct ClientGroups
+{
+    /// members of each group
+    members: HashMap<String, HashSet<ClientId>>,
+    /// groups of each member
+    client_groups: HashMap<ClientId, HashSet<String>>,
+}
+
+impl ClientGroups
+{
+    /// Adds a client to a group.
+    pub(crate) fn add(&mut self, group: String, client_id: ClientId)
+    {
+        self.members.entry(group.clone()).or_default().insert(client_id);
+        self.client_groups.entry(client_id).or_default().insert(group);
+    }
+
+    /// Removes a client from a group.
+    ///
+    /// Returns `false` if the client was not in the group.
+    pub(crate) fn remove(&mut self, group: &str, client_id: ClientId) -> bool
+    {
+        let Some(groups) = self.client_groups.get_mut(&client_id) else { return false; };
+        if !groups.remove(group) { return false; }
+        if groups.is_empty() { self.client_groups.remove(&client_id); }
+        self.remove_member(group, client_id);
+        true
+    }
+
+    /// Removes a client from all its groups.
+    pub(crate) fn remove_client(&mut self, client_id: ClientId)
+    {
+        let Some(groups) = self.client_groups.remove(&client_id) else { return; };
+        for group in groups.iter() { self.remove_member(group, client_id); }
+    }
+
+    /// Gets the members of a group.
+    pub(crate) fn members(&self, group: &str) -> impl Iterator<Item = ClientId> + '_
+    {
+        self.members.get(group).into_iter().flat_map(|members| members.iter().copied())
+    }
+
+    fn remove_member(&mut self, group: &str, client_id: ClientId)
+    {
+        let Some(members) = self.members.get_mut(group) else { return; };
+        members.remove(&client_id);
+        if members.is_empty() { self.members.remove(group); }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,50 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use serde::{Deserialize, Serialize};
+
+//standard shortcuts
+use core::fmt::Debug;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The role of a client connection.
+///
+/// See [`ServerFactory::with_role_fn()`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ClientRole
+{
+    /// The client may send messages and requests.
+    #[default]
+    Full,
+    /// The client may only receive server messages (e.g. spectators, dashboards, and replay viewers).
+    ///
+    /// Messages and text messages from read-only clients are dropped, and their requests are rejected, before they
+    /// reach the server API. Read-only clients may still subscribe to topics (see [`Server::publish()`]).
+    ReadOnly,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(&AuthRequest, &ConnectMsg) -> ClientRole`.
+///
+/// Assigns a role to each client connection when it authenticates. The function is invoked on the client's session
+/// task, so it should not block.
+///
+/// See [`ServerFactory::with_role_fn()`].
+pub trait ClientRoleFn<Channel: ChannelPack>:
+    Fn(&AuthRequest, &Channel::ConnectMsg) -> ClientRole + Send + Sync + 'static
+{}
+impl<Channel, F> ClientRoleFn<Channel> for F
+where
+    Channel: ChannelPack,
+    F: Fn(&AuthRequest, &Channel::ConnectMsg) -> ClientRole + Send + Sync + 'static
+{}
+
+impl<Channel: ChannelPack> Debug for dyn ClientRoleFn<Channel>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("ClientRoleFn") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
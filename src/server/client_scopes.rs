@@ -0,0 +1,33 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(&AuthRequest, &ConnectMsg) -> Scopes`.
+///
+/// Assigns permission scopes to each client connection when it authenticates. Messages and requests from clients
+/// without the scopes required by [`ChannelPack::client_msg_scopes()`] and [`ChannelPack::client_request_scopes()`]
+/// are rejected before they reach the server API, and the client receives [`ClientEvent::Unauthorized`]. The function
+/// is invoked on the client's session task, so it should not block.
+///
+/// See [`ServerFactory::with_scopes_fn()`].
+pub trait ClientScopesFn<Channel: ChannelPack>:
+    Fn(&AuthRequest, &Channel::ConnectMsg) -> Scopes + Send + Sync + 'static
+{}
+impl<Channel, F> ClientScopesFn<Channel> for F
+where
+    Channel: ChannelPack,
+    F: Fn(&AuthRequest, &Channel::ConnectMsg) -> Scopes + Send + Sync + 'static
+{}
+
+impl<Channel: ChannelPack> Debug for dyn ClientScopesFn<Channel>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("ClientScopesFn") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
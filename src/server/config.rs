@@ -5,35 +5,183 @@ use crate::*;
 
 //standard shortcuts
 use core::fmt::Debug;
+use std::sync::Arc;
 use std::time::Duration;
 
 //-------------------------------------------------------------------------------------------------------------------
 
 /// Config for the [`Server`].
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct ServerConfig
 {
     /// Max number of pending client connections. Defaults to 11K.
     pub max_pending: u32,
+    /// Policy for new connections when `max_pending` is reached. Defaults to [`PendingOverflowPolicy::Reject`].
+    pub pending_overflow_policy: PendingOverflowPolicy,
     /// Max number of concurrent client connections. Defaults to 100K.
     ///
     /// In practice the number of connections can fluctuate up to `max_connections + max_pending` if connections
     /// arrive very quickly. This flexibility ensures if a session passes pre-validation and connects, then if its
     /// authentication is valid it won't be force-disconnected.
+    ///
+    /// Can be changed with [`Server::update_config()`].
     pub max_connections: u32,
     /// Max message size allowed from clients (in bytes). Defaults to 1MB.
     pub max_msg_size: u32,
+    /// Encoding of messages sent to and received from clients. Defaults to [`WireFormat::Bincode`].
+    ///
+    /// Clients with a different wire format will be rejected when they try to connect. Making a server with a format
+    /// that isn't [supported](WireFormat::is_supported) by the enabled features will panic (or fail with
+    /// [`ServerBuildError::UnsupportedWireFormat`] if using a [`ServerBuilder`]).
+    pub wire_format: WireFormat,
+    /// Limits for decoding messages from clients. See [`DecodeLimits`] for defaults.
+    pub decode_limits: DecodeLimits,
+    /// Cipher for encrypting message payloads. Defaults to `None`.
+    ///
+    /// The same cipher is used for all clients, so clients must be configured with equivalent ciphers.
+    pub cipher: Option<Arc<dyn PayloadCipher>>,
+    /// Checksum for detecting corrupted frames. Defaults to `None`.
+    ///
+    /// Clients must be configured with the same checksum.
+    pub checksum: Option<FrameChecksum>,
+    /// Allow clients to send text messages (see [`ServerEvent::Text`]). Defaults to `false`.
+    ///
+    /// Text messages are sent as raw websocket text frames, so they bypass the [`Self::wire_format`] and
+    /// [`Self::checksum`]. They can't be encrypted, so making a server with text messages and a [`Self::cipher`] will
+    /// panic (or fail with [`ServerBuildError::TextWithCipher`] if using a [`ServerBuilder`]). If disabled, clients
+    /// that send text messages will be disconnected. Text messages from clients are passed through
+    /// [`ServerMiddleware::on_text()`].
+    pub text_messages: bool,
+    /// Queue messages sent to disconnected clients and deliver them when the clients reconnect. Defaults to `None`.
+    ///
+    /// Only messages sent with [`Server::send()`] and [`Server::send_unordered()`] are queued. Queued messages
+    /// are delivered immediately after the client's [`ServerReport::Connected`] event is emitted. Mailboxes are checked
+    /// for expired messages a few times per second, and dropped messages are reported with
+    /// [`ServerEvent::MailboxExpired`].
+    pub mailbox: Option<MailboxConfig>,
+    /// Length of time to wait for a disconnected client to reconnect before reporting the disconnect. Defaults to
+    /// zero (disconnects are reported immediately).
+    ///
+    /// If a client reconnects within the grace window, then [`ServerReport::Reconnected`] is emitted instead of
+    /// [`ServerReport::Disconnected`] followed by [`ServerReport::Connected`].
+    pub disconnect_grace: Duration,
     /// Duration to wait for an authentication message after a session connects. Defaults to 3 seconds.
     ///
     /// Authentication is sent securly over websocket channels, so we have a 'waiting period' after a session
     /// initially connects to get its authentication. Sessions that don't authenticate will time out and be closed.
+    ///
+    /// Use [`Server::handshake_stats()`](crate::Server::handshake_stats) to tune this against real auth delays.
     pub auth_timeout: Duration,
+    /// Lockout policy for addresses that repeatedly fail authentication. See [`AuthFailureConfig`] for defaults.
+    ///
+    /// Failures are keyed by the socket's peer address, so if the server is behind a reverse proxy then all clients
+    /// will share the proxy's address.
+    pub auth_failure_config: AuthFailureConfig,
+    /// Caching and offloading for [`AuthToken`] verification. See [`TokenVerificationConfig`] for defaults.
+    pub token_verification: TokenVerificationConfig,
     /// Rate limit for messages received from a session. See [`RateLimitConfig`] for defaults.
+    ///
+    /// Can be changed with [`Server::update_config()`].
     pub rate_limit_config: RateLimitConfig,
+    /// Policy for clients that exceed their rate limit. Defaults to [`RateLimitPolicy::Disconnect`].
+    pub rate_limit_policy: RateLimitPolicy,
+    /// Drops client messages that are identical to a message the client sent recently. Defaults to `None`.
+    ///
+    /// This guards against accidental duplicate sends (e.g. from naive retry logic in the client app). Use
+    /// [`ChannelPack::dedup_client_msg()`] to exempt messages that are legitimately repeated. Dropped messages are
+    /// counted by [`Server::deduplicated_msgs()`].
+    pub msg_dedup: Option<MsgDedupConfig>,
+    /// Emit each client's requests one at a time. Defaults to `false`.
+    ///
+    /// Requests from a client are always emitted in the order the client sent them within a session. If this is
+    /// set, then a client's next request (or request batch) is only emitted after the [`RequestToken`] of its
+    /// previous request is consumed by a response, ack, or rejection (including dropping the token). This gives
+    /// serialized request semantics to apps that need them, at the cost of head-of-line blocking: all of a
+    /// client's requests wait behind a slow request. Client messages are not affected.
+    pub serialize_requests: bool,
+    /// Registry of tenants that clients can connect to. Defaults to `None`.
+    ///
+    /// If set, sessions that authenticate without a registered tenant key will be closed, and clients will be
+    /// limited by their tenant's [`TenantConfig`]. If not set, client tenant keys are ignored.
+    pub tenants: Option<Arc<TenantRegistry>>,
+    /// Limit for concurrent connections per account. Defaults to `None`.
+    pub account_limit: Option<AccountLimitConfig>,
+    /// Observes when the number of connected clients crosses thresholds. Defaults to `None`.
+    pub connection_thresholds: Option<ConnectionThresholdConfig>,
+    /// Detects clients that reconnect excessively (see [`ServerReport::FlappingClient`]). Defaults to `None`.
+    pub flapping: Option<FlappingConfig>,
     /// Duration between socket heartbeat pings if the connection is inactive. Defaults to 5 seconds.
     pub heartbeat_interval: Duration,
     /// Duration after which a socket will shut down if the connection is inactive. Defaults to 10 seconds.
+    ///
+    /// Can be changed with [`Server::update_config()`].
     pub keepalive_timeout: Duration,
+    /// Replaces the payload of heartbeat pings sent to clients. Defaults to `None`.
+    ///
+    /// Native clients normally receive websocket ping frames. If this, [`Self::heartbeat_callback`],
+    /// [`Self::quality_callback`], or [`Self::keepalive_miss_callback`] is set, then all clients will receive text
+    /// pings (see the 'Wire format' section of the README).
+    pub heartbeat_ping_fn: Option<Arc<dyn HeartbeatPingFn>>,
+    /// Send websocket ping frames to WASM clients instead of text pings. Defaults to `false`.
+    ///
+    /// Browsers can't send websocket ping frames, but they answer them automatically, so this reduces keepalive
+    /// traffic (pongs are not delivered to WASM clients as text messages). Ignored if [`Self::heartbeat_ping_fn`],
+    /// [`Self::heartbeat_callback`], [`Self::quality_callback`], or [`Self::keepalive_miss_callback`] is set, since
+    /// those need text pongs to measure round-trip times and detect missed heartbeats.
+    pub wasm_ping_frames: bool,
+    /// Called whenever a heartbeat pong is received from an authenticated client. Defaults to `None`.
+    ///
+    /// The callback is invoked on the client's session task, so it should not block.
+    pub heartbeat_callback: Option<Arc<dyn ServerHeartbeatCallback>>,
+    /// Called with a [`ConnectionQuality`] report for each authenticated client once per [`Self::heartbeat_interval`].
+    /// Defaults to `None`.
+    ///
+    /// The callback is invoked on the client's session task, so it should not block.
+    pub quality_callback: Option<Arc<dyn ServerConnectionQualityCallback>>,
+    /// Number of consecutive unanswered heartbeats that triggers [`Self::keepalive_miss_callback`]. Defaults to 1.
+    ///
+    /// This should be less than `keepalive_timeout / heartbeat_interval`, otherwise sessions will be closed before
+    /// the callback is invoked. Setting this to zero disables the callback.
+    pub keepalive_miss_threshold: u32,
+    /// Called with the number of missed heartbeats when [`Self::keepalive_miss_threshold`] heartbeats to an
+    /// authenticated client have gone unanswered. Defaults to `None`.
+    ///
+    /// The callback is invoked at most once until the next pong is received. It is invoked on the client's session
+    /// task, so it should not block.
+    pub keepalive_miss_callback: Option<Arc<dyn ServerKeepaliveMissCallback>>,
+    /// Decides if a client may subscribe to a topic. Defaults to `None` (all subscriptions are allowed).
+    ///
+    /// The callback is invoked on the client's session task, so it should not block. See [`Server::publish()`].
+    pub topic_permission: Option<Arc<dyn TopicPermissionFn>>,
+    /// Id of this server instance, sent to clients when they authenticate. Defaults to `None` (no id is sent).
+    ///
+    /// When several servers run behind a load balancer, each should have a unique id so clients can detect when a
+    /// reconnect lands on a different instance (see [`ClientReport::InstanceChanged`]).
+    pub instance_id: Option<u64>,
+    /// Externally visible url of the server's websocket route (e.g. `wss://game.example.com/ws`). Defaults to `None`.
+    ///
+    /// If set, [`Server::url()`] returns this instead of the url of the bound local address. This is useful when the
+    /// server is behind NAT or an ingress, and its url is shared with clients (e.g. via a matchmaking service).
+    /// Required for servers without a listener (see [`ServerFactory::new_handler()`]).
+    pub public_url: Option<url::Url>,
+    /// Add `/healthz` and `/readyz` routes to the server's router. Defaults to `false`.
+    ///
+    /// `/healthz` responds with `200 OK` while the server's listener and connection handler are running. `/readyz`
+    /// also requires that the server is not draining (see [`Server::set_draining()`]). Otherwise the routes respond
+    /// with `503 Service Unavailable`.
+    pub health_routes: bool,
+    /// Source of time for rate limiting, auth-failure tracking, and auth token expiry. Defaults to
+    /// [`SystemTimeSource`].
+    ///
+    /// Tests can use a [`ManualTimeSource`] to control time deterministically.
+    pub time_source: Arc<dyn TimeSource>,
+    /// Simulated network conditions applied to frames received from clients. Defaults to `None`.
+    ///
+    /// Each session's simulator is seeded with `seed + session id`. Simulated delays can reorder frames, which a
+    /// [`Self::cipher`] would reject as replays, so making a server with a simulator and a cipher will panic (or fail
+    /// with [`ServerBuildError::NetsimWithCipher`] if using a [`ServerBuilder`]).
+    #[cfg(feature = "netsim")]
+    pub netsim: Option<NetsimConfig>,
 }
 
 impl Default for ServerConfig
@@ -41,13 +189,43 @@ impl Default for ServerConfig
     fn default() -> ServerConfig
     {
         ServerConfig{
-                max_pending        : 10_000u32,
-                max_connections    : 100_000u32,
-                max_msg_size       : 1_000_000u32,
-                auth_timeout       : Duration::from_secs(3),
-                rate_limit_config  : RateLimitConfig::default(),
-                heartbeat_interval : Duration::from_secs(5),
-                keepalive_timeout  : Duration::from_secs(10),
+                max_pending              : 10_000u32,
+                pending_overflow_policy  : PendingOverflowPolicy::default(),
+                max_connections          : 100_000u32,
+                max_msg_size             : 1_000_000u32,
+                wire_format              : WireFormat::default(),
+                decode_limits            : DecodeLimits::default(),
+                cipher                   : None,
+                checksum                 : None,
+                text_messages            : false,
+                mailbox                  : None,
+                disconnect_grace         : Duration::default(),
+                auth_timeout             : Duration::from_secs(3),
+                auth_failure_config      : AuthFailureConfig::default(),
+                token_verification       : TokenVerificationConfig::default(),
+                rate_limit_config        : RateLimitConfig::default(),
+                rate_limit_policy        : RateLimitPolicy::default(),
+                msg_dedup                : None,
+                serialize_requests       : false,
+                tenants                  : None,
+                account_limit            : None,
+                connection_thresholds    : None,
+                flapping                 : None,
+                heartbeat_interval       : Duration::from_secs(5),
+                keepalive_timeout        : Duration::from_secs(10),
+                heartbeat_ping_fn        : None,
+                wasm_ping_frames         : false,
+                heartbeat_callback       : None,
+                quality_callback         : None,
+                keepalive_miss_threshold : 1,
+                keepalive_miss_callback  : None,
+                topic_permission         : None,
+                instance_id              : None,
+                public_url               : None,
+                health_routes            : false,
+                time_source              : Arc::new(SystemTimeSource),
+                #[cfg(feature = "netsim")]
+                netsim                   : None,
             }
     }
 }
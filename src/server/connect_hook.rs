@@ -0,0 +1,35 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(ClientId, &ConnectMsg) -> Vec<ServerMsg>`.
+///
+/// Produces messages that are sent to a client as soon as it connects (e.g. a 'server state sync' message).
+///
+/// The hook is invoked inside the server backend when a client is authenticated, so it should not block. The messages
+/// it returns will be delivered immediately after the client's [`ClientReport::Connected`](crate::ClientReport)
+/// event (following any messages queued in the client's mailbox, see [`ServerConfig::mailbox`]), and before any
+/// messages sent by the server after it handles the corresponding [`ServerReport::Connected`] event.
+///
+/// See [`ServerFactory::with_connect_hook()`].
+pub trait ConnectHook<Channel: ChannelPack>:
+    Fn(ClientId, &Channel::ConnectMsg) -> Vec<Channel::ServerMsg> + Send + Sync + 'static
+{}
+impl<Channel, F> ConnectHook<Channel> for F
+where
+    Channel: ChannelPack,
+    F: Fn(ClientId, &Channel::ConnectMsg) -> Vec<Channel::ServerMsg> + Send + Sync + 'static
+{}
+
+impl<Channel: ChannelPack> Debug for dyn ConnectHook<Channel>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("ConnectHook") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
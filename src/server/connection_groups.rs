@@ -0,0 +1,56 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the number of connected clients in each group (e.g. tenants or accounts).
+#[derive(Debug)]
+pub(crate) struct ConnectionGroups<Key: Clone + Debug + Eq + Hash>
+{
+    /// number of connected clients in each group
+    counts: HashMap<Key, u32>,
+    /// group of each connected client
+    client_groups: HashMap<ClientId, Key>,
+}
+
+impl<Key: Clone + Debug + Eq + Hash> ConnectionGroups<Key>
+{
+    /// Tries to add a client to a group.
+    ///
+    /// Returns false if the group already has `max_connections` clients.
+    pub(crate) fn try_add(&mut self, client_id: ClientId, group: &Key, max_connections: u32) -> bool
+    {
+        if self.counts.get(group).copied().unwrap_or(0) >= max_connections { return false; }
+
+        *self.counts.entry(group.clone()).or_default() += 1;
+        self.client_groups.insert(client_id, group.clone());
+        true
+    }
+
+    /// Removes a client from its group.
+    pub(crate) fn remove(&mut self, client_id: ClientId)
+    {
+        let Some(group) = self.client_groups.remove(&client_id) else { return; };
+        let Some(count) = self.counts.get_mut(&group) else { return; };
+
+        *count = count.saturating_sub(1);
+        if *count == 0 { self.counts.remove(&group); }
+    }
+}
+
+impl<Key: Clone + Debug + Eq + Hash> Default for ConnectionGroups<Key>
+{
+    fn default() -> Self
+    {
+        Self{ counts: HashMap::default(), client_groups: HashMap::default() }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
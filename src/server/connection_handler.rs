@@ -2,37 +2,128 @@
 use crate::*;
 
 //third-party shortcuts
-use bincode::Options;
 use enfync::{Handle, TryAdopt};
 
 //standard shortcuts
 use core::fmt::Debug;
-use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
-use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{BTreeSet, HashMap};
 
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
 fn reject_client_request<Channel: ChannelPack>(
-    session    : &ezsockets::Session<SessionId, ()>,
-    session_id : SessionId,
-    request_id : u64
+    session     : &ezsockets::Session<SessionId, SessionCall>,
+    codec       : &WireCodec,
+    session_id  : SessionId,
+    request_id  : u64,
+    trace_id    : Option<String>,
 ){
     // pack the message
-    let packed_msg = ClientMetaEventFrom::<Channel>::Reject(request_id);
+    let packed_msg = ClientMetaEventFrom::<Channel>::Reject(request_id, trace_id);
 
-    // serialize message
+    // serialize message and forward it to the target session
+    // - forwarding may fail if the session is disconnected
     tracing::trace!(session_id, "sending request rejection to session");
-    let Ok(ser_msg) = bincode::DefaultOptions::new().serialize(&packed_msg)
+    let Ok(result) = codec.send(&packed_msg, |ser_msg| session.binary(ser_msg))
     else { tracing::error!(session_id, "serializing rejection failed"); return; };
-
-    // forward server message to target session
-    // - this may fail if the session is disconnected
-    if let Err(_) = session.binary(ser_msg)
+    if let Err(_) = result
     { tracing::debug!(session_id, "dropping request rejection sent to broken session"); }
 }
 
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Makes the report for a client disconnect.
+///
+/// Close frames are reported with their code and reason unless the client was closed with `Client::close()`. Frames
+/// of sessions closed by the server itself (e.g. for misbehaving clients) should not be passed in.
+fn disconnect_report<Channel: ChannelPack>(
+    close_frame: Option<ezsockets::CloseFrame>
+) -> ServerReport<Channel::ConnectMsg>
+{
+    match close_frame
+    {
+        Some(frame) if !is_client_done_close(&frame) => ServerReport::DisconnectedWithReason(frame),
+        _                                            => ServerReport::Disconnected,
+    }
+}
+
+/// Checks if a close frame was sent by `Client::close()`.
+fn is_client_done_close(frame: &ezsockets::CloseFrame) -> bool
+{
+    matches!(frame.code, ezsockets::CloseCode::Normal) && frame.reason == CLIENT_DONE_CLOSE_REASON
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn send_msg_to_session<Channel: ChannelPack>(
+    session   : &ezsockets::Session<SessionId, SessionCall>,
+    codec     : &WireCodec,
+    client_id : ClientId,
+    msg       : Channel::ServerMsg,
+) -> Result<ezsockets::MessageSignal, SendDropReason>
+{
+    // serialize message and forward it to the target session
+    // - forwarding may fail if the session is disconnected
+    let Ok(result) = codec.send(&ClientMetaEventFrom::<Channel>::Msg(msg), |ser_msg| session.binary(ser_msg))
+    else
+    {
+        tracing::error!(client_id, "serializing message failed");
+        return Err(SendDropReason::SerializationFailed);
+    };
+
+    result.map_err(
+            |_|
+            {
+                tracing::debug!(client_id, "dropping message sent to broken session");
+                SendDropReason::SessionBroken
+            }
+        )
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn report_expired_mail<Channel: ChannelPack>(
+    server_event_sender : &crossbeam::channel::Sender<ClientSourceMsg<ClientId, ServerEventFrom<Channel>>>,
+    expired             : impl Iterator<Item = (ClientId, u64)>
+){
+    for (client_id, message_id) in expired
+    {
+        tracing::debug!(client_id, message_id, "message expired in mailbox");
+        if let Err(err) = server_event_sender.send(
+                ClientSourceMsg::new(client_id, ServerEventFrom::<Channel>::MailboxExpired(message_id))
+            )
+        {
+            tracing::debug!(?err, "failed reporting expired message");
+            return;
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Periodically tells the connection handler to drop expired mail, until the connection handler shuts down.
+pub(crate) fn spawn_mailbox_expiry<Channel: ChannelPack>(
+    client_event_sender: tokio::sync::mpsc::UnboundedSender<ClientTargetMsg<ClientId, SessionCommand<Channel>>>
+){
+    let handle = enfync::builtin::native::TokioHandle::try_adopt().unwrap();
+    handle.spawn(
+            async move {
+                let mut interval = tokio::time::interval_at(
+                        tokio::time::Instant::now() + MAILBOX_EXPIRY_INTERVAL,
+                        MAILBOX_EXPIRY_INTERVAL
+                    );
+                loop
+                {
+                    interval.tick().await;
+                    let command = SessionCommand::<Channel>::ExpireMail;
+                    if client_event_sender.send(ClientTargetMsg::new(0u128, command)).is_err() { break; }
+                }
+            }
+        );
+}
+
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -42,9 +133,15 @@ pub(crate) struct ConnectionHandler<Channel: ChannelPack>
 {
     /// authenticator used to evaluate authentication requests
     pub(crate) authenticator: Arc<Authenticator>,
+    /// tracks failed authentication attempts per address
+    pub(crate) auth_failure_tracker: AuthFailureTracker,
+    /// recently verified auth tokens
+    pub(crate) token_cache: VerifiedTokenCache,
 
     /// config
     pub(crate) config: ServerConfig,
+    /// encodes messages sent to clients
+    pub(crate) codec: WireCodec,
 
     /// counter for number of pending connections
     ///
@@ -56,9 +153,51 @@ pub(crate) struct ConnectionHandler<Channel: ChannelPack>
     pub(crate) session_counter: u64,
     /// counter for total number of authenticated connections encountered
     pub(crate) total_connections_count: u64,
+    /// counters exposed to the server API for detecting synchronization drift
+    pub(crate) sync_counters: SyncCounters,
+    /// counters exposed to the server API for tuning the auth timeout
+    pub(crate) handshake_counters: HandshakeStatsCounters,
+    /// counter exposed to the server API for duplicate client messages
+    pub(crate) msg_dedup_counter: MsgDedupCounter,
 
     /// registered sessions
-    pub(crate) session_registry: HashMap<SessionId, ezsockets::Session<SessionId, ()>>,
+    pub(crate) session_registry: HashMap<SessionId, ezsockets::Session<SessionId, SessionCall>>,
+    /// codecs of registered sessions
+    ///
+    /// Each session has its own codec so encrypted frames are numbered per connection.
+    pub(crate) session_codecs: HashMap<SessionId, WireCodec>,
+    /// signals set when sessions are closed by the server itself (see `disconnect_report()`)
+    pub(crate) internal_closes: HashMap<SessionId, Arc<AtomicBool>>,
+    /// sessions waiting to be authenticated, ordered from oldest to newest
+    ///
+    /// Sessions evicted by [`PendingOverflowPolicy::EvictOldest`] are removed immediately.
+    pub(crate) pending_sessions: BTreeSet<SessionId>,
+    /// reliable messages awaiting acknowledgement in each registered session
+    pub(crate) reliable_trackers: HashMap<SessionId, ReliableMsgTracker>,
+    /// latest-only send slots of each connected session
+    pub(crate) latest_slots: HashMap<SessionId, LatestSlots>,
+    /// messages waiting for disconnected clients
+    pub(crate) mailboxes: Option<Mailboxes<Channel::ServerMsg>>,
+    /// produces messages to send to newly connected clients
+    pub(crate) connect_hook: Option<Arc<dyn ConnectHook<Channel>>>,
+    /// intercepts client messages and requests
+    pub(crate) middleware: MiddlewareChain<Channel>,
+    /// assigns roles to newly authenticated clients
+    pub(crate) role_fn: Option<Arc<dyn ClientRoleFn<Channel>>>,
+    /// assigns permission scopes to newly authenticated clients
+    pub(crate) scopes_fn: Option<Arc<dyn ClientScopesFn<Channel>>>,
+    /// initializes the session stores of newly authenticated clients
+    pub(crate) session_init_fn: Option<Arc<dyn SessionInitFn<Channel>>>,
+    /// disconnected clients that are within their disconnect grace window, mapped to their last session id
+    pub(crate) lingering_clients: HashMap<ClientId, SessionId>,
+    /// tenants of connected clients
+    pub(crate) tenant_connections: ConnectionGroups<String>,
+    /// accounts of connected clients
+    pub(crate) account_connections: ConnectionGroups<u128>,
+    /// topic subscriptions of connected sessions
+    pub(crate) topic_subscriptions: TopicSubscriptions,
+    /// detects clients that reconnect excessively
+    pub(crate) flapping_tracker: Option<FlappingTracker>,
 
     /// session id to client id maps
     ///
@@ -87,11 +226,26 @@ impl<Channel: ChannelPack> ezsockets::ServerExt for ConnectionHandler<Channel>
         &mut self,
         socket   : ezsockets::Socket,
         request  : ezsockets::Request,
-        _address : std::net::SocketAddr,
-    ) -> Result<ezsockets::Session<SessionId, ()>, Option<ezsockets::CloseFrame>>
+        address  : std::net::SocketAddr,
+    ) -> Result<ezsockets::Session<SessionId, SessionCall>, Option<ezsockets::CloseFrame>>
     {
+        // make room for the new session if there are too many pending sessions
+        let mut token_required = false;
+        if self.pending_sessions.len() >= self.config.max_pending as usize
+        {
+            match self.config.pending_overflow_policy
+            {
+                PendingOverflowPolicy::Reject           => (),
+                PendingOverflowPolicy::EvictOldest      => self.evict_oldest_pending(),
+                PendingOverflowPolicy::PrioritizeTokens => token_required = true,
+            }
+        }
+
         // reject connection if max connections reached
-        if self.session_registry.len() >= (self.config.max_connections + self.config.max_pending) as usize
+        // - evicted sessions are ignored since they are closing
+        let max_sessions = self.config.max_connections as u64
+            + self.config.pending_overflow_policy.pending_capacity(self.config.max_pending);
+        if (self.pending_sessions.len() + self.session_to_client.len()) as u64 >= max_sessions
         {
             tracing::trace!("max connections reached, dropping connection request...");
             return Err(Some(ezsockets::CloseFrame{
@@ -110,10 +264,40 @@ impl<Channel: ChannelPack> ezsockets::ServerExt for ConnectionHandler<Channel>
 
         // make a session
         let authenticator       = self.authenticator.clone();
+        let token_cache         = self.token_cache.clone();
+        let offload_tokens      = self.config.token_verification.offload;
+        let auth_failures       = self.auth_failure_tracker.clone();
         let client_event_sender = self.client_event_sender.clone();
         let server_event_sender = self.server_event_sender.clone();
         let max_msg_size        = self.config.max_msg_size;
-        let rate_limit_config   = self.config.rate_limit_config.clone();
+        let text_messages       = self.config.text_messages;
+        let reliable_tracker    = ReliableMsgTracker::default();
+        let session_tracker     = reliable_tracker.clone();
+        let timestamped_pings   = self.config.heartbeat_ping_fn.is_none();
+        let heartbeat_callback  = self.config.heartbeat_callback.clone();
+        let quality_callback    = self.config.quality_callback.clone();
+        let miss_callback       = self.config.keepalive_miss_callback.clone();
+        let miss_threshold      = self.config.keepalive_miss_threshold;
+        let heartbeat_interval  = self.config.heartbeat_interval;
+        let topic_permission    = self.config.topic_permission.clone();
+        let middleware          = self.middleware.clone();
+        let role_fn             = self.role_fn.clone();
+        let scopes_fn           = self.scopes_fn.clone();
+        let session_init_fn     = self.session_init_fn.clone();
+        let codec               = self.codec.for_connection();
+        let session_codec       = codec.clone();
+        let closed_internally   = Arc::new(AtomicBool::new(false));
+        let closed_internally_clone = closed_internally.clone();
+        let rate_limit_config   = self.config.rate_limit_config;
+        let rate_limit_policy   = self.config.rate_limit_policy;
+        let msg_dedup           = self.config.msg_dedup.map(
+                |config| MsgDedupFilter::new(config, self.config.time_source.clone(), self.msg_dedup_counter.clone())
+            );
+        let serial_requests     = self.config.serialize_requests.then(SerialRequestQueue::default);
+        let tenants             = self.config.tenants.clone();
+        let time_source         = self.config.time_source.clone();
+        #[cfg(feature = "netsim")]
+        let netsim_config       = self.config.netsim;
         let (auth_signal_sender, mut auth_signal_receiver) = tokio::sync::mpsc::channel(1);
 
         let session = ezsockets::Session::create(
@@ -121,26 +305,68 @@ impl<Channel: ChannelPack> ezsockets::ServerExt for ConnectionHandler<Channel>
                 {
                     // prep client request rejector
                     let session_clone = session.clone();
+                    let codec_clone = codec.clone();
                     let request_rejector =
-                        move |request_id: u64|
+                        move |request_id: u64, trace_id: Option<String>|
                         {
-                            reject_client_request::<Channel>(&session_clone, session_id, request_id);
+                            reject_client_request::<Channel>(
+                                    &session_clone,
+                                    &codec_clone,
+                                    session_id,
+                                    request_id,
+                                    trace_id
+                                );
                         };
 
                     // make session handler
                     SessionHandler::<Channel>{
                             authenticator,
+                            token_cache,
+                            offload_token_verification: offload_tokens,
+                            auth_failure_tracker: auth_failures,
                             id: session_id,
+                            address,
                             client_id: None,
                             session,
                             auth_signal_sender,
                             client_event_sender,
                             server_event_sender,
                             max_msg_size,
+                            text_messages,
+                            timestamped_pings,
+                            heartbeat_callback,
+                            quality_callback,
+                            miss_callback,
+                            miss_threshold,
+                            heartbeat_interval,
+                            topic_permission,
+                            codec,
                             env_type: info.client_env_type,
-                            rate_limit_tracker: RateLimitTracker::new(rate_limit_config),
+                            rate_limit_tracker: RateLimitTracker::new_with_time_source(
+                                rate_limit_config,
+                                time_source.clone()
+                            ),
+                            tenant_rate_limit: false,
+                            rate_limit_policy,
+                            msg_dedup,
+                            serial_requests,
+                            tenants,
+                            token_required,
+                            time_source,
                             request_rejector: Arc::new(request_rejector),
+                            reliable_tracker: session_tracker,
+                            middleware,
+                            role_fn,
+                            role: ClientRole::default(),
+                            scopes_fn,
+                            scopes: Scopes::ALL,
+                            session_init_fn,
+                            store: SessionStore::default(),
+                            quality_tracker: Arc::new(Mutex::new(ConnectionQualityTracker::default())),
                             death_signal: Arc::new(AtomicBool::new(false)),
+                            closed_internally: closed_internally_clone,
+                            #[cfg(feature = "netsim")]
+                            netsim: netsim_config.map(|config| NetworkSimulator::new(config, session_id)),
                         }
                 },
                 session_id,
@@ -151,14 +377,22 @@ impl<Channel: ChannelPack> ezsockets::ServerExt for ConnectionHandler<Channel>
         let handle = enfync::builtin::native::TokioHandle::try_adopt().unwrap();
         let auth_timeout = self.config.auth_timeout;
         let session_clone = session.clone();
+        let handshake_counters = self.handshake_counters.clone();
+        let upgraded_at = tokio::time::Instant::now();
 
         handle.spawn(
                 async move {
                     tokio::select! {
                         biased; // Successful auth takes priority.
-                        _ = auth_signal_receiver.recv() => return,
+                        signal = auth_signal_receiver.recv() =>
+                        {
+                            // The signal is `None` if the session closed before authenticating.
+                            if signal.is_some() { handshake_counters.record_authenticated(upgraded_at.elapsed()); }
+                        }
                         _ = tokio::time::sleep(auth_timeout) =>
                         {
+                            handshake_counters.record_auth_timeout();
+
                             // Tell the session to close itself.
                             let _ = session_clone.close(Some(
                                     ezsockets::CloseFrame
@@ -174,6 +408,11 @@ impl<Channel: ChannelPack> ezsockets::ServerExt for ConnectionHandler<Channel>
 
         // save session in registry while it's waiting to be authenticated
         self.session_registry.insert(session_id, session.clone());
+        self.session_codecs.insert(session_id, session_codec);
+        self.internal_closes.insert(session_id, closed_internally);
+        self.pending_sessions.insert(session_id);
+        self.reliable_trackers.insert(session_id, reliable_tracker);
+        self.update_sync_counters();
 
         Ok(session)
     }
@@ -182,26 +421,60 @@ impl<Channel: ChannelPack> ezsockets::ServerExt for ConnectionHandler<Channel>
     async fn on_disconnect(
         &mut self,
         id      : SessionId,
-        _reason : Result<Option<ezsockets::CloseFrame>, ezsockets::Error>
+        reason  : Result<Option<ezsockets::CloseFrame>, ezsockets::Error>
     ) -> Result<(), ezsockets::Error>
     {
         // unregister session
         tracing::info!(id, "unregistering session");
         self.session_registry.remove(&id);
+        self.session_codecs.remove(&id);
+        let closed_internally = self.internal_closes.remove(&id).map_or(false, |flag| flag.load(Ordering::Acquire));
+        if let Some(reliable_tracker) = self.reliable_trackers.remove(&id) { reliable_tracker.close(); }
+        self.latest_slots.remove(&id);
+        self.topic_subscriptions.remove_session(id);
+        self.update_sync_counters();
 
         // clean up session/client id maps
         let Some(client_id) = self.session_to_client.remove(&id)
         else
         {
+            self.pending_sessions.remove(&id);
             self.pending_counter.decrement();
             tracing::debug!(id, "disconnecting unathenticated session");
             return Ok(());
         };
         self.connection_counter.decrement();
+        self.report_connection_thresholds(self.connection_counter.load() + 1);
         let _ = self.client_to_session.remove(&client_id);
+        self.tenant_connections.remove(client_id);
+        self.account_connections.remove(client_id);
+        self.update_sync_counters();
+
+        // sessions closed by the server itself are reported as plain disconnects
+        let close_frame = reason.ok().flatten().filter(|_| !closed_internally);
+
+        // wait for the client to reconnect before reporting the disconnect
+        if !self.config.disconnect_grace.is_zero()
+        {
+            tracing::debug!(id, client_id, "waiting for client to reconnect");
+            self.lingering_clients.insert(client_id, id);
+
+            let handle = enfync::builtin::native::TokioHandle::try_adopt().unwrap();
+            let disconnect_grace = self.config.disconnect_grace;
+            let client_event_sender = self.client_event_sender.clone();
+            handle.spawn(
+                    async move {
+                        tokio::time::sleep(disconnect_grace).await;
+                        let command = SessionCommand::<Channel>::GraceExpired(id, close_frame);
+                        let _ = client_event_sender.send(ClientTargetMsg::new(client_id, command));
+                    }
+                );
+
+            return Ok(());
+        }
 
         // send disconnect report
-        let report = ServerReport::<Channel::ConnectMsg>::Disconnected;
+        let report = disconnect_report::<Channel>(close_frame);
         if let Err(err) = self.server_event_sender.send(
                 ClientSourceMsg::new(client_id, ServerEventFrom::<Channel>::Report(report))
             )
@@ -222,40 +495,125 @@ impl<Channel: ChannelPack> ezsockets::ServerExt for ConnectionHandler<Channel>
     {
         // handle newly authenticated clients
         // - We overload ClientTargetMsg for this due to the limited API surface.
-        if let SessionCommand::<Channel>::Add{ session_id, msg, env_type } = client_msg.msg
+        if let SessionCommand::<Channel>::Add{ session_id, msg, meta, rate_limit } = client_msg.msg
         {
-            let Some(session) = self.session_registry.get(&session_id)
+            let (Some(session), Some(codec)) =
+                (self.session_registry.get(&session_id), self.session_codecs.get(&session_id))
             else
             {
                 // Not an error since the client may have disconnected while this message was in transit.
                 tracing::debug!(session_id, "ignoring authentication from unknown session");
                 return Ok(());
             };
+            if !self.pending_sessions.remove(&session_id)
+            {
+                tracing::debug!(session_id, "ignoring authentication from evicted session");
+                return Ok(());
+            }
 
             // check if the client already exists
             if self.client_to_session.contains_key(&client_msg.id)
             {
+                tracing::warn!(client_msg.id, session_id, "client id collision, closing session...");
                 let _ = session.close(Some(
                     ezsockets::CloseFrame
                     {
                         code   : ezsockets::CloseCode::Policy,
-                        reason : String::from("client already connected")
+                        reason : String::from(ID_COLLISION_CLOSE_REASON)
                     }
                 ));
 
+                let report = ServerReport::<Channel::ConnectMsg>::IdCollision;
+                if let Err(err) = self.server_event_sender.send(
+                        ClientSourceMsg::new(client_msg.id, ServerEventFrom::<Channel>::Report(report))
+                    )
+                {
+                    tracing::debug!(?err, "failed reporting client id collision");
+                }
+
                 return Ok(());
             }
 
+            // check if the client's tenant is at capacity
+            let tenant_config = meta.tenant.as_ref().and_then(
+                    |tenant| Some((tenant, self.config.tenants.as_ref()?.get(tenant)?))
+                );
+            if let Some((tenant, tenant_config)) = tenant_config
+            {
+                if !self.tenant_connections.try_add(client_msg.id, tenant, tenant_config.max_connections)
+                {
+                    tracing::trace!(client_msg.id, %tenant, "tenant max connections reached, closing session...");
+                    let _ = session.close(Some(
+                        ezsockets::CloseFrame
+                        {
+                            code   : ezsockets::CloseCode::Policy,
+                            reason : String::from("tenant max connections")
+                        }
+                    ));
+
+                    return Ok(());
+                }
+            }
+
+            // check if the client's account is at capacity
+            if let Some(account_limit) = &self.config.account_limit
+            {
+                let account_id = (account_limit.account_id_fn)(client_msg.id);
+                if !self.account_connections.try_add(client_msg.id, &account_id, account_limit.max_connections)
+                {
+                    tracing::trace!(client_msg.id, account_id, "account max connections reached, closing session...");
+                    self.tenant_connections.remove(client_msg.id);
+                    let _ = session.close(Some(
+                        ezsockets::CloseFrame
+                        {
+                            code   : ezsockets::CloseCode::Policy,
+                            reason : String::from("account max connections")
+                        }
+                    ));
+
+                    return Ok(());
+                }
+            }
+
             // add client to connected
             self.pending_counter.decrement();
             self.connection_counter.increment();
+            self.report_connection_thresholds(self.connection_counter.load().saturating_sub(1));
             self.total_connections_count += 1;
 
             self.client_to_session.insert(client_msg.id, (session_id, self.total_connections_count));
             self.session_to_client.insert(session_id, client_msg.id);
+            self.update_sync_counters();
+
+            // tell the client which server instance it connected to
+            // - this is sent before all other messages so the client can react to instance changes first
+            if let Some(instance_id) = self.config.instance_id
+            {
+                let instance_msg = ClientMetaEventFrom::<Channel>::Instance(instance_id);
+                if let Err(_) = codec.send(&instance_msg, |ser_msg| session.binary(ser_msg))
+                { tracing::error!(client_msg.id, "serializing server instance id failed"); }
+            }
+
+            // tell the client its rate limit so it can avoid violating it
+            let rate_limit_msg = ClientMetaEventFrom::<Channel>::RateLimit(rate_limit);
+            if let Err(_) = codec.send(&rate_limit_msg, |ser_msg| session.binary(ser_msg))
+            { tracing::error!(client_msg.id, "serializing client rate limit failed"); }
+
+            // collect state-sync messages for the client
+            // - we do this before emitting the connection report so the hook observes the connect message first
+            let sync_msgs = match &self.connect_hook
+            {
+                Some(connect_hook) => (connect_hook)(client_msg.id, &msg),
+                None               => Vec::default(),
+            };
 
             // report the connection
-            let report = ServerReport::Connected(env_type, msg);
+            // - clients that reconnect within their disconnect grace window are reported as reconnected
+            let report = match self.lingering_clients.remove(&client_msg.id)
+            {
+                Some(_) => ServerReport::Reconnected(meta, msg),
+                None    => ServerReport::Connected(meta, msg),
+            };
             if let Err(err) = self.server_event_sender.send(
                     ClientSourceMsg::new(client_msg.id, ServerEvent::Report(report))
                 )
@@ -268,19 +626,112 @@ impl<Channel: ChannelPack> ezsockets::ServerExt for ConnectionHandler<Channel>
                         reason : String::default(),
                     }
                 ));
+                return Ok(());
             };
 
+            // report the client if it is reconnecting excessively
+            let flapping = self.flapping_tracker.as_mut().and_then(|tracker| tracker.record_connect(client_msg.id));
+            if let Some(reconnects) = flapping
+            {
+                let report = ServerReport::FlappingClient(client_msg.id, reconnects);
+                if let Err(err) = self.server_event_sender.send(
+                        ClientSourceMsg::new(client_msg.id, ServerEvent::Report(report))
+                    )
+                {
+                    tracing::debug!(?err, "failed reporting flapping client");
+                }
+            }
+
+            // deliver messages that were queued while the client was disconnected
+            if let Some(mailboxes) = &mut self.mailboxes
+            {
+                let (queued, expired) = mailboxes.take(client_msg.id);
+
+                for (queued_msg, resolver) in queued
+                {
+                    match send_msg_to_session::<Channel>(session, codec, client_msg.id, queued_msg)
+                    {
+                        Ok(message_signal) => resolver.forwarded(message_signal),
+                        Err(reason)        => resolver.drop_with(reason),
+                    }
+                }
+                report_expired_mail::<Channel>(
+                    &self.server_event_sender,
+                    expired.into_iter().map(|message_id| (client_msg.id, message_id))
+                );
+            }
+
+            // sync the client with the server's current state
+            for sync_msg in sync_msgs
+            {
+                let _ = send_msg_to_session::<Channel>(session, codec, client_msg.id, sync_msg);
+            }
+
             return Ok(())
         }
 
+        // report disconnects for clients that did not reconnect in time
+        if let SessionCommand::<Channel>::GraceExpired(session_id, close_frame) = client_msg.msg
+        {
+            if self.lingering_clients.get(&client_msg.id) != Some(&session_id) { return Ok(()); }
+            self.lingering_clients.remove(&client_msg.id);
+
+            tracing::debug!(session_id, client_msg.id, "disconnect grace window expired");
+            let report = disconnect_report::<Channel>(close_frame);
+            if let Err(err) = self.server_event_sender.send(
+                    ClientSourceMsg::new(client_msg.id, ServerEventFrom::<Channel>::Report(report))
+                )
+            {
+                tracing::warn!(?err, "forwarding disconnect report failed");
+                return Err(Box::new(ConnectionError::SystemError));
+            }
+
+            return Ok(());
+        }
+
+        // drop expired mail
+        // - the command's target client id is ignored
+        if let SessionCommand::<Channel>::ExpireMail = client_msg.msg
+        {
+            let Some(mailboxes) = &mut self.mailboxes else { return Ok(()); };
+            report_expired_mail::<Channel>(&self.server_event_sender, mailboxes.expire().into_iter());
+            return Ok(());
+        }
+
+        // publish messages to topic subscribers
+        // - We overload ClientTargetMsg for this due to the limited API surface.
+        if let SessionCommand::<Channel>::Publish(topic, msg, consumed_count) = client_msg.msg
+        {
+            self.publish(topic, msg, consumed_count);
+            return Ok(());
+        }
+
+        // update config
+        // - the command's target client id is ignored
+        if let SessionCommand::<Channel>::UpdateConfig(update) = client_msg.msg
+        {
+            self.update_config(update);
+            return Ok(());
+        }
+
         // try to get targeted session (ignore if missing)
         let Some((session_id, connection_idx)) = self.client_to_session.get(&client_msg.id)
         else
         {
+            // queue messages for disconnected clients
+            if let (Some(mailboxes), SessionCommand::<Channel>::Send(ClientMetaEvent::Msg(msg), _, _, Some(resolver))) =
+                (&mut self.mailboxes, client_msg.msg)
+            {
+                tracing::debug!(client_msg.id, "queueing message sent to disconnected client");
+                let dropped = mailboxes.push(client_msg.id, msg, resolver);
+                report_expired_mail::<Channel>(&self.server_event_sender, dropped.into_iter());
+                return Ok(());
+            }
+
             tracing::debug!(client_msg.id, "dropping message sent to unknown client");
             return Ok(());
         };
-        let Some(session) = self.session_registry.get(session_id)
+        let (Some(session), Some(codec)) = (self.session_registry.get(session_id), self.session_codecs.get(session_id))
         else
         {
             tracing::debug!(client_msg.id, "dropping message sent to unknown session");
@@ -292,7 +743,7 @@ impl<Channel: ChannelPack> ezsockets::ServerExt for ConnectionHandler<Channel>
         {
             //todo: consider marshalling the message into the session via Session::call() so the session's
             //      thread can do serializing instead of the connection handler which is a bottleneck
-            SessionCommand::<Channel>::Send(msg_to_send, maybe_consumed_count, maybe_death_signal) =>
+            SessionCommand::<Channel>::Send(msg_to_send, maybe_consumed_count, maybe_death_signal, maybe_resolver) =>
             {
                 // check if the connection event for the target session was consumed before this message was sent
                 if let Some(consumed_count) = maybe_consumed_count
@@ -301,6 +752,8 @@ impl<Channel: ChannelPack> ezsockets::ServerExt for ConnectionHandler<Channel>
                     {
                         tracing::debug!(consumed_count, connection_idx,
                             "dropping message targeted at session before its connection event was handled");
+                        if let Some(resolver) = maybe_resolver
+                        { resolver.drop_with(SendDropReason::UnconsumedConnectionReport); }
                         return Ok(());
                     }
                 }
@@ -317,15 +770,166 @@ impl<Channel: ChannelPack> ezsockets::ServerExt for ConnectionHandler<Channel>
                     { tracing::debug!(client_msg.id, "dropping response targeted at dead session"); return Ok(()); }
                 }
 
-                // serialize message
+                // serialize message and forward it to the target session
+                // - forwarding may fail if the session is disconnected
                 tracing::trace!(client_msg.id, "sending message to client");
-                let Ok(ser_msg) = bincode::DefaultOptions::new().serialize(&msg_to_send)
-                else { tracing::error!(client_msg.id, "serializing message failed"); return Ok(()); };
+                let Ok(result) = codec.send(&msg_to_send, |ser_msg| session.binary(ser_msg))
+                else
+                {
+                    tracing::error!(client_msg.id, "serializing message failed");
+                    if let Some(resolver) = maybe_resolver { resolver.drop_with(SendDropReason::SerializationFailed); }
+                    return Ok(());
+                };
+
+                match result
+                {
+                    Ok(message_signal) =>
+                    {
+                        if let Some(resolver) = maybe_resolver { resolver.forwarded(message_signal); }
+                    }
+                    Err(_) =>
+                    {
+                        tracing::debug!(client_msg.id, "dropping message sent to broken session");
+                        if let Some(resolver) = maybe_resolver { resolver.drop_with(SendDropReason::SessionBroken); }
+                    }
+                }
+            }
+            SessionCommand::<Channel>::SendText(text, maybe_consumed_count, resolver) =>
+            {
+                // discard text if text messages are disabled
+                if !self.config.text_messages
+                {
+                    tracing::warn!(client_msg.id, "dropping text message (text messages are disabled)");
+                    resolver.drop_with(SendDropReason::TextDisabled);
+                    return Ok(());
+                }
+
+                // check if the connection event for the target session was consumed before this message was sent
+                if let Some(consumed_count) = maybe_consumed_count
+                {
+                    if consumed_count < *connection_idx
+                    {
+                        tracing::debug!(consumed_count, connection_idx,
+                            "dropping text message targeted at session before its connection event was handled");
+                        resolver.drop_with(SendDropReason::UnconsumedConnectionReport);
+                        return Ok(());
+                    }
+                }
 
-                // forward server message to target session
+                // forward text to target session
                 // - this may fail if the session is disconnected
-                if let Err(_) = session.binary(ser_msg)
-                { tracing::debug!(client_msg.id, "dropping message sent to broken session"); }
+                tracing::trace!(client_msg.id, "sending text message to client");
+                match session.text(format!("{}{}", TEXT_MSG_PREFIX, text))
+                {
+                    Ok(message_signal) => resolver.forwarded(message_signal),
+                    Err(_) =>
+                    {
+                        tracing::debug!(client_msg.id, "dropping text message sent to broken session");
+                        resolver.drop_with(SendDropReason::SessionBroken);
+                    }
+                }
+            }
+            SessionCommand::<Channel>::SendReliable(msg_to_send, maybe_consumed_count, resolver, ack_signal) =>
+            {
+                // check if the connection event for the target session was consumed before this message was sent
+                if let Some(consumed_count) = maybe_consumed_count
+                {
+                    if consumed_count < *connection_idx
+                    {
+                        tracing::debug!(consumed_count, connection_idx,
+                            "dropping reliable message targeted at session before its connection event was handled");
+                        resolver.drop_with(SendDropReason::UnconsumedConnectionReport);
+                        return Ok(());
+                    }
+                }
+
+                // register the message so the session can receive its acknowledgement
+                let Some(reliable_tracker) = self.reliable_trackers.get(session_id)
+                else { resolver.drop_with(SendDropReason::SessionBroken); return Ok(()); };
+                let Some(msg_id) = reliable_tracker.register(ack_signal)
+                else { resolver.drop_with(SendDropReason::SessionBroken); return Ok(()); };
+
+                // serialize message and forward it to the target session
+                // - forwarding may fail if the session is disconnected
+                tracing::trace!(client_msg.id, msg_id, "sending reliable message to client");
+                let reliable_msg = ClientMetaEventFrom::<Channel>::ReliableMsg(msg_to_send, msg_id);
+                let Ok(result) = codec.send(&reliable_msg, |ser_msg| session.binary(ser_msg))
+                else
+                {
+                    tracing::error!(client_msg.id, "serializing reliable message failed");
+                    reliable_tracker.remove(msg_id);
+                    resolver.drop_with(SendDropReason::SerializationFailed);
+                    return Ok(());
+                };
+
+                match result
+                {
+                    Ok(message_signal) => resolver.forwarded(message_signal),
+                    Err(_) =>
+                    {
+                        tracing::debug!(client_msg.id, "dropping reliable message sent to broken session");
+                        reliable_tracker.remove(msg_id);
+                        resolver.drop_with(SendDropReason::SessionBroken);
+                    }
+                }
+            }
+            SessionCommand::<Channel>::SendLatest(slot, msg_to_send, maybe_consumed_count, resolver) =>
+            {
+                // check if the connection event for the target session was consumed before this message was sent
+                if let Some(consumed_count) = maybe_consumed_count
+                {
+                    if consumed_count < *connection_idx
+                    {
+                        tracing::debug!(consumed_count, connection_idx,
+                            "dropping latest-only message targeted at session before its connection event was handled");
+                        resolver.drop_with(SendDropReason::UnconsumedConnectionReport);
+                        return Ok(());
+                    }
+                }
+
+                // serialize message
+                tracing::trace!(client_msg.id, slot, "sending latest-only message to client");
+                let Ok(ser_msg) = codec.serialize(&ClientMetaEventFrom::<Channel>::Msg(msg_to_send))
+                else
+                {
+                    tracing::error!(client_msg.id, "serializing latest-only message failed");
+                    resolver.drop_with(SendDropReason::SerializationFailed);
+                    return Ok(());
+                };
+
+                // forward the message to the target session, or hold it until the session asks for it
+                let latest_slots = self.latest_slots.entry(*session_id).or_default();
+                if !latest_slots.send(session, codec, client_msg.id, slot, ser_msg, resolver) { return Ok(()); }
+                if let Err(_) = session.call(SessionCall::FlushLatest(slot))
+                { tracing::debug!(client_msg.id, slot, "failed scheduling latest-only flush for broken session"); }
+            }
+            SessionCommand::<Channel>::FlushLatest(target_session_id, slot) =>
+            {
+                // discard flushes for old sessions
+                if target_session_id != *session_id { return Ok(()); }
+                let Some(latest_slots) = self.latest_slots.get_mut(session_id) else { return Ok(()); };
+                latest_slots.flush(session, codec, client_msg.id, slot);
+            }
+            SessionCommand::<Channel>::Subscribe(target_session_id, topic, request_id) =>
+            {
+                // discard subscriptions from old sessions
+                if target_session_id != *session_id { return Ok(()); }
+                tracing::trace!(client_msg.id, %topic, "subscribing client to topic");
+                self.topic_subscriptions.subscribe(target_session_id, topic);
+
+                // acknowledge the subscription
+                let ack = ClientMetaEventFrom::<Channel>::Ack(request_id, None);
+                let Ok(result) = codec.send(&ack, |ser_msg| session.binary(ser_msg))
+                else { tracing::error!(client_msg.id, "serializing subscription ack failed"); return Ok(()); };
+                if let Err(_) = result
+                { tracing::debug!(client_msg.id, "dropping subscription ack sent to broken session"); }
+            }
+            SessionCommand::<Channel>::Unsubscribe(target_session_id, topic) =>
+            {
+                // discard unsubscriptions from old sessions
+                if target_session_id != *session_id { return Ok(()); }
+                tracing::trace!(client_msg.id, %topic, "unsubscribing client from topic");
+                self.topic_subscriptions.unsubscribe(target_session_id, &topic);
             }
             SessionCommand::<Channel>::Close(close_frame) =>
             {
@@ -343,3 +947,93 @@ impl<Channel: ChannelPack> ezsockets::ServerExt for ConnectionHandler<Channel>
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+impl<Channel: ChannelPack> ConnectionHandler<Channel>
+{
+    /// Publishes the handler's registry sizes and connection count to the server API.
+    fn update_sync_counters(&self)
+    {
+        self.sync_counters.store(
+                self.total_connections_count,
+                self.session_registry.len(),
+                self.client_to_session.len()
+            );
+    }
+
+    /// Sends a message to all sessions subscribed to a topic.
+    ///
+    /// Sessions whose connection events were not consumed before the message was published are skipped.
+    fn publish(&self, topic: String, msg: Channel::ServerMsg, consumed_count: u64)
+    {
+        let subscribers: Vec<SessionId> = self.topic_subscriptions.subscribers(&topic).collect();
+        if subscribers.is_empty() { return; }
+
+        // serialize message
+        // - the message is only serialized once for all subscribers
+        tracing::trace!(%topic, "publishing message to topic");
+        let Ok(ser_msg) = self.codec.serialize(&ClientMetaEventFrom::<Channel>::TopicMsg(topic, msg))
+        else { tracing::error!("serializing topic message failed"); return; };
+
+        for session_id in subscribers
+        {
+            let Some(client_id) = self.session_to_client.get(&session_id) else { continue; };
+            let Some((_, connection_idx)) = self.client_to_session.get(client_id) else { continue; };
+            if consumed_count < *connection_idx
+            {
+                tracing::debug!(consumed_count, connection_idx,
+                    "dropping topic message targeted at session before its connection event was handled");
+                continue;
+            }
+            let Some(session) = self.session_registry.get(&session_id) else { continue; };
+            let Some(codec) = self.session_codecs.get(&session_id) else { continue; };
+
+            // forward the message to the subscribed session
+            // - this may fail if the session is disconnected
+            let Ok(result) = codec.send_serialized(ser_msg.clone(), |ser_msg| session.binary(ser_msg))
+            else { tracing::error!(client_id, "encrypting topic message failed"); continue; };
+            if let Err(_) = result
+            { tracing::debug!(client_id, "dropping topic message sent to broken session"); }
+        }
+    }
+
+    /// Updates the config values used for new sessions, and optionally updates existing sessions.
+    fn update_config(&mut self, update: PartialServerConfig)
+    {
+        tracing::info!(?update, "updating server config");
+        if let Some(max_connections) = update.max_connections { self.config.max_connections = max_connections; }
+        if let Some(timeout) = update.keepalive_timeout { self.config.keepalive_timeout = timeout; }
+        let Some(rate_limit_config) = update.rate_limit_config else { return; };
+        self.config.rate_limit_config = rate_limit_config;
+
+        if !update.apply_to_existing { return; }
+        for session in self.session_registry.values()
+        {
+            // this may fail if the session is disconnected
+            let _ = session.call(SessionCall::SetRateLimit(rate_limit_config));
+        }
+    }
+
+    /// Closes the oldest pending session.
+    fn evict_oldest_pending(&mut self)
+    {
+        let Some(session_id) = self.pending_sessions.pop_first() else { return; };
+        let Some(session) = self.session_registry.get(&session_id) else { return; };
+
+        tracing::trace!(session_id, "max pending connections reached, evicting oldest pending session...");
+        let _ = session.close(Some(
+                ezsockets::CloseFrame{
+                    code   : ezsockets::CloseCode::Policy,
+                    reason : String::from("evicted")
+                }
+            ));
+    }
+
+    /// Reports connection thresholds crossed since the connection count was `prev_connections`.
+    fn report_connection_thresholds(&self, prev_connections: u64)
+    {
+        let Some(connection_thresholds) = &self.config.connection_thresholds else { return; };
+        connection_thresholds.report_crossings(prev_connections, self.connection_counter.load());
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,79 @@
+//local shortcuts
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::sync::Arc;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Emitted when the number of connected clients crosses a threshold in [`ConnectionThresholdConfig`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ConnectionThresholdEvent
+{
+    /// The number of connections rose to the threshold.
+    Reached
+    {
+        threshold: u64,
+        connections: u64,
+    },
+    /// The number of connections fell below the threshold.
+    Receded
+    {
+        threshold: u64,
+        connections: u64,
+    },
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(ConnectionThresholdEvent)`.
+///
+/// The callback is invoked inside the server backend when clients connect or disconnect, so it should not block.
+pub trait ConnectionThresholdCallback: Fn(ConnectionThresholdEvent) + Send + Sync + 'static {}
+impl<F> ConnectionThresholdCallback for F where F: Fn(ConnectionThresholdEvent) + Send + Sync + 'static {}
+
+impl Debug for dyn ConnectionThresholdCallback
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("ConnectionThresholdCallback") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Config for observing when the number of connected clients crosses thresholds.
+///
+/// This can be used to drive autoscaling or 'server nearly full' notices without polling
+/// [`Server::num_connections()`](crate::Server::num_connections). For example, to be notified at 80% capacity use
+/// `max_connections * 4 / 5` as a threshold.
+///
+/// Only authenticated connections are counted.
+#[derive(Debug, Clone)]
+pub struct ConnectionThresholdConfig
+{
+    /// Thresholds to observe.
+    pub thresholds: Vec<u64>,
+    /// Called whenever the number of connections crosses a threshold.
+    pub callback: Arc<dyn ConnectionThresholdCallback>,
+}
+
+impl ConnectionThresholdConfig
+{
+    /// Reports thresholds crossed by a change in the number of connections.
+    pub(crate) fn report_crossings(&self, prev_connections: u64, connections: u64)
+    {
+        for &threshold in self.thresholds.iter()
+        {
+            if prev_connections < threshold && connections >= threshold
+            {
+                (self.callback)(ConnectionThresholdEvent::Reached{ threshold, connections });
+            }
+            else if prev_connections >= threshold && connections < threshold
+            {
+                (self.callback)(ConnectionThresholdEvent::Receded{ threshold, connections });
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -6,6 +6,7 @@ use crate::*;
 //standard shortcuts
 use core::fmt::Debug;
 use std::borrow::Cow;
+use std::net::SocketAddr;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -62,10 +63,79 @@ fn try_extract_client_env<'a>(
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
+fn check_wire_format<'a>(
+    query_element : Option<(Cow<str>, Cow<str>)>,
+    wire_format   : WireFormat
+) -> Result<(), &'static str>
+{
+    // get query element
+    let Some((key, value)) = query_element
+    else { tracing::trace!("invalid wire format (not present)"); return Err("Wire format missing."); };
+
+    // check key
+    if key != FORMAT_MSG_KEY
+    { tracing::trace!("invalid wire format (not present)"); return Err("Wire format missing."); };
+
+    // check wire format
+    let Some(client_wire_format) = wire_format_from_str(&value)
+    else { tracing::trace!("invalid wire format (unknown)"); return Err("Unknown wire format."); };
+
+    if client_wire_format != wire_format
+    { tracing::trace!(?client_wire_format, ?wire_format, "wire format mismatch"); return Err("Wire format mismatch."); };
+
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+fn check_fingerprint<'a>(
+    query_elements : &mut std::iter::Peekable<form_urlencoded::Parse<'a>>,
+    fingerprint    : Option<u32>
+) -> Result<(), &'static str>
+{
+    // the fingerprint is optional for servers without a fingerprint
+    let has_fingerprint = matches!(query_elements.peek(), Some((key, _)) if key == HASH_MSG_KEY);
+    let client_fingerprint = match has_fingerprint
+    {
+        true  => query_elements.next().map(|(_, value)| value),
+        false => None,
+    };
+    let Some(fingerprint) = fingerprint else { return Ok(()); };
+
+    // check fingerprint
+    let Some(value) = client_fingerprint
+    else { tracing::trace!(fingerprint, "missing fingerprint"); return Err("Missing fingerprint."); };
+    if value != fingerprint_as_str(fingerprint)
+    { tracing::trace!(?value, fingerprint, "fingerprint mismatch"); return Err("Fingerprint mismatch."); };
+
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Publishes the current value of a counter to its watch channel.
+///
+/// The value is read while the channel is locked, so the last update to the counter is always published.
+fn publish_counter(counter: &AtomicU64, watch: &tokio::sync::watch::Sender<u64>)
+{
+    watch.send_if_modified(|value| {
+            let new_value = counter.load(Ordering::Acquire);
+            let modified = *value != new_value;
+            *value = new_value;
+            modified
+        });
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[derive(Debug, Clone)]
 pub(crate) struct ConnectionCounter
 {
     counter: Arc<AtomicU64>,
+    /// publishes the counter's value
+    watch: Arc<tokio::sync::watch::Sender<u64>>,
 }
 
 impl ConnectionCounter
@@ -78,6 +148,7 @@ impl ConnectionCounter
     pub(crate) fn increment(&self)
     {
         self.counter.fetch_add(1u64, Ordering::Release);
+        publish_counter(&self.counter, &self.watch);
     }
 
     pub(crate) fn decrement(&self)
@@ -86,11 +157,24 @@ impl ConnectionCounter
         if self.counter.fetch_sub(1u64, Ordering::Release) == u64::MAX
         {
             self.increment();
+            return;
         }
+        publish_counter(&self.counter, &self.watch);
+    }
+
+    pub(crate) fn subscribe(&self) -> tokio::sync::watch::Receiver<u64>
+    {
+        self.watch.subscribe()
     }
 }
 
-impl Default for ConnectionCounter { fn default() -> Self { Self{ counter: Arc::new(AtomicU64::new(0u64)) } } }
+impl Default for ConnectionCounter
+{
+    fn default() -> Self
+    {
+        Self{ counter: Arc::new(AtomicU64::new(0u64)), watch: Arc::new(tokio::sync::watch::channel(0u64).0) }
+    }
+}
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -98,6 +182,8 @@ impl Default for ConnectionCounter { fn default() -> Self { Self{ counter: Arc::
 pub(crate) struct PendingCounter
 {
     counter: Arc<AtomicU64>,
+    /// publishes the counter's value
+    watch: Arc<tokio::sync::watch::Sender<u64>>,
 }
 
 impl PendingCounter
@@ -110,6 +196,7 @@ impl PendingCounter
     pub(crate) fn increment(&self)
     {
         self.counter.fetch_add(1u64, Ordering::Release);
+        publish_counter(&self.counter, &self.watch);
     }
 
     pub(crate) fn decrement(&self)
@@ -118,11 +205,24 @@ impl PendingCounter
         if self.counter.fetch_sub(1u64, Ordering::Release) == u64::MAX
         {
             self.increment();
+            return;
         }
+        publish_counter(&self.counter, &self.watch);
+    }
+
+    pub(crate) fn subscribe(&self) -> tokio::sync::watch::Receiver<u64>
+    {
+        self.watch.subscribe()
     }
 }
 
-impl Default for PendingCounter { fn default() -> Self { Self{ counter: Arc::new(AtomicU64::new(0u64)) } } }
+impl Default for PendingCounter
+{
+    fn default() -> Self
+    {
+        Self{ counter: Arc::new(AtomicU64::new(0u64)), watch: Arc::new(tokio::sync::watch::channel(0u64).0) }
+    }
+}
 
 //-------------------------------------------------------------------------------------------------------------------
 
@@ -130,29 +230,55 @@ impl Default for PendingCounter { fn default() -> Self { Self{ counter: Arc::new
 pub(crate) struct ConnectionPrevalidator
 {
     pub(crate) protocol_version   : &'static str,
+    pub(crate) wire_format        : WireFormat,
+    pub(crate) fingerprint        : Option<u32>,
     pub(crate) max_pending        : u32,
-    pub(crate) max_connections    : u32,
+    pub(crate) pending_policy     : PendingOverflowPolicy,
+    /// max connections, keepalive timeout, and pause state
+    pub(crate) limits             : RuntimeLimits,
     pub(crate) heartbeat_interval : Duration,
-    pub(crate) keepalive_timeout  : Duration,
+    pub(crate) heartbeat_ping_fn  : Option<Arc<dyn HeartbeatPingFn>>,
+    /// use text pings for native clients
+    pub(crate) text_heartbeats    : bool,
+    /// use websocket pings for WASM clients
+    pub(crate) wasm_ping_frames   : bool,
 }
 
 //-------------------------------------------------------------------------------------------------------------------
 
 pub(crate) fn prevalidate_connection_request(
     request         : &ezsockets::Request,
+    address         : SocketAddr,
     num_pending     : &PendingCounter,
     num_connections : &ConnectionCounter,
+    auth_failures   : &AuthFailureTracker,
     prevalidator    : &ConnectionPrevalidator,
 ) -> Result<EnvType, (axum::http::StatusCode, &'static str)>
 {
+    // check if the address is locked out due to repeated auth failures
+    if auth_failures.is_locked_out(address.ip())
+    {
+        tracing::trace!(?address, "address is locked out, dropping request...");
+        return Err((axum::http::StatusCode::TOO_MANY_REQUESTS, "Too many auth failures."));
+    }
+
+    // check if accepting connections is paused
+    if prevalidator.limits.is_paused()
+    {
+        tracing::trace!("accepting connections is paused, dropping request...");
+        return Err((axum::http::StatusCode::SERVICE_UNAVAILABLE, "Paused."));
+    }
+
     // check max connection counts
     // - this is an approximate test since the counters are updated async
-    if num_pending.load() >= prevalidator.max_pending as u64
+    // - pending sessions are evicted by the connection handler if that policy is set
+    if prevalidator.pending_policy != PendingOverflowPolicy::EvictOldest
+        && num_pending.load() >= prevalidator.pending_policy.pending_capacity(prevalidator.max_pending)
     {
         tracing::trace!("max pending connections reached, dropping request...");
         return Err((axum::http::StatusCode::SERVICE_UNAVAILABLE, "Max pending connections."));
     }
-    if num_connections.load() >= prevalidator.max_connections as u64
+    if num_connections.load() >= prevalidator.limits.max_connections() as u64
     {
         tracing::trace!("max connections reached, dropping request...");
         return Err((axum::http::StatusCode::SERVICE_UNAVAILABLE, "Max connections."));
@@ -165,7 +291,7 @@ pub(crate) fn prevalidate_connection_request(
         tracing::trace!("invalid uri query, dropping connection request...");
         return Err((axum::http::StatusCode::BAD_REQUEST, "Invalid query."));
     };
-    let mut query_elements_iterator = form_urlencoded::parse(query.as_bytes());
+    let mut query_elements_iterator = form_urlencoded::parse(query.as_bytes()).peekable();
 
     // check if there is a protocol version mismatch
     let _ = check_protocol_version(query_elements_iterator.next(), prevalidator.protocol_version)
@@ -175,6 +301,14 @@ pub(crate) fn prevalidate_connection_request(
     let client_env_type = try_extract_client_env(query_elements_iterator.next())
         .map_err(|reason| (axum::http::StatusCode::BAD_REQUEST, reason))?;
 
+    // check if there is a wire format mismatch
+    let _ = check_wire_format(query_elements_iterator.next(), prevalidator.wire_format)
+        .map_err(|reason| (axum::http::StatusCode::BAD_REQUEST, reason))?;
+
+    // check if there is a message type fingerprint mismatch
+    let _ = check_fingerprint(&mut query_elements_iterator, prevalidator.fingerprint)
+        .map_err(|reason| (axum::http::StatusCode::BAD_REQUEST, reason))?;
+
     // there should be no more query elements
     let None = query_elements_iterator.next()
     else { return Err((axum::http::StatusCode::PAYLOAD_TOO_LARGE, "Excess query elements.")); };
@@ -1,4 +1,5 @@
 //local shortcuts
+use crate::*;
 
 //third-party shortcuts
 
@@ -28,3 +29,103 @@ impl std::fmt::Display for ConnectionError
 impl std::error::Error for ConnectionError {}
 
 //-------------------------------------------------------------------------------------------------------------------
+
+/// Errors returned by the [`Server`](crate::Server) API.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ServerError
+{
+    /// The server is dead or its internal connection handler is unavailable.
+    Dead,
+    /// The target client is not connected, or the target session has closed.
+    UnknownClient,
+    /// The message could not be encoded for the target client.
+    SerializationFailed,
+    /// The message could not be queued because the target's queue is full.
+    Backpressure,
+    /// The operation did not complete before its timeout.
+    Timeout,
+    /// A text message was sent but [`ServerConfig::text_messages`](crate::ServerConfig::text_messages) is disabled.
+    TextDisabled,
+}
+
+impl From<SendDropReason> for ServerError
+{
+    fn from(reason: SendDropReason) -> Self
+    {
+        match reason
+        {
+            SendDropReason::ServerDead                 |
+            SendDropReason::ServerBroken               => ServerError::Dead,
+            SendDropReason::NotConnected               |
+            SendDropReason::UnconsumedConnectionReport |
+            SendDropReason::SessionBroken              |
+            SendDropReason::MailboxExpired             => ServerError::UnknownClient,
+            SendDropReason::SerializationFailed        => ServerError::SerializationFailed,
+            SendDropReason::TextDisabled               => ServerError::TextDisabled,
+            SendDropReason::MailboxFull                |
+            SendDropReason::Superseded                 => ServerError::Backpressure,
+        }
+    }
+}
+
+impl std::fmt::Display for ServerError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let _ = write!(f, "ServerError::");
+        match self
+        {
+            ServerError::Dead                => write!(f, "Dead"),
+            ServerError::UnknownClient       => write!(f, "UnknownClient"),
+            ServerError::SerializationFailed => write!(f, "SerializationFailed"),
+            ServerError::Backpressure        => write!(f, "Backpressure"),
+            ServerError::Timeout             => write!(f, "Timeout"),
+            ServerError::TextDisabled        => write!(f, "TextDisabled"),
+        }
+    }
+}
+impl std::error::Error for ServerError {}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Errors returned by [`ServerBuilder::build()`](crate::ServerBuilder::build).
+#[derive(Debug)]
+pub enum ServerBuildError
+{
+    /// No address was set with [`ServerBuilder::address()`](crate::ServerBuilder::address) and no listener was set
+    /// with [`ServerBuilder::listener()`](crate::ServerBuilder::listener).
+    MissingAddress,
+    /// The server address could not be resolved.
+    InvalidAddress(std::io::Error),
+    /// The server could not bind to its address.
+    BindFailed(std::io::Error),
+    /// The configured [`WireFormat`](crate::WireFormat) requires a crate feature that is disabled.
+    UnsupportedWireFormat(WireFormat),
+    /// Text messages are enabled along with a cipher. Text frames can't be encrypted, so they would bypass the cipher.
+    TextWithCipher,
+    /// A network simulator is enabled along with a cipher. Simulated delays can reorder frames, which the cipher
+    /// rejects as replays.
+    #[cfg(feature = "netsim")]
+    NetsimWithCipher,
+}
+
+impl std::fmt::Display for ServerBuildError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        let _ = write!(f, "ServerBuildError::");
+        match self
+        {
+            ServerBuildError::MissingAddress              => write!(f, "MissingAddress"),
+            ServerBuildError::InvalidAddress(err)         => write!(f, "InvalidAddress({err})"),
+            ServerBuildError::BindFailed(err)             => write!(f, "BindFailed({err})"),
+            ServerBuildError::UnsupportedWireFormat(wire) => write!(f, "UnsupportedWireFormat({wire:?})"),
+            ServerBuildError::TextWithCipher              => write!(f, "TextWithCipher"),
+            #[cfg(feature = "netsim")]
+            ServerBuildError::NetsimWithCipher            => write!(f, "NetsimWithCipher"),
+        }
+    }
+}
+impl std::error::Error for ServerBuildError {}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,476 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use serde::{Deserialize, Serialize};
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::collections::VecDeque;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// An entry in a recorded server event stream.
+///
+/// Incoming entries mirror the [`ServerEvent`]s consumed with [`Server::next()`]. Outgoing entries record messages
+/// and request replies that the server application sent through the [`Server`] API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub enum RecordedServerEvent<Channel: ChannelPack>
+{
+    /// Incoming [`ServerReport::Connected`].
+    Connected{
+        client_id   : ClientId,
+        env_type    : EnvType,
+        tenant      : Option<String>,
+        #[serde(default)]
+        role        : ClientRole,
+        connect_msg : Channel::ConnectMsg,
+    },
+    /// Incoming [`ServerReport::Reconnected`].
+    Reconnected{
+        client_id   : ClientId,
+        env_type    : EnvType,
+        tenant      : Option<String>,
+        #[serde(default)]
+        role        : ClientRole,
+        connect_msg : Channel::ConnectMsg,
+    },
+    /// Incoming [`ServerReport::Disconnected`].
+    Disconnected{ client_id: ClientId },
+    /// Incoming [`ServerReport::FlappingClient`].
+    FlappingClient{ client_id: ClientId, reconnects: u32 },
+    /// Incoming [`ServerReport::IdCollision`].
+    IdCollision{ client_id: ClientId },
+    /// Incoming [`ServerReport::DisconnectedWithReason`].
+    DisconnectedWithReason{ client_id: ClientId, code: u16, reason: String },
+    /// Incoming [`ServerEvent::Msg`].
+    Msg{ client_id: ClientId, msg: Channel::ClientMsg },
+    /// Incoming [`ServerEvent::Request`].
+    Request{ client_id: ClientId, request_id: u64, trace_id: Option<String>, request: Channel::ClientRequest },
+    /// Incoming [`ServerEvent::Text`].
+    Text{ client_id: ClientId, text: String },
+    /// Incoming [`ServerEvent::MailboxExpired`].
+    MailboxExpired{ client_id: ClientId, message_id: u64 },
+    /// Incoming [`ServerEvent::Control`].
+    Control{ client_id: ClientId, msg: ControlMsg },
+    /// Incoming [`ServerEvent::RateLimitViolation`].
+    RateLimitViolation{ client_id: ClientId, violation: RateLimitViolation },
+    /// Incoming [`ServerEvent::RequestBatch`].
+    RequestBatch{
+        client_id  : ClientId,
+        request_id : u64,
+        trace_id   : Option<String>,
+        requests   : Vec<Channel::ClientRequest>,
+    },
+    /// Outgoing message sent with [`Server::send()`], [`Server::send_unordered()`], or [`Server::send_reliable()`].
+    Sent{ client_id: ClientId, msg: Channel::ServerMsg },
+    /// Outgoing text message sent with [`Server::send_text()`].
+    SentText{ client_id: ClientId, text: String },
+    /// Outgoing control message sent with [`Server::send_control()`].
+    SentControl{ client_id: ClientId, msg: ControlMsg },
+    /// Outgoing response sent with [`Server::respond()`].
+    Responded{ client_id: ClientId, request_id: u64, response: Channel::ServerResponse },
+    /// Outgoing partial response sent with [`Server::respond_partial()`].
+    RespondedPart{ client_id: ClientId, request_id: u64, response: Channel::ServerResponse },
+    /// Outgoing progress report sent with [`Server::report_progress()`].
+    ReportedProgress{ client_id: ClientId, request_id: u64, progress: f32 },
+    /// Outgoing ack sent with [`Server::ack()`].
+    Acked{ client_id: ClientId, request_id: u64 },
+    /// Outgoing ack with a payload sent with [`Server::ack_with()`].
+    AckedWith{ client_id: ClientId, request_id: u64, payload: Vec<u8> },
+    /// Outgoing rejection sent with [`Server::reject()`].
+    Rejected{ client_id: ClientId, request_id: u64 },
+    /// Outgoing rejection with a reason sent with [`Server::reject_with()`].
+    RejectedWith{ client_id: ClientId, request_id: u64, reason: Channel::RejectReason },
+}
+
+impl<Channel: ChannelPack> RecordedServerEvent<Channel>
+{
+    /// Records an incoming server event.
+    pub(crate) fn incoming(client_id: ClientId, event: &ServerEventFrom<Channel>) -> Self
+    {
+        match event
+        {
+            ServerEvent::Report(ServerReport::Connected(meta, connect_msg)) => Self::Connected{
+                    client_id,
+                    env_type    : meta.env_type,
+                    tenant      : meta.tenant.clone(),
+                    role        : meta.role,
+                    connect_msg : connect_msg.clone(),
+                },
+            ServerEvent::Report(ServerReport::Reconnected(meta, connect_msg)) => Self::Reconnected{
+                    client_id,
+                    env_type    : meta.env_type,
+                    tenant      : meta.tenant.clone(),
+                    role        : meta.role,
+                    connect_msg : connect_msg.clone(),
+                },
+            ServerEvent::Report(ServerReport::Disconnected) => Self::Disconnected{ client_id },
+            ServerEvent::Report(ServerReport::FlappingClient(_, reconnects)) => Self::FlappingClient{
+                    client_id,
+                    reconnects: *reconnects,
+                },
+            ServerEvent::Report(ServerReport::IdCollision) => Self::IdCollision{ client_id },
+            ServerEvent::Report(ServerReport::DisconnectedWithReason(frame)) => Self::DisconnectedWithReason{
+                    client_id,
+                    code   : frame.code.into(),
+                    reason : frame.reason.clone(),
+                },
+            ServerEvent::Msg(msg) => Self::Msg{ client_id, msg: msg.clone() },
+            ServerEvent::Request(token, request) => Self::Request{
+                    client_id,
+                    request_id : token.request_id(),
+                    trace_id   : token.trace_id().map(String::from),
+                    request    : request.clone(),
+                },
+            ServerEvent::Text(text) => Self::Text{ client_id, text: text.clone() },
+            ServerEvent::MailboxExpired(message_id) => Self::MailboxExpired{ client_id, message_id: *message_id },
+            ServerEvent::Control(msg) => Self::Control{ client_id, msg: msg.clone() },
+            ServerEvent::RateLimitViolation(violation) => Self::RateLimitViolation{ client_id, violation: *violation },
+            ServerEvent::RequestBatch(token, requests) => Self::RequestBatch{
+                    client_id,
+                    request_id : token.request_id(),
+                    trace_id   : token.trace_id().map(String::from),
+                    requests   : requests.clone(),
+                },
+        }
+    }
+
+    /// The client this entry is associated with.
+    pub fn client_id(&self) -> ClientId
+    {
+        match self
+        {
+            Self::Connected{ client_id, .. }              |
+            Self::Reconnected{ client_id, .. }            |
+            Self::Disconnected{ client_id }               |
+            Self::FlappingClient{ client_id, .. }         |
+            Self::IdCollision{ client_id }                |
+            Self::DisconnectedWithReason{ client_id, .. } |
+            Self::Msg{ client_id, .. }                    |
+            Self::Request{ client_id, .. }                |
+            Self::Text{ client_id, .. }                   |
+            Self::MailboxExpired{ client_id, .. }         |
+            Self::Control{ client_id, .. }                |
+            Self::RateLimitViolation{ client_id, .. }     |
+            Self::RequestBatch{ client_id, .. }           |
+            Self::Sent{ client_id, .. }                   |
+            Self::SentText{ client_id, .. }               |
+            Self::SentControl{ client_id, .. }            |
+            Self::Responded{ client_id, .. }              |
+            Self::RespondedPart{ client_id, .. }          |
+            Self::ReportedProgress{ client_id, .. }       |
+            Self::Acked{ client_id, .. }                  |
+            Self::AckedWith{ client_id, .. }              |
+            Self::Rejected{ client_id, .. }               |
+            Self::RejectedWith{ client_id, .. }           => *client_id,
+        }
+    }
+
+    /// Tests if this entry was sent by the server application.
+    pub fn is_outgoing(&self) -> bool
+    {
+        matches!(self,
+            Self::Sent{ .. }             |
+            Self::SentText{ .. }         |
+            Self::SentControl{ .. }      |
+            Self::Responded{ .. }        |
+            Self::RespondedPart{ .. }    |
+            Self::ReportedProgress{ .. } |
+            Self::Acked{ .. }            |
+            Self::AckedWith{ .. }        |
+            Self::Rejected{ .. }         |
+            Self::RejectedWith{ .. }
+        )
+    }
+
+    /// Converts an incoming entry back into a server event.
+    ///
+    /// Returns `None` for outgoing entries.
+    fn into_event(self, make_token: impl FnOnce(ClientId, u64, Option<String>) -> RequestToken)
+        -> Option<(ClientId, ServerEventFrom<Channel>)>
+    {
+        let event = match self
+        {
+            Self::Connected{ client_id, env_type, tenant, role, connect_msg } =>
+            {
+                let meta = ConnectionMeta{ env_type, tenant, role };
+                (client_id, ServerEvent::Report(ServerReport::Connected(meta, connect_msg)))
+            }
+            Self::Reconnected{ client_id, env_type, tenant, role, connect_msg } =>
+            {
+                let meta = ConnectionMeta{ env_type, tenant, role };
+                (client_id, ServerEvent::Report(ServerReport::Reconnected(meta, connect_msg)))
+            }
+            Self::Disconnected{ client_id } => (client_id, ServerEvent::Report(ServerReport::Disconnected)),
+            Self::FlappingClient{ client_id, reconnects } =>
+            {
+                (client_id, ServerEvent::Report(ServerReport::FlappingClient(client_id, reconnects)))
+            }
+            Self::IdCollision{ client_id } => (client_id, ServerEvent::Report(ServerReport::IdCollision)),
+            Self::DisconnectedWithReason{ client_id, code, reason } =>
+            {
+                let frame = CloseFrame{ code: code.into(), reason };
+                (client_id, ServerEvent::Report(ServerReport::DisconnectedWithReason(frame)))
+            }
+            Self::Msg{ client_id, msg } => (client_id, ServerEvent::Msg(msg)),
+            Self::Request{ client_id, request_id, trace_id, request } =>
+            {
+                (client_id, ServerEvent::Request(make_token(client_id, request_id, trace_id), request))
+            }
+            Self::Text{ client_id, text } => (client_id, ServerEvent::Text(text)),
+            Self::MailboxExpired{ client_id, message_id } => (client_id, ServerEvent::MailboxExpired(message_id)),
+            Self::Control{ client_id, msg } => (client_id, ServerEvent::Control(msg)),
+            Self::RateLimitViolation{ client_id, violation } => (client_id, ServerEvent::RateLimitViolation(violation)),
+            Self::RequestBatch{ client_id, request_id, trace_id, requests } =>
+            {
+                (client_id, ServerEvent::RequestBatch(make_token(client_id, request_id, trace_id), requests))
+            }
+            _ => return None,
+        };
+
+        Some(event)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Records a [`Server`]'s event stream.
+///
+/// Attach a recorder with [`Server::set_recorder()`]. Entries are written as newline-delimited JSON in the order they
+/// are observed by the server application, and can be replayed with [`ServerReplay`].
+///
+/// Replies sent with a [`Responder`] are not recorded.
+pub struct ServerRecorder<Channel: ChannelPack>
+{
+    writer: Mutex<Box<dyn Write + Send>>,
+    _phantom: std::marker::PhantomData<fn() -> Channel>,
+}
+
+impl<Channel: ChannelPack> ServerRecorder<Channel>
+{
+    /// Makes a new recorder that writes to a file.
+    ///
+    /// The file will be truncated if it already exists.
+    pub fn create(path: impl AsRef<Path>) -> std::io::Result<Self>
+    {
+        let file = std::fs::File::create(path)?;
+        Ok(Self::new(std::io::BufWriter::new(file)))
+    }
+
+    /// Makes a new recorder that writes to an arbitrary writer.
+    pub fn new(writer: impl Write + Send + 'static) -> Self
+    {
+        Self{ writer: Mutex::new(Box::new(writer)), _phantom: std::marker::PhantomData }
+    }
+
+    /// Writes an entry to the recording.
+    pub fn record(&self, entry: &RecordedServerEvent<Channel>)
+    {
+        let Ok(mut writer) = self.writer.lock() else { return; };
+        let result = serde_json::to_writer(&mut *writer, entry)
+            .map_err(std::io::Error::from)
+            .and_then(|_| writer.write_all(b"\n"));
+        if let Err(err) = result
+        {
+            tracing::warn!(?err, "failed writing server event recording");
+        }
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&self) -> std::io::Result<()>
+    {
+        let Ok(mut writer) = self.writer.lock() else { return Ok(()); };
+        writer.flush()
+    }
+}
+
+impl<Channel: ChannelPack> Drop for ServerRecorder<Channel>
+{
+    fn drop(&mut self)
+    {
+        let _ = self.flush();
+    }
+}
+
+impl<Channel: ChannelPack> Debug for ServerRecorder<Channel>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("ServerRecorder") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Replays a recording made by a [`ServerRecorder`].
+///
+/// The replay mimics the [`Server`] API so server-side event handling can be driven by a recording (e.g. to reproduce
+/// a desync reported from production). Incoming entries are emitted by [`Self::next()`], and replies sent through
+/// the replay are collected in [`Self::outputs()`] so they can be compared against [`Self::recorded_outputs()`].
+#[derive(Debug)]
+pub struct ServerReplay<Channel: ChannelPack>
+{
+    /// remaining entries
+    entries: VecDeque<RecordedServerEvent<Channel>>,
+    /// outgoing entries from the recording
+    recorded_outputs: Vec<RecordedServerEvent<Channel>>,
+    /// outgoing entries from the replay
+    outputs: Arc<Mutex<Vec<RecordedServerEvent<Channel>>>>,
+}
+
+impl<Channel: ChannelPack> ServerReplay<Channel>
+{
+    /// Loads a recording from a file.
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self>
+    {
+        let file = std::fs::File::open(path)?;
+        Self::from_reader(std::io::BufReader::new(file))
+    }
+
+    /// Loads a recording from an arbitrary reader.
+    ///
+    /// Returns [`std::io::ErrorKind::InvalidData`] if an entry can't be deserialized.
+    pub fn from_reader(reader: impl BufRead) -> std::io::Result<Self>
+    {
+        let mut entries = VecDeque::default();
+        for line in reader.lines()
+        {
+            let line = line?;
+            if line.is_empty() { continue; }
+            let entry: RecordedServerEvent<Channel> = serde_json::from_str(&line)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+            entries.push_back(entry);
+        }
+
+        let recorded_outputs = entries.iter().filter(|e| e.is_outgoing()).cloned().collect();
+
+        Ok(Self{ entries, recorded_outputs, outputs: Arc::new(Mutex::new(Vec::default())) })
+    }
+
+    /// Gets the next recorded server event.
+    ///
+    /// Request tokens emitted by the replay are not connected to any client. Replies to them are collected in
+    /// [`Self::outputs()`], including rejections caused by dropping a token.
+    pub fn next(&mut self) -> Option<(ClientId, ServerEventFrom<Channel>)>
+    {
+        while let Some(entry) = self.entries.pop_front()
+        {
+            let outputs = self.outputs.clone();
+            let make_token = move |client_id: ClientId, request_id: u64, trace_id: Option<String>|
+            {
+                RequestToken::new(
+                        client_id,
+                        request_id,
+                        trace_id,
+                        Arc::new(move |request_id: u64, _: Option<String>|
+                            {
+                                outputs.lock().unwrap().push(RecordedServerEvent::Rejected{ client_id, request_id });
+                            }),
+                        Arc::new(AtomicBool::new(false))
+                    )
+            };
+
+            if let Some(event) = entry.into_event(make_token) { return Some(event); }
+        }
+
+        None
+    }
+
+    /// Records a message sent to a client.
+    pub fn send(&self, id: ClientId, msg: Channel::ServerMsg)
+    {
+        self.push_output(RecordedServerEvent::Sent{ client_id: id, msg });
+    }
+
+    /// Records a text message sent to a client.
+    pub fn send_text(&self, id: ClientId, text: impl Into<String>)
+    {
+        self.push_output(RecordedServerEvent::SentText{ client_id: id, text: text.into() });
+    }
+
+    /// Records a control message sent to a client.
+    pub fn send_control(&self, id: ClientId, msg: ControlMsg)
+    {
+        self.push_output(RecordedServerEvent::SentControl{ client_id: id, msg });
+    }
+
+    /// Records a response to a client request.
+    pub fn respond(&self, token: RequestToken, response: Channel::ServerResponse)
+    {
+        let client_id = token.client_id();
+        let (request_id, _, _) = token.take();
+        self.push_output(RecordedServerEvent::Responded{ client_id, request_id, response });
+    }
+
+    /// Records a partial response to a client request.
+    pub fn respond_partial(&self, token: &RequestToken, response: Channel::ServerResponse)
+    {
+        self.push_output(RecordedServerEvent::RespondedPart{
+                client_id  : token.client_id(),
+                request_id : token.request_id(),
+                response,
+            });
+    }
+
+    /// Records a progress report for a client request.
+    pub fn report_progress(&self, token: &RequestToken, progress: f32)
+    {
+        self.push_output(RecordedServerEvent::ReportedProgress{
+                client_id  : token.client_id(),
+                request_id : token.request_id(),
+                progress   : progress.clamp(0.0, 1.0),
+            });
+    }
+
+    /// Records an ack for a client request.
+    pub fn ack(&self, token: RequestToken)
+    {
+        let client_id = token.client_id();
+        let (request_id, _, _) = token.take();
+        self.push_output(RecordedServerEvent::Acked{ client_id, request_id });
+    }
+
+    /// Records an ack with a payload for a client request.
+    pub fn ack_with(&self, token: RequestToken, payload: impl Into<Vec<u8>>)
+    {
+        let client_id = token.client_id();
+        let (request_id, _, _) = token.take();
+        self.push_output(RecordedServerEvent::AckedWith{ client_id, request_id, payload: payload.into() });
+    }
+
+    /// Records a rejection with a reason for a client request.
+    pub fn reject_with(&self, token: RequestToken, reason: Channel::RejectReason)
+    {
+        let client_id = token.client_id();
+        let (request_id, _, _) = token.take();
+        self.push_output(RecordedServerEvent::RejectedWith{ client_id, request_id, reason });
+    }
+
+    /// Records a rejection for a client request.
+    pub fn reject(&self, _token: RequestToken)
+    {
+        // drop the token: the rejection will be recorded by the token's custom Drop
+    }
+
+    /// Gets the outgoing entries from the recording.
+    pub fn recorded_outputs(&self) -> &[RecordedServerEvent<Channel>]
+    {
+        &self.recorded_outputs
+    }
+
+    /// Gets the outgoing entries produced while replaying.
+    pub fn outputs(&self) -> Vec<RecordedServerEvent<Channel>>
+    {
+        self.outputs.lock().unwrap().clone()
+    }
+
+    fn push_output(&self, entry: RecordedServerEvent<Channel>)
+    {
+        self.outputs.lock().unwrap().push(entry);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
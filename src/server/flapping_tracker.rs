@@ -0,0 +1,96 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Max number of tracked clients before stale records are pruned.
+const FLAPPING_PRUNE_THRESHOLD: usize = 1_000;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks recent connections per client to detect clients that reconnect excessively.
+///
+/// Clients that reconnect more than [`FlappingConfig::max_reconnects`] times in one [`FlappingConfig::window`] are
+/// reported with [`ServerReport::FlappingClient`].
+#[derive(Debug)]
+pub(crate) struct FlappingTracker
+{
+    config: FlappingConfig,
+    time_source: Arc<dyn TimeSource>,
+    /// connection times of each client within the window, from oldest to newest
+    records: HashMap<ClientId, VecDeque<Duration>>,
+}
+
+impl FlappingTracker
+{
+    pub(crate) fn new(config: FlappingConfig, time_source: Arc<dyn TimeSource>) -> Self
+    {
+        Self{ config, time_source, records: HashMap::default() }
+    }
+
+    /// Records a client connection.
+    ///
+    /// Returns the number of reconnects in the current window if the client is flapping.
+    pub(crate) fn record_connect(&mut self, client_id: ClientId) -> Option<u32>
+    {
+        let now = self.time_source.now();
+        let window = self.config.window;
+
+        // clean up old records so the map can't grow without bound
+        if self.records.len() >= FLAPPING_PRUNE_THRESHOLD
+        {
+            self.records.retain(
+                    |_, connects| connects.back().map(|last| now.saturating_sub(*last) < window).unwrap_or(false)
+                );
+        }
+
+        let connects = self.records.entry(client_id).or_default();
+        while connects.front().map(|first| now.saturating_sub(*first) >= window).unwrap_or(false)
+        {
+            connects.pop_front();
+        }
+        connects.push_back(now);
+
+        // the first connection in the window is not a reconnect
+        let reconnects = (connects.len() - 1) as u32;
+        if reconnects <= self.config.max_reconnects { return None; }
+
+        tracing::debug!(client_id, reconnects, "client is reconnecting excessively");
+        Some(reconnects)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Configuration for detecting clients that reconnect excessively (see [`ServerReport::FlappingClient`]).
+///
+/// Defaults to reporting clients that reconnect more than 10 times in 60 seconds.
+#[derive(Debug, Copy, Clone)]
+pub struct FlappingConfig
+{
+    /// Length of time to count reconnects. Defaults to 60 seconds.
+    pub window: Duration,
+    /// Max number of reconnects allowed in a window before the client is reported. Defaults to 10 reconnects.
+    pub max_reconnects: u32,
+}
+
+impl Default for FlappingConfig
+{
+    fn default() -> FlappingConfig
+    {
+        FlappingConfig{
+                window         : Duration::from_secs(60),
+                max_reconnects : 10u32,
+            }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,98 @@
+//local shortcuts
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Upper bounds (inclusive) of the buckets in [`HandshakeStats::auth_delay_histogram`], in milliseconds.
+///
+/// The last bucket in the histogram counts delays above the last bound.
+pub const AUTH_DELAY_BUCKET_BOUNDS_MS: [u64; 8] = [10, 25, 50, 100, 250, 500, 1_000, 2_500];
+
+/// Number of buckets in [`HandshakeStats::auth_delay_histogram`].
+pub const AUTH_DELAY_BUCKETS: usize = AUTH_DELAY_BUCKET_BOUNDS_MS.len() + 1;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Statistics about connection handshakes accumulated by a [`Server`](crate::Server) over its lifetime.
+///
+/// These are intended for tuning [`ServerConfig::auth_timeout`](crate::ServerConfig::auth_timeout) against real
+/// connection data. The auth delay of a session is measured from when its socket is upgraded until it successfully
+/// authenticates.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct HandshakeStats
+{
+    /// Number of sessions that authenticated successfully.
+    pub authenticated: u64,
+    /// Number of sessions closed because they did not authenticate within the auth timeout.
+    pub auth_timeouts: u64,
+    /// Sum of the auth delays of authenticated sessions.
+    ///
+    /// Divide by [`Self::authenticated`] to get the mean auth delay.
+    pub total_auth_delay: Duration,
+    /// Histogram of the auth delays of authenticated sessions.
+    ///
+    /// Each bucket counts delays up to its bound in [`AUTH_DELAY_BUCKET_BOUNDS_MS`] that exceed the previous bucket's
+    /// bound.
+    pub auth_delay_histogram: [u64; AUTH_DELAY_BUCKETS],
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct HandshakeStatsInner
+{
+    authenticated           : AtomicU64,
+    auth_timeouts           : AtomicU64,
+    total_auth_delay_micros : AtomicU64,
+    auth_delay_histogram    : [AtomicU64; AUTH_DELAY_BUCKETS],
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Shared counters for [`HandshakeStats`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct HandshakeStatsCounters
+{
+    inner: Arc<HandshakeStatsInner>,
+}
+
+impl HandshakeStatsCounters
+{
+    pub(crate) fn record_authenticated(&self, delay: Duration)
+    {
+        let delay_millis = delay.as_millis();
+        let bucket = AUTH_DELAY_BUCKET_BOUNDS_MS
+            .iter()
+            .position(|bound| delay_millis <= *bound as u128)
+            .unwrap_or(AUTH_DELAY_BUCKET_BOUNDS_MS.len());
+
+        self.inner.authenticated.fetch_add(1, Ordering::Relaxed);
+        self.inner.total_auth_delay_micros.fetch_add(delay.as_micros() as u64, Ordering::Relaxed);
+        self.inner.auth_delay_histogram[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_auth_timeout(&self)
+    {
+        self.inner.auth_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stats(&self) -> HandshakeStats
+    {
+        HandshakeStats{
+            authenticated        : self.inner.authenticated.load(Ordering::Relaxed),
+            auth_timeouts        : self.inner.auth_timeouts.load(Ordering::Relaxed),
+            total_auth_delay     : Duration::from_micros(self.inner.total_auth_delay_micros.load(Ordering::Relaxed)),
+            auth_delay_histogram : std::array::from_fn(
+                |bucket| self.inner.auth_delay_histogram[bucket].load(Ordering::Relaxed)
+            ),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
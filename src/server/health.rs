@@ -0,0 +1,166 @@
+//local shortcuts
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Path of the liveness route added when [`ServerConfig::health_routes`](crate::ServerConfig::health_routes) is
+/// enabled.
+pub const HEALTH_ROUTE: &str = "/healthz";
+/// Path of the readiness route added when [`ServerConfig::health_routes`](crate::ServerConfig::health_routes) is
+/// enabled.
+pub const READY_ROUTE: &str = "/readyz";
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The startup state of a server.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ServerStartup
+{
+    /// The server's listener is starting.
+    Starting,
+    /// The server's listener is serving connections.
+    Serving,
+    /// The server died before its listener started serving connections.
+    Failed,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct ServerHealthInner
+{
+    listening     : AtomicBool,
+    handler_alive : AtomicBool,
+    draining      : AtomicBool,
+    startup       : tokio::sync::watch::Sender<ServerStartup>,
+}
+
+impl Default for ServerHealthInner
+{
+    fn default() -> Self
+    {
+        Self{
+            listening     : AtomicBool::default(),
+            handler_alive : AtomicBool::default(),
+            draining      : AtomicBool::default(),
+            startup       : tokio::sync::watch::channel(ServerStartup::Starting).0,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the internal signals reported by a server's health routes.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ServerHealth
+{
+    inner: Arc<ServerHealthInner>,
+}
+
+impl ServerHealth
+{
+    /// Sets whether the server's listener is serving connections.
+    pub(crate) fn set_listening(&self, listening: bool)
+    {
+        self.inner.listening.store(listening, Ordering::Release);
+        self.end_startup(match listening { true => ServerStartup::Serving, false => ServerStartup::Failed });
+    }
+
+    /// Sets whether the server's connection handler is running.
+    pub(crate) fn set_handler_alive(&self, alive: bool)
+    {
+        self.inner.handler_alive.store(alive, Ordering::Release);
+        if !alive { self.end_startup(ServerStartup::Failed); }
+    }
+
+    /// Gets the server's startup state.
+    pub(crate) fn startup(&self) -> ServerStartup
+    {
+        *self.inner.startup.borrow()
+    }
+
+    /// Subscribes to the server's startup state.
+    pub(crate) fn subscribe_startup(&self) -> tokio::sync::watch::Receiver<ServerStartup>
+    {
+        self.inner.startup.subscribe()
+    }
+
+    /// Sets whether the server is draining.
+    pub(crate) fn set_draining(&self, draining: bool)
+    {
+        self.inner.draining.store(draining, Ordering::Release);
+    }
+
+    /// Checks if the server is draining.
+    pub(crate) fn is_draining(&self) -> bool
+    {
+        self.inner.draining.load(Ordering::Acquire)
+    }
+
+    /// Checks if the server's listener and connection handler are running.
+    pub(crate) fn is_live(&self) -> bool
+    {
+        self.inner.listening.load(Ordering::Acquire) && self.inner.handler_alive.load(Ordering::Acquire)
+    }
+
+    /// Checks if the server is live and not draining.
+    pub(crate) fn is_ready(&self) -> bool
+    {
+        self.is_live() && !self.is_draining()
+    }
+
+    /// Updates the startup state if the server is still starting.
+    fn end_startup(&self, state: ServerStartup)
+    {
+        self.inner.startup.send_if_modified(
+                |startup|
+                {
+                    if *startup != ServerStartup::Starting { return false; }
+                    *startup = state;
+                    true
+                }
+            );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+async fn health_handler(axum::Extension(health): axum::Extension<ServerHealth>) -> axum::http::StatusCode
+{
+    match health.is_live()
+    {
+        true  => axum::http::StatusCode::OK,
+        false => axum::http::StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+async fn ready_handler(axum::Extension(health): axum::Extension<ServerHealth>) -> axum::http::StatusCode
+{
+    match health.is_ready()
+    {
+        true  => axum::http::StatusCode::OK,
+        false => axum::http::StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Adds the health and readiness routes to a router.
+pub(crate) fn add_health_routes(router: axum::Router, health: ServerHealth) -> axum::Router
+{
+    let routes = axum::Router::new()
+        .route(HEALTH_ROUTE, axum::routing::get(health_handler))
+        .route(READY_ROUTE, axum::routing::get(ready_handler))
+        .layer(axum::Extension(health));
+
+    router.merge(routes)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
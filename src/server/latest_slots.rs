@@ -0,0 +1,119 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::collections::HashMap;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Encrypts a serialized message and forwards it to a session, returning the message's send signal if it was forwarded.
+fn forward_to_session(
+    session   : &ezsockets::Session<SessionId, SessionCall>,
+    codec     : &WireCodec,
+    client_id : ClientId,
+    ser_msg   : Vec<u8>,
+    resolver  : SendSignalResolver,
+) -> Option<SendSignal>
+{
+    let signal = resolver.signal();
+    let Ok(result) = codec.send_serialized(ser_msg, |ser_msg| session.binary(ser_msg))
+    else
+    {
+        tracing::error!(client_id, "encrypting latest-only message failed");
+        resolver.drop_with(SendDropReason::SerializationFailed);
+        return None;
+    };
+
+    match result
+    {
+        Ok(message_signal) =>
+        {
+            resolver.forwarded(message_signal);
+            signal
+        }
+        Err(_) =>
+        {
+            tracing::debug!(client_id, "dropping latest-only message sent to broken session");
+            resolver.drop_with(SendDropReason::SessionBroken);
+            None
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct LatestSlot
+{
+    /// the most recent message forwarded to the session
+    in_flight: Option<SendSignal>,
+    /// the newest message waiting for the in-flight message to be sent
+    pending: Option<(Vec<u8>, SendSignalResolver)>,
+    /// set when a flush has been scheduled for the pending message
+    flush_scheduled: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Latest-only send slots for a session.
+///
+/// Each slot holds at most one message that hasn't been flushed to the session's socket. Newer messages replace
+/// older ones that are still waiting.
+#[derive(Debug, Default)]
+pub(crate) struct LatestSlots
+{
+    slots: HashMap<u64, LatestSlot>,
+}
+
+impl LatestSlots
+{
+    /// Sends a message in a slot, or holds it if the slot's previous message hasn't been flushed yet.
+    ///
+    /// Returns `true` if a flush needs to be scheduled for the held message.
+    pub(crate) fn send(
+        &mut self,
+        session   : &ezsockets::Session<SessionId, SessionCall>,
+        codec     : &WireCodec,
+        client_id : ClientId,
+        slot      : u64,
+        ser_msg   : Vec<u8>,
+        resolver  : SendSignalResolver,
+    ) -> bool
+    {
+        let entry = self.slots.entry(slot).or_default();
+
+        if entry.in_flight.as_ref().is_some_and(|in_flight| in_flight.outcome().is_queued())
+        {
+            if let Some((_, superseded)) = entry.pending.replace((ser_msg, resolver))
+            {
+                tracing::trace!(client_id, slot, "replacing unsent latest-only message");
+                superseded.drop_with(SendDropReason::Superseded);
+            }
+            if entry.flush_scheduled { return false; }
+            entry.flush_scheduled = true;
+            return true;
+        }
+
+        entry.in_flight = forward_to_session(session, codec, client_id, ser_msg, resolver);
+        false
+    }
+
+    /// Sends the message held in a slot.
+    pub(crate) fn flush(
+        &mut self,
+        session   : &ezsockets::Session<SessionId, SessionCall>,
+        codec     : &WireCodec,
+        client_id : ClientId,
+        slot      : u64,
+    )
+    {
+        let Some(entry) = self.slots.get_mut(&slot) else { return; };
+        entry.flush_scheduled = false;
+        let Some((ser_msg, resolver)) = entry.pending.take() else { return; };
+        entry.in_flight = forward_to_session(session, codec, client_id, ser_msg, resolver);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,168 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Interval between checking all mailboxes for expired messages.
+pub(crate) const MAILBOX_EXPIRY_INTERVAL: Duration = Duration::from_millis(250);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Configuration for queueing messages sent to disconnected clients.
+///
+/// Defaults to 64 messages per client that expire after 60 seconds.
+#[derive(Debug, Copy, Clone)]
+pub struct MailboxConfig
+{
+    /// Max number of messages queued for each client. Defaults to 64.
+    ///
+    /// The oldest message is dropped when a full mailbox receives a new message.
+    pub max_messages: usize,
+    /// Length of time a message can wait in a mailbox before it expires. Defaults to 60 seconds.
+    pub ttl: Duration,
+}
+
+impl Default for MailboxConfig
+{
+    fn default() -> MailboxConfig
+    {
+        MailboxConfig{
+                max_messages : 64usize,
+                ttl          : Duration::from_secs(60),
+            }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct MailboxEntry<ServerMsg>
+{
+    msg: ServerMsg,
+    expires_at: Duration,
+    resolver: SendSignalResolver,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Queues messages for disconnected clients.
+#[derive(Debug)]
+pub(crate) struct Mailboxes<ServerMsg>
+{
+    config: MailboxConfig,
+    time_source: Arc<dyn TimeSource>,
+    mailboxes: HashMap<ClientId, VecDeque<MailboxEntry<ServerMsg>>>,
+}
+
+impl<ServerMsg> Mailboxes<ServerMsg>
+{
+    pub(crate) fn new(config: MailboxConfig, time_source: Arc<dyn TimeSource>) -> Self
+    {
+        Self{ config, time_source, mailboxes: HashMap::default() }
+    }
+
+    /// Queues a message for a client.
+    ///
+    /// Returns the ids of messages that were dropped because they expired or the client's mailbox is full.
+    pub(crate) fn push(
+        &mut self,
+        client_id : ClientId,
+        msg       : ServerMsg,
+        resolver  : SendSignalResolver
+    ) -> Vec<(ClientId, u64)>
+    {
+        let now = self.time_source.now();
+        let mut dropped = Vec::default();
+
+        // add the message
+        if self.config.max_messages == 0
+        {
+            dropped.push((client_id, resolver.id()));
+            resolver.drop_with(SendDropReason::MailboxFull);
+            return dropped;
+        }
+
+        let mailbox = self.mailboxes.entry(client_id).or_default();
+        Self::drain_expired(client_id, mailbox, now, &mut dropped);
+
+        while mailbox.len() >= self.config.max_messages
+        {
+            let Some(entry) = mailbox.pop_front() else { break; };
+            dropped.push((client_id, entry.resolver.id()));
+            entry.resolver.drop_with(SendDropReason::MailboxFull);
+        }
+
+        mailbox.push_back(MailboxEntry{ msg, expires_at: now.saturating_add(self.config.ttl), resolver });
+
+        dropped
+    }
+
+    /// Removes expired messages from all mailboxes.
+    ///
+    /// Returns the ids of the expired messages. This should be called periodically (see
+    /// [`MAILBOX_EXPIRY_INTERVAL`]) so messages for clients that never reconnect are dropped.
+    pub(crate) fn expire(&mut self) -> Vec<(ClientId, u64)>
+    {
+        let now = self.time_source.now();
+        let mut dropped = Vec::default();
+
+        for (client_id, mailbox) in self.mailboxes.iter_mut()
+        {
+            Self::drain_expired(*client_id, mailbox, now, &mut dropped);
+        }
+        self.mailboxes.retain(|_, mailbox| !mailbox.is_empty());
+
+        dropped
+    }
+
+    /// Removes a client's mailbox.
+    ///
+    /// Returns `(unexpired messages, ids of expired messages)`.
+    pub(crate) fn take(&mut self, client_id: ClientId) -> (Vec<(ServerMsg, SendSignalResolver)>, Vec<u64>)
+    {
+        let Some(mailbox) = self.mailboxes.remove(&client_id) else { return (Vec::default(), Vec::default()); };
+        let now = self.time_source.now();
+
+        let mut live = Vec::with_capacity(mailbox.len());
+        let mut expired = Vec::default();
+        for entry in mailbox
+        {
+            if entry.expires_at <= now
+            {
+                expired.push(entry.resolver.id());
+                entry.resolver.drop_with(SendDropReason::MailboxExpired);
+            }
+            else
+            {
+                live.push((entry.msg, entry.resolver));
+            }
+        }
+
+        (live, expired)
+    }
+
+    fn drain_expired(
+        client_id : ClientId,
+        mailbox   : &mut VecDeque<MailboxEntry<ServerMsg>>,
+        now       : Duration,
+        dropped   : &mut Vec<(ClientId, u64)>
+    ){
+        // messages are queued in order of expiration
+        while mailbox.front().map_or(false, |entry| entry.expires_at <= now)
+        {
+            let Some(entry) = mailbox.pop_front() else { break; };
+            dropped.push((client_id, entry.resolver.id()));
+            entry.resolver.drop_with(SendDropReason::MailboxExpired);
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,165 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::sync::Arc;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The result of passing a client message or request through [`ServerMiddleware`].
+#[derive(Debug, Clone)]
+pub enum MiddlewareAction<T>
+{
+    /// Pass the (possibly modified) value to the next middleware, or emit it as a [`ServerEvent`] if this is the last
+    /// middleware.
+    Continue(T),
+    /// Discard the value.
+    ///
+    /// Requests will be rejected (the client will receive a [`ClientEvent::Reject`](crate::ClientEvent)).
+    Reject,
+    /// Discard the value and disconnect the client.
+    Disconnect,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Intercepts client messages, requests, and text messages before they are emitted as [`ServerEvent`]s.
+///
+/// Middleware is invoked in each client's session handler in the order it was registered with
+/// [`ServerFactory::with_middleware()`]. Messages, requests, and text messages are only passed to middleware after the
+/// client is authenticated. Middleware runs inside the server backend, so it should not block.
+///
+/// Use middleware for cross-cutting policies like permission checks, schema validation, or tracing.
+pub trait ServerMiddleware<Channel: ChannelPack>: Debug + Send + Sync + 'static
+{
+    /// Intercepts a client message.
+    fn on_msg(&self, _client_id: ClientId, msg: Channel::ClientMsg) -> MiddlewareAction<Channel::ClientMsg>
+    {
+        MiddlewareAction::Continue(msg)
+    }
+
+    /// Intercepts a client request.
+    fn on_request(
+        &self,
+        _client_id : ClientId,
+        request    : Channel::ClientRequest
+    ) -> MiddlewareAction<Channel::ClientRequest>
+    {
+        MiddlewareAction::Continue(request)
+    }
+
+    /// Intercepts a client text message (see [`ServerEvent::Text`]).
+    fn on_text(&self, _client_id: ClientId, text: String) -> MiddlewareAction<String>
+    {
+        MiddlewareAction::Continue(text)
+    }
+
+    /// Intercepts a client message with access to the client's [`SessionStore`].
+    ///
+    /// Defaults to [`Self::on_msg()`].
+    fn on_msg_with_store(
+        &self,
+        client_id : ClientId,
+        _store    : &mut SessionStore,
+        msg       : Channel::ClientMsg
+    ) -> MiddlewareAction<Channel::ClientMsg>
+    {
+        self.on_msg(client_id, msg)
+    }
+
+    /// Intercepts a client request with access to the client's [`SessionStore`].
+    ///
+    /// Defaults to [`Self::on_request()`].
+    fn on_request_with_store(
+        &self,
+        client_id : ClientId,
+        _store    : &mut SessionStore,
+        request   : Channel::ClientRequest
+    ) -> MiddlewareAction<Channel::ClientRequest>
+    {
+        self.on_request(client_id, request)
+    }
+
+    /// Intercepts a client text message with access to the client's [`SessionStore`].
+    ///
+    /// Defaults to [`Self::on_text()`].
+    fn on_text_with_store(
+        &self,
+        client_id : ClientId,
+        _store    : &mut SessionStore,
+        text      : String
+    ) -> MiddlewareAction<String>
+    {
+        self.on_text(client_id, text)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// An ordered list of middleware.
+pub(crate) type MiddlewareChain<Channel> = Arc<[Arc<dyn ServerMiddleware<Channel>>]>;
+
+/// Passes a client message through a middleware chain.
+pub(crate) fn apply_msg_middleware<Channel: ChannelPack>(
+    chain     : &MiddlewareChain<Channel>,
+    client_id : ClientId,
+    store     : &mut SessionStore,
+    mut msg   : Channel::ClientMsg
+) -> MiddlewareAction<Channel::ClientMsg>
+{
+    for middleware in chain.iter()
+    {
+        match middleware.on_msg_with_store(client_id, store, msg)
+        {
+            MiddlewareAction::Continue(next) => msg = next,
+            action                           => return action,
+        }
+    }
+
+    MiddlewareAction::Continue(msg)
+}
+
+/// Passes a client request through a middleware chain.
+pub(crate) fn apply_request_middleware<Channel: ChannelPack>(
+    chain       : &MiddlewareChain<Channel>,
+    client_id   : ClientId,
+    store       : &mut SessionStore,
+    mut request : Channel::ClientRequest
+) -> MiddlewareAction<Channel::ClientRequest>
+{
+    for middleware in chain.iter()
+    {
+        match middleware.on_request_with_store(client_id, store, request)
+        {
+            MiddlewareAction::Continue(next) => request = next,
+            action                           => return action,
+        }
+    }
+
+    MiddlewareAction::Continue(request)
+}
+
+/// Passes a client text message through a middleware chain.
+pub(crate) fn apply_text_middleware<Channel: ChannelPack>(
+    chain     : &MiddlewareChain<Channel>,
+    client_id : ClientId,
+    store     : &mut SessionStore,
+    mut text  : String
+) -> MiddlewareAction<String>
+{
+    for middleware in chain.iter()
+    {
+        match middleware.on_text_with_store(client_id, store, text)
+        {
+            MiddlewareAction::Continue(next) => text = next,
+            action                           => return action,
+        }
+    }
+
+    MiddlewareAction::Continue(text)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -1,21 +1,81 @@
 //module tree
+mod account_limit;
+mod auth_failure_tracker;
+mod client_groups;
+mod client_role;
+mod client_scopes;
 mod config;
+mod connect_hook;
+mod connection_groups;
 mod connection_handler;
+mod connection_thresholds;
 mod connection_validation;
 mod errors;
+mod event_recording;
+mod flapping_tracker;
+mod handshake_stats;
+mod health;
+mod latest_slots;
+mod mailbox;
+mod middleware;
+mod msg_dedup;
+mod pending_policy;
+mod rate_limit_policy;
+mod reliable_msg;
 mod request_token;
+mod request_token_store;
+mod responder;
+mod runtime_config;
+mod send_outcome;
+mod serial_requests;
 mod server;
+mod server_builder;
 mod server_event;
 mod session_handler;
+mod session_store;
 mod session_utils;
+mod sync_state;
+mod tenants;
+mod token_verification;
+mod topics;
 
 //API exports
+pub use account_limit::*;
+pub use auth_failure_tracker::*;
+pub(crate) use client_groups::*;
+pub use client_role::*;
+pub use client_scopes::*;
 pub use config::*;
+pub use connect_hook::*;
+pub(crate) use connection_groups::*;
 pub(crate) use connection_handler::*;
+pub use connection_thresholds::*;
 pub(crate) use connection_validation::*;
 pub use errors::*;
+pub use event_recording::*;
+pub use flapping_tracker::*;
+pub use handshake_stats::*;
+pub use health::*;
+pub(crate) use latest_slots::*;
+pub use mailbox::*;
+pub use middleware::*;
+pub use msg_dedup::*;
+pub use pending_policy::*;
+pub use rate_limit_policy::*;
+pub use reliable_msg::*;
 pub use request_token::*;
+pub use request_token_store::*;
+pub use responder::*;
+pub use runtime_config::*;
+pub use send_outcome::*;
+pub(crate) use serial_requests::*;
 pub use server::*;
+pub use server_builder::*;
 pub use server_event::*;
 pub(crate) use session_handler::*;
+pub use session_store::*;
 pub(crate) use session_utils::*;
+pub use sync_state::*;
+pub use tenants::*;
+pub use token_verification::*;
+pub use topics::*;
@@ -0,0 +1,119 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use serde::Serialize;
+
+//standard shortcuts
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Drops client messages that are identical to a message the client sent recently.
+///
+/// Messages are compared by a hash of their serialized payload, so there is a negligible chance that a distinct
+/// message is dropped.
+#[derive(Debug)]
+pub(crate) struct MsgDedupFilter
+{
+    config: MsgDedupConfig,
+    time_source: Arc<dyn TimeSource>,
+    /// hashes and arrival times of recent messages, from oldest to newest
+    recent: VecDeque<(u64, Duration)>,
+    /// counts dropped messages for the server API
+    counter: MsgDedupCounter,
+}
+
+impl MsgDedupFilter
+{
+    pub(crate) fn new(config: MsgDedupConfig, time_source: Arc<dyn TimeSource>, counter: MsgDedupCounter) -> Self
+    {
+        Self{ config, time_source, recent: VecDeque::default(), counter }
+    }
+
+    /// Records a message from the client.
+    ///
+    /// Returns `true` if the message duplicates a message in the current window and should be dropped.
+    pub(crate) fn is_duplicate<T: Serialize>(&mut self, msg: &T) -> bool
+    {
+        let Ok(payload) = bincode::serialize(msg) else { return false; };
+        let mut hasher = DefaultHasher::new();
+        payload.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        // forget messages that left the window
+        let now = self.time_source.now();
+        let window = self.config.window;
+        while self.recent.front().map(|(_, time)| now.saturating_sub(*time) >= window).unwrap_or(false)
+        {
+            self.recent.pop_front();
+        }
+
+        if self.recent.iter().any(|(recent_hash, _)| *recent_hash == hash)
+        {
+            self.counter.increment();
+            return true;
+        }
+
+        if self.recent.len() >= self.config.max_msgs { self.recent.pop_front(); }
+        if self.config.max_msgs > 0 { self.recent.push_back((hash, now)); }
+
+        false
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Shared counter for messages dropped by [`MsgDedupFilter`]s.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct MsgDedupCounter
+{
+    inner: Arc<AtomicU64>,
+}
+
+impl MsgDedupCounter
+{
+    fn increment(&self)
+    {
+        self.inner.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn load(&self) -> u64
+    {
+        self.inner.load(Ordering::Relaxed)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Configuration for dropping duplicate client messages (see [`ServerConfig::msg_dedup`]).
+///
+/// Defaults to dropping messages that are identical to one of the client's last 16 messages within 500 milliseconds.
+#[derive(Debug, Copy, Clone)]
+pub struct MsgDedupConfig
+{
+    /// Length of time to remember a client's messages. Defaults to 500 milliseconds.
+    pub window: Duration,
+    /// Max number of messages remembered per client. Defaults to 16 messages.
+    ///
+    /// Each message is compared against all remembered messages, so this should be kept small.
+    pub max_msgs: usize,
+}
+
+impl Default for MsgDedupConfig
+{
+    fn default() -> MsgDedupConfig
+    {
+        MsgDedupConfig{
+                window   : Duration::from_millis(500),
+                max_msgs : 16usize,
+            }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,44 @@
+//local shortcuts
+
+//third-party shortcuts
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Policy for handling new connections when [`ServerConfig::max_pending`](crate::ServerConfig::max_pending) is reached.
+///
+/// Pending connections are sessions that have connected but not authenticated yet.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum PendingOverflowPolicy
+{
+    /// Reject new connection requests.
+    #[default]
+    Reject,
+    /// Accept new connections and close the oldest pending session to make room.
+    ///
+    /// This prevents a flood of idle connections from locking out real clients until the idle connections time out.
+    EvictOldest,
+    /// Accept up to `max_pending` extra connections, but only let them authenticate with
+    /// [`AuthRequest::Token`](crate::AuthRequest::Token).
+    ///
+    /// Overflow sessions that try to authenticate with other auth types are closed with `"server busy"`, so clients
+    /// with tokens issued by a trusted backend can still connect while the server is flooded.
+    PrioritizeTokens,
+}
+
+impl PendingOverflowPolicy
+{
+    /// Max number of pending sessions retained by the policy.
+    pub(crate) fn pending_capacity(&self, max_pending: u32) -> u64
+    {
+        match self
+        {
+            Self::Reject | Self::EvictOldest => max_pending as u64,
+            Self::PrioritizeTokens           => max_pending as u64 * 2,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,47 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Policy for handling authenticated clients that exceed
+/// [`ServerConfig::rate_limit_config`](crate::ServerConfig::rate_limit_config).
+///
+/// Sessions that exceed the rate limit before authenticating are always closed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum RateLimitPolicy
+{
+    /// Close the client's session.
+    #[default]
+    Disconnect,
+    /// Emit [`ServerEvent::RateLimitViolation`](crate::ServerEvent::RateLimitViolation), then close the client's
+    /// session.
+    ReportAndDisconnect,
+    /// Emit [`ServerEvent::RateLimitViolation`](crate::ServerEvent::RateLimitViolation) and discard messages that
+    /// exceed the rate limit.
+    ///
+    /// Only the first violation in each rate limit period is reported. This lets the server penalize clients without
+    /// closing their sessions.
+    ReportAndDrop,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Details of a client exceeding its rate limit (see [`RateLimitPolicy`]).
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RateLimitViolation
+{
+    /// The rate limit that was exceeded.
+    ///
+    /// This is the client's tenant's rate limit if the tenant has one (see [`TenantConfig::rate_limit_config`]).
+    pub config: RateLimitConfig,
+    /// Number of messages received in the current rate limit period, including the message that exceeded the limit.
+    pub count: u64,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
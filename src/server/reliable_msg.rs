@@ -0,0 +1,158 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const WAITING   : u8 = 0u8;
+const DELIVERED : u8 = 1u8;
+const LOST      : u8 = 2u8;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks whether a reliable message was acknowledged by its client.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReliableAckSignal
+{
+    signal: Arc<AtomicU8>,
+}
+
+impl ReliableAckSignal
+{
+    fn set(&self, state: u8)
+    {
+        let _ = self.signal.compare_exchange(WAITING, state, Ordering::AcqRel, Ordering::Acquire);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Indicates the current status of a reliable server message.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ReliableMsgStatus
+{
+    /// The message is queued for delivery.
+    Sending,
+    /// The message was sent and now we are waiting for the client to acknowledge it.
+    Waiting,
+    /// The client acknowledged receiving the message.
+    Delivered,
+    /// The message was dropped before it could be sent.
+    Dropped(SendDropReason),
+    /// The client's session closed before the message was acknowledged.
+    ///
+    /// The client may or may not have received the message.
+    Lost,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the current status of a reliable server message.
+///
+/// See [`Server::send_reliable()`].
+#[derive(Debug, Clone)]
+pub struct ReliableMsgSignal
+{
+    send_signal : SendSignal,
+    ack_signal  : ReliableAckSignal,
+}
+
+impl ReliableMsgSignal
+{
+    /// Makes a new signal.
+    pub(crate) fn new(send_signal: SendSignal, ack_signal: ReliableAckSignal) -> Self
+    {
+        Self{ send_signal, ack_signal }
+    }
+
+    /// Gets the message status.
+    pub fn status(&self) -> ReliableMsgStatus
+    {
+        match self.ack_signal.signal.load(Ordering::Acquire)
+        {
+            DELIVERED => ReliableMsgStatus::Delivered,
+            LOST      => ReliableMsgStatus::Lost,
+            _ => match self.send_signal.outcome()
+            {
+                SendOutcome::Queued          => ReliableMsgStatus::Sending,
+                SendOutcome::Sent            => ReliableMsgStatus::Waiting,
+                SendOutcome::Dropped(reason) => ReliableMsgStatus::Dropped(reason),
+            }
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct ReliableMsgTrackerInner
+{
+    next_id: u64,
+    pending: HashMap<u64, ReliableAckSignal>,
+    closed: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks reliable messages sent to a session that are waiting for acknowledgement.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReliableMsgTracker
+{
+    inner: Arc<Mutex<ReliableMsgTrackerInner>>,
+}
+
+impl ReliableMsgTracker
+{
+    /// Registers a message that is about to be sent.
+    ///
+    /// Returns the message's id, or `None` if the session is closed.
+    pub(crate) fn register(&self, ack_signal: ReliableAckSignal) -> Option<u64>
+    {
+        let Ok(mut inner) = self.inner.lock()
+        else { tracing::error!("reliable message tracker lock error"); return None; };
+        if inner.closed { return None; }
+
+        let id = inner.next_id;
+        inner.next_id = inner.next_id.wrapping_add(1);
+        inner.pending.insert(id, ack_signal);
+        Some(id)
+    }
+
+    /// Removes a message that failed to send.
+    pub(crate) fn remove(&self, id: u64)
+    {
+        let Ok(mut inner) = self.inner.lock()
+        else { tracing::error!("reliable message tracker lock error"); return; };
+        inner.pending.remove(&id);
+    }
+
+    /// Marks a message as delivered.
+    ///
+    /// Returns `false` if the message is unknown.
+    pub(crate) fn acknowledge(&self, id: u64) -> bool
+    {
+        let Ok(mut inner) = self.inner.lock()
+        else { tracing::error!("reliable message tracker lock error"); return false; };
+        let Some(ack_signal) = inner.pending.remove(&id) else { return false; };
+        ack_signal.set(DELIVERED);
+        true
+    }
+
+    /// Marks all pending messages as lost and rejects new messages.
+    pub(crate) fn close(&self)
+    {
+        let Ok(mut inner) = self.inner.lock()
+        else { tracing::error!("reliable message tracker lock error"); return; };
+        inner.closed = true;
+        for (_, ack_signal) in inner.pending.drain() { ack_signal.set(LOST); }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -24,9 +24,9 @@ impl SessionDeathSignal
 
 //-------------------------------------------------------------------------------------------------------------------
 
-/// Wrapper trait for `Fn(u64)`.
-pub(crate) trait RequestRejectorFn: Fn(u64) + Send + Sync + 'static {}
-impl<F> RequestRejectorFn for F where F: Fn(u64) + Send + Sync + 'static {}
+/// Wrapper trait for `Fn(u64, Option<String>)`.
+pub(crate) trait RequestRejectorFn: Fn(u64, Option<String>) + Send + Sync + 'static {}
+impl<F> RequestRejectorFn for F where F: Fn(u64, Option<String>) + Send + Sync + 'static {}
 pub(crate) type RequestRejectorFnT = dyn RequestRejectorFn<Output = ()>;
 
 impl std::fmt::Debug for RequestRejectorFnT
@@ -45,8 +45,11 @@ pub struct RequestToken
 {
     client_id    : ClientId,
     request_id   : u64,
+    trace_id     : Option<String>,
     rejector     : Option<Arc<dyn RequestRejectorFn>>,
     death_signal : Option<SessionDeathSignal>,
+    /// notifies the session when this token is consumed
+    _consumed    : Option<RequestConsumedGuard>,
 }
 
 impl RequestToken
@@ -55,6 +58,7 @@ impl RequestToken
     pub(crate) fn new(
         client_id    : ClientId,
         request_id   : u64,
+        trace_id     : Option<String>,
         rejector     : Arc<dyn RequestRejectorFn>,
         death_signal : Arc<AtomicBool>
     ) -> Self
@@ -62,11 +66,20 @@ impl RequestToken
         Self{
             client_id,
             request_id,
+            trace_id,
             rejector     : Some(rejector),
-            death_signal : Some(SessionDeathSignal::new(death_signal))
+            death_signal : Some(SessionDeathSignal::new(death_signal)),
+            _consumed    : None,
         }
     }
 
+    /// Attaches a guard that notifies the session when this token is consumed.
+    pub(crate) fn with_consumed_guard(mut self, guard: RequestConsumedGuard) -> Self
+    {
+        self._consumed = Some(guard);
+        self
+    }
+
     /// The id of the client that sent this request.
     pub fn client_id(&self) -> ClientId
     {
@@ -79,6 +92,15 @@ impl RequestToken
         self.request_id
     }
 
+    /// The trace id attached to this request by the client, if any.
+    ///
+    /// See [`Client::request_traced()`]. The trace id will be echoed back to the client in the request's response,
+    /// ack, or rejection.
+    pub fn trace_id(&self) -> Option<&str>
+    {
+        self.trace_id.as_deref()
+    }
+
     /// Check if the destination session is dead.
     ///
     /// Request tokens are tied to a specific server session. When a client reconnects they get a new session and
@@ -88,11 +110,17 @@ impl RequestToken
         self.death_signal.as_ref().unwrap().is_dead()
     }
 
+    /// Get the destination session's death signal.
+    pub(crate) fn death_signal(&self) -> SessionDeathSignal
+    {
+        self.death_signal.clone().unwrap()
+    }
+
     /// Consume the token, preventing it from sending a rejection message when dropped.
-    pub(crate) fn take(mut self) -> (u64, SessionDeathSignal)
+    pub(crate) fn take(mut self) -> (u64, Option<String>, SessionDeathSignal)
     {
         let _ = self.rejector.take();
-        (self.request_id, self.death_signal.take().unwrap())
+        (self.request_id, self.trace_id.take(), self.death_signal.take().unwrap())
     }
 }
 
@@ -102,7 +130,7 @@ impl Drop for RequestToken
     {
         let Some(rejector) = self.rejector.take() else { return; };
         if self.destination_is_dead() { return; }
-        (rejector)(self.request_id);
+        (rejector)(self.request_id, self.trace_id.take());
     }
 }
 
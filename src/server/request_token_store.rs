@@ -0,0 +1,110 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct StoredToken
+{
+    token: RequestToken,
+    expires_at: Duration,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Stores [`RequestToken`]s for requests that will be answered later.
+///
+/// Tokens are keyed by `(client id, request id)`. Expired tokens are dropped when [`Self::prune()`] is called, which
+/// sends a rejection to the client. Tokens whose sessions have died are discarded without sending anything, so
+/// tokens never leak across client reconnects.
+///
+/// Call [`Self::remove_client()`] when a client disconnects to release their tokens immediately.
+#[cfg_attr(feature = "bevy", derive(bevy_ecs::system::Resource))]
+#[derive(Debug)]
+pub struct RequestTokenStore
+{
+    ttl: Duration,
+    time_source: Arc<dyn TimeSource>,
+    tokens: HashMap<(ClientId, u64), StoredToken>,
+}
+
+impl RequestTokenStore
+{
+    /// Makes a new store where tokens expire after `ttl`.
+    pub fn new(ttl: Duration) -> Self
+    {
+        Self{ ttl, time_source: Arc::new(SystemTimeSource), tokens: HashMap::default() }
+    }
+
+    /// Sets the time source used to expire tokens. Defaults to [`SystemTimeSource`].
+    pub fn with_time_source(mut self, time_source: Arc<dyn TimeSource>) -> Self
+    {
+        self.time_source = time_source;
+        self
+    }
+
+    /// Adds a token to the store.
+    ///
+    /// If a token with the same key is already stored, then the old token will be rejected.
+    pub fn insert(&mut self, token: RequestToken)
+    {
+        let key = (token.client_id(), token.request_id());
+        let expires_at = self.time_source.now().saturating_add(self.ttl);
+        self.tokens.insert(key, StoredToken{ token, expires_at });
+    }
+
+    /// Removes a token from the store.
+    ///
+    /// Returns `None` if the token is not stored, has expired, or its session has died. Expired tokens are rejected.
+    pub fn take(&mut self, client_id: ClientId, request_id: u64) -> Option<RequestToken>
+    {
+        let stored = self.tokens.remove(&(client_id, request_id))?;
+        if stored.expires_at <= self.time_source.now() { return None; }
+        if stored.token.destination_is_dead() { return None; }
+
+        Some(stored.token)
+    }
+
+    /// Checks if a token is stored.
+    pub fn contains(&self, client_id: ClientId, request_id: u64) -> bool
+    {
+        self.tokens.contains_key(&(client_id, request_id))
+    }
+
+    /// Rejects all tokens from a client.
+    pub fn remove_client(&mut self, client_id: ClientId)
+    {
+        self.tokens.retain(|(id, _), _| *id != client_id);
+    }
+
+    /// Rejects expired tokens and discards tokens whose sessions have died.
+    pub fn prune(&mut self)
+    {
+        let now = self.time_source.now();
+        self.tokens.retain(|_, stored| stored.expires_at > now && !stored.token.destination_is_dead());
+    }
+
+    /// The number of stored tokens.
+    ///
+    /// This may include expired tokens that have not been pruned yet.
+    pub fn len(&self) -> usize
+    {
+        self.tokens.len()
+    }
+
+    /// Checks if there are no stored tokens.
+    pub fn is_empty(&self) -> bool
+    {
+        self.tokens.is_empty()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
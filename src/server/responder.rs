@@ -0,0 +1,193 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Forwards a non-final update for a request (a partial response or progress report) to the request's session.
+pub(crate) fn send_request_update<Channel: ChannelPack>(
+    client_event_sender : &tokio::sync::mpsc::UnboundedSender<ClientTargetMsg<ClientId, SessionCommand<Channel>>>,
+    token               : &RequestToken,
+    update              : ClientMetaEventFrom<Channel>,
+) -> Result<(), ServerError>
+{
+    // check token liveness
+    let client_id  = token.client_id();
+    let request_id = token.request_id();
+    if token.destination_is_dead()
+    {
+        tracing::debug!(client_id, request_id, "tried to send request update to dead session");
+        return Err(ServerError::UnknownClient);
+    }
+
+    // send to endpoint of ezsockets::Server::call() (will be picked up by ConnectionHandler::on_call())
+    if let Err(err) = client_event_sender.send(ClientTargetMsg::new(
+            client_id,
+            SessionCommand::<Channel>::Send(
+                update,
+                None,
+                Some(token.death_signal()),
+                None
+            )
+        ))
+    {
+        tracing::error!(?err, "failed to forward request update to session");
+        return Err(ServerError::Dead);
+    }
+
+    Ok(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Sends responses to a single client request.
+///
+/// Responders can be moved into async tasks so requests can be answered without access to the [`Server`]. Use
+/// [`Server::responder()`] to make a responder from a [`RequestToken`].
+///
+/// When dropped without using [`Self::respond()`] or [`Self::ack()`], a [`ClientEvent::Reject`] message will be sent
+/// to the client (see [`RequestToken`]).
+pub struct Responder<Channel: ChannelPack>
+{
+    token: RequestToken,
+    client_event_sender: tokio::sync::mpsc::UnboundedSender<ClientTargetMsg<ClientId, SessionCommand<Channel>>>,
+}
+
+impl<Channel: ChannelPack> Responder<Channel>
+{
+    /// New responder.
+    pub(crate) fn new(
+        token               : RequestToken,
+        client_event_sender : tokio::sync::mpsc::UnboundedSender<ClientTargetMsg<ClientId, SessionCommand<Channel>>>,
+    ) -> Self
+    {
+        Self{ token, client_event_sender }
+    }
+
+    /// The id of the client that sent the request.
+    pub fn client_id(&self) -> ClientId
+    {
+        self.token.client_id()
+    }
+
+    /// The request id defined by the client who sent the request.
+    pub fn request_id(&self) -> u64
+    {
+        self.token.request_id()
+    }
+
+    /// The trace id attached to the request by the client, if any.
+    ///
+    /// See [`RequestToken::trace_id()`].
+    pub fn trace_id(&self) -> Option<&str>
+    {
+        self.token.trace_id()
+    }
+
+    /// Check if the destination session is dead.
+    ///
+    /// See [`RequestToken::destination_is_dead()`].
+    pub fn destination_is_dead(&self) -> bool
+    {
+        self.token.destination_is_dead()
+    }
+
+    /// Responds to the request.
+    ///
+    /// The response will be dropped under the same conditions as [`Server::respond()`], or if the server has shut
+    /// down.
+    pub fn respond(self, response: Channel::ServerResponse) -> Result<(), ServerError>
+    {
+        self.send(move |request_id, trace_id| ClientMetaEvent::Response(response, request_id, trace_id))
+    }
+
+    /// Sends a partial response to the request.
+    ///
+    /// See [`Server::respond_partial()`].
+    pub fn respond_partial(&self, response: Channel::ServerResponse) -> Result<(), ServerError>
+    {
+        let update = ClientMetaEvent::ResponsePart(response, self.token.request_id());
+        send_request_update::<Channel>(&self.client_event_sender, &self.token, update)
+    }
+
+    /// Reports progress for the request.
+    ///
+    /// See [`Server::report_progress()`].
+    pub fn report_progress(&self, progress: f32) -> Result<(), ServerError>
+    {
+        let update = ClientMetaEvent::Progress(self.token.request_id(), progress.clamp(0.0, 1.0));
+        send_request_update::<Channel>(&self.client_event_sender, &self.token, update)
+    }
+
+    /// Acknowledges the request.
+    ///
+    /// The ack will be dropped under the same conditions as [`Server::ack()`], or if the server has shut down. An
+    /// acknowledged request cannot be responded to.
+    pub fn ack(self) -> Result<(), ServerError>
+    {
+        self.send(|request_id, trace_id| ClientMetaEvent::Ack(request_id, trace_id))
+    }
+
+    /// Acknowledges the request with a small payload.
+    ///
+    /// See [`Server::ack_with()`].
+    pub fn ack_with(self, payload: impl Into<Vec<u8>>) -> Result<(), ServerError>
+    {
+        let payload = payload.into();
+        self.send(move |request_id, trace_id| ClientMetaEvent::AckWith(request_id, payload, trace_id))
+    }
+
+    /// Rejects the request.
+    pub fn reject(self)
+    {
+        // drop the token: rejection will happen automatically using the token's custom Drop
+    }
+
+    /// Rejects the request with a reason.
+    ///
+    /// See [`Server::reject_with()`].
+    pub fn reject_with(self, reason: Channel::RejectReason) -> Result<(), ServerError>
+    {
+        self.send(move |request_id, trace_id| ClientMetaEvent::RejectWith(request_id, reason, trace_id))
+    }
+
+    /// Forwards a response event to the request's session.
+    fn send(self, event: impl FnOnce(u64, Option<String>) -> ClientMetaEventFrom<Channel>) -> Result<(), ServerError>
+    {
+        // check token liveness
+        let client_id  = self.token.client_id();
+        let request_id = self.token.request_id();
+        if self.token.destination_is_dead()
+        {
+            tracing::debug!(client_id, request_id, "tried to send response to dead session");
+            return Err(ServerError::UnknownClient);
+        }
+
+        // send to endpoint of ezsockets::Server::call() (will be picked up by ConnectionHandler::on_call())
+        let (request_id, trace_id, death_signal) = self.token.take();
+        if let Err(err) = self.client_event_sender.send(ClientTargetMsg::new(
+                client_id,
+                SessionCommand::<Channel>::Send((event)(request_id, trace_id), None, Some(death_signal), None)
+            ))
+        {
+            tracing::error!(?err, "failed to forward response to session");
+            return Err(ServerError::Dead);
+        }
+
+        Ok(())
+    }
+}
+
+impl<Channel: ChannelPack> Debug for Responder<Channel>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "Responder [{}, {}]", self.token.client_id(), self.token.request_id())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,104 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::sync::{Arc, Mutex, PoisonError};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// [`ServerConfig`] values that can be changed while the server is running (see [`Server::update_config()`]).
+///
+/// Values that are `None` are not changed.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct PartialServerConfig
+{
+    /// Replaces [`ServerConfig::max_connections`].
+    ///
+    /// Connected clients are not disconnected if the new limit is lower than the current number of connections.
+    pub max_connections: Option<u32>,
+    /// Replaces [`ServerConfig::rate_limit_config`].
+    ///
+    /// Sessions that use their tenant's rate limit (see [`TenantConfig::rate_limit_config`]) are not affected.
+    pub rate_limit_config: Option<RateLimitConfig>,
+    /// Replaces [`ServerConfig::keepalive_timeout`].
+    ///
+    /// The timeout is fixed when a session's socket is opened, so this only affects new sessions.
+    pub keepalive_timeout: Option<Duration>,
+    /// Apply the new rate limit to existing sessions. Defaults to `false` (only new sessions are affected).
+    ///
+    /// The rate limit trackers of existing sessions are reset.
+    pub apply_to_existing: bool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct RuntimeLimitsInner
+{
+    max_connections   : AtomicU32,
+    keepalive_timeout : Mutex<Duration>,
+    paused            : AtomicBool,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Limits shared between the [`Server`] and the connection prevalidator that can be changed at runtime.
+///
+/// This includes whether accepting connections is paused (see [`Server::pause_accepting()`]).
+#[derive(Debug, Clone)]
+pub(crate) struct RuntimeLimits
+{
+    inner: Arc<RuntimeLimitsInner>,
+}
+
+impl RuntimeLimits
+{
+    pub(crate) fn new(config: &ServerConfig) -> Self
+    {
+        Self{
+            inner: Arc::new(RuntimeLimitsInner{
+                    max_connections   : AtomicU32::new(config.max_connections),
+                    keepalive_timeout : Mutex::new(config.keepalive_timeout),
+                    paused            : AtomicBool::new(false),
+                })
+        }
+    }
+
+    pub(crate) fn update(&self, update: &PartialServerConfig)
+    {
+        if let Some(max_connections) = update.max_connections
+        {
+            self.inner.max_connections.store(max_connections, Ordering::Relaxed);
+        }
+        if let Some(keepalive_timeout) = update.keepalive_timeout
+        {
+            *self.inner.keepalive_timeout.lock().unwrap_or_else(PoisonError::into_inner) = keepalive_timeout;
+        }
+    }
+
+    pub(crate) fn max_connections(&self) -> u32
+    {
+        self.inner.max_connections.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn keepalive_timeout(&self) -> Duration
+    {
+        *self.inner.keepalive_timeout.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn set_paused(&self, paused: bool)
+    {
+        self.inner.paused.store(paused, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_paused(&self) -> bool
+    {
+        self.inner.paused.load(Ordering::Relaxed)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
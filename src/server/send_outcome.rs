@@ -0,0 +1,248 @@
+//local shortcuts
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU8, AtomicU64, Ordering};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+const QUEUED    : u8 = 0u8;
+const FORWARDED : u8 = 1u8;
+
+/// Source of [`SendSignal`] ids.
+static NEXT_SIGNAL_ID: AtomicU64 = AtomicU64::new(0u64);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reason a server message was dropped.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SendDropReason
+{
+    /// The server is dead.
+    ServerDead,
+    /// The server's internal connection handler is unavailable.
+    ServerBroken,
+    /// The client is not connected.
+    NotConnected,
+    /// There were unconsumed connection reports for the client when the message was sent.
+    UnconsumedConnectionReport,
+    /// The message failed to serialize.
+    SerializationFailed,
+    /// The message was a text message but [`ServerConfig::text_messages`](crate::ServerConfig::text_messages) is
+    /// disabled.
+    TextDisabled,
+    /// The client's session broke before the message could be sent.
+    SessionBroken,
+    /// The message expired in the client's mailbox (see [`MailboxConfig`](crate::MailboxConfig)).
+    MailboxExpired,
+    /// The message was evicted from the client's full mailbox (see [`MailboxConfig`](crate::MailboxConfig)).
+    MailboxFull,
+    /// The message was replaced by a newer message in the same slot before it could be sent (see
+    /// [`Server::send_latest()`](crate::Server::send_latest)).
+    Superseded,
+}
+
+impl SendDropReason
+{
+    fn to_u8(self) -> u8
+    {
+        match self
+        {
+            Self::ServerDead                 => 2u8,
+            Self::ServerBroken               => 3u8,
+            Self::NotConnected               => 4u8,
+            Self::UnconsumedConnectionReport => 5u8,
+            Self::SerializationFailed        => 6u8,
+            Self::TextDisabled               => 7u8,
+            Self::SessionBroken              => 8u8,
+            Self::MailboxExpired             => 9u8,
+            Self::MailboxFull                => 10u8,
+            Self::Superseded                 => 11u8,
+        }
+    }
+
+    fn from_u8(val: u8) -> Self
+    {
+        match val
+        {
+            2u8  => Self::ServerDead,
+            3u8  => Self::ServerBroken,
+            4u8  => Self::NotConnected,
+            5u8  => Self::UnconsumedConnectionReport,
+            6u8  => Self::SerializationFailed,
+            7u8  => Self::TextDisabled,
+            9u8  => Self::MailboxExpired,
+            10u8 => Self::MailboxFull,
+            11u8 => Self::Superseded,
+            _    => Self::SessionBroken,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// The outcome of sending a message from the server.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SendOutcome
+{
+    /// The message is queued for delivery.
+    Queued,
+    /// The message was sent to the client's socket.
+    ///
+    /// This does not guarantee the client received it.
+    Sent,
+    /// The message was dropped.
+    Dropped(SendDropReason),
+}
+
+impl SendOutcome
+{
+    /// Returns `true` if the message has not been sent or dropped yet.
+    pub fn is_queued(&self) -> bool
+    {
+        matches!(self, Self::Queued)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct SendSignalInner
+{
+    id: u64,
+    status: AtomicU8,
+    message_signal: Mutex<Option<ezsockets::MessageSignal>>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the outcome of a message sent by the server.
+#[derive(Debug, Clone)]
+pub struct SendSignal
+{
+    inner: Arc<SendSignalInner>,
+}
+
+impl SendSignal
+{
+    /// Makes a new queued signal.
+    pub(crate) fn new() -> Self
+    {
+        Self{
+            inner: Arc::new(SendSignalInner{
+                id             : NEXT_SIGNAL_ID.fetch_add(1u64, Ordering::Relaxed),
+                status         : AtomicU8::new(QUEUED),
+                message_signal : Mutex::new(None),
+            })
+        }
+    }
+
+    /// Gets the id of the message.
+    ///
+    /// Ids are unique within the process. They are used to identify messages reported with
+    /// [`ServerEvent::MailboxExpired`](crate::ServerEvent::MailboxExpired).
+    pub fn id(&self) -> u64
+    {
+        self.inner.id
+    }
+
+    /// Gets the current outcome of the message.
+    pub fn outcome(&self) -> SendOutcome
+    {
+        match self.inner.status.load(Ordering::Acquire)
+        {
+            QUEUED    => SendOutcome::Queued,
+            FORWARDED =>
+            {
+                let Ok(message_signal) = self.inner.message_signal.lock()
+                else { return SendOutcome::Dropped(SendDropReason::SessionBroken); };
+                let Some(message_signal) = &*message_signal else { return SendOutcome::Queued; };
+
+                match message_signal.status()
+                {
+                    ezsockets::MessageStatus::Sending => SendOutcome::Queued,
+                    ezsockets::MessageStatus::Sent    => SendOutcome::Sent,
+                    ezsockets::MessageStatus::Failed  => SendOutcome::Dropped(SendDropReason::SessionBroken),
+                }
+            }
+            val => SendOutcome::Dropped(SendDropReason::from_u8(val)),
+        }
+    }
+
+    /// Marks the message as dropped if it wasn't already resolved.
+    fn set_dropped(&self, reason: SendDropReason)
+    {
+        let _ = self.inner.status.compare_exchange(QUEUED, reason.to_u8(), Ordering::AcqRel, Ordering::Acquire);
+    }
+
+    /// Marks the message as forwarded to the client's socket if it wasn't already resolved.
+    fn set_forwarded(&self, message_signal: ezsockets::MessageSignal)
+    {
+        let Ok(mut slot) = self.inner.message_signal.lock() else { return; };
+        if self.inner.status.load(Ordering::Acquire) != QUEUED { return; }
+        *slot = Some(message_signal);
+        self.inner.status.store(FORWARDED, Ordering::Release);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Resolves a [`SendSignal`] from inside the server backend.
+///
+/// If the resolver is dropped without being used, then the message is assumed dropped because the client is not
+/// connected.
+#[derive(Debug)]
+pub(crate) struct SendSignalResolver
+{
+    signal: Option<SendSignal>,
+}
+
+impl SendSignalResolver
+{
+    /// Makes a new resolver for a signal.
+    pub(crate) fn new(signal: SendSignal) -> Self
+    {
+        Self{ signal: Some(signal) }
+    }
+
+    /// Gets the signal this resolver will resolve.
+    pub(crate) fn signal(&self) -> Option<SendSignal>
+    {
+        self.signal.clone()
+    }
+
+    /// Gets the id of the message this resolver tracks.
+    pub(crate) fn id(&self) -> u64
+    {
+        // the signal is only taken when the resolver is consumed
+        self.signal.as_ref().map(SendSignal::id).unwrap_or_default()
+    }
+
+    /// Marks the message as dropped.
+    pub(crate) fn drop_with(mut self, reason: SendDropReason)
+    {
+        let Some(signal) = self.signal.take() else { return; };
+        signal.set_dropped(reason);
+    }
+
+    /// Marks the message as forwarded to the client's socket.
+    pub(crate) fn forwarded(mut self, message_signal: ezsockets::MessageSignal)
+    {
+        let Some(signal) = self.signal.take() else { return; };
+        signal.set_forwarded(message_signal);
+    }
+}
+
+impl Drop for SendSignalResolver
+{
+    fn drop(&mut self)
+    {
+        let Some(signal) = self.signal.take() else { return; };
+        signal.set_dropped(SendDropReason::NotConnected);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
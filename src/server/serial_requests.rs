@@ -0,0 +1,85 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::collections::VecDeque;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Notifies a session when a request token is consumed (see [`ServerConfig::serialize_requests`]).
+///
+/// Request tokens hold this guard, so the session is notified when the token is dropped, which happens when the
+/// server replies to the request or drops the token.
+pub(crate) struct RequestConsumedGuard
+{
+    session: ezsockets::Session<SessionId, SessionCall>,
+}
+
+impl RequestConsumedGuard
+{
+    pub(crate) fn new(session: ezsockets::Session<SessionId, SessionCall>) -> Self
+    {
+        Self{ session }
+    }
+}
+
+impl Drop for RequestConsumedGuard
+{
+    fn drop(&mut self)
+    {
+        // this fails if the session is closed, in which case there are no more requests to emit
+        let _ = self.session.call(SessionCall::RequestConsumed);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Queues a session's requests so they are emitted one at a time (see [`ServerConfig::serialize_requests`]).
+#[derive(Debug)]
+pub(crate) struct SerialRequestQueue<Channel: ChannelPack>
+{
+    /// a request was emitted and its token has not been consumed yet
+    in_flight: bool,
+    /// requests waiting for the in-flight request's token to be consumed, from oldest to newest
+    queued: VecDeque<ServerEventFrom<Channel>>,
+}
+
+impl<Channel: ChannelPack> SerialRequestQueue<Channel>
+{
+    /// Adds a request event to the queue.
+    ///
+    /// Returns the event if it can be emitted immediately.
+    pub(crate) fn push(&mut self, event: ServerEventFrom<Channel>) -> Option<ServerEventFrom<Channel>>
+    {
+        if self.in_flight
+        {
+            self.queued.push_back(event);
+            return None;
+        }
+
+        self.in_flight = true;
+        Some(event)
+    }
+
+    /// Handles the in-flight request's token being consumed.
+    ///
+    /// Returns the next request event to emit, if there is one.
+    pub(crate) fn pop_consumed(&mut self) -> Option<ServerEventFrom<Channel>>
+    {
+        let next = self.queued.pop_front();
+        self.in_flight = next.is_some();
+        next
+    }
+}
+
+impl<Channel: ChannelPack> Default for SerialRequestQueue<Channel>
+{
+    fn default() -> Self
+    {
+        Self{ in_flight: false, queued: VecDeque::default() }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
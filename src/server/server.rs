@@ -7,34 +7,38 @@ use enfync::Handle;
 
 //standard shortcuts
 use core::fmt::Debug;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::net::SocketAddr;
 use std::marker::PhantomData;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
 
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
 fn socket_config(prevalidator: &ConnectionPrevalidator, client_env_type: EnvType) -> ezsockets::SocketConfig
 {
-    match client_env_type
+    match (client_env_type, prevalidator.text_heartbeats, prevalidator.wasm_ping_frames)
     {
-        EnvType::Native =>
+        (EnvType::Native, false, _) | (EnvType::Wasm, false, true) =>
         {
             // use the default heartbeat ping message function
             ezsockets::SocketConfig{
                 heartbeat : prevalidator.heartbeat_interval,
-                timeout   : prevalidator.keepalive_timeout,
+                timeout   : prevalidator.limits.keepalive_timeout(),
                 ..Default::default()
             }
         }
-        EnvType::Wasm =>
+        (EnvType::Wasm, _, false) | (_, true, _) =>
         {
             // use a custom Text-based ping message
             ezsockets::SocketConfig{
                     heartbeat : prevalidator.heartbeat_interval,
-                    timeout   : prevalidator.keepalive_timeout,
-                    heartbeat_ping_msg_fn : Arc::new(text_ping_fn)
+                    timeout   : prevalidator.limits.keepalive_timeout(),
+                    heartbeat_ping_msg_fn : make_text_ping_fn(prevalidator.heartbeat_ping_fn.clone())
                 }
         }
     }
@@ -44,15 +48,17 @@ fn socket_config(prevalidator: &ConnectionPrevalidator, client_env_type: EnvType
 //-------------------------------------------------------------------------------------------------------------------
 
 async fn websocket_handler<Channel: ChannelPack>(
-    axum::Extension(server)  : axum::Extension<ezsockets::Server<ConnectionHandler<Channel>>>,
-    axum::Extension(pending) : axum::Extension<PendingCounter>,
-    axum::Extension(count)   : axum::Extension<ConnectionCounter>,
-    axum::Extension(preval)  : axum::Extension<Arc<ConnectionPrevalidator>>,
-    ezsocket_upgrade         : ezsockets::axum::Upgrade,
+    axum::Extension(server)   : axum::Extension<ezsockets::Server<ConnectionHandler<Channel>>>,
+    axum::Extension(pending)  : axum::Extension<PendingCounter>,
+    axum::Extension(count)    : axum::Extension<ConnectionCounter>,
+    axum::Extension(preval)   : axum::Extension<Arc<ConnectionPrevalidator>>,
+    axum::Extension(failures) : axum::Extension<AuthFailureTracker>,
+    axum::extract::ConnectInfo(address) : axum::extract::ConnectInfo<SocketAddr>,
+    ezsocket_upgrade          : ezsockets::axum::Upgrade,
 ) -> impl axum::response::IntoResponse
 {
     // prevalidate then prepare upgrade
-    match prevalidate_connection_request(ezsocket_upgrade.request(), &pending, &count, &preval)
+    match prevalidate_connection_request(ezsocket_upgrade.request(), address, &pending, &count, &failures, &preval)
     {
         Ok(client_env_type) => ezsocket_upgrade.on_upgrade_with_config(server, socket_config(&preval, client_env_type)),
         Err(err) => err.into_response()
@@ -62,10 +68,15 @@ async fn websocket_handler<Channel: ChannelPack>(
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
-async fn run_server(router: axum::Router, listener: std::net::TcpListener, acceptor_config: AcceptorConfig)
+pub(crate) async fn run_server(
+    router          : axum::Router,
+    listener        : std::net::TcpListener,
+    acceptor_config : AcceptorConfig,
+    handle          : axum_server::Handle,
+)
 {
     // set listener
-    let server = axum_server::Server::from_tcp(listener);
+    let server = axum_server::Server::from_tcp(listener).handle(handle);
 
     // set acceptor and serve
     let make_service = router.into_make_service_with_connect_info::<SocketAddr>();
@@ -107,14 +118,41 @@ async fn run_server(router: axum::Router, listener: std::net::TcpListener, accep
 #[cfg_attr(feature = "bevy", derive(bevy_ecs::system::Resource))]
 pub struct Server<Channel: ChannelPack>
 {
-    /// The server's address.
-    server_address: SocketAddr,
+    /// The server's address, if it has a listener.
+    server_address: Option<SocketAddr>,
+    /// The server's externally visible url.
+    public_url: Option<url::Url>,
     /// Indicates whether or not the server uses TLS.
     uses_tls: bool,
     /// The number of current connections.
     connection_counter: ConnectionCounter,
+    /// The number of pending sessions.
+    pending_counter: PendingCounter,
     /// The number of connection events consumed.
     consumed_connection_events: u64,
+    /// Counters for frames received from clients.
+    frame_counters: FrameCounters,
+    /// Counters for detecting synchronization drift.
+    sync_counters: SyncCounters,
+    /// Counters for connection handshakes.
+    handshake_counters: HandshakeStatsCounters,
+    /// Counter for duplicate client messages.
+    msg_dedup_counter: MsgDedupCounter,
+    /// Clients whose latest consumed connection report is [`ServerReport::Connected`] or
+    /// [`ServerReport::Reconnected`], mapped to the epochs of their sessions.
+    connected_clients: HashMap<ClientId, u64>,
+    /// Named groups of connected clients.
+    groups: ClientGroups,
+    /// Indicates whether messages sent to disconnected clients are queued in mailboxes.
+    mailbox_enabled: bool,
+    /// Indicates whether text messages can be sent to clients.
+    text_messages: bool,
+    /// Records the server's event stream.
+    recorder: Option<ServerRecorder<Channel>>,
+    /// Signals reported by the server's health routes.
+    health: ServerHealth,
+    /// The server's composed router.
+    router: axum::Router,
 
     /// Sends client events to the internal connection handler.
     client_event_sender: tokio::sync::mpsc::UnboundedSender<
@@ -125,35 +163,230 @@ pub struct Server<Channel: ChannelPack>
 
     /// A signal that indicates if the server's internal worker has stopped.
     server_closed_signal: enfync::PendingResult<()>,
-    /// A signal that indicates if the server runner has stopped.
-    server_running_signal: enfync::PendingResult<()>,
+    /// A signal that indicates if the server runner has stopped, if it has a listener.
+    server_running_signal: Option<enfync::PendingResult<()>>,
+    /// Limits that can be changed at runtime.
+    runtime_limits: RuntimeLimits,
+    /// Controls the server's listener, if it has one.
+    listener_handle: Option<axum_server::Handle>,
+    /// Indicates whether the server's listener was stopped with [`Self::stop_accepting()`].
+    stopped_accepting: bool,
 }
 
 impl<Channel: ChannelPack> Server<Channel>
 {
     /// Sends a message to the target client.
     ///
-    /// Messages will be silently dropped if the client is not connected *or* if the
-    /// client is connected but there are unconsumed connection reports for that client.
-    pub fn send(&self, id: ClientId, msg: Channel::ServerMsg)
+    /// Messages will be dropped if the client is not connected *or* if the
+    /// client is connected but there are unconsumed connection reports for that client. This ensures messages sent in
+    /// response to a client's state won't leak into a new session if the client reconnects.
+    ///
+    /// Returns a [`SendSignal`] that can be used to check if the message was sent or dropped. Returns
+    /// [`ServerError::UnknownClient`] if the client's latest consumed connection report is not
+    /// [`ServerReport::Connected`] (unless [`ServerConfig::mailbox`] is enabled).
+    pub fn send(&self, id: ClientId, msg: Channel::ServerMsg) -> Result<SendSignal, ServerError>
     {
-        if self.is_dead() { tracing::warn!(id, "tried to send message to client but server is dead"); return; }
+        self.check_target(id)?;
+        self.record(|| RecordedServerEvent::Sent{ client_id: id, msg: msg.clone() });
+        let consumed_connection_events = self.consumed_connection_events;
+        self.send_command(
+                id,
+                move |resolver| SessionCommand::<Channel>::Send(
+                    ClientMetaEvent::Msg(msg),
+                    Some(consumed_connection_events),
+                    None,
+                    Some(resolver)
+                )
+            )
+    }
 
-        // send to endpoint of ezsockets::Server::call() (will be picked up by ConnectionHandler::on_call())
-        if let Err(err) = self.client_event_sender.send(
-                ClientTargetMsg::new(
-                    id,
-                    SessionCommand::<Channel>::Send(
-                        ClientMetaEvent::Msg(msg),
-                        Some(self.consumed_connection_events),
-                        None
-                    )
+    /// Sends a message to the target client without synchronizing with connection reports.
+    ///
+    /// Unlike [`Self::send()`], messages will be delivered even if there are unconsumed connection reports for the
+    /// client. Messages sent this way may arrive at a newly reconnected client before the server has observed the
+    /// reconnect, so they should not depend on the client's previous state.
+    ///
+    /// Messages will be dropped if the client is not connected. Since connection reports are ignored, this will only
+    /// return [`ServerError::Dead`].
+    pub fn send_unordered(&self, id: ClientId, msg: Channel::ServerMsg) -> Result<SendSignal, ServerError>
+    {
+        self.record(|| RecordedServerEvent::Sent{ client_id: id, msg: msg.clone() });
+        self.send_command(
+                id,
+                move |resolver| SessionCommand::<Channel>::Send(ClientMetaEvent::Msg(msg), None, None, Some(resolver))
+            )
+    }
+
+    /// Sends a message to the target client that the client will acknowledge.
+    ///
+    /// Reliable messages are dropped under the same conditions as [`Self::send()`]. The returned signal will
+    /// become [`ReliableMsgStatus::Delivered`] once the client's current session acknowledges receiving the message, or
+    /// [`ReliableMsgStatus::Lost`] if the session closes first.
+    ///
+    /// Acknowledgements are sent as client messages, so they count toward the client's rate limit.
+    pub fn send_reliable(&self, id: ClientId, msg: Channel::ServerMsg) -> Result<ReliableMsgSignal, ServerError>
+    {
+        self.check_target(id)?;
+        self.record(|| RecordedServerEvent::Sent{ client_id: id, msg: msg.clone() });
+        let consumed_connection_events = self.consumed_connection_events;
+        let ack_signal = ReliableAckSignal::default();
+        let ack_signal_clone = ack_signal.clone();
+        let send_signal = self.send_command(
+                id,
+                move |resolver| SessionCommand::<Channel>::SendReliable(
+                    msg,
+                    Some(consumed_connection_events),
+                    resolver,
+                    ack_signal_clone
+                )
+            )?;
+
+        Ok(ReliableMsgSignal::new(send_signal, ack_signal))
+    }
+
+    /// Sends a message to the target client in a latest-only slot.
+    ///
+    /// If the slot's previous message hasn't been flushed to the client's socket yet, then this message will wait
+    /// until the client's session asks for it. Waiting messages are replaced by newer messages sent to the same slot,
+    /// and the replaced messages are dropped with [`SendDropReason::Superseded`]. This is useful for messages like
+    /// state snapshots, where only the newest message is worth sending.
+    ///
+    /// Slots are per-session, so they are reset when the client reconnects. Latest-only messages are dropped under
+    /// the same conditions as [`Self::send()`], but they are never added to the client's mailbox.
+    pub fn send_latest(&self, id: ClientId, slot: u64, msg: Channel::ServerMsg) -> Result<SendSignal, ServerError>
+    {
+        self.check_target(id)?;
+        self.record(|| RecordedServerEvent::Sent{ client_id: id, msg: msg.clone() });
+        let consumed_connection_events = self.consumed_connection_events;
+        self.send_command(
+                id,
+                move |resolver| SessionCommand::<Channel>::SendLatest(
+                    slot,
+                    msg,
+                    Some(consumed_connection_events),
+                    resolver
                 )
             )
+    }
+
+    /// Sends a text message to the target client.
+    ///
+    /// Text messages are dropped under the same conditions as [`Self::send()`]. Returns
+    /// [`ServerError::TextDisabled`] if [`ServerConfig::text_messages`] is disabled.
+    pub fn send_text(&self, id: ClientId, text: impl Into<String>) -> Result<SendSignal, ServerError>
+    {
+        if !self.text_messages
         {
-            tracing::error!(?err, "failed to forward message to session");
-            return;
+            tracing::warn!(id, "tried to send text message but text messages are disabled");
+            return Err(ServerError::TextDisabled);
         }
+        self.check_target(id)?;
+        let consumed_connection_events = self.consumed_connection_events;
+        let text = text.into();
+        self.record(|| RecordedServerEvent::SentText{ client_id: id, text: text.clone() });
+        self.send_command(
+                id,
+                move |resolver| SessionCommand::<Channel>::SendText(text, Some(consumed_connection_events), resolver)
+            )
+    }
+
+    /// Sends a control message to the target client.
+    ///
+    /// Control messages are dropped under the same conditions as [`Self::send()`], but they are never added to the
+    /// client's mailbox.
+    pub fn send_control(&self, id: ClientId, msg: ControlMsg) -> Result<SendSignal, ServerError>
+    {
+        self.check_target(id)?;
+        self.record(|| RecordedServerEvent::SentControl{ client_id: id, msg: msg.clone() });
+        let consumed_connection_events = self.consumed_connection_events;
+        self.send_command(
+                id,
+                move |resolver| SessionCommand::<Channel>::Send(
+                    ClientMetaEvent::Control(msg),
+                    Some(consumed_connection_events),
+                    None,
+                    Some(resolver)
+                )
+            )
+    }
+
+    /// Adds a connected client to a named group.
+    ///
+    /// Group membership is synchronized with the server's event stream. Clients are removed from all their groups when
+    /// their [`ServerReport::Disconnected`] report is consumed, and they stay in their groups if they reconnect within
+    /// the disconnect grace window (see [`ServerConfig::disconnect_grace`]).
+    ///
+    /// Returns [`ServerError::UnknownClient`] if the client's latest consumed connection report is not
+    /// [`ServerReport::Connected`] or [`ServerReport::Reconnected`].
+    pub fn add_to_group(&mut self, group: impl Into<String>, id: ClientId) -> Result<(), ServerError>
+    {
+        if !self.connected_clients.contains_key(&id)
+        {
+            tracing::debug!(id, "tried to add unknown client to group");
+            return Err(ServerError::UnknownClient);
+        }
+
+        self.groups.add(group.into(), id);
+        Ok(())
+    }
+
+    /// Removes a client from a named group.
+    ///
+    /// Returns `false` if the client was not in the group.
+    pub fn remove_from_group(&mut self, group: &str, id: ClientId) -> bool
+    {
+        self.groups.remove(group, id)
+    }
+
+    /// Gets the members of a named group.
+    pub fn group_members(&self, group: &str) -> impl Iterator<Item = ClientId> + '_
+    {
+        self.groups.members(group)
+    }
+
+    /// Sends a message to all members of a named group.
+    ///
+    /// The message goes to exactly the group's members as of the last consumed server event. Messages are sent with
+    /// [`Self::send()`], so a member that reconnected after the last consumed connection report will not receive the
+    /// message in its new session.
+    ///
+    /// Returns the result of sending to each member. Failing to send to one member does not stop the message from
+    /// being sent to the other members.
+    pub fn send_to_group(
+        &self,
+        group : &str,
+        msg   : Channel::ServerMsg
+    ) -> Vec<(ClientId, Result<SendSignal, ServerError>)>
+    {
+        self.groups.members(group)
+            .map(|id| (id, self.send(id, msg.clone())))
+            .collect()
+    }
+
+    /// Publishes a message to all clients subscribed to a topic.
+    ///
+    /// Clients subscribe with [`Client::subscribe()`](crate::Client::subscribe) and receive published messages as
+    /// [`ClientEvent::TopicMsg`](crate::ClientEvent::TopicMsg). Subscriptions are per-session, so clients must
+    /// resubscribe after reconnecting. Messages are dropped for subscribers whose connection reports have not been
+    /// consumed (see [`Self::send()`]). Published messages are not recorded.
+    pub fn publish(&self, topic: impl Into<String>, msg: Channel::ServerMsg) -> Result<(), ServerError>
+    {
+        if self.is_dead()
+        {
+            tracing::warn!("tried to publish message but server is dead");
+            return Err(ServerError::Dead);
+        }
+
+        // send to endpoint of ezsockets::Server::call() (will be picked up by ConnectionHandler::on_call())
+        // - the command's target client id is ignored
+        let command = SessionCommand::<Channel>::Publish(topic.into(), msg, self.consumed_connection_events);
+        if let Err(err) = self.client_event_sender.send(ClientTargetMsg::new(ClientId::default(), command))
+        {
+            tracing::error!(?err, "failed to forward published message to connection handler");
+            return Err(ServerError::Dead);
+        }
+
+        Ok(())
     }
 
     /// Responds to a client request.
@@ -162,38 +395,79 @@ impl<Channel: ChannelPack> Server<Channel>
     /// Note that the client may have reconnected with a fresh session, but
     /// the response will still be dropped. This ensures reconnects are strongly synchronized (requests cannot leak
     /// across sessions).
-    pub fn respond(&self, token: RequestToken, response: Channel::ServerResponse)
+    ///
+    /// Returns [`ServerError::UnknownClient`] if the request's session is dead.
+    pub fn respond(&self, token: RequestToken, response: Channel::ServerResponse) -> Result<(), ServerError>
     {
         // check server liveness
-        let client_id  = token.client_id();
-        let request_id = token.request_id();
         if self.is_dead()
         {
+            let client_id  = token.client_id();
+            let request_id = token.request_id();
             tracing::warn!(client_id, request_id, "tried to send response to session but server is dead");
-            return;
+            return Err(ServerError::Dead);
         }
 
-        // check token liveness
-        if token.destination_is_dead()
+        self.record(|| RecordedServerEvent::Responded{
+                client_id  : token.client_id(),
+                request_id : token.request_id(),
+                response   : response.clone(),
+            });
+        self.responder(token).respond(response)
+    }
+
+    /// Sends a partial response to a client request.
+    ///
+    /// Partial responses let long-running requests stream results to the client, which receives them as
+    /// [`ClientEvent::ResponsePart`]. The request stays pending until it is responded to, acknowledged, or rejected.
+    ///
+    /// Partial responses are dropped under the same conditions as [`Self::respond()`].
+    pub fn respond_partial(&self, token: &RequestToken, response: Channel::ServerResponse) -> Result<(), ServerError>
+    {
+        // check server liveness
+        if self.is_dead()
         {
-            tracing::debug!(client_id, request_id, "tried to send response to dead session");
-            return;
+            let client_id  = token.client_id();
+            let request_id = token.request_id();
+            tracing::warn!(client_id, request_id, "tried to send partial response to session but server is dead");
+            return Err(ServerError::Dead);
         }
 
-        // send to endpoint of ezsockets::Server::call() (will be picked up by ConnectionHandler::on_call())
-        let (request_id, death_signal) = token.take();
-        if let Err(err) = self.client_event_sender.send(ClientTargetMsg::new(
-                client_id,
-                SessionCommand::<Channel>::Send(
-                    ClientMetaEvent::Response(response, request_id),
-                    None,
-                    Some(death_signal)
-                )
-            ))
+        self.record(|| RecordedServerEvent::RespondedPart{
+                client_id  : token.client_id(),
+                request_id : token.request_id(),
+                response   : response.clone(),
+            });
+        let update = ClientMetaEvent::ResponsePart(response, token.request_id());
+        send_request_update::<Channel>(&self.client_event_sender, token, update)
+    }
+
+    /// Reports progress for a client request.
+    ///
+    /// Progress is clamped to `[0.0, 1.0]`, and is exposed to the client as [`RequestStatus::InProgress`] on the
+    /// request's [`RequestSignal`]. Progress updates sent after the request is responded to, acknowledged, or rejected
+    /// are ignored by the client.
+    ///
+    /// Progress updates are dropped under the same conditions as [`Self::respond()`].
+    pub fn report_progress(&self, token: &RequestToken, progress: f32) -> Result<(), ServerError>
+    {
+        // check server liveness
+        if self.is_dead()
         {
-            tracing::error!(?err, "failed to forward response to session");
-            return;
+            let client_id  = token.client_id();
+            let request_id = token.request_id();
+            tracing::warn!(client_id, request_id, "tried to send progress to session but server is dead");
+            return Err(ServerError::Dead);
         }
+
+        let progress = progress.clamp(0.0, 1.0);
+        self.record(|| RecordedServerEvent::ReportedProgress{
+                client_id  : token.client_id(),
+                request_id : token.request_id(),
+                progress,
+            });
+        let update = ClientMetaEvent::Progress(token.request_id(), progress);
+        send_request_update::<Channel>(&self.client_event_sender, token, update)
     }
 
     /// Acknowledges a client request.
@@ -204,83 +478,232 @@ impl<Channel: ChannelPack> Server<Channel>
     /// across sessions).
     ///
     /// An acknowledged request cannot be responded to.
-    pub fn ack(&self, token: RequestToken)
+    ///
+    /// Returns [`ServerError::UnknownClient`] if the request's session is dead.
+    pub fn ack(&self, token: RequestToken) -> Result<(), ServerError>
     {
         // check server liveness
-        let client_id  = token.client_id();
-        let request_id = token.request_id();
         if self.is_dead()
         {
+            let client_id  = token.client_id();
+            let request_id = token.request_id();
             tracing::warn!(client_id, request_id, "tried to send ack to session but server is dead");
-            return;
+            return Err(ServerError::Dead);
         }
 
-        // check token liveness
-        if token.destination_is_dead()
-        {
-            tracing::debug!(client_id, request_id, "tried to send response to dead session");
-            return;
-        }
+        self.record(|| RecordedServerEvent::Acked{ client_id: token.client_id(), request_id: token.request_id() });
+        self.responder(token).ack()
+    }
 
-        // send to endpoint of ezsockets::Server::call() (will be picked up by ConnectionHandler::on_call())
-        let (request_id, death_signal) = token.take();
-        if let Err(err) = self.client_event_sender.send(ClientTargetMsg::new(
-                client_id,
-                SessionCommand::<Channel>::Send(ClientMetaEvent::Ack(request_id), None, Some(death_signal))
-            ))
+    /// Acknowledges a client request with a small payload (e.g. the id of a newly created resource).
+    ///
+    /// The client receives the payload in [`ClientEvent::AckWith`]. This is useful for requests that only need a tiny
+    /// result, without defining a variant of [`ChannelPack::ServerResponse`] for it. The payload's encoding is defined
+    /// by the application, and it is not encrypted by [`ServerConfig::cipher`] separately from the rest of the frame.
+    ///
+    /// Acks with payloads are dropped under the same conditions as [`Self::ack()`].
+    pub fn ack_with(&self, token: RequestToken, payload: impl Into<Vec<u8>>) -> Result<(), ServerError>
+    {
+        // check server liveness
+        if self.is_dead()
         {
-            tracing::error!(?err, "failed to forward ack to session");
-            return;
+            let client_id  = token.client_id();
+            let request_id = token.request_id();
+            tracing::warn!(client_id, request_id, "tried to send ack to session but server is dead");
+            return Err(ServerError::Dead);
         }
+
+        let payload = payload.into();
+        self.record(|| RecordedServerEvent::AckedWith{
+                client_id  : token.client_id(),
+                request_id : token.request_id(),
+                payload    : payload.clone(),
+            });
+        self.responder(token).ack_with(payload)
     }
 
     /// Rejects a client request.
-    pub fn reject(&self, _token: RequestToken)
+    pub fn reject(&self, token: RequestToken)
     {
+        self.record(|| RecordedServerEvent::Rejected{ client_id: token.client_id(), request_id: token.request_id() });
+
         // drop the token: rejection will happen automatically using the token's custom Drop
     }
 
+    /// Rejects a client request with a reason.
+    ///
+    /// The client receives the reason in [`ClientEvent::RejectWith`], which lets it distinguish rejections (e.g.
+    /// invalid input, not authorized, or try again later) without encoding them in [`ChannelPack::ServerResponse`].
+    ///
+    /// Rejections with reasons are dropped under the same conditions as [`Self::respond()`].
+    pub fn reject_with(&self, token: RequestToken, reason: Channel::RejectReason) -> Result<(), ServerError>
+    {
+        // check server liveness
+        if self.is_dead()
+        {
+            let client_id  = token.client_id();
+            let request_id = token.request_id();
+            tracing::warn!(client_id, request_id, "tried to send rejection to session but server is dead");
+            return Err(ServerError::Dead);
+        }
+
+        self.record(|| RecordedServerEvent::RejectedWith{
+                client_id  : token.client_id(),
+                request_id : token.request_id(),
+                reason     : reason.clone(),
+            });
+        self.responder(token).reject_with(reason)
+    }
+
+    /// Converts a request token into a [`Responder`].
+    ///
+    /// Responders can be moved into async tasks to answer requests without access to the server.
+    pub fn responder(&self, token: RequestToken) -> Responder<Channel>
+    {
+        Responder::new(token, self.client_event_sender.clone())
+    }
+
     /// Disconnects the target client.
     ///
     /// The client's session may remain open until some time after this method is called.
-    pub fn disconnect_client(&self, id: ClientId, close_frame: Option<ezsockets::CloseFrame>)
+    ///
+    /// Returns [`ServerError::UnknownClient`] if the client's latest consumed connection report is not
+    /// [`ServerReport::Connected`].
+    pub fn disconnect_client(
+        &self,
+        id          : ClientId,
+        close_frame : Option<ezsockets::CloseFrame>
+    ) -> Result<(), ServerError>
     {
         // send to endpoint of ezsockets::Server::call() (will be picked up by ConnectionHandler::on_call())
         tracing::info!(id, "closing client");
         if self.is_dead()
         {
             tracing::warn!(id, "tried to close session but server is dead");
-            return;
+            return Err(ServerError::Dead);
+        }
+        if !self.connected_clients.contains_key(&id)
+        {
+            tracing::debug!(id, "tried to close session of unknown client");
+            return Err(ServerError::UnknownClient);
         }
         if let Err(err) = self.client_event_sender.send(
                 ClientTargetMsg::new(id, SessionCommand::<Channel>::Close(close_frame))
             )
         {
             tracing::error!(?err, "failed to forward session close command to session");
-            return;
+            return Err(ServerError::Dead);
         }
+
+        Ok(())
     }
 
     /// Gets the next available server event.
     pub fn next(&mut self) -> Option<(ClientId, ServerEventFrom<Channel>)>
+    {
+        self.next_with_epoch().map(|(id, _, msg)| (id, msg))
+    }
+
+    /// Gets the next available server event, tagged with the epoch of the client session it came from.
+    ///
+    /// Each client session gets a new epoch when its [`ServerReport::Connected`] or [`ServerReport::Reconnected`]
+    /// report is consumed, and the epoch ends when its disconnect report is consumed (the disconnect report is tagged
+    /// with the ending epoch). Events from clients without a session are tagged with epoch `0`.
+    ///
+    /// This is useful if events are queued in the app before they are handled. Use [`Self::is_current()`] to cheaply
+    /// skip events that became stale because their client disconnected or reconnected.
+    pub fn next_with_epoch(&mut self) -> Option<(ClientId, u64, ServerEventFrom<Channel>)>
     {
         let Ok(ClientSourceMsg{ id, msg }) = self.server_event_receiver.try_recv() else { return None; };
 
         // count the number of connection events received
-        if let ServerEventFrom::<Channel>::Report(ServerReport::Connected(_, _)) = &msg
+        let epoch = match &msg
         {
-            // we assume this never rolls over
-            // - it should last 30million years even with 1mill new connections per minute
-            self.consumed_connection_events += 1u64;
-        }
+            ServerEventFrom::<Channel>::Report(ServerReport::Connected(_, _)) |
+            ServerEventFrom::<Channel>::Report(ServerReport::Reconnected(_, _)) =>
+            {
+                // we assume this never rolls over
+                // - it should last 30million years even with 1mill new connections per minute
+                // - connection event counts are unique, so we use them as session epochs
+                self.consumed_connection_events += 1u64;
+                self.connected_clients.insert(id, self.consumed_connection_events);
+                self.consumed_connection_events
+            }
+            ServerEventFrom::<Channel>::Report(ServerReport::Disconnected) |
+            ServerEventFrom::<Channel>::Report(ServerReport::DisconnectedWithReason(_)) =>
+            {
+                self.groups.remove_client(id);
+                self.connected_clients.remove(&id).unwrap_or_default()
+            }
+            _ => self.connected_clients.get(&id).copied().unwrap_or_default(),
+        };
+
+        self.record(|| RecordedServerEvent::incoming(id, &msg));
+
+        Some((id, epoch, msg))
+    }
+
+    /// Tests if a session epoch is the epoch of a client's current session.
+    ///
+    /// Returns `false` if a disconnect or reconnect report for the client was consumed after the epoch was obtained
+    /// from [`Self::next_with_epoch()`].
+    pub fn is_current(&self, client_id: ClientId, epoch: u64) -> bool
+    {
+        epoch != 0 && self.connected_clients.get(&client_id) == Some(&epoch)
+    }
 
-        Some((id, msg))
+    /// Attaches a recorder for the server's event stream.
+    ///
+    /// Events consumed with [`Self::next()`] and messages/replies sent with the server API will be recorded in order.
+    /// Replaces and returns the previous recorder, if there was one.
+    pub fn set_recorder(&mut self, recorder: ServerRecorder<Channel>) -> Option<ServerRecorder<Channel>>
+    {
+        self.recorder.replace(recorder)
+    }
+
+    /// Detaches the server's recorder, if there is one.
+    pub fn take_recorder(&mut self) -> Option<ServerRecorder<Channel>>
+    {
+        self.recorder.take()
     }
 
     /// Gets the server's url.
+    ///
+    /// Returns [`ServerConfig::public_url`] if it is set, otherwise returns [`Self::local_url()`]. Servers without a
+    /// listener always have a public url (see [`ServerFactory::new_handler()`]).
     pub fn url(&self) -> url::Url
     {
-        make_websocket_url(self.uses_tls, self.server_address).unwrap()
+        match &self.public_url
+        {
+            Some(public_url) => public_url.clone(),
+            None             => self.local_url().expect("servers without a public url have a listener"),
+        }
+    }
+
+    /// Gets the url of the server's bound local address.
+    ///
+    /// This is useful for connecting to the server from the same machine (e.g. in tests).
+    ///
+    /// Returns `None` if the server doesn't have a listener (see [`ServerFactory::new_handler()`]).
+    pub fn local_url(&self) -> Option<url::Url>
+    {
+        let server_address = self.server_address?;
+        Some(make_websocket_url(self.uses_tls, server_address).unwrap())
+    }
+
+    /// Gets the server's composed router.
+    ///
+    /// The router contains the user-constructed router passed to the server along with the server's websocket route
+    /// (`/ws`), health routes, and the extensions they need. It can be mounted in a larger application (e.g. with
+    /// `.nest("/realtime", server.router())`) that is served by a listener you drive yourself. Clients connecting to
+    /// the application should use the nested websocket route (e.g. `ws://host/realtime/ws`). The application must be
+    /// served with `into_make_service_with_connect_info::<SocketAddr>()` so connection requests can be prevalidated.
+    ///
+    /// The server's own listener keeps serving the router. Use [`ServerFactory::new_handler()`] to make a server
+    /// without a listener.
+    pub fn router(&self) -> axum::Router
+    {
+        self.router.clone()
     }
 
     /// Gets the number of client connections.
@@ -289,10 +712,238 @@ impl<Channel: ChannelPack> Server<Channel>
         self.connection_counter.load()
     }
 
+    /// Watches the number of client connections.
+    ///
+    /// This lets async services running alongside the server (e.g. autoscalers or health reporters) await changes to
+    /// [`Self::num_connections()`] instead of polling it.
+    pub fn connection_watch(&self) -> tokio::sync::watch::Receiver<u64>
+    {
+        self.connection_counter.subscribe()
+    }
+
+    /// Watches the number of pending sessions (sessions that have connected but not yet authenticated).
+    pub fn pending_watch(&self) -> tokio::sync::watch::Receiver<u64>
+    {
+        self.pending_counter.subscribe()
+    }
+
+    /// Gets statistics about frames received from clients.
+    pub fn frame_stats(&self) -> FrameStats
+    {
+        self.frame_counters.stats()
+    }
+
+    /// Gets statistics about connection handshakes (see [`HandshakeStats`]).
+    pub fn handshake_stats(&self) -> HandshakeStats
+    {
+        self.handshake_counters.stats()
+    }
+
+    /// Gets the number of client messages dropped as duplicates (see [`ServerConfig::msg_dedup`]).
+    pub fn deduplicated_msgs(&self) -> u64
+    {
+        self.msg_dedup_counter.load()
+    }
+
+    /// Gets the counters used to synchronize message sends with connection reports.
+    ///
+    /// This is intended for debugging. See [`ServerSyncState`].
+    pub fn sync_state(&self) -> ServerSyncState
+    {
+        self.sync_counters.snapshot(self.consumed_connection_events, self.connected_clients.len())
+    }
+
+    /// Marks the server as draining (or not draining).
+    ///
+    /// While the server is draining, the `/readyz` route responds with `503 Service Unavailable` so load balancers
+    /// stop routing new clients to the server (see [`ServerConfig::health_routes`]). Existing and new connections are
+    /// not affected.
+    pub fn set_draining(&self, draining: bool)
+    {
+        tracing::info!(draining, "setting server drain state");
+        self.health.set_draining(draining);
+    }
+
+    /// Tests if the server is draining.
+    pub fn is_draining(&self) -> bool
+    {
+        self.health.is_draining()
+    }
+
+    /// Updates [`ServerConfig`] values that can be changed while the server is running.
+    ///
+    /// New sessions use the updated values immediately. The new rate limit is also applied to existing sessions if
+    /// [`PartialServerConfig::apply_to_existing`] is set. This is useful for tightening limits during an attack
+    /// without restarting the server.
+    pub fn update_config(&self, update: PartialServerConfig) -> Result<(), ServerError>
+    {
+        if self.is_dead()
+        {
+            tracing::warn!("tried to update config but server is dead");
+            return Err(ServerError::Dead);
+        }
+
+        // update the limits checked before connections are upgraded
+        self.runtime_limits.update(&update);
+
+        // send to endpoint of ezsockets::Server::call() (will be picked up by ConnectionHandler::on_call())
+        // - the command's target client id is ignored
+        let command = SessionCommand::<Channel>::UpdateConfig(update);
+        if let Err(err) = self.client_event_sender.send(ClientTargetMsg::new(ClientId::default(), command))
+        {
+            tracing::error!(?err, "failed to forward config update to connection handler");
+            return Err(ServerError::Dead);
+        }
+
+        Ok(())
+    }
+
+    /// Waits until the server is accepting connections.
+    ///
+    /// Returns [`ServerError::Dead`] if the server dies before it starts accepting connections, or
+    /// [`ServerError::Timeout`] if the timeout expires first. This blocks the current thread, so it can't be called
+    /// from inside an async runtime. See [`Self::ready()`] for an async alternative.
+    pub fn wait_until_ready(&self, timeout: Duration) -> Result<(), ServerError>
+    {
+        match self.health.startup()
+        {
+            ServerStartup::Serving  => return Ok(()),
+            ServerStartup::Failed   => return Err(ServerError::Dead),
+            ServerStartup::Starting => (),
+        }
+
+        // block on the startup signal with a throwaway runtime that only drives the timeout
+        let Ok(runtime) = tokio::runtime::Builder::new_current_thread().enable_time().build()
+        else
+        {
+            tracing::error!("failed building runtime for waiting on server startup");
+            return Err(ServerError::Dead);
+        };
+
+        runtime.block_on(tokio::time::timeout(timeout, self.ready())).unwrap_or(Err(ServerError::Timeout))
+    }
+
+    /// Gets a future that resolves once the server is accepting connections.
+    ///
+    /// The future resolves to [`ServerError::Dead`] if the server dies before it starts accepting connections. It
+    /// does not depend on the server's runtime, so it can be awaited on any executor.
+    pub fn ready(&self) -> impl Future<Output = Result<(), ServerError>> + Send + 'static
+    {
+        let mut startup = self.health.subscribe_startup();
+        async move {
+            let serving = startup.wait_for(|startup| *startup != ServerStartup::Starting)
+                .await
+                .map(|startup| *startup == ServerStartup::Serving)
+                .unwrap_or(false);
+
+            match serving
+            {
+                true  => Ok(()),
+                false => Err(ServerError::Dead),
+            }
+        }
+    }
+
+    /// Pauses accepting new connections.
+    ///
+    /// While paused, connection requests are rejected with `503 Service Unavailable`. Existing connections are not
+    /// affected, and the server keeps listening on its address. This is useful for maintenance windows, or for
+    /// testing how clients behave when the server is down.
+    pub fn pause_accepting(&self)
+    {
+        tracing::info!("server paused accepting connections");
+        self.runtime_limits.set_paused(true);
+    }
+
+    /// Resumes accepting new connections after [`Self::pause_accepting()`].
+    pub fn resume_accepting(&self)
+    {
+        tracing::info!("server resumed accepting connections");
+        self.runtime_limits.set_paused(false);
+    }
+
+    /// Tests if accepting new connections is paused (see [`Self::pause_accepting()`]).
+    pub fn is_accepting_paused(&self) -> bool
+    {
+        self.runtime_limits.is_paused()
+    }
+
+    /// Stops accepting new connections, and marks the server as draining (see [`Self::set_draining()`]).
+    ///
+    /// Existing connections are not affected, so the server can keep serving its clients until they are disconnected
+    /// with [`Self::disconnect_client()`]. This is irreversible, and the server's HTTP routes (e.g. health routes)
+    /// will stop responding.
+    ///
+    /// For zero-downtime restarts on a single host, bind the listeners of both the old and new server processes with
+    /// `SO_REUSEPORT` (or pass the old listener's socket to the new process) and give them to
+    /// [`ServerBuilder::listener()`]. Once the new server is ready (see [`Self::wait_until_ready()`]), call this on
+    /// the old server, then disconnect its clients so they reconnect to the new server.
+    pub fn stop_accepting(&mut self)
+    {
+        if self.stopped_accepting { return; }
+        tracing::info!("server stopped accepting connections");
+        self.stopped_accepting = true;
+        self.set_draining(true);
+        if let Some(listener_handle) = &self.listener_handle { listener_handle.graceful_shutdown(None); }
+    }
+
+    /// Tests if the server stopped accepting new connections (see [`Self::stop_accepting()`]).
+    pub fn is_accepting_stopped(&self) -> bool
+    {
+        self.stopped_accepting
+    }
+
     /// Tests if the server is dead.
+    ///
+    /// Servers that stopped accepting connections with [`Self::stop_accepting()`] are not dead until their connection
+    /// handler stops.
     pub fn is_dead(&self) -> bool
     {
-        self.server_closed_signal.done() || self.server_running_signal.done()
+        let listener_stopped = self.server_running_signal.as_ref().map(|signal| signal.done()).unwrap_or(false);
+        self.server_closed_signal.done() || (listener_stopped && !self.stopped_accepting)
+    }
+
+    /// Records an entry if the server has a recorder.
+    fn record(&self, entry: impl FnOnce() -> RecordedServerEvent<Channel>)
+    {
+        let Some(recorder) = &self.recorder else { return; };
+        recorder.record(&entry());
+    }
+
+    /// Checks if a message can be sent to a client.
+    fn check_target(&self, id: ClientId) -> Result<(), ServerError>
+    {
+        if self.mailbox_enabled || self.connected_clients.contains_key(&id) { return Ok(()); }
+        if self.is_dead() { return Err(ServerError::Dead); }
+
+        tracing::debug!(id, "tried to send message to unknown client");
+        Err(ServerError::UnknownClient)
+    }
+
+    /// Forwards a send command to the connection handler.
+    fn send_command(
+        &self,
+        id      : ClientId,
+        command : impl FnOnce(SendSignalResolver) -> SessionCommand<Channel>
+    ) -> Result<SendSignal, ServerError>
+    {
+        if self.is_dead()
+        {
+            tracing::warn!(id, "tried to send message to client but server is dead");
+            return Err(ServerError::Dead);
+        }
+
+        // send to endpoint of ezsockets::Server::call() (will be picked up by ConnectionHandler::on_call())
+        let signal = SendSignal::new();
+        if let Err(err) = self.client_event_sender.send(
+                ClientTargetMsg::new(id, (command)(SendSignalResolver::new(signal.clone())))
+            )
+        {
+            tracing::error!(?err, "failed to forward message to session");
+            return Err(ServerError::Dead);
+        }
+
+        Ok(signal)
     }
 }
 
@@ -304,6 +955,11 @@ impl<Channel: ChannelPack> Server<Channel>
 pub struct ServerFactory<Channel: ChannelPack>
 {
     protocol_version : &'static str,
+    connect_hook     : Option<Arc<dyn ConnectHook<Channel>>>,
+    middleware       : Vec<Arc<dyn ServerMiddleware<Channel>>>,
+    role_fn          : Option<Arc<dyn ClientRoleFn<Channel>>>,
+    scopes_fn        : Option<Arc<dyn ClientScopesFn<Channel>>>,
+    session_init_fn  : Option<Arc<dyn SessionInitFn<Channel>>>,
     _phantom         : PhantomData<Channel>,
 }
 
@@ -312,12 +968,78 @@ impl<Channel: ChannelPack> ServerFactory<Channel>
     /// Makes a new server factory with a given protocol version.
     pub fn new(protocol_version: &'static str) -> Self
     {
-        ServerFactory{ protocol_version, _phantom: PhantomData }
+        ServerFactory{
+            protocol_version,
+            connect_hook    : None,
+            middleware      : Vec::default(),
+            role_fn         : None,
+            scopes_fn       : None,
+            session_init_fn : None,
+            _phantom        : PhantomData,
+        }
+    }
+
+    /// Adds a hook that produces messages to send to clients when they connect.
+    ///
+    /// This is useful for syncing server state to clients with correct ordering relative to other server messages.
+    /// See [`ConnectHook`].
+    pub fn with_connect_hook(mut self, hook: impl ConnectHook<Channel>) -> Self
+    {
+        self.connect_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Adds middleware for intercepting client messages and requests.
+    ///
+    /// Middleware is invoked in the order it is added. See [`ServerMiddleware`].
+    pub fn with_middleware(mut self, middleware: impl ServerMiddleware<Channel>) -> Self
+    {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Adds a function that assigns a [`ClientRole`] to each client connection when it authenticates.
+    ///
+    /// Clients are [`ClientRole::Full`] by default. A client's role is exposed in the [`ConnectionMeta`] of its
+    /// connection report. See [`ClientRoleFn`].
+    pub fn with_role_fn(mut self, role_fn: impl ClientRoleFn<Channel>) -> Self
+    {
+        self.role_fn = Some(Arc::new(role_fn));
+        self
+    }
+
+    /// Adds a function that assigns permission [`Scopes`] to each client connection when it authenticates.
+    ///
+    /// Clients have [`Scopes::ALL`] by default. See [`ClientScopesFn`].
+    pub fn with_scopes_fn(mut self, scopes_fn: impl ClientScopesFn<Channel>) -> Self
+    {
+        self.scopes_fn = Some(Arc::new(scopes_fn));
+        self
+    }
+
+    /// Adds a function that initializes the [`SessionStore`] of each client session when the client authenticates.
+    ///
+    /// Session stores can be accessed by middleware (see [`ServerMiddleware::on_request_with_store()`]). See
+    /// [`SessionInitFn`].
+    pub fn with_session_init_fn(mut self, init_fn: impl SessionInitFn<Channel>) -> Self
+    {
+        self.session_init_fn = Some(Arc::new(init_fn));
+        self
+    }
+
+    /// Makes a [`ServerBuilder`] for configuring a new server.
+    ///
+    /// This is an alternative to [`Self::new_server()`] that won't break when new server options are added.
+    pub fn builder(&self) -> ServerBuilder<Channel>
+    {
+        ServerBuilder::new(self.clone())
     }
 
     /// Makes a new server with a default [`axum::Router`].
     ///
-    /// Only works with a tokio runtime handle.
+    /// Only works with a tokio runtime handle. Applications that use a different async runtime can pass
+    /// `TokioHandle::default()`, which hosts the server on a dedicated tokio runtime. The [`Server`] API is
+    /// synchronous, so it can be used from any runtime.
     pub fn new_server<A>(&self,
         runtime_handle  : enfync::builtin::native::TokioHandle,
         address         : A,
@@ -352,6 +1074,122 @@ impl<Channel: ChannelPack> ServerFactory<Channel>
     where
         A: std::net::ToSocketAddrs + Send + 'static,
     {
+        let connection_listener = std::net::TcpListener::bind(address).unwrap();
+
+        self.new_server_with_listener(
+                runtime_handle,
+                connection_listener,
+                acceptor_config,
+                authenticator,
+                config,
+                router,
+            )
+    }
+
+    /// Makes a new server that doesn't bind a listener, with a default [`axum::Router`].
+    ///
+    /// Returns the server along with its composed router (see [`Server::router()`]), which you must serve with your
+    /// own listener (e.g. an existing hyper/axum setup with custom TLS or HTTP/2). Clients connect to the router's
+    /// websocket route (`/ws`, or e.g. `/realtime/ws` if the router is nested at `/realtime`). The router must be
+    /// served with `into_make_service_with_connect_info::<SocketAddr>()` so connection requests can be prevalidated.
+    ///
+    /// The server doesn't know its address, so [`ServerConfig::public_url`] must be set (it is returned by
+    /// [`Server::url()`]), and [`Server::local_url()`] returns `None`. [`Server::stop_accepting()`] only marks the
+    /// server as draining.
+    ///
+    /// Panics if [`ServerConfig::public_url`] is not set.
+    ///
+    /// Only works with a tokio runtime handle.
+    pub fn new_handler(&self,
+        runtime_handle : enfync::builtin::native::TokioHandle,
+        authenticator  : Authenticator,
+        config         : ServerConfig,
+    ) -> (Server<Channel>, axum::Router)
+    {
+        self.new_handler_with_router(runtime_handle, authenticator, config, axum::Router::new())
+    }
+
+    /// Makes a new server that doesn't bind a listener, with a user-constructed [`axum::Router`].
+    ///
+    /// See [`Self::new_handler()`].
+    pub fn new_handler_with_router(&self,
+        runtime_handle : enfync::builtin::native::TokioHandle,
+        authenticator  : Authenticator,
+        config         : ServerConfig,
+        router         : axum::Router,
+    ) -> (Server<Channel>, axum::Router)
+    {
+        assert!(config.public_url.is_some(), "servers without a listener need a public url");
+        let (server, router) = self.make_handler(runtime_handle, authenticator, config, router);
+
+        // the user's listener is responsible for serving the router
+        server.health.set_listening(true);
+
+        (server, router)
+    }
+
+    /// Makes a new server with a user-constructed [`axum::Router`] that accepts connections from a bound listener.
+    ///
+    /// This is useful for sockets passed in by a service manager (e.g. systemd socket activation, where the listener
+    /// can be made with `TcpListener::from_raw_fd()`), or for test harnesses that need to choose and hold a port
+    /// before the server is made. See [`ServerBuilder::listener()`] for the builder equivalent.
+    ///
+    /// Only works with a tokio runtime handle.
+    pub fn new_server_with_listener(&self,
+        runtime_handle      : enfync::builtin::native::TokioHandle,
+        connection_listener : std::net::TcpListener,
+        acceptor_config     : AcceptorConfig,
+        authenticator       : Authenticator,
+        config              : ServerConfig,
+        router              : axum::Router,
+    ) -> Server<Channel>
+    {
+        let (mut server, router) = self.make_handler(runtime_handle.clone(), authenticator, config, router);
+
+        // prepare listener
+        let server_address = connection_listener.local_addr().unwrap();
+        let uses_tls = !matches!(acceptor_config, AcceptorConfig::Default);
+
+        // launch the server core
+        // - the server is marked as listening once the router is serving connections
+        let listener_handle = axum_server::Handle::new();
+        let listener_handle_clone = listener_handle.clone();
+        let health_clone = server.health.clone();
+        runtime_handle.spawn(
+                async move {
+                    if listener_handle_clone.listening().await.is_some() { health_clone.set_listening(true); }
+                }
+            );
+        let listener_handle_clone = listener_handle.clone();
+        let health_clone = server.health.clone();
+        let server_running_signal = runtime_handle.spawn(
+                async move {
+                    run_server(router, connection_listener, acceptor_config, listener_handle_clone).await;
+                    health_clone.set_listening(false);
+                }
+            );
+
+        server.server_address = Some(server_address);
+        server.uses_tls = uses_tls;
+        server.listener_handle = Some(listener_handle);
+        server.server_running_signal = Some(server_running_signal);
+        server
+    }
+
+    /// Makes a new server and its composed router without a listener.
+    fn make_handler(&self,
+        runtime_handle : enfync::builtin::native::TokioHandle,
+        authenticator  : Authenticator,
+        config         : ServerConfig,
+        router         : axum::Router,
+    ) -> (Server<Channel>, axum::Router)
+    {
+        assert!(config.wire_format.is_supported(), "wire format {:?} isn't supported by the enabled features",
+            config.wire_format);
+        assert!(!(config.text_messages && config.cipher.is_some()), "text messages can't be used with a cipher");
+        #[cfg(feature = "netsim")]
+        assert!(!(config.netsim.is_some() && config.cipher.is_some()), "netsim can't be used with a cipher");
+
         // prepare message channel that points out of the connection handler
         let (
                 server_event_sender,
@@ -364,78 +1202,169 @@ impl<Channel: ChannelPack> ServerFactory<Channel>
         let pending_counter    = PendingCounter::default();
         let connection_counter = ConnectionCounter::default();
 
+        // prepare auth failure tracker
+        // - this is shared between session handlers (which record failures) and the connection prevalidator (which
+        //   rejects locked-out addresses)
+        let auth_failure_tracker = AuthFailureTracker::new(config.auth_failure_config, config.time_source.clone());
+
+        // prepare runtime limits
+        // - these are shared with the connection prevalidator so they can be changed with Server::update_config()
+        let runtime_limits = RuntimeLimits::new(&config);
+
+        // prepare prevalidator
+        let prevalidator = ConnectionPrevalidator{
+                protocol_version   : self.protocol_version,
+                wire_format        : config.wire_format,
+                fingerprint        : Channel::fingerprint(),
+                max_pending        : config.max_pending,
+                pending_policy     : config.pending_overflow_policy,
+                limits             : runtime_limits.clone(),
+                heartbeat_interval : config.heartbeat_interval,
+                heartbeat_ping_fn  : config.heartbeat_ping_fn.clone(),
+                text_heartbeats    : config.heartbeat_ping_fn.is_some()
+                    || config.heartbeat_callback.is_some()
+                    || config.quality_callback.is_some()
+                    || config.keepalive_miss_callback.is_some(),
+                wasm_ping_frames   : config.wasm_ping_frames,
+            };
+
         // make server core with our connection handler
         // note: ezsockets::Server::create() must be called from within a tokio runtime
         let pending_counter_clone    = pending_counter.clone();
         let connection_counter_clone = connection_counter.clone();
+        let auth_failure_tracker_clone = auth_failure_tracker.clone();
+        let codec = WireCodec::new(
+                config.wire_format,
+                config.decode_limits,
+                config.cipher.clone(),
+                config.checksum,
+                FrameDirection::ServerToClient,
+            );
+        let frame_counters = codec.counters().clone();
+        let sync_counters = SyncCounters::default();
+        let sync_counters_clone = sync_counters.clone();
+        let handshake_counters = HandshakeStatsCounters::default();
+        let handshake_counters_clone = handshake_counters.clone();
+        let msg_dedup_counter = MsgDedupCounter::default();
+        let msg_dedup_counter_clone = msg_dedup_counter.clone();
+        let mailbox_enabled = config.mailbox.is_some();
+        let text_messages = config.text_messages;
+        let mailboxes = config.mailbox.map(|mailbox| Mailboxes::new(mailbox, config.time_source.clone()));
+        let token_cache = VerifiedTokenCache::new(config.token_verification.cache_capacity);
+        let flapping_tracker = config.flapping
+            .map(|flapping| FlappingTracker::new(flapping, config.time_source.clone()));
+        let connect_hook = self.connect_hook.clone();
+        let middleware: MiddlewareChain<Channel> = self.middleware.clone().into();
+        let role_fn = self.role_fn.clone();
+        let scopes_fn = self.scopes_fn.clone();
+        let session_init_fn = self.session_init_fn.clone();
+        let health_routes = config.health_routes;
+        let public_url = config.public_url.clone();
+        let health = ServerHealth::default();
+        health.set_handler_alive(true);
 
         let (server, server_worker) = enfync::blocking::extract(runtime_handle.spawn(async move {
                 ezsockets::Server::create(
                         move |server|
-                        ConnectionHandler::<Channel>{
-                                authenticator           : Arc::new(authenticator),
-                                config,
-                                pending_counter         : pending_counter_clone,
-                                connection_counter      : connection_counter_clone,
-                                session_counter         : 0u64,
-                                total_connections_count : 0u64,
-                                session_registry        : HashMap::default(),
-                                client_to_session       : HashMap::default(),
-                                session_to_client       : HashMap::default(),
-                                client_event_sender     : server.into(),
-                                server_event_sender,
-                            }
+                        {
+                            // mail is dropped when it expires even if its client never reconnects
+                            let client_event_sender: tokio::sync::mpsc::UnboundedSender<_> = server.into();
+                            if mailboxes.is_some() { spawn_mailbox_expiry::<Channel>(client_event_sender.clone()); }
+
+                            ConnectionHandler::<Channel>{
+                                    authenticator           : Arc::new(authenticator),
+                                    auth_failure_tracker    : auth_failure_tracker_clone,
+                                    token_cache,
+                                    codec,
+                                    config,
+                                    pending_counter         : pending_counter_clone,
+                                    connection_counter      : connection_counter_clone,
+                                    session_counter         : 0u64,
+                                    total_connections_count : 0u64,
+                                    sync_counters           : sync_counters_clone,
+                                    handshake_counters      : handshake_counters_clone,
+                                    msg_dedup_counter       : msg_dedup_counter_clone,
+                                    session_registry        : HashMap::default(),
+                                    session_codecs          : HashMap::default(),
+                                    internal_closes         : HashMap::default(),
+                                    pending_sessions        : BTreeSet::default(),
+                                    reliable_trackers       : HashMap::default(),
+                                    latest_slots            : HashMap::default(),
+                                    mailboxes,
+                                    connect_hook,
+                                    middleware,
+                                    role_fn,
+                                    scopes_fn,
+                                    session_init_fn,
+                                    lingering_clients       : HashMap::default(),
+                                    tenant_connections      : ConnectionGroups::default(),
+                                    account_connections     : ConnectionGroups::default(),
+                                    topic_subscriptions     : TopicSubscriptions::default(),
+                                    flapping_tracker,
+                                    client_to_session       : HashMap::default(),
+                                    session_to_client       : HashMap::default(),
+                                    client_event_sender,
+                                    server_event_sender,
+                                }
+                        }
                     )
             })).unwrap();
 
+        let health_clone = health.clone();
         let server_closed_signal = runtime_handle.spawn(
                 async move {
                     if let Err(err) = server_worker.await
                     {
                         tracing::error!(?err, "server closed with error");
                     }
+                    health_clone.set_handler_alive(false);
                 }
             );
 
-        // prepare prevalidator
-        let prevalidator = ConnectionPrevalidator{
-                protocol_version   : self.protocol_version,
-                max_pending        : config.max_pending,
-                max_connections    : config.max_connections,
-                heartbeat_interval : config.heartbeat_interval,
-                keepalive_timeout  : config.keepalive_timeout,
-            };
-
         // prepare router
         let router = router
             .route("/ws", axum::routing::get(websocket_handler::<Channel>))
             .layer(axum::Extension(server.clone()))
             .layer(axum::Extension(Arc::new(prevalidator)))
             .layer(axum::Extension(pending_counter.clone()))
+            .layer(axum::Extension(auth_failure_tracker))
             .layer(axum::Extension(connection_counter.clone()));
-
-        // prepare listener
-        let connection_listener = std::net::TcpListener::bind(address).unwrap();
-        let server_address = connection_listener.local_addr().unwrap();
-        let uses_tls = !matches!(acceptor_config, AcceptorConfig::Default);
-
-        // launch the server core
-        let server_running_signal = runtime_handle.spawn(
-                async move { run_server(router, connection_listener, acceptor_config).await }
-            );
+        let router = match health_routes
+        {
+            true  => add_health_routes(router, health.clone()),
+            false => router,
+        };
 
         // finish assembling our server
         tracing::info!("new server created");
-        Server{
-                server_address,
-                uses_tls,
+        let server = Server{
+                server_address: None,
+                public_url,
+                uses_tls: false,
                 connection_counter,
+                pending_counter,
                 consumed_connection_events: 0u64,
+                frame_counters,
+                sync_counters,
+                handshake_counters,
+                msg_dedup_counter,
+                connected_clients: HashMap::default(),
+                groups: ClientGroups::default(),
+                mailbox_enabled,
+                text_messages,
+                recorder: None,
+                health,
+                router: router.clone(),
                 client_event_sender: server.into(),  //extract the call sender
                 server_event_receiver,
                 server_closed_signal,
-                server_running_signal,
-            }
+                server_running_signal: None,
+                runtime_limits,
+                listener_handle: None,
+                stopped_accepting: false,
+            };
+
+        (server, router)
     }
 }
 
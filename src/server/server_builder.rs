@@ -0,0 +1,152 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::net::{SocketAddr, ToSocketAddrs};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Builder for a [`Server`]. Make one with [`ServerFactory::builder()`].
+///
+/// Options that aren't set use the same defaults as [`ServerFactory::new_server()`]:
+/// - runtime: `TokioHandle::default()`
+/// - acceptor: [`AcceptorConfig::Default`]
+/// - authenticator: [`Authenticator::None`]
+/// - config: [`ServerConfig::default()`]
+/// - router: an empty [`axum::Router`]
+///
+/// Only the address (or a listener) is required.
+pub struct ServerBuilder<Channel: ChannelPack>
+{
+    factory         : ServerFactory<Channel>,
+    runtime_handle  : Option<enfync::builtin::native::TokioHandle>,
+    address         : Option<std::io::Result<Vec<SocketAddr>>>,
+    listener        : Option<std::net::TcpListener>,
+    acceptor_config : AcceptorConfig,
+    authenticator   : Authenticator,
+    config          : ServerConfig,
+    router          : axum::Router,
+}
+
+impl<Channel: ChannelPack> ServerBuilder<Channel>
+{
+    pub(crate) fn new(factory: ServerFactory<Channel>) -> Self
+    {
+        Self{
+            factory,
+            runtime_handle  : None,
+            address         : None,
+            listener        : None,
+            acceptor_config : AcceptorConfig::default(),
+            authenticator   : Authenticator::None,
+            config          : ServerConfig::default(),
+            router          : axum::Router::new(),
+        }
+    }
+
+    /// Sets the tokio runtime the server runs on.
+    pub fn runtime(mut self, runtime_handle: enfync::builtin::native::TokioHandle) -> Self
+    {
+        self.runtime_handle = Some(runtime_handle);
+        self
+    }
+
+    /// Sets the address the server listens on (e.g. `"127.0.0.1:0"`).
+    ///
+    /// The address is resolved immediately, but resolution errors are reported by [`Self::build()`].
+    pub fn address(mut self, address: impl ToSocketAddrs) -> Self
+    {
+        self.address = Some(address.to_socket_addrs().map(|addresses| addresses.collect()));
+        self
+    }
+
+    /// Sets a bound listener for the server to accept connections from. This replaces [`Self::address()`].
+    ///
+    /// This is useful for listeners with custom socket options (e.g. `SO_REUSEPORT`), or sockets inherited from
+    /// another process (see [`Server::stop_accepting()`]).
+    pub fn listener(mut self, listener: std::net::TcpListener) -> Self
+    {
+        self.listener = Some(listener);
+        self
+    }
+
+    /// Sets the acceptor config (e.g. for TLS).
+    pub fn acceptor(mut self, acceptor_config: AcceptorConfig) -> Self
+    {
+        self.acceptor_config = acceptor_config;
+        self
+    }
+
+    /// Sets the authenticator for evaluating client auth requests.
+    pub fn authenticator(mut self, authenticator: Authenticator) -> Self
+    {
+        self.authenticator = authenticator;
+        self
+    }
+
+    /// Sets the server config.
+    ///
+    /// This replaces options set by other config setters (e.g. [`Self::rate_limit()`]), so it should be called first.
+    pub fn config(mut self, config: ServerConfig) -> Self
+    {
+        self.config = config;
+        self
+    }
+
+    /// Sets [`ServerConfig::rate_limit_config`].
+    pub fn rate_limit(mut self, rate_limit_config: RateLimitConfig) -> Self
+    {
+        self.config.rate_limit_config = rate_limit_config;
+        self
+    }
+
+    /// Sets a user-constructed router. The server's websocket route is added to it.
+    pub fn router(mut self, router: axum::Router) -> Self
+    {
+        self.router = router;
+        self
+    }
+
+    /// Builds the server.
+    pub fn build(self) -> Result<Server<Channel>, ServerBuildError>
+    {
+        if !self.config.wire_format.is_supported()
+        { return Err(ServerBuildError::UnsupportedWireFormat(self.config.wire_format)); }
+        if self.config.text_messages && self.config.cipher.is_some()
+        { return Err(ServerBuildError::TextWithCipher); }
+        #[cfg(feature = "netsim")]
+        if self.config.netsim.is_some() && self.config.cipher.is_some()
+        { return Err(ServerBuildError::NetsimWithCipher); }
+
+        let connection_listener = match self.listener
+        {
+            Some(listener) => listener,
+            None =>
+            {
+                let addresses = self.address
+                    .ok_or(ServerBuildError::MissingAddress)?
+                    .map_err(ServerBuildError::InvalidAddress)?;
+                std::net::TcpListener::bind(&addresses[..]).map_err(ServerBuildError::BindFailed)?
+            }
+        };
+
+        Ok(self.factory.new_server_with_listener(
+                self.runtime_handle.unwrap_or_default(),
+                connection_listener,
+                self.acceptor_config,
+                self.authenticator,
+                self.config,
+                self.router,
+            ))
+    }
+}
+
+impl<Channel: ChannelPack> Debug for ServerBuilder<Channel>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("ServerBuilder") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
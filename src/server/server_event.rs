@@ -8,6 +8,24 @@ use core::fmt::Debug;
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Information about a client's connection.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ConnectionMeta
+{
+    /// The client's environment type.
+    pub env_type: EnvType,
+    /// The client's tenant key.
+    ///
+    /// Always `None` if the server has no [`ServerConfig::tenants`] registry.
+    pub tenant: Option<String>,
+    /// The client's role.
+    ///
+    /// Always [`ClientRole::Full`] if the server has no role function (see [`ServerFactory::with_role_fn()`]).
+    pub role: ClientRole,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Emitted by servers when a client connects/disconnects.
 #[derive(Debug, Clone)]
 pub enum ServerReport<ConnectMsg: Debug + Clone>
@@ -19,9 +37,38 @@ pub enum ServerReport<ConnectMsg: Debug + Clone>
     /// message immediately after its [`ClientReport::Connected`] event.
     /// 
     /// See the [`ServerEvent::Request`] docs for one qualification on state syncing.
-    Connected(EnvType, ConnectMsg),
+    Connected(ConnectionMeta, ConnectMsg),
     /// The client disconnected.
+    ///
+    /// If [`ServerConfig::disconnect_grace`] is set, this is emitted once the grace window elapses without the client
+    /// reconnecting.
     Disconnected,
+    /// The client disconnected and then reconnected within [`ServerConfig::disconnect_grace`].
+    ///
+    /// This replaces the [`Self::Disconnected`] and [`Self::Connected`] reports for the reconnect, and synchronizes
+    /// with the client in the same way as [`Self::Connected`]. Note that messages sent to the client while it was
+    /// disconnected will be dropped (unless [`ServerConfig::mailbox`] is enabled), and any pending requests from
+    /// the client's previous session have been lost.
+    Reconnected(ConnectionMeta, ConnectMsg),
+    /// The client reconnected more than [`FlappingConfig::max_reconnects`] times in one [`FlappingConfig::window`].
+    ///
+    /// Contains the client's id and its number of reconnects in the current window. This is emitted after every
+    /// excessive [`Self::Connected`] or [`Self::Reconnected`] report while [`ServerConfig::flapping`] is set. It does
+    /// not affect the client's connection.
+    FlappingClient(ClientId, u32),
+    /// Another session tried to authenticate with the id of this connected client.
+    ///
+    /// The new session was closed and this client's connection is not affected. This usually means two clients
+    /// picked the same id (e.g. with `AuthRequest::None`), or one client was started twice. See
+    /// `ClientConfig::disambiguate_id`.
+    IdCollision,
+    /// The client disconnected with a close frame (e.g. from [`Client::close_with()`](crate::Client::close_with)).
+    ///
+    /// This replaces [`Self::Disconnected`] for the disconnect, and is emitted at the same time. Any close frame is
+    /// reported with its code and reason except the frame sent by [`Client::close()`](crate::Client::close), and
+    /// frames the server sends when it closes a misbehaving client. Sessions closed with
+    /// [`Server::disconnect_client()`] are also reported with their close frame.
+    DisconnectedWithReason(CloseFrame),
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -38,6 +85,10 @@ pub enum ServerEvent<ConnectMsg: Debug + Clone, ClientMsg: Debug, ClientRequest:
     ///
     /// The server should reply with a response, ack, or rejection.
     ///
+    /// Requests from a client are emitted in the order the client sent them within a session. Use
+    /// [`ServerConfig::serialize_requests`] to only emit a client's next request after the previous request's token is
+    /// consumed.
+    ///
     /// It is not recommended to use a request/response pattern for requests that are not immediately handled on the
     /// server (i.e. not handled before pulling any new client values from the server API).
     ///
@@ -64,6 +115,26 @@ pub enum ServerEvent<ConnectMsg: Debug + Clone, ClientMsg: Debug, ClientRequest:
     ///             will receive a server-state sync message that will include the updated state from the prior request
     ///             (which at that point would have been sent two full reconnect cycles ago).
     Request(RequestToken, ClientRequest),
+    /// A text message.
+    ///
+    /// Only emitted if [`ServerConfig::text_messages`] is enabled.
+    Text(String),
+    /// A message queued in the client's mailbox was dropped before the client reconnected.
+    ///
+    /// Includes the id of the message's [`SendSignal`] (see [`SendSignal::id()`]). Only emitted if
+    /// [`ServerConfig::mailbox`] is enabled.
+    MailboxExpired(u64),
+    /// An application-defined control message (see [`ControlMsg`]).
+    Control(ControlMsg),
+    /// The client exceeded its rate limit.
+    ///
+    /// Only emitted if [`ServerConfig::rate_limit_policy`] reports violations.
+    RateLimitViolation(RateLimitViolation),
+    /// A batch of requests to the server (see [`Client::request_batch()`](crate::Client::request_batch)).
+    ///
+    /// The batch has one token, so the server should reply to the whole batch with one response, ack, or rejection.
+    /// The caveats of [`Self::Request`] also apply to request batches.
+    RequestBatch(RequestToken, Vec<ClientRequest>),
 }
 
 //-------------------------------------------------------------------------------------------------------------------
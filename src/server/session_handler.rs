@@ -2,12 +2,13 @@
 use crate::*;
 
 //third-party shortcuts
-use bincode::Options;
 
 //standard shortcuts
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::fmt::Debug;
+use std::net::SocketAddr;
+use std::time::Duration;
 use std::vec::Vec;
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -17,15 +18,23 @@ pub(crate) struct SessionHandler<Channel: ChannelPack>
 {
     /// authenticator used to evaluate authentication requests
     pub(crate) authenticator: Arc<Authenticator>,
+    /// recently verified auth tokens
+    pub(crate) token_cache: VerifiedTokenCache,
+    /// config: verify auth tokens on the blocking thread pool
+    pub(crate) offload_token_verification: bool,
+    /// tracks failed authentication attempts per address
+    pub(crate) auth_failure_tracker: AuthFailureTracker,
 
     /// id of this session
     pub(crate) id: SessionId,
+    /// address of the session's peer
+    pub(crate) address: SocketAddr,
     /// client id for this session
     ///
     /// Unavailable until the client is authenticated.
     pub(crate) client_id: Option<ClientId>,
     /// this session
-    pub(crate) session: ezsockets::Session<SessionId, ()>,
+    pub(crate) session: ezsockets::Session<SessionId, SessionCall>,
     /// oneshot to notify the server when this session has become authenticated
     pub(crate) auth_signal_sender: tokio::sync::mpsc::Sender::<()>,
     /// Sends client events to the internal connection handler.
@@ -39,25 +48,82 @@ pub(crate) struct SessionHandler<Channel: ChannelPack>
 
     /// config: maximum message size (bytes)
     pub(crate) max_msg_size: u32,
+    /// config: allow text messages
+    pub(crate) text_messages: bool,
+    /// config: text pings use the default timestamp payload
+    pub(crate) timestamped_pings: bool,
+    /// config: called when heartbeat pongs are received
+    pub(crate) heartbeat_callback: Option<Arc<dyn ServerHeartbeatCallback>>,
+    /// config: called with connection quality reports
+    pub(crate) quality_callback: Option<Arc<dyn ServerConnectionQualityCallback>>,
+    /// config: called when heartbeats go unanswered
+    pub(crate) miss_callback: Option<Arc<dyn ServerKeepaliveMissCallback>>,
+    /// config: number of unanswered heartbeats that triggers the miss callback
+    pub(crate) miss_threshold: u32,
+    /// config: duration between heartbeats
+    pub(crate) heartbeat_interval: Duration,
+    /// config: decides if clients may subscribe to topics
+    pub(crate) topic_permission: Option<Arc<dyn TopicPermissionFn>>,
+    /// decodes client messages
+    pub(crate) codec: WireCodec,
     /// client's environment type
     pub(crate) env_type: EnvType,
 
     /// rate limit tracker
     pub(crate) rate_limit_tracker: RateLimitTracker,
+    /// the rate limit tracker uses the client's tenant's rate limit
+    pub(crate) tenant_rate_limit: bool,
+    /// config: policy for clients that exceed their rate limit
+    pub(crate) rate_limit_policy: RateLimitPolicy,
+    /// drops duplicate client messages
+    pub(crate) msg_dedup: Option<MsgDedupFilter>,
+    /// queues client requests so they are emitted one at a time
+    pub(crate) serial_requests: Option<SerialRequestQueue<Channel>>,
+    /// registry of tenants clients can connect to
+    pub(crate) tenants: Option<Arc<TenantRegistry>>,
+    /// only accept token auth (the session overflowed the pending-auth queue)
+    pub(crate) token_required: bool,
+    /// source of time for auth expiry checks
+    pub(crate) time_source: Arc<dyn TimeSource>,
 
     /// session wrapper for sending request rejections
     pub(crate) request_rejector: Arc<dyn RequestRejectorFn>,
+    /// reliable messages awaiting acknowledgement
+    pub(crate) reliable_tracker: ReliableMsgTracker,
+    /// intercepts client messages and requests
+    pub(crate) middleware: MiddlewareChain<Channel>,
+    /// assigns a role to the client when it authenticates
+    pub(crate) role_fn: Option<Arc<dyn ClientRoleFn<Channel>>>,
+    /// the client's role
+    pub(crate) role: ClientRole,
+    /// assigns permission scopes to the client when it authenticates
+    pub(crate) scopes_fn: Option<Arc<dyn ClientScopesFn<Channel>>>,
+    /// the client's permission scopes
+    pub(crate) scopes: Scopes,
+    /// initializes the session store when the client authenticates
+    pub(crate) session_init_fn: Option<Arc<dyn SessionInitFn<Channel>>>,
+    /// values stored by hooks that run on the session task
+    pub(crate) store: SessionStore,
+    /// tracks heartbeat statistics for connection quality reports
+    pub(crate) quality_tracker: Arc<Mutex<ConnectionQualityTracker>>,
 
     /// Signal used to inform request tokens of the session's death, to avoid sending responses to new sessions
     /// for requests made with old sessions.
     pub(crate) death_signal: Arc<AtomicBool>,
+    /// Signal set when the server closes the session itself (e.g. because the client misbehaved), so the close frame
+    /// isn't reported as an application-defined reason.
+    pub(crate) closed_internally: Arc<AtomicBool>,
+
+    /// simulated network conditions
+    #[cfg(feature = "netsim")]
+    pub(crate) netsim: Option<NetworkSimulator>,
 }
 
 #[async_trait::async_trait]
 impl<Channel: ChannelPack> ezsockets::SessionExt for SessionHandler<Channel>
 {
     type ID   = SessionId;
-    type Call = ();
+    type Call = SessionCall;
 
     fn id(&self) -> &SessionId
     {
@@ -67,51 +133,52 @@ impl<Channel: ChannelPack> ezsockets::SessionExt for SessionHandler<Channel>
     // Receive text from client (via session connection).
     async fn on_text(&mut self, text: String) -> Result<(), ezsockets::Error>
     {
-        match self.env_type
+        if let Ok(mut tracker) = self.quality_tracker.lock() { tracker.record_recv(); }
+
+        // received text message
+        if let Some(text) = text.strip_prefix(TEXT_MSG_PREFIX)
+        {
+            return self.handle_text(String::from(text));
+        }
+
+        // received Ping or Pong
+        // - native clients only send text pings if they customize their heartbeats
+        let Some((var, value)) = text.as_str().split_once(':')
+        else
+        {
+            tracing::trace!("received invalid text from client, closing session...");
+            self.close("only ping/pong text allowed"); return Ok(());
+        };
+
+        // sanity check the payload size
+        if value.len() > self.max_msg_size as usize
         {
-            EnvType::Native =>
+            tracing::trace!("received ping/pong that's too large, closing session...");
+            self.close("message size violation"); return Ok(());
+        }
+
+        match var
+        {
+            "ping" =>
             {
-                // reject text from client
-                tracing::trace!("received text from native client (not implemented), closing session...");
-                self.close("text not allowed"); return Ok(());
+                // received Ping, send Pong back
+                let _ = self.session.text(format!("pong:{}", value))?;
             }
-            EnvType::Wasm =>
+            "pong" =>
             {
-                // received Ping or Pong
-                let Some((var, value)) = text.as_str().split_once(':')
-                else
-                {
-                    tracing::trace!("received invalid text from WASM client, closing session...");
-                    self.close("only ping/pong text allowed"); return Ok(());
-                };
-
-                // try to deserialize timestamp
-                let Ok(timestamp) = u128::from_str_radix(value, 10u32)
-                else
+                // received Pong, report it
+                let pong = read_pong(value, self.timestamped_pings);
+                if let Ok(mut tracker) = self.quality_tracker.lock() { tracker.record_pong(&pong); }
+                if let (Some(callback), Some(client_id)) = (&self.heartbeat_callback, self.client_id)
                 {
-                    tracing::trace!("received invalid ping/pong timestamp from WASM client, closing session...");
-                    self.close("only timestamp ping/pong allowed"); return Ok(());
-                };
-
-                match var
-                {
-                    "ping" =>
-                    {
-                        // received Ping, send Pong back
-                        let _ = self.session.text(format!("pong:{}", value))?;
-                    }
-                    "pong" =>
-                    {
-                        // received Pong, log latency
-                        log_ping_pong_latency(timestamp);
-                    }
-                    _ =>
-                    {
-                        tracing::trace!("received invalid ping/pong timestamp from WASM client, closing session...");
-                        self.close("only ping/pong prefixes allowed");
-                    }
+                    (callback)(client_id, pong);
                 }
             }
+            _ =>
+            {
+                tracing::trace!("received invalid ping/pong prefix from client, closing session...");
+                self.close("only ping/pong prefixes allowed");
+            }
         }
 
         Ok(())
@@ -120,12 +187,88 @@ impl<Channel: ChannelPack> ezsockets::SessionExt for SessionHandler<Channel>
     // Receive binary from client (via session connection).
     async fn on_binary(&mut self, bytes: Vec<u8>) -> Result<(), ezsockets::Error>
     {
-        // try to update rate limit tracker
-        if !self.rate_limit_tracker.try_count_msg()
+        // apply simulated network conditions
+        #[cfg(feature = "netsim")]
+        if let Some(netsim) = &mut self.netsim
         {
-            tracing::trace!("client messages exceeded rate limit, closing session...");
-            self.close("rate limit violation"); return Ok(());
+            match netsim.next_action()
+            {
+                NetsimAction::Deliver => (),
+                NetsimAction::Drop =>
+                {
+                    tracing::trace!(self.id, "netsim: dropping frame from client");
+                    return Ok(());
+                }
+                NetsimAction::Delay(delay) =>
+                {
+                    let session = self.session.clone();
+                    tokio::spawn(async move {
+                            tokio::time::sleep(delay).await;
+                            let _ = session.call(SessionCall::DelayedBinary(bytes));
+                        });
+                    return Ok(());
+                }
+            }
+        }
+
+        self.handle_binary(bytes).await
+    }
+
+    // Responds to calls to the session connected to this handler (i.e. ezsockets::Session::call()).
+    async fn on_call(&mut self, msg: SessionCall) -> Result<(), ezsockets::Error>
+    {
+        match msg
+        {
+            SessionCall::SetRateLimit(rate_limit_config) =>
+            {
+                // sessions that use their tenant's rate limit are not affected
+                if self.tenant_rate_limit { return Ok(()); }
+
+                self.rate_limit_tracker = RateLimitTracker::new_with_time_source(
+                        rate_limit_config,
+                        self.time_source.clone()
+                    );
+
+                // tell authenticated clients about their new rate limit
+                if self.client_id.is_none() { return Ok(()); }
+                let rate_limit_msg = ClientMetaEventFrom::<Channel>::RateLimit(rate_limit_config);
+                if let Err(_) = self.codec.send(&rate_limit_msg, |ser_msg| self.session.binary(ser_msg))
+                { tracing::error!(self.id, "serializing session rate limit failed"); }
+                Ok(())
+            }
+            SessionCall::RequestConsumed =>
+            {
+                // emit the client's next queued request
+                let Some(client_id) = self.client_id else { return Ok(()); };
+                let Some(event) = self.serial_requests.as_mut().and_then(SerialRequestQueue::pop_consumed)
+                else { return Ok(()); };
+                self.send_server_event(client_id, event);
+                Ok(())
+            }
+            SessionCall::FlushLatest(slot) =>
+            {
+                // ask the connection handler for the slot's held message
+                let Some(client_id) = self.client_id else { return Ok(()); };
+                let flush = SessionCommand::<Channel>::FlushLatest(self.id, slot);
+                if let Err(_) = self.client_event_sender.send(ClientTargetMsg::new(client_id, flush))
+                { tracing::debug!(self.id, "client event sender is broken, dropping latest-only flush"); }
+                Ok(())
+            }
+            #[cfg(feature = "netsim")]
+            SessionCall::DelayedBinary(bytes) => self.handle_binary(bytes).await,
         }
+    }
+}
+
+impl<Channel: ChannelPack> SessionHandler<Channel>
+{
+    /// Handles a binary frame from the client.
+    async fn handle_binary(&mut self, bytes: Vec<u8>) -> Result<(), ezsockets::Error>
+    {
+        if let Ok(mut tracker) = self.quality_tracker.lock() { tracker.record_recv(); }
+
+        // try to update rate limit tracker
+        if !self.try_count_msg() { return Ok(()); }
 
         // try to deserialize message
         if bytes.len() > self.max_msg_size as usize
@@ -133,11 +276,19 @@ impl<Channel: ChannelPack> ezsockets::SessionExt for SessionHandler<Channel>
             tracing::trace!("received client message that's too large, closing session...");
             self.close("message size violation"); return Ok(());
         }
-        let Ok(message) = bincode::DefaultOptions::new().deserialize(&bytes[..])
-        else
+        let message = match self.codec.decode::<ServerMetaEventFrom<Channel>>(&bytes[..])
         {
-            tracing::trace!("received client message that failed to deserialize, closing session...");
-            self.close("deserialization failure"); return Ok(());
+            Ok(message) => message,
+            Err(DecodeError::Corrupted) =>
+            {
+                tracing::debug!(self.id, "discarding corrupted client message");
+                return Ok(());
+            }
+            Err(err) =>
+            {
+                tracing::trace!(?err, "received client message that failed to deserialize, closing session...");
+                self.close("deserialization failure"); return Ok(());
+            }
         };
 
         // decide what to do with the message
@@ -151,13 +302,61 @@ impl<Channel: ChannelPack> ezsockets::SessionExt for SessionHandler<Channel>
                     self.close("extra auth message"); return Ok(());
                 }
 
+                // overflow sessions may only authenticate with tokens
+                // - this is not an auth failure, since the client may be valid
+                if self.token_required && !matches!(msg.auth, AuthRequest::Token{..})
+                {
+                    self.close("server busy"); return Ok(());
+                }
+
                 // authenticate the connection
-                if !self.authenticator.authenticate(&msg.auth)
+                let authenticated = authenticate_with_cache(
+                        self.authenticator.clone(),
+                        self.token_cache.clone(),
+                        self.offload_token_verification,
+                        msg.auth,
+                        self.time_source.wall_time()
+                    ).await;
+                if !authenticated
                 {
-                    self.close("invalid auth"); return Ok(());
+                    self.auth_failure_tracker.record_failure(self.address.ip());
+                    self.close(AUTH_REJECTED_CLOSE_REASON); return Ok(());
                 }
+
+                // validate the client's tenant
+                // - sessions for tenants with custom rate limits switch to the tenant's limits
+                let tenant = match &self.tenants
+                {
+                    Some(tenants) =>
+                    {
+                        let Some((tenant, tenant_config)) = msg.tenant
+                            .and_then(|tenant| tenants.get(&tenant).map(|config| (tenant, *config)))
+                        else
+                        {
+                            self.auth_failure_tracker.record_failure(self.address.ip());
+                            self.close("invalid tenant"); return Ok(());
+                        };
+
+                        if let Some(rate_limit_config) = tenant_config.rate_limit_config
+                        {
+                            self.rate_limit_tracker = RateLimitTracker::new_with_time_source(
+                                    rate_limit_config,
+                                    self.time_source.clone()
+                                );
+                            self.tenant_rate_limit = true;
+                        }
+
+                        Some(tenant)
+                    }
+                    None => None,
+                };
                 self.client_id = Some(msg.auth.client_id());
 
+                // assign the client's role
+                if let Some(role_fn) = &self.role_fn { self.role = (role_fn)(&msg.auth, &msg.msg); }
+                if let Some(scopes_fn) = &self.scopes_fn { self.scopes = (scopes_fn)(&msg.auth, &msg.msg); }
+                if let Some(init_fn) = &self.session_init_fn { (init_fn)(&msg.auth, &msg.msg, &mut self.store); }
+
                 // notify auto-disconnector not to disconnect this client
                 if self.auth_signal_sender.try_send(()).is_err()
                 {
@@ -172,7 +371,8 @@ impl<Channel: ChannelPack> ezsockets::SessionExt for SessionHandler<Channel>
                             SessionCommand::Add{
                                 session_id: self.id,
                                 msg: msg.msg,
-                                env_type: self.env_type
+                                meta: ConnectionMeta{ env_type: self.env_type, tenant, role: self.role },
+                                rate_limit: self.rate_limit_tracker.config(),
                             }
                         )
                     )
@@ -180,6 +380,8 @@ impl<Channel: ChannelPack> ezsockets::SessionExt for SessionHandler<Channel>
                     tracing::debug!(?err, "authenticated notifier is broken, closing session...");
                     self.close("session error"); return Ok(());
                 }
+
+                self.start_heartbeat_monitor(msg.auth.client_id());
             }
             ServerMetaEventFrom::<Channel>::Msg(msg) =>
             {
@@ -190,6 +392,46 @@ impl<Channel: ChannelPack> ezsockets::SessionExt for SessionHandler<Channel>
                     self.close("message before auth"); return Ok(());
                 };
 
+                // drop messages from read-only clients
+                if self.role == ClientRole::ReadOnly
+                {
+                    tracing::trace!(self.id, "dropping message from read-only client");
+                    return Ok(());
+                }
+
+                // reject messages the client isn't authorized to send
+                let required = Channel::client_msg_scopes(&msg);
+                if !self.scopes.contains(required)
+                {
+                    tracing::trace!(self.id, "rejecting unauthorized message from client");
+                    self.send_unauthorized(None, self.scopes.missing(required));
+                    return Ok(());
+                }
+
+                // drop duplicates of recent messages
+                if Channel::dedup_client_msg(&msg)
+                    && self.msg_dedup.as_mut().map(|filter| filter.is_duplicate(&msg)).unwrap_or(false)
+                {
+                    tracing::trace!(self.id, "dropping duplicate client message");
+                    return Ok(());
+                }
+
+                // pass the message through middleware
+                let msg = match apply_msg_middleware(&self.middleware, client_id, &mut self.store, msg)
+                {
+                    MiddlewareAction::Continue(msg) => msg,
+                    MiddlewareAction::Reject =>
+                    {
+                        tracing::trace!(self.id, "middleware rejected client message");
+                        return Ok(());
+                    }
+                    MiddlewareAction::Disconnect =>
+                    {
+                        tracing::trace!("middleware rejected client message, closing session...");
+                        self.close("rejected by middleware"); return Ok(());
+                    }
+                };
+
                 // try to forward client message to session owner
                 if let Err(err) = self.server_event_sender.send(
                         ClientSourceMsg::new(client_id, ServerEventFrom::<Channel>::Msg(msg))
@@ -199,7 +441,7 @@ impl<Channel: ChannelPack> ezsockets::SessionExt for SessionHandler<Channel>
                     self.close("session error"); return Ok(());
                 }
             }
-            ServerMetaEventFrom::<Channel>::Request(request, request_id) =>
+            ServerMetaEventFrom::<Channel>::Request(request, request_id, trace_id) =>
             {
                 // disconnect client if not fully authenticated yet
                 let Some(client_id) = self.client_id
@@ -208,42 +450,400 @@ impl<Channel: ChannelPack> ezsockets::SessionExt for SessionHandler<Channel>
                     self.close("request before auth"); return Ok(());
                 };
 
+                // reject requests from read-only clients
+                if self.role == ClientRole::ReadOnly
+                {
+                    tracing::trace!(self.id, request_id, ?trace_id, "rejecting request from read-only client");
+                    (self.request_rejector)(request_id, trace_id);
+                    return Ok(());
+                }
+
+                // reject requests the client isn't authorized to send
+                let required = Channel::client_request_scopes(&request);
+                if !self.scopes.contains(required)
+                {
+                    tracing::trace!(self.id, request_id, ?trace_id, "rejecting unauthorized request from client");
+                    self.send_unauthorized(Some(request_id), self.scopes.missing(required));
+                    return Ok(());
+                }
+
+                // pass the request through middleware
+                let request = match apply_request_middleware(&self.middleware, client_id, &mut self.store, request)
+                {
+                    MiddlewareAction::Continue(request) => request,
+                    MiddlewareAction::Reject =>
+                    {
+                        tracing::trace!(self.id, request_id, ?trace_id, "middleware rejected client request");
+                        (self.request_rejector)(request_id, trace_id);
+                        return Ok(());
+                    }
+                    MiddlewareAction::Disconnect =>
+                    {
+                        tracing::trace!("middleware rejected client request, closing session...");
+                        self.close("rejected by middleware"); return Ok(());
+                    }
+                };
+
                 // prepare token
                 let token = RequestToken::new(
                         client_id,
                         request_id,
+                        trace_id,
                         self.request_rejector.clone(),
                         self.death_signal.clone(),
                     );
 
                 // try to forward client request to session owner
+                self.forward_request(
+                        client_id,
+                        token,
+                        move |token| ServerEventFrom::<Channel>::Request(token, request)
+                    );
+            }
+            ServerMetaEventFrom::<Channel>::MsgAck(msg_id) =>
+            {
+                // disconnect client if not fully authenticated yet
+                if self.client_id.is_none()
+                {
+                    self.close("ack before auth"); return Ok(());
+                }
+
+                // mark the reliable message as delivered
+                if !self.reliable_tracker.acknowledge(msg_id)
+                {
+                    tracing::debug!(self.id, msg_id, "ignoring ack for unknown reliable message");
+                }
+            }
+            ServerMetaEventFrom::<Channel>::Subscribe(topic, request_id) =>
+            {
+                // disconnect client if not fully authenticated yet
+                let Some(client_id) = self.client_id
+                else
+                {
+                    self.close("subscribe before auth"); return Ok(());
+                };
+
+                // check if the client may subscribe to the topic
+                if let Some(permission) = &self.topic_permission
+                {
+                    if !(permission)(client_id, &topic)
+                    {
+                        tracing::trace!(self.id, request_id, %topic, "rejecting topic subscription");
+                        (self.request_rejector)(request_id, None);
+                        return Ok(());
+                    }
+                }
+
+                // forward the subscription to the connection handler
+                // - the connection handler acknowledges the subscription once it's registered
+                if let Err(err) = self.client_event_sender.send(
+                        ClientTargetMsg::new(client_id, SessionCommand::Subscribe(self.id, topic, request_id))
+                    )
+                {
+                    tracing::debug!(?err, "client event sender is broken, closing session...");
+                    self.close("session error"); return Ok(());
+                }
+            }
+            ServerMetaEventFrom::<Channel>::Unsubscribe(topic) =>
+            {
+                // disconnect client if not fully authenticated yet
+                let Some(client_id) = self.client_id
+                else
+                {
+                    self.close("unsubscribe before auth"); return Ok(());
+                };
+
+                // forward the unsubscription to the connection handler
+                if let Err(err) = self.client_event_sender.send(
+                        ClientTargetMsg::new(client_id, SessionCommand::Unsubscribe(self.id, topic))
+                    )
+                {
+                    tracing::debug!(?err, "client event sender is broken, closing session...");
+                    self.close("session error"); return Ok(());
+                }
+            }
+            ServerMetaEventFrom::<Channel>::Control(msg) =>
+            {
+                // disconnect client if not fully authenticated yet
+                let Some(client_id) = self.client_id
+                else
+                {
+                    self.close("control before auth"); return Ok(());
+                };
+
+                // forward the control message to the session owner
+                // - control messages bypass client roles, scopes, and middleware
                 if let Err(err) = self.server_event_sender.send(
-                        ClientSourceMsg::new(client_id, ServerEventFrom::<Channel>::Request(token, request))
+                        ClientSourceMsg::new(client_id, ServerEventFrom::<Channel>::Control(msg))
                     )
                 {
                     tracing::debug!(?err, "client msg sender is broken, closing session...");
                     self.close("session error"); return Ok(());
                 }
             }
+            ServerMetaEventFrom::<Channel>::RequestBatch(requests, request_id, trace_id) =>
+            {
+                // disconnect client if not fully authenticated yet
+                let Some(client_id) = self.client_id
+                else
+                {
+                    self.close("request before auth"); return Ok(());
+                };
+
+                // reject request batches from read-only clients
+                if self.role == ClientRole::ReadOnly
+                {
+                    tracing::trace!(self.id, request_id, ?trace_id, "rejecting request batch from read-only client");
+                    (self.request_rejector)(request_id, trace_id);
+                    return Ok(());
+                }
+
+                // reject the batch if the client isn't authorized to send any of its requests
+                let required = requests
+                    .iter()
+                    .fold(Scopes::NONE, |required, request| required | Channel::client_request_scopes(request));
+                if !self.scopes.contains(required)
+                {
+                    tracing::trace!(self.id, request_id, ?trace_id, "rejecting unauthorized request batch from client");
+                    self.send_unauthorized(Some(request_id), self.scopes.missing(required));
+                    return Ok(());
+                }
+
+                // pass each request through middleware
+                // - the batch is rejected if any request is rejected
+                let mut checked_requests = Vec::with_capacity(requests.len());
+                for request in requests
+                {
+                    match apply_request_middleware(&self.middleware, client_id, &mut self.store, request)
+                    {
+                        MiddlewareAction::Continue(request) => checked_requests.push(request),
+                        MiddlewareAction::Reject =>
+                        {
+                            tracing::trace!(self.id, request_id, ?trace_id, "middleware rejected client request batch");
+                            (self.request_rejector)(request_id, trace_id);
+                            return Ok(());
+                        }
+                        MiddlewareAction::Disconnect =>
+                        {
+                            tracing::trace!("middleware rejected client request batch, closing session...");
+                            self.close("rejected by middleware"); return Ok(());
+                        }
+                    }
+                }
+
+                // prepare token
+                let token = RequestToken::new(
+                        client_id,
+                        request_id,
+                        trace_id,
+                        self.request_rejector.clone(),
+                        self.death_signal.clone(),
+                    );
+
+                // try to forward client request batch to session owner
+                self.forward_request(
+                        client_id,
+                        token,
+                        move |token| ServerEventFrom::<Channel>::RequestBatch(token, checked_requests)
+                    );
+            }
         }
 
         Ok(())
     }
 
-    // Responds to calls to the session connected to this handler (i.e. ezsockets::Session::call()).
-    async fn on_call(&mut self, _msg: ()) -> Result<(), ezsockets::Error>
+    /// Forwards a client request (or request batch) to the session owner.
+    ///
+    /// If requests are serialized, the request is queued until the token of the client's previous request is consumed.
+    fn forward_request(
+        &mut self,
+        client_id : ClientId,
+        token     : RequestToken,
+        event     : impl FnOnce(RequestToken) -> ServerEventFrom<Channel>
+    )
     {
-        tracing::info!(self.id, "received call (not implemented), closing session...");
-        self.close("session error"); return Ok(());
+        let event = match &mut self.serial_requests
+        {
+            Some(queue) =>
+            {
+                let token = token.with_consumed_guard(RequestConsumedGuard::new(self.session.clone()));
+                let Some(event) = queue.push((event)(token)) else { return; };
+                event
+            }
+            None => (event)(token),
+        };
+
+        self.send_server_event(client_id, event);
+    }
+
+    /// Forwards a client event to the session owner.
+    fn send_server_event(&mut self, client_id: ClientId, event: ServerEventFrom<Channel>)
+    {
+        if let Err(err) = self.server_event_sender.send(ClientSourceMsg::new(client_id, event))
+        {
+            tracing::debug!(?err, "client msg sender is broken, closing session...");
+            self.close("session error");
+        }
+    }
+
+    /// Counts a client message toward the session's rate limit.
+    ///
+    /// Returns `false` if the message exceeded the rate limit and should be discarded. The violation is handled
+    /// according to the session's [`RateLimitPolicy`].
+    fn try_count_msg(&mut self) -> bool
+    {
+        if self.rate_limit_tracker.try_count_msg() { return true; }
+
+        // sessions are always closed if they aren't authenticated yet
+        let Some(client_id) = self.client_id.filter(|_| self.rate_limit_policy != RateLimitPolicy::Disconnect)
+        else
+        {
+            tracing::trace!("client messages exceeded rate limit, closing session...");
+            self.close("rate limit violation"); return false;
+        };
+
+        // report the first violation in each rate limit period
+        let config = self.rate_limit_tracker.config();
+        let count = self.rate_limit_tracker.count();
+        if count == config.max_count as u64 + 1
+        {
+            tracing::trace!(self.id, count, "client messages exceeded rate limit");
+            let violation = RateLimitViolation{ config, count };
+            if let Err(err) = self.server_event_sender.send(
+                    ClientSourceMsg::new(client_id, ServerEventFrom::<Channel>::RateLimitViolation(violation))
+                )
+            {
+                tracing::debug!(?err, "client msg sender is broken, closing session...");
+                self.close("session error"); return false;
+            }
+        }
+
+        match self.rate_limit_policy
+        {
+            RateLimitPolicy::ReportAndDrop => tracing::trace!(self.id, "dropping message that exceeded rate limit"),
+            _ =>
+            {
+                tracing::trace!("client messages exceeded rate limit, closing session...");
+                self.close("rate limit violation");
+            }
+        }
+
+        false
+    }
+
+    /// Handles a text message from the client.
+    fn handle_text(&mut self, text: String) -> Result<(), ezsockets::Error>
+    {
+        // disconnect client if text messages are disabled
+        if !self.text_messages
+        {
+            tracing::trace!("received text message but text messages are disabled, closing session...");
+            self.close("text not allowed"); return Ok(());
+        }
+
+        // try to update rate limit tracker
+        if !self.try_count_msg() { return Ok(()); }
+
+        // check message size
+        if text.len() > self.max_msg_size as usize
+        {
+            tracing::trace!("received client text message that's too large, closing session...");
+            self.close("message size violation"); return Ok(());
+        }
+
+        // disconnect client if not fully authenticated yet
+        let Some(client_id) = self.client_id
+        else
+        {
+            self.close("text message before auth"); return Ok(());
+        };
+
+        // drop text messages from read-only clients
+        if self.role == ClientRole::ReadOnly
+        {
+            tracing::trace!(self.id, "dropping text message from read-only client");
+            return Ok(());
+        }
+
+        // pass the text through middleware
+        let text = match apply_text_middleware(&self.middleware, client_id, &mut self.store, text)
+        {
+            MiddlewareAction::Continue(text) => text,
+            MiddlewareAction::Reject =>
+            {
+                tracing::trace!(self.id, "middleware rejected client text message");
+                return Ok(());
+            }
+            MiddlewareAction::Disconnect =>
+            {
+                tracing::trace!("middleware rejected client text message, closing session...");
+                self.close("rejected by middleware"); return Ok(());
+            }
+        };
+
+        // try to forward client text to session owner
+        if let Err(err) = self.server_event_sender.send(
+                ClientSourceMsg::new(client_id, ServerEventFrom::<Channel>::Text(text))
+            )
+        {
+            tracing::debug!(?err, "client msg sender is broken, closing session...");
+            self.close("session error"); return Ok(());
+        }
+
+        Ok(())
+    }
+
+    /// Reports the session's connection quality and missed heartbeats once per heartbeat interval until the session
+    /// dies.
+    fn start_heartbeat_monitor(&self, client_id: ClientId)
+    {
+        if self.quality_callback.is_none() && self.miss_callback.is_none() { return; }
+        if self.heartbeat_interval.is_zero() { return; }
+
+        let quality_callback = self.quality_callback.clone();
+        let miss_callback = self.miss_callback.clone();
+        let miss_threshold = self.miss_threshold;
+        let tracker = self.quality_tracker.clone();
+        let death_signal = self.death_signal.clone();
+        let heartbeat_interval = self.heartbeat_interval;
+        tokio::spawn(async move {
+                let mut interval = tokio::time::interval_at(
+                        tokio::time::Instant::now() + heartbeat_interval,
+                        heartbeat_interval
+                    );
+                loop
+                {
+                    interval.tick().await;
+                    if death_signal.load(Ordering::Acquire) { break; }
+
+                    let Ok(mut tracker) = tracker.lock() else { break; };
+                    let report = tracker.report(heartbeat_interval);
+                    let missed = tracker.check_missed(heartbeat_interval, miss_threshold);
+                    drop(tracker);
+
+                    if let Some(callback) = &quality_callback { (callback)(client_id, report); }
+                    if let (Some(callback), Some(missed)) = (&miss_callback, missed) { (callback)(client_id, missed); }
+                }
+            });
+    }
+
+    /// Tells the client that a message or request was rejected because the client lacks the required scopes.
+    fn send_unauthorized(&self, request_id: Option<u64>, missing: Scopes)
+    {
+        let unauthorized = ClientMetaEventFrom::<Channel>::Unauthorized(request_id, missing);
+        let Ok(result) = self.codec.send(&unauthorized, |ser_msg| self.session.binary(ser_msg))
+        else { tracing::error!(self.id, "serializing unauthorized rejection failed"); return; };
+
+        // this may fail if the session is disconnected
+        if let Err(_) = result
+        { tracing::debug!(self.id, "dropping unauthorized rejection sent to broken session"); }
     }
-}
 
-impl<Channel: ChannelPack> SessionHandler<Channel>
-{
     /// Close the session
     fn close(&mut self, reason: &str)
     {
         tracing::info!(self.id, "closing...");
+        self.closed_internally.store(true, Ordering::Release);
         if let Err(_) = self.session.close(Some(
                 ezsockets::CloseFrame
                 {
@@ -262,6 +862,7 @@ impl<Channel: ChannelPack> Drop for SessionHandler<Channel>
     fn drop(&mut self)
     {
         self.death_signal.store(true, Ordering::Release);
+        self.reliable_tracker.close();
     }
 }
 
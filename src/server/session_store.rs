@@ -0,0 +1,106 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A per-session store of values keyed by type.
+///
+/// Each client session has its own store, which lives on the session task and is dropped when the session closes.
+/// Hooks that run on the session task can use it to share data without round trips to the server application, e.g.
+/// a [`SessionInitFn`] can store decoded claims when the client authenticates and a [`ServerMiddleware`] can read them
+/// when checking requests.
+#[derive(Default)]
+pub struct SessionStore
+{
+    values: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl SessionStore
+{
+    /// Inserts a value, returning the previous value of the same type if there was one.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) -> Option<T>
+    {
+        self.values
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|prev| prev.downcast::<T>().ok())
+            .map(|prev| *prev)
+    }
+
+    /// Gets a reference to the value of type `T`.
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T>
+    {
+        self.values.get(&TypeId::of::<T>()).and_then(|value| value.downcast_ref::<T>())
+    }
+
+    /// Gets a mutable reference to the value of type `T`.
+    pub fn get_mut<T: Send + Sync + 'static>(&mut self) -> Option<&mut T>
+    {
+        self.values.get_mut(&TypeId::of::<T>()).and_then(|value| value.downcast_mut::<T>())
+    }
+
+    /// Removes the value of type `T`.
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> Option<T>
+    {
+        self.values
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast::<T>().ok())
+            .map(|value| *value)
+    }
+
+    /// Tests if the store contains a value of type `T`.
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool
+    {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Gets the number of values in the store.
+    pub fn len(&self) -> usize
+    {
+        self.values.len()
+    }
+
+    /// Tests if the store is empty.
+    pub fn is_empty(&self) -> bool
+    {
+        self.values.is_empty()
+    }
+}
+
+impl Debug for SessionStore
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        write!(f, "SessionStore [{} values]", self.values.len())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(&AuthRequest, &ConnectMsg, &mut SessionStore)`.
+///
+/// Initializes the [`SessionStore`] of each client session when the client authenticates. The function is invoked on
+/// the client's session task after the client's role and scopes are assigned, so it should not block.
+///
+/// See [`ServerFactory::with_session_init_fn()`].
+pub trait SessionInitFn<Channel: ChannelPack>:
+    Fn(&AuthRequest, &Channel::ConnectMsg, &mut SessionStore) + Send + Sync + 'static
+{}
+impl<Channel, F> SessionInitFn<Channel> for F
+where
+    Channel: ChannelPack,
+    F: Fn(&AuthRequest, &Channel::ConnectMsg, &mut SessionStore) + Send + Sync + 'static
+{}
+
+impl<Channel: ChannelPack> Debug for dyn SessionInitFn<Channel>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("SessionInitFn") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -13,6 +13,25 @@ pub(crate) type SessionId = u64;
 
 //-------------------------------------------------------------------------------------------------------------------
 
+/// Calls into a session handler (i.e. `ezsockets::Session::call()`).
+#[derive(Debug)]
+pub(crate) enum SessionCall
+{
+    /// Replaces the session's rate limit (see [`PartialServerConfig::apply_to_existing`]).
+    SetRateLimit(RateLimitConfig),
+    /// The token of the session's in-flight request was consumed (see [`ServerConfig::serialize_requests`]).
+    RequestConsumed,
+    /// A latest-only slot is holding a message (see [`SessionCommand::FlushLatest`]).
+    ///
+    /// Includes the slot key.
+    FlushLatest(u64),
+    /// A binary frame delayed by the network simulator.
+    #[cfg(feature = "netsim")]
+    DelayedBinary(Vec<u8>),
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 /// Message targeted at a session.
 #[derive(Debug)]
 pub(crate) struct ClientTargetMsg<I, T>
@@ -51,7 +70,9 @@ pub(crate) enum SessionCommand<Channel: ChannelPack>
     Add {
         session_id: SessionId,
         msg: Channel::ConnectMsg,
-        env_type: EnvType,
+        meta: ConnectionMeta,
+        /// the session's rate limit, which is sent to the client
+        rate_limit: RateLimitConfig,
     },
     /// Send a client meta event.
     ///
@@ -61,9 +82,51 @@ pub(crate) enum SessionCommand<Channel: ChannelPack>
     /// Includes an optional 'death signal' for the target session of responses. We need this signal in order to
     /// address a race condition between the server API and the server backend where a response for a request received
     /// by an old session could be sent via a new session.
-    Send(ClientMetaEventFrom<Channel>, Option<u64>, Option<SessionDeathSignal>),
+    ///
+    /// Includes an optional resolver for reporting the outcome of the send.
+    Send(ClientMetaEventFrom<Channel>, Option<u64>, Option<SessionDeathSignal>, Option<SendSignalResolver>),
+    /// Send a text message.
+    ///
+    /// Includes an optional 'connection events consumed counter' and a send resolver (see [`Self::Send`]).
+    SendText(String, Option<u64>, SendSignalResolver),
+    /// Send a reliable message.
+    ///
+    /// Includes an optional 'connection events consumed counter' and a send resolver (see [`Self::Send`]), and a signal
+    /// for tracking acknowledgement.
+    SendReliable(Channel::ServerMsg, Option<u64>, SendSignalResolver, ReliableAckSignal),
+    /// Send a message in a latest-only slot.
+    ///
+    /// Includes the slot key, an optional 'connection events consumed counter', and a send resolver (see
+    /// [`Self::Send`]).
+    SendLatest(u64, Channel::ServerMsg, Option<u64>, SendSignalResolver),
+    /// Sends the message held in a latest-only slot of a session once the session asks for it.
+    ///
+    /// Includes the id of the session that owns the slot. The flush is discarded if the client reconnected.
+    FlushLatest(SessionId, u64),
     /// Close a session.
-    Close(Option<ezsockets::CloseFrame>)
+    Close(Option<ezsockets::CloseFrame>),
+    /// Reports a disconnect after the client's disconnect grace window elapses.
+    ///
+    /// Includes the id of the session that disconnected and the session's close frame. The report is discarded if the
+    /// client reconnected.
+    GraceExpired(SessionId, Option<ezsockets::CloseFrame>),
+    /// Drops expired messages from all mailboxes. The command's target client is ignored.
+    ExpireMail,
+    /// Subscribes a session to a topic.
+    ///
+    /// Includes the id of the subscribing session and the subscription's request id. The subscription is discarded if
+    /// the client reconnected.
+    Subscribe(SessionId, String, u64),
+    /// Unsubscribes a session from a topic.
+    ///
+    /// Includes the id of the unsubscribing session.
+    Unsubscribe(SessionId, String),
+    /// Publishes a message to all sessions subscribed to a topic.
+    ///
+    /// Includes a 'connection events consumed counter' (see [`Self::Send`]). The command's target client is ignored.
+    Publish(String, Channel::ServerMsg, u64),
+    /// Updates the server's runtime-adjustable config values. The command's target client is ignored.
+    UpdateConfig(PartialServerConfig),
 }
 
 //-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,92 @@
+//local shortcuts
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A snapshot of the counters used to synchronize server message sends with connection reports.
+///
+/// Messages sent with [`Server::send()`](crate::Server::send) are dropped if the server has not consumed all
+/// connection reports produced for the target client. These counters can be used to detect drift between the server
+/// backend and the server API (e.g. in integration tests, or in ops dashboards) instead of chasing silently dropped
+/// messages.
+///
+/// Counters are read individually, so the snapshot may be slightly inconsistent if the backend is active.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ServerSyncState
+{
+    /// Number of [`ServerReport::Connected`](crate::ServerReport::Connected) and
+    /// [`ServerReport::Reconnected`](crate::ServerReport::Reconnected) events consumed by
+    /// [`Server::next()`](crate::Server::next).
+    pub consumed_connection_events: u64,
+    /// Number of connection events produced by the server backend.
+    pub produced_connection_events: u64,
+    /// Number of clients whose latest consumed connection report is `Connected` or `Reconnected`.
+    pub connected_clients: u64,
+    /// Number of sessions registered in the server backend, including unauthenticated sessions.
+    pub registered_sessions: u64,
+    /// Number of authenticated clients registered in the server backend.
+    pub registered_clients: u64,
+}
+
+impl ServerSyncState
+{
+    /// Number of connection events that have been produced but not consumed yet.
+    pub fn unconsumed_connection_events(&self) -> u64
+    {
+        self.produced_connection_events.saturating_sub(self.consumed_connection_events)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Counters shared between the connection handler and the server API.
+#[derive(Debug, Clone)]
+pub(crate) struct SyncCounters
+{
+    produced_connection_events: Arc<AtomicU64>,
+    registered_sessions: Arc<AtomicU64>,
+    registered_clients: Arc<AtomicU64>,
+}
+
+impl SyncCounters
+{
+    /// Updates the counters from the connection handler.
+    pub(crate) fn store(&self, produced_connection_events: u64, registered_sessions: usize, registered_clients: usize)
+    {
+        self.produced_connection_events.store(produced_connection_events, Ordering::Release);
+        self.registered_sessions.store(registered_sessions as u64, Ordering::Release);
+        self.registered_clients.store(registered_clients as u64, Ordering::Release);
+    }
+
+    /// Makes a snapshot using the server API's counters.
+    pub(crate) fn snapshot(&self, consumed_connection_events: u64, connected_clients: usize) -> ServerSyncState
+    {
+        ServerSyncState{
+            consumed_connection_events,
+            produced_connection_events : self.produced_connection_events.load(Ordering::Acquire),
+            connected_clients          : connected_clients as u64,
+            registered_sessions        : self.registered_sessions.load(Ordering::Acquire),
+            registered_clients         : self.registered_clients.load(Ordering::Acquire),
+        }
+    }
+}
+
+impl Default for SyncCounters
+{
+    fn default() -> Self
+    {
+        Self{
+            produced_connection_events : Arc::new(AtomicU64::new(0u64)),
+            registered_sessions        : Arc::new(AtomicU64::new(0u64)),
+            registered_clients         : Arc::new(AtomicU64::new(0u64)),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
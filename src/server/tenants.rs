@@ -0,0 +1,79 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::collections::HashMap;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Limits for clients connecting with a tenant key.
+#[derive(Debug, Copy, Clone)]
+pub struct TenantConfig
+{
+    /// Max number of concurrent client connections for the tenant. Defaults to unlimited.
+    ///
+    /// Connections are still bounded by [`ServerConfig::max_connections`].
+    pub max_connections: u32,
+    /// Rate limit for messages received from each of the tenant's sessions. Defaults to `None`, which uses
+    /// [`ServerConfig::rate_limit_config`].
+    ///
+    /// The tenant's rate limit is applied once a session authenticates.
+    pub rate_limit_config: Option<RateLimitConfig>,
+}
+
+impl Default for TenantConfig
+{
+    fn default() -> TenantConfig
+    {
+        TenantConfig{
+                max_connections   : u32::MAX,
+                rate_limit_config : None,
+            }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Registry of tenants that can connect to a [`Server`].
+///
+/// Tenants let one server host clients of multiple games or environments. Clients select a tenant with
+/// [`ClientConfig::tenant`](crate::ClientConfig::tenant), and servers with a registry (see [`ServerConfig::tenants`])
+/// close sessions that authenticate with an unknown tenant key.
+#[derive(Debug, Clone, Default)]
+pub struct TenantRegistry
+{
+    tenants: HashMap<String, TenantConfig>,
+}
+
+impl TenantRegistry
+{
+    /// Adds a tenant to the registry.
+    pub fn with_tenant(mut self, tenant: impl Into<String>, config: TenantConfig) -> Self
+    {
+        self.tenants.insert(tenant.into(), config);
+        self
+    }
+
+    /// Gets a tenant's config.
+    pub fn get(&self, tenant: &str) -> Option<&TenantConfig>
+    {
+        self.tenants.get(tenant)
+    }
+
+    /// Number of registered tenants.
+    pub fn len(&self) -> usize
+    {
+        self.tenants.len()
+    }
+
+    /// Tests if there are no registered tenants.
+    pub fn is_empty(&self) -> bool
+    {
+        self.tenants.is_empty()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,168 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Authenticates an auth request at a given duration since `UNIX_EPOCH`.
+///
+/// Auth tokens that were verified recently are not verified again, and verification is moved to the blocking thread
+/// pool if `offload` is set.
+pub(crate) async fn authenticate_with_cache(
+    authenticator : Arc<Authenticator>,
+    token_cache   : VerifiedTokenCache,
+    offload       : bool,
+    request       : AuthRequest,
+    now           : Duration,
+) -> bool
+{
+    let (Authenticator::Token{..}, AuthRequest::Token{ token }) = (&*authenticator, &request)
+    else { return authenticator.authenticate_at(&request, now); };
+    let token = *token;
+
+    // skip verifying the token's signature if it was verified recently
+    if !token.is_expired_at(now) && token_cache.contains(&token) { return true; }
+
+    // verify the token
+    let authenticated = match offload
+    {
+        true =>
+        {
+            tokio::task::spawn_blocking(move || authenticator.authenticate_at(&request, now))
+                .await
+                .unwrap_or(false)
+        }
+        false => authenticator.authenticate_at(&request, now),
+    };
+    if authenticated { token_cache.insert(&token); }
+
+    authenticated
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct VerifiedToken
+{
+    protocol_version: u16,
+    expiry: u64,
+    client_id: u128,
+    /// cache access counter value when this token was last used
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct VerifiedTokenCacheInner
+{
+    /// verified tokens keyed by their signatures
+    tokens: HashMap<[u8; AUTH_TOKEN_SIGNATURE_BYTES], VerifiedToken>,
+    /// counter for ordering token accesses
+    access_counter: u64,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Caches auth tokens with verified signatures so reconnecting clients don't need to be re-verified.
+///
+/// When the cache is full, the least recently used token is evicted.
+#[derive(Debug, Clone)]
+pub(crate) struct VerifiedTokenCache
+{
+    capacity: usize,
+    inner: Arc<Mutex<VerifiedTokenCacheInner>>,
+}
+
+impl VerifiedTokenCache
+{
+    pub(crate) fn new(capacity: usize) -> Self
+    {
+        Self{ capacity, inner: Arc::new(Mutex::new(VerifiedTokenCacheInner::default())) }
+    }
+
+    /// Checks if a token's signature was verified recently.
+    ///
+    /// Only tokens with the same payload as the verified token are matched.
+    pub(crate) fn contains(&self, token: &AuthToken) -> bool
+    {
+        if self.capacity == 0 { return false; }
+        let Ok(mut inner) = self.inner.lock()
+        else { tracing::error!("verified token cache lock error"); return false; };
+        inner.access_counter += 1;
+        let access_counter = inner.access_counter;
+
+        let Some(entry) = inner.tokens.get_mut(&token.signature) else { return false; };
+        if entry.protocol_version != token.protocol_version
+            || entry.expiry != token.expiry
+            || entry.client_id != token.client_id
+        { return false; }
+
+        entry.last_used = access_counter;
+        true
+    }
+
+    /// Adds a token with a verified signature.
+    pub(crate) fn insert(&self, token: &AuthToken)
+    {
+        if self.capacity == 0 { return; }
+        let Ok(mut inner) = self.inner.lock()
+        else { tracing::error!("verified token cache lock error"); return; };
+        inner.access_counter += 1;
+        let access_counter = inner.access_counter;
+
+        // evict the least recently used token if the cache is full
+        if inner.tokens.len() >= self.capacity && !inner.tokens.contains_key(&token.signature)
+        {
+            let oldest = inner.tokens.iter().min_by_key(|(_, entry)| entry.last_used).map(|(signature, _)| *signature);
+            if let Some(oldest) = oldest { inner.tokens.remove(&oldest); }
+        }
+
+        inner.tokens.insert(
+                token.signature,
+                VerifiedToken{
+                    protocol_version : token.protocol_version,
+                    expiry           : token.expiry,
+                    client_id        : token.client_id,
+                    last_used        : access_counter,
+                }
+            );
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Configuration for verifying [`AuthToken`] signatures when clients authenticate with [`Authenticator::Token`].
+///
+/// Defaults to caching 1000 tokens and verifying signatures on the session task.
+#[derive(Debug, Copy, Clone)]
+pub struct TokenVerificationConfig
+{
+    /// Max number of verified tokens to cache. Defaults to 1000 tokens.
+    ///
+    /// Clients that reconnect with a cached token skip signature verification, which reduces CPU load when many
+    /// clients reconnect at once. Tokens are still checked for expiry. Setting this to zero disables the cache.
+    pub cache_capacity: usize,
+    /// Verify signatures on tokio's blocking thread pool instead of the session task. Defaults to `false`.
+    ///
+    /// This prevents verification from stalling other tasks on the server's runtime, at the cost of a thread
+    /// handoff per verification.
+    pub offload: bool,
+}
+
+impl Default for TokenVerificationConfig
+{
+    fn default() -> TokenVerificationConfig
+    {
+        TokenVerificationConfig{
+                cache_capacity : 1_000usize,
+                offload        : false,
+            }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,76 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::collections::{HashMap, HashSet};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wrapper trait for `Fn(ClientId, &str) -> bool`.
+///
+/// Called by servers when a client tries to subscribe to a topic. Returns `true` if the client may subscribe.
+pub trait TopicPermissionFn: Fn(ClientId, &str) -> bool + Send + Sync + 'static {}
+impl<F> TopicPermissionFn for F where F: Fn(ClientId, &str) -> bool + Send + Sync + 'static {}
+
+impl Debug for dyn TopicPermissionFn
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("TopicPermissionFn") }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks the topic subscriptions of connected sessions.
+#[derive(Debug, Default)]
+pub(crate) struct TopicSubscriptions
+{
+    /// subscribed sessions of each topic
+    subscribers: HashMap<String, HashSet<SessionId>>,
+    /// topics of each subscribed session
+    session_topics: HashMap<SessionId, HashSet<String>>,
+}
+
+impl TopicSubscriptions
+{
+    /// Subscribes a session to a topic.
+    pub(crate) fn subscribe(&mut self, session_id: SessionId, topic: String)
+    {
+        self.subscribers.entry(topic.clone()).or_default().insert(session_id);
+        self.session_topics.entry(session_id).or_default().insert(topic);
+    }
+
+    /// Unsubscribes a session from a topic.
+    pub(crate) fn unsubscribe(&mut self, session_id: SessionId, topic: &str)
+    {
+        if let Some(topics) = self.session_topics.get_mut(&session_id)
+        {
+            topics.remove(topic);
+            if topics.is_empty() { self.session_topics.remove(&session_id); }
+        }
+        self.remove_subscriber(session_id, topic);
+    }
+
+    /// Unsubscribes a session from all its topics.
+    pub(crate) fn remove_session(&mut self, session_id: SessionId)
+    {
+        let Some(topics) = self.session_topics.remove(&session_id) else { return; };
+        for topic in topics.iter() { self.remove_subscriber(session_id, topic); }
+    }
+
+    /// Gets the sessions subscribed to a topic.
+    pub(crate) fn subscribers(&self, topic: &str) -> impl Iterator<Item = SessionId> + '_
+    {
+        self.subscribers.get(topic).into_iter().flat_map(|sessions| sessions.iter().copied())
+    }
+
+    fn remove_subscriber(&mut self, session_id: SessionId, topic: &str)
+    {
+        let Some(sessions) = self.subscribers.get_mut(topic) else { return; };
+        sessions.remove(&session_id);
+        if sessions.is_empty() { self.subscribers.remove(topic); }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
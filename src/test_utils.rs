@@ -0,0 +1,191 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Default time to wait for an expected event before panicking.
+const DEFAULT_EXPECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Interval between polls while waiting for events.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A connected [`Server`] and [`Client`] pair for tests.
+///
+/// The server listens on an ephemeral localhost port. Construction blocks until both sides have consumed their
+/// connection reports, so messages can be sent immediately.
+///
+/// Use [`Self::pump()`] and the `expect_*` methods instead of sleeping for a fixed duration and hoping the async
+/// machinery has caught up.
+pub struct TestNetPair<Channel: ChannelPack>
+{
+    /// The server.
+    pub server: Server<Channel>,
+    /// The client.
+    pub client: Client<Channel>,
+
+    /// server events received while pumping
+    server_events: VecDeque<(ClientId, ServerEventFrom<Channel>)>,
+    /// client events received while pumping
+    client_events: VecDeque<ClientEventFrom<Channel>>,
+    /// how long to wait for expected events
+    expect_timeout: Duration,
+}
+
+impl<Channel: ChannelPack> TestNetPair<Channel>
+{
+    /// Makes a new pair using default configs, [`Authenticator::None`], and client id `0`.
+    pub fn new(connect_msg: Channel::ConnectMsg) -> Self
+    {
+        Self::new_with(
+                Authenticator::None,
+                AuthRequest::None{ client_id: 0u128 },
+                ServerConfig::default(),
+                ClientConfig::default(),
+                connect_msg,
+            )
+    }
+
+    /// Makes a new pair with custom authentication and configs.
+    ///
+    /// Panics if the client fails to connect.
+    pub fn new_with(
+        authenticator : Authenticator,
+        auth          : AuthRequest,
+        server_config : ServerConfig,
+        client_config : ClientConfig,
+        connect_msg   : Channel::ConnectMsg,
+    ) -> Self
+    {
+        let server = ServerFactory::<Channel>::new("test").new_server(
+                enfync::builtin::native::TokioHandle::default(),
+                "127.0.0.1:0",
+                AcceptorConfig::Default,
+                authenticator,
+                server_config,
+            );
+        server.wait_until_ready(DEFAULT_EXPECT_TIMEOUT).expect("server failed to start");
+        let client = ClientFactory::<Channel>::new("test").new_client(
+                enfync::builtin::Handle::default(),
+                server.local_url().unwrap(),
+                auth,
+                client_config,
+                connect_msg,
+            );
+
+        let mut pair = Self{
+                server,
+                client,
+                server_events  : VecDeque::default(),
+                client_events  : VecDeque::default(),
+                expect_timeout : DEFAULT_EXPECT_TIMEOUT,
+            };
+
+        // wait for the connection to be established
+        let (_, ServerEvent::Report(ServerReport::Connected(..))) = pair.expect_server_event()
+        else { panic!("expected server connection report"); };
+        let ClientEvent::Report(ClientReport::Connected) = pair.expect_client_event()
+        else { panic!("expected client connection report"); };
+
+        pair
+    }
+
+    /// Sets how long the `expect_*` methods wait before panicking. Defaults to 1 second.
+    pub fn set_expect_timeout(&mut self, timeout: Duration)
+    {
+        self.expect_timeout = timeout;
+    }
+
+    /// Drains all currently-available events from the server and client into internal queues.
+    pub fn poll(&mut self)
+    {
+        while let Some(event) = self.server.next() { self.server_events.push_back(event); }
+        while let Some(event) = self.client.next() { self.client_events.push_back(event); }
+    }
+
+    /// Collects events from the server and client for the given duration.
+    pub fn pump(&mut self, duration: Duration)
+    {
+        let start = std::time::Instant::now();
+        loop
+        {
+            self.poll();
+            if start.elapsed() >= duration { break; }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Gets the next server event if one is available.
+    pub fn next_server_event(&mut self) -> Option<(ClientId, ServerEventFrom<Channel>)>
+    {
+        self.poll();
+        self.server_events.pop_front()
+    }
+
+    /// Gets the next client event if one is available.
+    pub fn next_client_event(&mut self) -> Option<ClientEventFrom<Channel>>
+    {
+        self.poll();
+        self.client_events.pop_front()
+    }
+
+    /// Waits for the next server event.
+    ///
+    /// Panics if no event arrives before the expect timeout.
+    pub fn expect_server_event(&mut self) -> (ClientId, ServerEventFrom<Channel>)
+    {
+        let start = std::time::Instant::now();
+        loop
+        {
+            if let Some(event) = self.next_server_event() { return event; }
+            if start.elapsed() >= self.expect_timeout { panic!("timed out waiting for server event"); }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Waits for the next client event.
+    ///
+    /// Panics if no event arrives before the expect timeout.
+    pub fn expect_client_event(&mut self) -> ClientEventFrom<Channel>
+    {
+        let start = std::time::Instant::now();
+        loop
+        {
+            if let Some(event) = self.next_client_event() { return event; }
+            if start.elapsed() >= self.expect_timeout { panic!("timed out waiting for client event"); }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Pumps for the given duration then panics if any server or client events were received.
+    pub fn expect_no_events(&mut self, duration: Duration)
+    {
+        self.pump(duration);
+        if let Some((client_id, event)) = self.server_events.front()
+        { panic!("unexpected server event from client {client_id}: {event:?}"); }
+        if let Some(event) = self.client_events.front()
+        { panic!("unexpected client event: {event:?}"); }
+    }
+}
+
+impl<Channel: ChannelPack> Debug for TestNetPair<Channel>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        f.debug_struct("TestNetPair")
+            .field("client_id", &self.client.id())
+            .field("queued_server_events", &self.server_events.len())
+            .field("queued_client_events", &self.client_events.len())
+            .finish()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
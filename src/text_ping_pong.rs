@@ -1,14 +1,20 @@
 //local shortcuts
+use crate::*;
 
 //third-party shortcuts
 use wasm_timer::{SystemTime, UNIX_EPOCH};
 
 //standard shortcuts
+use std::sync::Arc;
 use std::time::Duration;
 
 //-------------------------------------------------------------------------------------------------------------------
 
-#[allow(dead_code)]
+/// Heartbeat ping message function used by the websocket backend.
+pub(crate) type PingMsgFn = Arc<dyn Fn(Duration) -> ezsockets::RawMessage + Send + Sync>;
+
+//-------------------------------------------------------------------------------------------------------------------
+
 pub(crate) fn text_ping_fn(timestamp: std::time::Duration) -> ezsockets::RawMessage
 {
     let timestamp = timestamp.as_millis();
@@ -17,13 +23,43 @@ pub(crate) fn text_ping_fn(timestamp: std::time::Duration) -> ezsockets::RawMess
 
 //-------------------------------------------------------------------------------------------------------------------
 
-pub(crate) fn log_ping_pong_latency(timestamp: u128)
+/// Makes a text-based ping message function, with an optional custom payload.
+pub(crate) fn make_text_ping_fn(ping_fn: Option<Arc<dyn HeartbeatPingFn>>) -> PingMsgFn
 {
-    let timestamp = Duration::from_millis(timestamp as u64); // TODO: handle overflow
-    let latency = SystemTime::now()
-        .duration_since(UNIX_EPOCH + timestamp)
-        .unwrap_or_default();
-    tracing::trace!("latency: {}ms", latency.as_millis());
+    match ping_fn
+    {
+        None          => Arc::new(text_ping_fn),
+        Some(ping_fn) => Arc::new(
+                move |timestamp| ezsockets::RawMessage::Text(format!("ping:{}", (ping_fn)(timestamp)))
+            ),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Interprets a pong payload.
+///
+/// The round-trip time is only computed if our pings use the default millisecond timestamp payload (see
+/// [`text_ping_fn()`]), since custom payloads may also parse as numbers.
+pub(crate) fn read_pong(payload: &str, timestamped_pings: bool) -> HeartbeatPong
+{
+    let timestamp = match timestamped_pings
+    {
+        true  => u64::from_str_radix(payload, 10u32).ok(),
+        false => None,
+    };
+    let rtt = timestamp.map(
+            |timestamp|
+            {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .saturating_sub(Duration::from_millis(timestamp))
+            }
+        );
+    if let Some(rtt) = rtt { tracing::trace!("latency: {}ms", rtt.as_millis()); }
+
+    HeartbeatPong{ payload: String::from(payload), rtt }
 }
 
 //-------------------------------------------------------------------------------------------------------------------
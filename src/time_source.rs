@@ -0,0 +1,111 @@
+//local shortcuts
+
+//third-party shortcuts
+use wasm_timer::{Instant, SystemTime, UNIX_EPOCH};
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A source of the current time.
+///
+/// Used by rate limiting, auth-failure tracking, message deduplication, mailbox expiry, and auth token expiry checks.
+/// Socket heartbeats and keepalive timeouts are driven by the websocket backend and don't use this yet (see the
+/// 'TODOs' section of the README).
+pub trait TimeSource: Debug + Send + Sync + 'static
+{
+    /// Gets the current monotonic time as a duration since an arbitrary fixed origin.
+    ///
+    /// This must never go backwards. It is used to measure elapsed time.
+    fn now(&self) -> Duration;
+
+    /// Gets the current wall-clock time as a duration since `UNIX_EPOCH`.
+    ///
+    /// This may jump when the system clock is adjusted. It is only used to check timestamps issued by other machines
+    /// (e.g. auth token expiry).
+    fn wall_time(&self) -> Duration;
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A [`TimeSource`] that reads the system's clocks.
+///
+/// Monotonic time is measured from the first time it is read in the process.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SystemTimeSource;
+
+impl TimeSource for SystemTimeSource
+{
+    fn now(&self) -> Duration
+    {
+        static ORIGIN: OnceLock<Instant> = OnceLock::new();
+        ORIGIN.get_or_init(Instant::now).elapsed()
+    }
+
+    fn wall_time(&self) -> Duration
+    {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A [`TimeSource`] that only changes when advanced manually.
+///
+/// Useful for making time-dependent tests deterministic. Clones share the same clock, which is used as both
+/// monotonic time and wall-clock time.
+#[derive(Debug, Clone)]
+pub struct ManualTimeSource
+{
+    now: Arc<Mutex<Duration>>,
+}
+
+impl ManualTimeSource
+{
+    /// Makes a new manual time source starting at the given duration since `UNIX_EPOCH`.
+    pub fn new(start: Duration) -> Self
+    {
+        Self{ now: Arc::new(Mutex::new(start)) }
+    }
+
+    /// Moves the clock forward.
+    pub fn advance(&self, duration: Duration)
+    {
+        let Ok(mut now) = self.now.lock() else { return; };
+        *now = now.saturating_add(duration);
+    }
+
+    /// Sets the clock to a specific duration since `UNIX_EPOCH`.
+    pub fn set(&self, time: Duration)
+    {
+        let Ok(mut now) = self.now.lock() else { return; };
+        *now = time;
+    }
+}
+
+impl Default for ManualTimeSource
+{
+    /// Starts the clock at the current wall-clock time.
+    fn default() -> Self
+    {
+        Self::new(SystemTimeSource.wall_time())
+    }
+}
+
+impl TimeSource for ManualTimeSource
+{
+    fn now(&self) -> Duration
+    {
+        self.now.lock().map(|now| *now).unwrap_or_default()
+    }
+
+    fn wall_time(&self) -> Duration
+    {
+        self.now()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,205 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use serde::{Deserialize, Serialize};
+
+//standard shortcuts
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A message sent on a numbered transport channel.
+///
+/// Transport channels are opaque to this crate. They are meant to be mapped to the channels of a replication crate
+/// (e.g. `bevy_replicon` channel ids).
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct TransportMsg
+{
+    /// The transport channel.
+    pub channel: u8,
+    /// The message payload.
+    pub bytes: Vec<u8>,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A [`ChannelPack`] for using clients and servers as message transports.
+///
+/// See [`ClientTransport`] and [`ServerTransport`].
+#[derive(Debug, Clone)]
+pub struct TransportChannel;
+
+impl ChannelPack for TransportChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = TransportMsg;
+    type ClientRequest = ();
+    type ServerMsg = TransportMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// An event emitted by a [`ClientTransport`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ClientTransportEvent
+{
+    /// The transport connected to the server.
+    Connected,
+    /// The transport disconnected from the server.
+    ///
+    /// The transport may reconnect depending on the client's [`ClientConfig`](crate::ClientConfig).
+    Disconnected,
+    /// A message was received from the server.
+    Msg(TransportMsg),
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// An event emitted by a [`ServerTransport`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ServerTransportEvent
+{
+    /// A client connected.
+    Connected(ClientId),
+    /// A client disconnected and reconnected within the server's disconnect grace window.
+    ///
+    /// Replication state for the client should be reset as if it disconnected and connected again.
+    Reconnected(ClientId),
+    /// A client disconnected.
+    Disconnected(ClientId),
+    /// A message was received from a client.
+    Msg(ClientId, TransportMsg),
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A client-side message transport.
+///
+/// This is the interface expected by replication crates that need a message backend (e.g. `bevy_replicon`). An
+/// adapter for such a crate should:
+/// - Call [`Self::poll()`] every tick, updating its connection status on `Connected`/`Disconnected` and forwarding
+///   received messages to the replication crate.
+/// - Forward messages produced by the replication crate with [`Self::send()`].
+///
+/// Implemented for [`Client<TransportChannel>`](crate::Client), so replication can use this crate's auth and
+/// reconnect machinery.
+pub trait ClientTransport
+{
+    /// Checks if the transport is connected to the server.
+    fn is_connected(&self) -> bool;
+    /// Checks if the transport has shut down and will not reconnect.
+    fn is_dead(&self) -> bool;
+    /// Sends a message to the server.
+    ///
+    /// Returns `false` if the message failed to send.
+    fn send(&self, msg: TransportMsg) -> bool;
+    /// Gets the next transport event.
+    fn poll(&mut self) -> Option<ClientTransportEvent>;
+}
+
+#[cfg(feature = "client")]
+impl ClientTransport for Client<TransportChannel>
+{
+    fn is_connected(&self) -> bool
+    {
+        Client::is_connected(self)
+    }
+
+    fn is_dead(&self) -> bool
+    {
+        Client::is_dead(self)
+    }
+
+    fn send(&self, msg: TransportMsg) -> bool
+    {
+        !matches!(Client::send(self, msg).status(), MessageStatus::Failed)
+    }
+
+    fn poll(&mut self) -> Option<ClientTransportEvent>
+    {
+        while let Some(event) = self.next()
+        {
+            match event
+            {
+                ClientEvent::Report(ClientReport::Connected) => return Some(ClientTransportEvent::Connected),
+                ClientEvent::Report(ClientReport::Disconnected)      |
+                ClientEvent::Report(ClientReport::ClosedByServer(_)) |
+                ClientEvent::Report(ClientReport::ClosedBySelf)      =>
+                    return Some(ClientTransportEvent::Disconnected),
+                ClientEvent::Msg(msg) => return Some(ClientTransportEvent::Msg(msg)),
+                _ => continue,
+            }
+        }
+
+        None
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// A server-side message transport.
+///
+/// This is the server counterpart of [`ClientTransport`]. An adapter for a replication crate should call
+/// [`Self::poll()`] every tick, and forward messages produced by the replication crate with [`Self::send()`].
+///
+/// Implemented for [`Server<TransportChannel>`](crate::Server). Requests and text messages from clients are
+/// ignored.
+pub trait ServerTransport
+{
+    /// Checks if the transport has shut down.
+    fn is_dead(&self) -> bool;
+    /// Sends a message to a client.
+    ///
+    /// Returns `false` if the message could not be sent.
+    fn send(&self, client_id: ClientId, msg: TransportMsg) -> bool;
+    /// Disconnects a client.
+    fn disconnect(&self, client_id: ClientId);
+    /// Gets the next transport event.
+    fn poll(&mut self) -> Option<ServerTransportEvent>;
+}
+
+#[cfg(feature = "server")]
+impl ServerTransport for Server<TransportChannel>
+{
+    fn is_dead(&self) -> bool
+    {
+        Server::is_dead(self)
+    }
+
+    fn send(&self, client_id: ClientId, msg: TransportMsg) -> bool
+    {
+        Server::send(self, client_id, msg).is_ok()
+    }
+
+    fn disconnect(&self, client_id: ClientId)
+    {
+        let _ = self.disconnect_client(client_id, None);
+    }
+
+    fn poll(&mut self) -> Option<ServerTransportEvent>
+    {
+        while let Some((client_id, event)) = self.next()
+        {
+            match event
+            {
+                ServerEvent::Report(ServerReport::Connected(..)) =>
+                    return Some(ServerTransportEvent::Connected(client_id)),
+                ServerEvent::Report(ServerReport::Reconnected(..)) =>
+                    return Some(ServerTransportEvent::Reconnected(client_id)),
+                ServerEvent::Report(ServerReport::Disconnected) |
+                ServerEvent::Report(ServerReport::DisconnectedWithReason(_)) =>
+                    return Some(ServerTransportEvent::Disconnected(client_id)),
+                ServerEvent::Msg(msg) => return Some(ServerTransportEvent::Msg(client_id, msg)),
+                ServerEvent::Request(token, ()) => self.reject(token),
+                ServerEvent::RequestBatch(token, _) => self.reject(token),
+                _ => continue,
+            }
+        }
+
+        None
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,1047 @@
+//local shortcuts
+use crate::*;
+
+//third-party shortcuts
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+use serde::de::{DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor};
+
+//standard shortcuts
+use core::fmt::Debug;
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Encoding used for messages sent over the network.
+///
+/// Clients advertise their wire format when connecting, and servers reject clients with a different format.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WireFormat
+{
+    /// Compact binary encoding with [`bincode`](https://crates.io/crates/bincode).
+    Bincode,
+    /// JSON encoding with [`serde_json`](https://crates.io/crates/serde_json).
+    ///
+    /// Frames are still sent as websocket binary frames, but their contents are human-readable. This is intended for
+    /// inspecting traffic during development.
+    Json,
+    /// MessagePack encoding with [`rmp_serde`](https://crates.io/crates/rmp-serde). See the 'Wire format' section of
+    /// the README.
+    ///
+    /// Requires the `msgpack` feature. Clients and servers configured with this format fail to build if the feature
+    /// is disabled.
+    MsgPack,
+}
+
+impl WireFormat
+{
+    /// Checks if this format can be used with the crate's enabled features.
+    pub fn is_supported(&self) -> bool
+    {
+        match self
+        {
+            WireFormat::Bincode => true,
+            WireFormat::Json    => true,
+            WireFormat::MsgPack => cfg!(feature = "msgpack"),
+        }
+    }
+}
+
+impl Default for WireFormat
+{
+    /// Defaults to [`WireFormat::Bincode`].
+    fn default() -> WireFormat
+    {
+        WireFormat::Bincode
+    }
+}
+
+/// Convert [`WireFormat`] to a string.
+#[cfg(feature = "client")]
+pub(crate) fn wire_format_as_str(wire_format: WireFormat) -> &'static str
+{
+    match wire_format
+    {
+        WireFormat::Bincode => "b",
+        WireFormat::Json    => "j",
+        WireFormat::MsgPack => "m",
+    }
+}
+
+/// Get a [`WireFormat`] from a string.
+#[cfg(feature = "server")]
+pub(crate) fn wire_format_from_str(wire_format: &str) -> Option<WireFormat>
+{
+    match wire_format
+    {
+        "b" => Some(WireFormat::Bincode),
+        "j" => Some(WireFormat::Json),
+        "m" => Some(WireFormat::MsgPack),
+        _   => None
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Checksum appended to each frame to detect corruption in transit.
+///
+/// The client and server must use the same checksum. Frames that fail verification are discarded and counted in
+/// [`FrameStats::corrupted_frames`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FrameChecksum
+{
+    /// Little-endian CRC32 of the frame, appended after the (possibly encrypted) payload.
+    Crc32,
+}
+
+impl FrameChecksum
+{
+    fn len(&self) -> usize
+    {
+        match self
+        {
+            FrameChecksum::Crc32 => 4,
+        }
+    }
+
+    fn append(&self, frame: &mut Vec<u8>)
+    {
+        match self
+        {
+            FrameChecksum::Crc32 =>
+            {
+                let checksum = crc32fast::hash(frame);
+                frame.extend_from_slice(&checksum.to_le_bytes());
+            }
+        }
+    }
+
+    /// Verifies and strips the checksum from a frame.
+    fn verify<'a>(&self, frame: &'a [u8]) -> Option<&'a [u8]>
+    {
+        if frame.len() < self.len() { return None; }
+        let (payload, checksum) = frame.split_at(frame.len() - self.len());
+
+        let valid = match self
+        {
+            FrameChecksum::Crc32 => crc32fast::hash(payload).to_le_bytes() == checksum,
+        };
+
+        match valid
+        {
+            true  => Some(payload),
+            false => None,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Statistics about frames received by a [`Client`](crate::Client) or [`Server`](crate::Server).
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct FrameStats
+{
+    /// Number of frames discarded because they failed [`FrameChecksum`] verification.
+    pub corrupted_frames: u64,
+}
+
+/// Shared counters for [`FrameStats`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct FrameCounters
+{
+    corrupted_frames: Arc<AtomicU64>,
+}
+
+impl FrameCounters
+{
+    pub(crate) fn stats(&self) -> FrameStats
+    {
+        FrameStats{ corrupted_frames: self.corrupted_frames.load(Ordering::Relaxed) }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Limits applied when decoding frames received from the network.
+///
+/// Frames are always limited to their own byte length, so a malformed length prefix can't trigger an allocation
+/// larger than the frame itself. These limits guard against the remaining ways a small frame can be expensive to
+/// decode.
+#[derive(Debug, Copy, Clone)]
+pub struct DecodeLimits
+{
+    /// Max nesting depth of decoded values. Defaults to 128.
+    pub max_depth: u32,
+    /// Max total number of collection elements (sequence elements and map entries) in one frame. Defaults to
+    /// unbounded.
+    ///
+    /// Struct and tuple fields are not counted, and neither are the bytes of byte sequences (e.g. `Vec<u8>`).
+    ///
+    /// Regardless of this limit, the collections in a frame can't have more elements in total than the frame has
+    /// bytes, so collections that announce an oversized length are rejected as [`DecodeError::Malformed`] before any
+    /// elements are decoded. This means large collections of zero-sized values (e.g. `Vec<()>`) can't be decoded.
+    pub max_elements: u64,
+}
+
+impl Default for DecodeLimits
+{
+    fn default() -> DecodeLimits
+    {
+        DecodeLimits{
+                max_depth    : 128u32,
+                max_elements : u64::MAX,
+            }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Reasons a frame can fail to decode.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DecodeError
+{
+    /// The frame exceeded [`DecodeLimits::max_depth`].
+    TooDeep,
+    /// The frame exceeded [`DecodeLimits::max_elements`].
+    TooManyElements,
+    /// The frame failed [`FrameChecksum`] verification.
+    Corrupted,
+    /// The frame is not a valid encoding of the expected type.
+    Malformed,
+    /// The frame's [`CipherFrame`] counter was not greater than the previous frame's counter, or the frame was from a
+    /// different stream than the first frame received on the connection.
+    Replayed,
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Decodes a frame that a server would receive from a client, then discards the result.
+///
+/// This runs the same decode path as the server, so it is suitable as a `cargo fuzz` target.
+pub fn validate_server_frame<Channel: ChannelPack>(
+    wire_format : WireFormat,
+    bytes       : &[u8],
+    limits      : &DecodeLimits,
+) -> Result<(), DecodeError>
+{
+    decode_frame::<ServerMetaEventFrom<Channel>>(wire_format, bytes, limits).map(|_| ())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Decodes a frame that a client would receive from a server, then discards the result.
+///
+/// This runs the same decode path as the client, so it is suitable as a `cargo fuzz` target.
+pub fn validate_client_frame<Channel: ChannelPack>(
+    wire_format : WireFormat,
+    bytes       : &[u8],
+    limits      : &DecodeLimits,
+) -> Result<(), DecodeError>
+{
+    decode_frame::<ClientMetaEventFrom<Channel>>(wire_format, bytes, limits).map(|_| ())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Decodes the frames that a server would receive from one client connection, then discards the results.
+///
+/// Unlike [`validate_server_frame()`], this verifies [`FrameChecksum`]s, decrypts frames with a [`PayloadCipher`], and
+/// tracks the client's [`CipherFrame`] stream across frames the same way a server connection does. This makes it
+/// suitable for fuzzing the stateful parts of the server's decode path (e.g. replay detection).
+pub struct ServerFrameValidator<Channel: ChannelPack>
+{
+    codec: WireCodec,
+    _phantom: PhantomData<Channel>,
+}
+
+impl<Channel: ChannelPack> ServerFrameValidator<Channel>
+{
+    /// Makes a new validator for one client connection.
+    pub fn new(
+        wire_format : WireFormat,
+        limits      : DecodeLimits,
+        cipher      : Option<Arc<dyn PayloadCipher>>,
+        checksum    : Option<FrameChecksum>,
+    ) -> Self
+    {
+        Self{
+            codec    : WireCodec::new(wire_format, limits, cipher, checksum, FrameDirection::ServerToClient),
+            _phantom : PhantomData,
+        }
+    }
+
+    /// Decodes the next frame received from the client, then discards the result.
+    pub fn validate(&self, bytes: &[u8]) -> Result<(), DecodeError>
+    {
+        self.codec.decode::<ServerMetaEventFrom<Channel>>(bytes).map(|_| ())
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Number of bytes in the [`CipherFrame`] header of an encrypted frame (stream id and counter).
+const CIPHER_HEADER_BYTES: usize = 24;
+
+/// Frame streams of an encrypted connection.
+#[derive(Debug)]
+struct CipherStreams
+{
+    /// id of our stream and the counter of the next frame we send
+    ///
+    /// This is locked while frames are encrypted and handed to the socket, so counters reach the peer in order.
+    send: Mutex<(u128, u64)>,
+    /// id of the peer's stream and the counter of the last frame received from it
+    recv: Mutex<Option<(u128, u64)>>,
+}
+
+/// A cipher with the frame streams of one connection.
+#[derive(Debug, Clone)]
+struct ConnectionCipher
+{
+    cipher: Arc<dyn PayloadCipher>,
+    direction: FrameDirection,
+    streams: Arc<CipherStreams>,
+}
+
+impl ConnectionCipher
+{
+    fn new(cipher: Arc<dyn PayloadCipher>, direction: FrameDirection) -> Self
+    {
+        let streams = CipherStreams{ send: Mutex::new((cipher.new_stream_id(), 0u64)), recv: Mutex::new(None) };
+        Self{ cipher, direction, streams: Arc::new(streams) }
+    }
+
+    fn recv_direction(&self) -> FrameDirection
+    {
+        match self.direction
+        {
+            FrameDirection::ClientToServer => FrameDirection::ServerToClient,
+            FrameDirection::ServerToClient => FrameDirection::ClientToServer,
+        }
+    }
+
+    /// Encrypts a frame, then passes it to `send` while our stream is locked.
+    fn seal<R>(
+        &self,
+        plaintext : Vec<u8>,
+        checksum  : Option<FrameChecksum>,
+        send      : impl FnOnce(Vec<u8>) -> R
+    ) -> Result<R, ()>
+    {
+        let Ok(mut stream) = self.streams.send.lock() else { return Err(()); };
+        let (stream_id, counter) = *stream;
+        let frame = CipherFrame{ direction: self.direction, stream_id, counter };
+        let ciphertext = self.cipher.encrypt(plaintext, &frame)?;
+        stream.1 = counter.checked_add(1).ok_or(())?;
+
+        let mut bytes = Vec::with_capacity(CIPHER_HEADER_BYTES + ciphertext.len());
+        bytes.extend_from_slice(&stream_id.to_le_bytes());
+        bytes.extend_from_slice(&counter.to_le_bytes());
+        bytes.extend_from_slice(&ciphertext);
+        if let Some(checksum) = &checksum { checksum.append(&mut bytes); }
+
+        Ok((send)(bytes))
+    }
+
+    /// Checks a frame's position in the peer's stream, then decrypts it.
+    fn open(&self, bytes: &[u8]) -> Result<Vec<u8>, DecodeError>
+    {
+        if bytes.len() < CIPHER_HEADER_BYTES { return Err(DecodeError::Malformed); }
+        let (header, ciphertext) = bytes.split_at(CIPHER_HEADER_BYTES);
+        let (stream_id, counter) = header.split_at(16);
+        let stream_id = u128::from_le_bytes(stream_id.try_into().map_err(|_| DecodeError::Malformed)?);
+        let counter = u64::from_le_bytes(counter.try_into().map_err(|_| DecodeError::Malformed)?);
+
+        // the peer's stream is the stream of the first frame received on the connection
+        let Ok(mut peer_stream) = self.streams.recv.lock() else { return Err(DecodeError::Malformed); };
+        if let Some((peer_stream_id, last_counter)) = *peer_stream
+        {
+            if stream_id != peer_stream_id || counter <= last_counter { return Err(DecodeError::Replayed); }
+        }
+
+        // only advance the stream if the frame is authentic
+        let frame = CipherFrame{ direction: self.recv_direction(), stream_id, counter };
+        let plaintext = self.cipher.decrypt(ciphertext, &frame).map_err(|_| DecodeError::Malformed)?;
+        *peer_stream = Some((stream_id, counter));
+
+        Ok(plaintext)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Encodes and decodes frames for a connection.
+#[derive(Debug, Clone)]
+pub(crate) struct WireCodec
+{
+    wire_format: WireFormat,
+    decode_limits: DecodeLimits,
+    cipher: Option<ConnectionCipher>,
+    checksum: Option<FrameChecksum>,
+    counters: FrameCounters,
+}
+
+impl WireCodec
+{
+    /// Makes a new codec for frames sent in `direction`.
+    pub(crate) fn new(
+        wire_format   : WireFormat,
+        decode_limits : DecodeLimits,
+        cipher        : Option<Arc<dyn PayloadCipher>>,
+        checksum      : Option<FrameChecksum>,
+        direction     : FrameDirection,
+    ) -> Self
+    {
+        let cipher = cipher.map(|cipher| ConnectionCipher::new(cipher, direction));
+        Self{ wire_format, decode_limits, cipher, checksum, counters: FrameCounters::default() }
+    }
+
+    /// Makes a copy of this codec with new cipher streams, for use by a new connection.
+    ///
+    /// Frame counters are shared with this codec.
+    #[cfg(feature = "server")]
+    pub(crate) fn for_connection(&self) -> Self
+    {
+        let mut codec = self.clone();
+        codec.cipher = self.cipher
+            .as_ref()
+            .map(|cipher| ConnectionCipher::new(cipher.cipher.clone(), cipher.direction));
+        codec
+    }
+
+    /// Forgets the peer's cipher stream so frames from a new connection can be received.
+    ///
+    /// Our own stream continues, and the peer will bind it to the new connection when it receives our next frame.
+    #[cfg(feature = "client")]
+    pub(crate) fn reset_peer_stream(&self)
+    {
+        let Some(cipher) = &self.cipher else { return; };
+        if let Ok(mut peer_stream) = cipher.streams.recv.lock() { *peer_stream = None; }
+    }
+
+    /// Gets the frame counters shared by all clones of this codec.
+    pub(crate) fn counters(&self) -> &FrameCounters
+    {
+        &self.counters
+    }
+
+    /// Serializes a message without encrypting or checksumming it.
+    ///
+    /// Use [`Self::send_serialized()`] to send the message.
+    pub(crate) fn serialize<T: Serialize>(&self, msg: &T) -> Result<Vec<u8>, ()>
+    {
+        encode_frame(self.wire_format, msg)
+    }
+
+    /// Encrypts and checksums a serialized message, then passes it to `send`.
+    ///
+    /// If there is a cipher, then `send` is called while the cipher's stream is locked so frames are handed to the
+    /// socket in the order of their counters.
+    pub(crate) fn send_serialized<R>(&self, bytes: Vec<u8>, send: impl FnOnce(Vec<u8>) -> R) -> Result<R, ()>
+    {
+        if let Some(cipher) = &self.cipher
+        {
+            return cipher.seal(bytes, self.checksum, send);
+        }
+
+        let mut bytes = bytes;
+        if let Some(checksum) = &self.checksum
+        {
+            checksum.append(&mut bytes);
+        }
+        Ok((send)(bytes))
+    }
+
+    /// Serializes, encrypts, then checksums a message, and passes it to `send`.
+    ///
+    /// Returns `Err` if the message couldn't be encoded, otherwise returns the result of `send`.
+    pub(crate) fn send<T: Serialize, R>(&self, msg: &T, send: impl FnOnce(Vec<u8>) -> R) -> Result<R, ()>
+    {
+        self.send_serialized(self.serialize(msg)?, send)
+    }
+
+    /// Verifies, decrypts, then deserializes a message.
+    pub(crate) fn decode<T: for<'de> Deserialize<'de>>(&self, bytes: &[u8]) -> Result<T, DecodeError>
+    {
+        let bytes = match &self.checksum
+        {
+            Some(checksum) =>
+            {
+                let Some(payload) = checksum.verify(bytes)
+                else
+                {
+                    self.counters.corrupted_frames.fetch_add(1u64, Ordering::Relaxed);
+                    return Err(DecodeError::Corrupted);
+                };
+                payload
+            }
+            None => bytes,
+        };
+
+        match &self.cipher
+        {
+            Some(cipher) =>
+            {
+                let plaintext = cipher.open(bytes)?;
+                decode_frame(self.wire_format, &plaintext, &self.decode_limits)
+            }
+            None => decode_frame(self.wire_format, bytes, &self.decode_limits),
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Encodes a frame for sending over the network.
+fn encode_frame<T: Serialize>(wire_format: WireFormat, msg: &T) -> Result<Vec<u8>, ()>
+{
+    match wire_format
+    {
+        WireFormat::Bincode => bincode::DefaultOptions::new().serialize(msg).map_err(|_| ()),
+        WireFormat::Json    => serde_json::to_vec(msg).map_err(|_| ()),
+        WireFormat::MsgPack => encode_msgpack_frame(msg),
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Decodes a frame received from the network.
+fn decode_frame<T: for<'de> Deserialize<'de>>(
+    wire_format : WireFormat,
+    bytes       : &[u8],
+    limits      : &DecodeLimits,
+) -> Result<T, DecodeError>
+{
+    let state = LimitState::new(limits, bytes.len());
+
+    let result = match wire_format
+    {
+        WireFormat::Bincode => decode_bincode_frame(bytes, &state),
+        WireFormat::Json    => decode_json_frame(bytes, &state),
+        WireFormat::MsgPack => decode_msgpack_frame(bytes, &state),
+    };
+
+    result.map_err(|_| state.violation.get().unwrap_or(DecodeError::Malformed))
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn decode_bincode_frame<T: for<'de> Deserialize<'de>>(bytes: &[u8], state: &LimitState) -> Result<T, ()>
+{
+    bincode::DefaultOptions::new()
+        .with_limit(bytes.len() as u64)
+        .deserialize_seed(LimitedSeed{ seed: PhantomData::<T>, state }, bytes)
+        .map_err(|_| ())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn decode_json_frame<T: for<'de> Deserialize<'de>>(bytes: &[u8], state: &LimitState) -> Result<T, ()>
+{
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let value = LimitedSeed{ seed: PhantomData::<T>, state }
+        .deserialize(&mut deserializer)
+        .map_err(|_| ())?;
+
+    // reject trailing characters
+    deserializer.end().map_err(|_| ())?;
+
+    Ok(value)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "msgpack")]
+fn encode_msgpack_frame<T: Serialize>(msg: &T) -> Result<Vec<u8>, ()>
+{
+    rmp_serde::to_vec_named(msg).map_err(|_| ())
+}
+
+#[cfg(not(feature = "msgpack"))]
+fn encode_msgpack_frame<T: Serialize>(_msg: &T) -> Result<Vec<u8>, ()>
+{
+    // note: clients and servers can't be built with an unsupported format, so this is unreachable in practice
+    Err(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "msgpack")]
+fn decode_msgpack_frame<T: for<'de> Deserialize<'de>>(bytes: &[u8], state: &LimitState) -> Result<T, ()>
+{
+    // note: the msgpack reader only allocates as bytes are consumed, so length prefixes can't overrun the frame
+    let mut deserializer = rmp_serde::Deserializer::new(std::io::Cursor::new(bytes));
+    let value = LimitedSeed{ seed: PhantomData::<T>, state }
+        .deserialize(&mut deserializer)
+        .map_err(|_| ())?;
+
+    // reject trailing bytes
+    if deserializer.position() != bytes.len() as u64 { return Err(()); }
+
+    Ok(value)
+}
+
+#[cfg(not(feature = "msgpack"))]
+fn decode_msgpack_frame<T: for<'de> Deserialize<'de>>(_bytes: &[u8], _state: &LimitState) -> Result<T, ()>
+{
+    Err(())
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Tracks limits while decoding a single frame.
+struct LimitState
+{
+    limits: DecodeLimits,
+    /// number of bytes in the frame, which bounds the number of collection elements it can contain
+    frame_len: u64,
+    depth: Cell<u32>,
+    /// collection elements claimed so far, including bytes of byte sequences
+    claimed: Cell<u64>,
+    /// collection elements counted toward [`DecodeLimits::max_elements`]
+    elements: Cell<u64>,
+    /// depth of the latest decoded byte
+    byte_depth: Cell<Option<u32>>,
+    violation: Cell<Option<DecodeError>>,
+}
+
+impl LimitState
+{
+    fn new(limits: &DecodeLimits, frame_len: usize) -> Self
+    {
+        Self{
+            limits     : *limits,
+            frame_len  : frame_len as u64,
+            depth      : Cell::new(0),
+            claimed    : Cell::new(0),
+            elements   : Cell::new(0),
+            byte_depth : Cell::new(None),
+            violation  : Cell::new(None),
+        }
+    }
+
+    fn fail<E: serde::de::Error>(&self, violation: DecodeError) -> E
+    {
+        self.violation.set(Some(violation));
+        E::custom(match violation
+        {
+            DecodeError::TooDeep         => "max decode depth exceeded",
+            DecodeError::TooManyElements => "max decode elements exceeded",
+            DecodeError::Corrupted       => "corrupted frame",
+            DecodeError::Malformed       => "malformed frame",
+            DecodeError::Replayed        => "replayed frame",
+        })
+    }
+
+    fn enter<E: serde::de::Error>(&self) -> Result<DepthGuard<'_>, E>
+    {
+        let depth = self.depth.get() + 1;
+        if depth > self.limits.max_depth { return Err(self.fail(DecodeError::TooDeep)); }
+        self.depth.set(depth);
+        Ok(DepthGuard{ state: self })
+    }
+
+    /// Enters a byte value (`u8` or `i8`), which isn't counted if it is a collection element.
+    fn enter_byte<E: serde::de::Error>(&self) -> Result<DepthGuard<'_>, E>
+    {
+        let guard = self.enter::<E>()?;
+        self.byte_depth.set(Some(self.depth.get()));
+        Ok(guard)
+    }
+
+    /// Claims collection elements, which can't outnumber the bytes in the frame.
+    fn claim_elements<E: serde::de::Error>(&self, count: u64) -> Result<(), E>
+    {
+        let claimed = self.claimed.get().saturating_add(count);
+        if claimed > self.frame_len { return Err(self.fail(DecodeError::Malformed)); }
+        self.claimed.set(claimed);
+        Ok(())
+    }
+
+    /// Counts a collection element toward [`DecodeLimits::max_elements`].
+    fn count_element<E: serde::de::Error>(&self) -> Result<(), E>
+    {
+        let elements = self.elements.get().saturating_add(1);
+        if elements > self.limits.max_elements { return Err(self.fail(DecodeError::TooManyElements)); }
+        self.elements.set(elements);
+        Ok(())
+    }
+
+    /// Claims and counts a decoded collection element.
+    ///
+    /// The element is not counted toward [`DecodeLimits::max_elements`] if it is a byte decoded at `element_depth`.
+    fn count_decoded<E: serde::de::Error>(&self, claimed: bool, element_depth: u32) -> Result<(), E>
+    {
+        if !claimed { self.claim_elements::<E>(1)?; }
+        if self.byte_depth.get() == Some(element_depth) { return Ok(()); }
+        self.count_element()
+    }
+}
+
+struct DepthGuard<'a>
+{
+    state: &'a LimitState,
+}
+
+impl Drop for DepthGuard<'_>
+{
+    fn drop(&mut self)
+    {
+        self.state.depth.set(self.state.depth.get().saturating_sub(1));
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wraps a [`DeserializeSeed`] so the value it produces is decoded with limits.
+struct LimitedSeed<'a, S>
+{
+    seed: S,
+    state: &'a LimitState,
+}
+
+impl<'de, 'a, S: DeserializeSeed<'de>> DeserializeSeed<'de> for LimitedSeed<'a, S>
+{
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    {
+        self.seed.deserialize(LimitedDeserializer{ inner: deserializer, state: self.state })
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wraps a [`Deserializer`] to track the nesting depth of decoded values.
+struct LimitedDeserializer<'a, D>
+{
+    inner: D,
+    state: &'a LimitState,
+}
+
+macro_rules! forward_limited_deserialize
+{
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) =>
+    {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, D::Error>
+            {
+                let _guard = self.state.enter::<D::Error>()?;
+                self.inner.$method($($arg,)* LimitedVisitor{ inner: visitor, state: self.state, collection: false })
+            }
+        )*
+    }
+}
+
+impl<'de, 'a, D: Deserializer<'de>> Deserializer<'de> for LimitedDeserializer<'a, D>
+{
+    type Error = D::Error;
+
+    forward_limited_deserialize!{
+        deserialize_bool();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_option();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+        deserialize_identifier();
+        deserialize_ignored_any();
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error>
+    {
+        // self-describing formats may visit collections
+        let _guard = self.state.enter::<D::Error>()?;
+        self.inner.deserialize_any(LimitedVisitor{ inner: visitor, state: self.state, collection: true })
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error>
+    {
+        let _guard = self.state.enter_byte::<D::Error>()?;
+        self.inner.deserialize_i8(LimitedVisitor{ inner: visitor, state: self.state, collection: false })
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error>
+    {
+        let _guard = self.state.enter_byte::<D::Error>()?;
+        self.inner.deserialize_u8(LimitedVisitor{ inner: visitor, state: self.state, collection: false })
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error>
+    {
+        let _guard = self.state.enter::<D::Error>()?;
+        self.inner.deserialize_seq(LimitedVisitor{ inner: visitor, state: self.state, collection: true })
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error>
+    {
+        let _guard = self.state.enter::<D::Error>()?;
+        self.inner.deserialize_map(LimitedVisitor{ inner: visitor, state: self.state, collection: true })
+    }
+
+    fn is_human_readable(&self) -> bool
+    {
+        self.inner.is_human_readable()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Wraps a [`Visitor`] so nested values are decoded with limits.
+struct LimitedVisitor<'a, V>
+{
+    inner: V,
+    state: &'a LimitState,
+    /// true if sequences and maps visited are collections rather than structs or tuples
+    collection: bool,
+}
+
+macro_rules! forward_limited_visit
+{
+    ($($method:ident($ty:ty);)*) =>
+    {
+        $(
+            fn $method<E: serde::de::Error>(self, v: $ty) -> Result<Self::Value, E>
+            {
+                self.inner.$method(v)
+            }
+        )*
+    }
+}
+
+impl<'de, 'a, V: Visitor<'de>> Visitor<'de> for LimitedVisitor<'a, V>
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result
+    {
+        self.inner.expecting(formatter)
+    }
+
+    forward_limited_visit!{
+        visit_bool(bool);
+        visit_i8(i8);
+        visit_i16(i16);
+        visit_i32(i32);
+        visit_i64(i64);
+        visit_i128(i128);
+        visit_u8(u8);
+        visit_u16(u16);
+        visit_u32(u32);
+        visit_u64(u64);
+        visit_u128(u128);
+        visit_f32(f32);
+        visit_f64(f64);
+        visit_char(char);
+        visit_str(&str);
+        visit_borrowed_str(&'de str);
+        visit_string(String);
+        visit_bytes(&[u8]);
+        visit_borrowed_bytes(&'de [u8]);
+        visit_byte_buf(Vec<u8>);
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E>
+    {
+        self.inner.visit_none()
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E>
+    {
+        self.inner.visit_unit()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    {
+        self.inner.visit_some(LimitedDeserializer{ inner: deserializer, state: self.state })
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    {
+        self.inner.visit_newtype_struct(LimitedDeserializer{ inner: deserializer, state: self.state })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error>
+    {
+        // reject oversized sequences before the visitor can preallocate space for them
+        let claimed = match (self.collection, seq.size_hint())
+        {
+            (true, Some(len)) => { self.state.claim_elements::<A::Error>(len as u64)?; true }
+            _                 => false,
+        };
+        let collection = self.collection;
+        self.inner.visit_seq(LimitedSeqAccess{ inner: seq, state: self.state, collection, claimed })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error>
+    {
+        // reject oversized maps before the visitor can preallocate space for them
+        let claimed = match (self.collection, map.size_hint())
+        {
+            (true, Some(len)) => { self.state.claim_elements::<A::Error>(len as u64)?; true }
+            _                 => false,
+        };
+        let collection = self.collection;
+        self.inner.visit_map(LimitedMapAccess{ inner: map, state: self.state, collection, claimed })
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error>
+    {
+        self.inner.visit_enum(LimitedEnumAccess{ inner: data, state: self.state })
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+struct LimitedSeqAccess<'a, A>
+{
+    inner: A,
+    state: &'a LimitState,
+    /// true if the elements are collection elements
+    collection: bool,
+    /// true if all elements were claimed up front
+    claimed: bool,
+}
+
+impl<'de, 'a, A: SeqAccess<'de>> SeqAccess<'de> for LimitedSeqAccess<'a, A>
+{
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, A::Error>
+    {
+        let element_depth = self.state.depth.get() + 1;
+        self.state.byte_depth.set(None);
+        let element = self.inner.next_element_seed(LimitedSeed{ seed, state: self.state })?;
+        if element.is_some() && self.collection
+        {
+            self.state.count_decoded::<A::Error>(self.claimed, element_depth)?;
+        }
+        Ok(element)
+    }
+
+    fn size_hint(&self) -> Option<usize>
+    {
+        self.inner.size_hint()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+struct LimitedMapAccess<'a, A>
+{
+    inner: A,
+    state: &'a LimitState,
+    /// true if the entries are collection elements
+    collection: bool,
+    /// true if all entries were claimed up front
+    claimed: bool,
+}
+
+impl<'de, 'a, A: MapAccess<'de>> MapAccess<'de> for LimitedMapAccess<'a, A>
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error>
+    {
+        let key = self.inner.next_key_seed(LimitedSeed{ seed, state: self.state })?;
+        if key.is_some() && self.collection
+        {
+            if !self.claimed { self.state.claim_elements::<A::Error>(1)?; }
+            self.state.count_element::<A::Error>()?;
+        }
+        Ok(key)
+    }
+
+    fn next_value_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value, A::Error>
+    {
+        self.inner.next_value_seed(LimitedSeed{ seed, state: self.state })
+    }
+
+    fn size_hint(&self) -> Option<usize>
+    {
+        self.inner.size_hint()
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+struct LimitedEnumAccess<'a, A>
+{
+    inner: A,
+    state: &'a LimitState,
+}
+
+impl<'de, 'a, A: EnumAccess<'de>> EnumAccess<'de> for LimitedEnumAccess<'a, A>
+{
+    type Error = A::Error;
+    type Variant = LimitedVariantAccess<'a, A::Variant>;
+
+    fn variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<(T::Value, Self::Variant), A::Error>
+    {
+        let (value, variant) = self.inner.variant_seed(LimitedSeed{ seed, state: self.state })?;
+        Ok((value, LimitedVariantAccess{ inner: variant, state: self.state }))
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+struct LimitedVariantAccess<'a, A>
+{
+    inner: A,
+    state: &'a LimitState,
+}
+
+impl<'de, 'a, A: VariantAccess<'de>> VariantAccess<'de> for LimitedVariantAccess<'a, A>
+{
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error>
+    {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, A::Error>
+    {
+        self.inner.newtype_variant_seed(LimitedSeed{ seed, state: self.state })
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, A::Error>
+    {
+        let _guard = self.state.enter::<A::Error>()?;
+        self.inner.tuple_variant(len, LimitedVisitor{ inner: visitor, state: self.state, collection: false })
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields  : &'static [&'static str],
+        visitor : V
+    ) -> Result<V::Value, A::Error>
+    {
+        let _guard = self.state.enter::<A::Error>()?;
+        self.inner.struct_variant(fields, LimitedVisitor{ inner: visitor, state: self.state, collection: false })
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
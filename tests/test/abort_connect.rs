@@ -0,0 +1,89 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type _DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type _DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Aborting a client's connection attempt should kill the client immediately.
+#[test]
+fn abort_connect()
+{
+    // prepare tokio runtime for client
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // listen for connections without ever responding, so connection attempts hang mid-handshake
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = url::Url::parse(&format!("ws://{}/ws", listener.local_addr().unwrap())).unwrap();
+
+    // make client
+    let mut client: DemoClient = client_demo_factory().new_client(
+            client_runtime,
+            url,
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    assert!(!client.is_dead());
+    let None = client.next() else { unreachable!() };
+
+
+    // abort connecting
+    client.abort_connect();
+
+    assert!(client.is_dead());
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(death_reason, _))) = client.next()
+    else { unreachable!() };
+    assert_eq!(death_reason, bevy_simplenet::DeathReason::ConnectAborted);
+    let None = client.next() else { unreachable!() };
+
+    // backend events are discarded
+    drop(listener);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let None = client.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
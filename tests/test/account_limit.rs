@@ -0,0 +1,145 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Clients should be limited by the number of connections open for their account.
+#[test]
+fn account_limit()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                account_limit: Some(bevy_simplenet::AccountLimitConfig{
+                    max_connections: 1u32,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+        );
+
+    let websocket_url = server.url();
+    let make_client = |client_id: u128| -> DemoClient
+    {
+        client_demo_factory().new_client(
+                client_runtime.clone(),
+                websocket_url.clone(),
+                bevy_simplenet::AuthRequest::None{ client_id },
+                bevy_simplenet::ClientConfig::default(),
+                ()
+            )
+    };
+    let account_a = 1u128 << 64;
+    let account_b = 2u128 << 64;
+
+
+    // first client of an account connects
+    let mut client_a1 = make_client(account_a);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client_a1.next()
+    else { unreachable!() };
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    assert_eq!(client_id, account_a);
+
+
+    // second client of the same account is closed
+    let mut client_a2 = make_client(account_a + 1);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    assert!(client_a2.is_dead());
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client_a2.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_))) = client_a2.next()
+    else { unreachable!() };
+    let None = server.next() else { unreachable!() };
+
+
+    // clients of other accounts connect
+    let mut client_b1 = make_client(account_b);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client_b1.next()
+    else { unreachable!() };
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    assert_eq!(client_id, account_b);
+
+
+    // account capacity is released when clients disconnect
+    client_a1.close();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { unreachable!() };
+
+    let mut client_a3 = make_client(account_a + 2);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client_a3.next()
+    else { unreachable!() };
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    assert_eq!(client_id, account_a + 2);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
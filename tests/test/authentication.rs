@@ -30,11 +30,12 @@ impl bevy_simplenet::ChannelPack for DemoChannel
     type ClientRequest = ();
     type ServerMsg = DemoServerMsg;
     type ServerResponse = ();
+    type RejectReason = ();
 }
 
 type _DemoServer = bevy_simplenet::Server<DemoChannel>;
 type _DemoClient = bevy_simplenet::Client<DemoChannel>;
-type _DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
 type _DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
 
 fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
@@ -123,3 +124,102 @@ fn authentication()
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn auth_failure_lockout()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server that locks out addresses after one failure
+    let websocket_server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::default(),
+            bevy_simplenet::Authenticator::Secret{secret: (0u128).to_le_bytes()},
+            bevy_simplenet::ServerConfig{
+                auth_failure_config: bevy_simplenet::AuthFailureConfig{
+                    max_failures: 1u32,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+    let make_client = |secret: u128| -> _DemoClient
+    {
+        client_demo_factory().new_client(
+                client_runtime.clone(),
+                websocket_server.url(),
+                bevy_simplenet::AuthRequest::Secret{client_id: 0u128, secret: secret.to_le_bytes()},
+                bevy_simplenet::ClientConfig{
+                    max_initial_connect_attempts: 1usize,
+                    ..Default::default()
+                },
+                DemoConnectMsg(String::from("hello"))
+            )
+    };
+
+    // valid secret connects
+    let client = make_client(0u128);
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+    assert!(!client.is_dead());
+    client.close();
+
+    // invalid secrets fail until the address is locked out
+    let client = make_client(1u128);
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+    assert!(client.is_dead());
+
+    let client = make_client(1u128);
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+    assert!(client.is_dead());
+
+    // valid secret is rejected while locked out
+    let client = make_client(0u128);
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+    assert!(client.is_dead());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn auth_rejected_death_reason()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let websocket_server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::default(),
+            bevy_simplenet::Authenticator::Secret{secret: (0u128).to_le_bytes()},
+            bevy_simplenet::ServerConfig::default(),
+        );
+
+    // make client with the wrong secret
+    let mut websocket_client = client_demo_factory().new_client(
+            client_runtime,
+            websocket_server.url(),
+            bevy_simplenet::AuthRequest::Secret{client_id: 0u128, secret: (1u128).to_le_bytes()},
+            bevy_simplenet::ClientConfig::default(),
+            DemoConnectMsg(String::from("hello"))
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+
+    // the client should die because the server rejected its auth
+    assert!(websocket_client.is_dead());
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = websocket_client.next()
+    else { panic!("client should have connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_))) = websocket_client.next()
+    else { panic!("client should be closed by server"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(death_reason, _))) = websocket_client.next()
+    else { panic!("client should be dead"); };
+    assert_eq!(death_reason, bevy_simplenet::DeathReason::AuthRejected);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
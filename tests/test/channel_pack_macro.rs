@@ -0,0 +1,82 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+bevy_simplenet::channel_pack!{
+    /// demo channel
+    pub Demo
+    {
+        protocol_version = "test";
+        type ConnectMsg = ();
+        type ClientMsg = DemoClientMsg;
+        type ClientRequest = ();
+        type ServerMsg = DemoServerMsg;
+        type ServerResponse = ();
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Channels defined with the macro should be usable with the generated aliases and factories.
+#[test]
+fn channel_pack_macro()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server: DemoServer = demo_server_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+
+    // make client
+    let mut client: DemoClient = demo_client_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+    assert_eq!(client.protocol_version(), "test");
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+
+
+    // send messages
+    client.send(DemoClientMsg(1));
+    server.send(client_id, DemoServerMsg(2)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Msg(DemoClientMsg(1)))) = server.next() else { unreachable!() };
+    let Some(DemoClientEvent::Msg(DemoServerMsg(2))) = client.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
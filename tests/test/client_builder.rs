@@ -0,0 +1,178 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Client builders should reject invalid options.
+#[test]
+fn client_builder_validation()
+{
+    let url = url::Url::parse("ws://127.0.0.1:3000/ws").unwrap();
+    let builder = || -> bevy_simplenet::ClientBuilder<DemoChannel>
+    {
+        client_demo_factory()
+            .builder()
+            .url(url.clone())
+            .auth(bevy_simplenet::AuthRequest::None{ client_id: 0u128 })
+            .connect_msg(())
+    };
+
+    // required options
+    let Err(err) = client_demo_factory().builder().build() else { unreachable!() };
+    assert_eq!(err, bevy_simplenet::ClientBuildError::MissingUrl);
+    let Err(err) = client_demo_factory().builder().url(url.clone()).build() else { unreachable!() };
+    assert_eq!(err, bevy_simplenet::ClientBuildError::MissingAuth);
+
+    // url scheme
+    let Err(err) = builder().url(url::Url::parse("http://127.0.0.1:3000/ws").unwrap()).build()
+    else { unreachable!() };
+    assert_eq!(err, bevy_simplenet::ClientBuildError::InvalidScheme(String::from("http")));
+    let Err(err) = builder().require_tls(true).build() else { unreachable!() };
+    assert_eq!(err, bevy_simplenet::ClientBuildError::TlsRequired);
+
+    // config
+    let Err(err) = builder()
+        .config(bevy_simplenet::ClientConfig{
+            heartbeat_interval : std::time::Duration::from_secs(10),
+            keepalive_timeout  : std::time::Duration::from_secs(5),
+            ..Default::default()
+        })
+        .build()
+    else { unreachable!() };
+    assert_eq!(err, bevy_simplenet::ClientBuildError::HeartbeatNotBeforeKeepalive);
+    let Err(err) = builder()
+        .config(bevy_simplenet::ClientConfig{
+            max_initial_connect_attempts: 0usize,
+            ..Default::default()
+        })
+        .build()
+    else { unreachable!() };
+    assert_eq!(err, bevy_simplenet::ClientBuildError::NoConnectAttempts);
+
+    // wire format
+    #[cfg(not(feature = "msgpack"))]
+    {
+        let Err(err) = builder()
+            .config(bevy_simplenet::ClientConfig{
+                wire_format: bevy_simplenet::WireFormat::MsgPack,
+                ..Default::default()
+            })
+            .build()
+        else { unreachable!() };
+        assert_eq!(err, bevy_simplenet::ClientBuildError::UnsupportedWireFormat(bevy_simplenet::WireFormat::MsgPack));
+    }
+
+    // text messages can't be encrypted
+    #[cfg(feature = "encryption")]
+    {
+        let key = [0u8; bevy_simplenet::PAYLOAD_CIPHER_KEY_BYTES];
+        let Err(err) = builder()
+            .config(bevy_simplenet::ClientConfig{
+                text_messages: true,
+                cipher: Some(std::sync::Arc::new(bevy_simplenet::ChaChaPayloadCipher::new(&key))),
+                ..Default::default()
+            })
+            .build()
+        else { unreachable!() };
+        assert_eq!(err, bevy_simplenet::ClientBuildError::TextWithCipher);
+    }
+
+    // simulated delays can reorder encrypted frames
+    #[cfg(all(feature = "netsim", feature = "encryption"))]
+    {
+        let key = [0u8; bevy_simplenet::PAYLOAD_CIPHER_KEY_BYTES];
+        let Err(err) = builder()
+            .config(bevy_simplenet::ClientConfig{
+                netsim: Some(bevy_simplenet::NetsimConfig::default()),
+                cipher: Some(std::sync::Arc::new(bevy_simplenet::ChaChaPayloadCipher::new(&key))),
+                ..Default::default()
+            })
+            .build()
+        else { unreachable!() };
+        assert_eq!(err, bevy_simplenet::ClientBuildError::NetsimWithCipher);
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// Clients made with builders should connect.
+#[test]
+fn client_builder()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory()
+        .builder()
+        .runtime(server_runtime)
+        .address("127.0.0.1:0")
+        .build()
+        .unwrap();
+
+    // make client
+    let mut client = client_demo_factory()
+        .builder()
+        .runtime(client_runtime)
+        .url(server.url())
+        .auth(bevy_simplenet::AuthRequest::None{ client_id: 0u128 })
+        .connect_msg(())
+        .build()
+        .unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    assert_eq!(client_id, 0u128);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,156 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Group messages should go to the group's members as of the last consumed server event.
+#[test]
+fn client_groups()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+
+    // make clients
+    let mut client1: DemoClient = client_demo_factory().new_client(
+            client_runtime.clone(),
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 1u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+    let mut client2: DemoClient = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 2u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    // unconsumed connections can't be added to groups
+    assert_eq!(server.add_to_group("red", 1u128), Err(bevy_simplenet::ServerError::UnknownClient));
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client1.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client2.next()
+    else { unreachable!() };
+
+
+    // add clients to groups
+    server.add_to_group("red", 1u128).unwrap();
+    server.add_to_group("red", 2u128).unwrap();
+    server.add_to_group("blue", 2u128).unwrap();
+
+    let mut members: Vec<u128> = server.group_members("red").collect();
+    members.sort();
+    assert_eq!(members, vec![1u128, 2u128]);
+
+
+    // send to groups
+    let results = server.send_to_group("red", DemoServerMsg(1));
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|(_, result)| result.is_ok()));
+    assert_eq!(server.send_to_group("blue", DemoServerMsg(2)).len(), 1);
+    assert_eq!(server.send_to_group("green", DemoServerMsg(3)).len(), 0);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Msg(DemoServerMsg(1))) = client1.next() else { unreachable!() };
+    let None = client1.next() else { unreachable!() };
+    let Some(DemoClientEvent::Msg(DemoServerMsg(1))) = client2.next() else { unreachable!() };
+    let Some(DemoClientEvent::Msg(DemoServerMsg(2))) = client2.next() else { unreachable!() };
+    let None = client2.next() else { unreachable!() };
+
+
+    // remove a client from a group
+    assert!(server.remove_from_group("blue", 2u128));
+    assert!(!server.remove_from_group("blue", 2u128));
+    assert_eq!(server.group_members("blue").count(), 0);
+
+
+    // client 1 disconnects
+    client1.close();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    // the client is still a member until its disconnect is consumed
+    assert_eq!(server.group_members("red").count(), 2);
+
+    let Some((1u128, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { unreachable!() };
+
+    let members: Vec<u128> = server.group_members("red").collect();
+    assert_eq!(members, vec![2u128]);
+    let results = server.send_to_group("red", DemoServerMsg(4));
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results[0], (2u128, Ok(_))));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Msg(DemoServerMsg(4))) = client2.next() else { unreachable!() };
+    let None = client2.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,133 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Client messages in latest-only slots should be coalesced, with the newest message always delivered.
+#[test]
+fn client_latest_slots()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+    let websocket_url = server.url();
+
+
+    // make client
+    let mut client = client_demo_factory().new_client(
+            client_runtime,
+            websocket_url,
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+
+
+    // send a burst of messages to two slots
+    let mut signals = Vec::default();
+    for i in 0..100u64
+    {
+        signals.push(client.send_latest(0, DemoClientMsg(i)));
+        signals.push(client.send_latest(1, DemoClientMsg(1000 + i)));
+    }
+
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+
+
+    // messages are either sent or superseded
+    for signal in signals.iter()
+    {
+        match signal.status()
+        {
+            bevy_simplenet::LatestMsgStatus::Sent |
+            bevy_simplenet::LatestMsgStatus::Superseded => (),
+            status => panic!("unexpected status {:?}", status),
+        }
+    }
+    assert_eq!(signals[signals.len() - 2].status(), bevy_simplenet::LatestMsgStatus::Sent);
+    assert_eq!(signals[signals.len() - 1].status(), bevy_simplenet::LatestMsgStatus::Sent);
+
+
+    // the server receives messages in order, ending with the newest message in each slot
+    let mut slot_0 = Vec::default();
+    let mut slot_1 = Vec::default();
+    while let Some((_, DemoServerEvent::Msg(DemoClientMsg(i)))) = server.next()
+    {
+        if i < 1000 { slot_0.push(i); } else { slot_1.push(i); }
+    }
+    assert!(slot_0.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(slot_1.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(slot_0.last(), Some(&99));
+    assert_eq!(slot_1.last(), Some(&1099));
+    let None = server.next() else { unreachable!() };
+
+
+    // latest-only messages fail when disconnected
+    client.close();
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    assert_eq!(client.send_latest(0, DemoClientMsg(100)).status(), bevy_simplenet::LatestMsgStatus::Failed);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
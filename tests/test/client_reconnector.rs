@@ -0,0 +1,161 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::sync::{Arc, Mutex};
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+type DemoReconnectorEvent = bevy_simplenet::ClientReconnectorEvent<DemoChannel>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Reconnectors should replace clients that die, unless the client closed itself.
+#[test]
+fn client_reconnector()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+    let websocket_url = server.url();
+
+
+    // make reconnector
+    let death_reasons = Arc::new(Mutex::new(Vec::<bevy_simplenet::DeathReason>::default()));
+    let death_reasons_clone = death_reasons.clone();
+    let mut reconnector = bevy_simplenet::ClientReconnector::new(
+            client_demo_factory(),
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig{
+                reconnect_interval: std::time::Duration::from_millis(10),
+                ..Default::default()
+            },
+            (),
+            move |death_reason: &bevy_simplenet::DeathReason|
+            {
+                death_reasons_clone.lock().unwrap().push(death_reason.clone());
+                Some(bevy_simplenet::AuthRequest::None{ client_id: 0u128 })
+            }
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoReconnectorEvent::Event(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected))) =
+        reconnector.next()
+    else { unreachable!() };
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
+    else { unreachable!() };
+    assert_eq!(client_id, reconnector.id());
+
+
+    // server closes the client
+    let closure_frame =
+        ezsockets::CloseFrame{
+            code   : ezsockets::CloseCode::Normal,
+            reason : String::from("test")
+        };
+    server.disconnect_client(client_id, Some(closure_frame)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    assert!(reconnector.is_dead());
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { unreachable!() };
+    let Some(DemoReconnectorEvent::Event(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_)))) =
+        reconnector.next()
+    else { unreachable!() };
+    let Some(DemoReconnectorEvent::Event(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(..)))) =
+        reconnector.next()
+    else { unreachable!() };
+
+
+    // the client is replaced
+    let Some(DemoReconnectorEvent::ClientRecreated) = reconnector.next() else { unreachable!() };
+    assert!(!reconnector.is_dead());
+    assert_eq!(*death_reasons.lock().unwrap(), vec![bevy_simplenet::DeathReason::ClosedByServer]);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoReconnectorEvent::Event(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected))) =
+        reconnector.next()
+    else { unreachable!() };
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
+    else { unreachable!() };
+
+
+    // the new client works
+    let _ = reconnector.send(DemoClientMsg(1));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Msg(DemoClientMsg(1)))) = server.next() else { unreachable!() };
+
+
+    // clients that close themselves are not replaced
+    reconnector.close();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    assert!(reconnector.is_dead());
+    let Some(DemoReconnectorEvent::Event(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedBySelf))) =
+        reconnector.next()
+    else { unreachable!() };
+    let Some(DemoReconnectorEvent::Event(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(..)))) =
+        reconnector.next()
+    else { unreachable!() };
+    let None = reconnector.next() else { unreachable!() };
+    assert_eq!(death_reasons.lock().unwrap().len(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
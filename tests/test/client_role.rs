@@ -0,0 +1,139 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    // client 2 is read-only
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test").with_role_fn(
+            |auth: &bevy_simplenet::AuthRequest, _: &()|
+            {
+                match auth.client_id()
+                {
+                    2u128 => bevy_simplenet::ClientRole::ReadOnly,
+                    _     => bevy_simplenet::ClientRole::Full,
+                }
+            }
+        )
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Messages and requests from read-only clients should not reach the server.
+#[test]
+fn client_role_read_only()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+
+    // make clients
+    let mut full_client: DemoClient = client_demo_factory().new_client(
+            client_runtime.clone(),
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 1u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let mut read_only_client: DemoClient = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 2u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((1u128, DemoServerEvent::Report(DemoServerReport::Connected(meta, ())))) = server.next()
+    else { unreachable!() };
+    assert_eq!(meta.role, bevy_simplenet::ClientRole::Full);
+    let Some((2u128, DemoServerEvent::Report(DemoServerReport::Connected(meta, ())))) = server.next()
+    else { unreachable!() };
+    assert_eq!(meta.role, bevy_simplenet::ClientRole::ReadOnly);
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = full_client.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = read_only_client.next()
+    else { unreachable!() };
+
+
+    // read-only client messages are dropped and requests are rejected
+    read_only_client.send(DemoClientMsg(1));
+    let signal = read_only_client.request(());
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let None = server.next() else { unreachable!() };
+    let Some(DemoClientEvent::Reject(request_id)) = read_only_client.next() else { unreachable!() };
+    assert_eq!(request_id, signal.id());
+
+
+    // full clients can send messages
+    full_client.send(DemoClientMsg(2));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((1u128, DemoServerEvent::Msg(DemoClientMsg(2)))) = server.next() else { unreachable!() };
+
+
+    // read-only clients still receive server messages
+    server.send(2u128, DemoServerMsg(3)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Msg(DemoServerMsg(3))) = read_only_client.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
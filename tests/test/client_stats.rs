@@ -0,0 +1,151 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+/// request from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientRequest(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = DemoClientRequest;
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Clients should count reconnects, lost responses, and traffic.
+#[test]
+fn client_stats()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+    // make client
+    let mut client: DemoClient = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig{
+                reconnect_on_server_close : true,
+                reconnect_interval        : std::time::Duration::from_millis(5),
+                ..Default::default()
+            },
+            ()
+        );
+    assert_eq!(client.stats(), bevy_simplenet::ClientStats::default());
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { panic!("server should be connected once client is connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { panic!("client should be connected to server"); };
+    let initial = client.stats();
+    assert_eq!(initial.reconnects, 0);
+    assert_eq!(initial.msgs_sent, 0);
+
+    // client sends a message
+    client.send(DemoClientMsg(1));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Msg(DemoClientMsg(1)))) = server.next()
+    else { panic!("server did not receive client msg"); };
+    let stats = client.stats();
+    assert_eq!(stats.msgs_sent, 1);
+    assert!(stats.bytes_sent > 0);
+
+    // server sends a message
+    server.send(client_id, DemoServerMsg(2)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Msg(DemoServerMsg(2))) = client.next()
+    else { panic!("client did not receive server msg"); };
+    let stats = client.stats();
+    assert_eq!(stats.msgs_received, initial.msgs_received + 1);
+    assert!(stats.bytes_received > initial.bytes_received);
+
+    // client sends a request that the server doesn't respond to
+    let signal = client.request(DemoClientRequest(3));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Request(_, DemoClientRequest(3)))) = server.next()
+    else { panic!("server did not receive client request"); };
+    assert_eq!(client.stats().msgs_sent, 2);
+
+    // server closes client, client reconnects
+    let closure_frame =
+        ezsockets::CloseFrame{
+            code   : ezsockets::CloseCode::Normal,
+            reason : String::from("test")
+        };
+    server.disconnect_client(client_id, Some(closure_frame)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_))) = client.next()
+    else { panic!("client should be closed by server"); };
+    let Some(DemoClientEvent::ResponseLost(request_id)) = client.next()
+    else { panic!("client request should be lost"); };
+    assert_eq!(request_id, signal.id());
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { panic!("client should be reconnected to server"); };
+
+    let stats = client.stats();
+    assert_eq!(stats.reconnects, 1);
+    assert_eq!(stats.response_lost, 1);
+    assert_eq!(stats.send_failed, 0);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,111 @@
+//local shortcuts
+
+//third-party shortcuts
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = ();
+    type ClientRequest = ();
+    type ServerMsg = ();
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn client_close_with_reason()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default(),
+        );
+
+
+    // connect a client
+    let client = client_demo_factory().new_client(
+            client_runtime.clone(),
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { panic!("server should be connected once client is connected"); };
+
+
+    // close the client with a reason
+    client.close_with(
+            ezsockets::CloseFrame{
+                code   : ezsockets::CloseCode::Away,
+                reason : String::from("logging out"),
+            }
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::DisconnectedWithReason(frame)))) = server.next()
+    else { panic!("server should receive the client's close reason"); };
+    assert!(matches!(frame.code, ezsockets::CloseCode::Away));
+    assert_eq!(frame.reason, "logging out");
+    assert_eq!(server.num_connections(), 0u64);
+
+
+    // clients closed without a reason are reported as plain disconnects
+    let client = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { panic!("server should be connected once client is connected"); };
+
+    client.close();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { panic!("server should be disconnected once client is closed"); };
+    let None = server.next() else { panic!("server should receive no more events"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
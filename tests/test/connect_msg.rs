@@ -0,0 +1,128 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+/// client connect message
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct DemoConnectMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = DemoConnectMsg;
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Updated connect messages should be sent when the client reconnects.
+#[test]
+fn connect_msg_refresh()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+    let websocket_url = server.url();
+
+
+    // make client
+    let mut client = client_demo_factory().new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig{
+                reconnect_on_server_close : true,
+                reconnect_interval        : std::time::Duration::from_millis(10),
+                ..Default::default()
+            },
+            DemoConnectMsg(1)
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(_, connect_msg)))) = server.next()
+    else { unreachable!() };
+    assert_eq!(connect_msg, DemoConnectMsg(1));
+
+
+    // updating the connect message does not affect the current connection
+    client.set_connect_msg(DemoConnectMsg(2));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let None = server.next() else { unreachable!() };
+
+
+    // server closes the client
+    let closure_frame =
+        ezsockets::CloseFrame{
+            code   : ezsockets::CloseCode::Normal,
+            reason : String::from("test")
+        };
+    server.disconnect_client(client_id, Some(closure_frame)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+
+
+    // the client reconnects with the updated connect message
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_))) = client.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { unreachable!() };
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(_, connect_msg)))) = server.next()
+    else { unreachable!() };
+    assert_eq!(connect_msg, DemoConnectMsg(2));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,101 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type _DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type _DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test_other_version")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Clients should report progress while retrying their initial connection.
+#[test]
+fn connect_progress()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    // - the client uses a different protocol version, so its connection attempts will be rejected
+    let server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+    // make client
+    let reconnect_interval = std::time::Duration::from_millis(10);
+    let mut client = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig{
+                reconnect_interval,
+                max_initial_connect_attempts: 3usize,
+                ..Default::default()
+            },
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(100));  //wait for async machinery
+
+    // client reports each retry, then dies
+    assert!(client.is_dead());
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connecting{ attempt: 2, next_retry_in })) =
+        client.next()
+    else { unreachable!() };
+    assert_eq!(next_retry_in, reconnect_interval);
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connecting{ attempt: 3, next_retry_in })) =
+        client.next()
+    else { unreachable!() };
+    assert_eq!(next_retry_in, reconnect_interval);
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(death_reason, _))) = client.next()
+    else { unreachable!() };
+    assert_eq!(death_reason, bevy_simplenet::DeathReason::ConnectRejected);
+    let None = client.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
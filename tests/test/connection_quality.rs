@@ -0,0 +1,133 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::sync::{Arc, Mutex};
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+/// client connect message
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoConnectMsg(pub String);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = DemoConnectMsg;
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Connection quality reports should be emitted periodically on both sides.
+#[test]
+fn connection_quality_reports()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // collect quality reports on both sides
+    let server_reports = Arc::new(Mutex::new(Vec::<(u128, bevy_simplenet::ConnectionQuality)>::new()));
+    let client_reports = Arc::new(Mutex::new(Vec::<bevy_simplenet::ConnectionQuality>::new()));
+    let server_reports_clone = server_reports.clone();
+    let client_reports_clone = client_reports.clone();
+
+    // launch websocket server
+    let mut websocket_server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                heartbeat_interval: std::time::Duration::from_millis(10),
+                quality_callback: Some(Arc::new(
+                        move |client_id, quality| server_reports_clone.lock().unwrap().push((client_id, quality))
+                    )),
+                ..Default::default()
+            }
+        );
+    let websocket_url = websocket_server.url();
+
+
+    // make client
+    let mut websocket_client = client_demo_factory().new_client(
+            client_runtime,
+            websocket_url,
+            bevy_simplenet::AuthRequest::None{ client_id: 7u128 },
+            bevy_simplenet::ClientConfig{
+                heartbeat_interval: std::time::Duration::from_millis(10),
+                quality_callback: Some(Arc::new(move |quality| client_reports_clone.lock().unwrap().push(quality))),
+                ..Default::default()
+            },
+            DemoConnectMsg(String::from("hello"))
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = websocket_server.next()
+    else { panic!("server should be connected once client is connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = websocket_client.next()
+    else { panic!("client should be connected to server"); };
+
+
+    // wait for some heartbeats
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    let server_reports = server_reports.lock().unwrap();
+    assert!(server_reports.len() > 1);
+    for (client_id, _) in server_reports.iter()
+    {
+        assert_eq!(*client_id, 7u128);
+    }
+    let (_, last_report) = server_reports.last().unwrap();
+    assert!(last_report.rtt.is_some());
+    assert!(last_report.rtt_var.is_some());
+    assert!(last_report.missed_heartbeats <= 1);
+
+    let client_reports = client_reports.lock().unwrap();
+    assert!(client_reports.len() > 1);
+    let last_report = client_reports.last().unwrap();
+    assert!(last_report.rtt.is_some());
+    assert!(last_report.missed_heartbeats <= 1);
+
+    // quality reports should not produce events
+    let None = websocket_server.next() else { panic!("server should not receive events"); };
+    let None = websocket_client.next() else { panic!("client should not receive events"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,135 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::sync::{Arc, Mutex};
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Connection threshold callbacks should fire when the number of connections crosses a threshold.
+#[test]
+fn connection_thresholds()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let threshold_events = Arc::new(Mutex::new(Vec::<bevy_simplenet::ConnectionThresholdEvent>::default()));
+    let threshold_events_clone = threshold_events.clone();
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                connection_thresholds: Some(bevy_simplenet::ConnectionThresholdConfig{
+                    thresholds: vec![2u64],
+                    callback: Arc::new(
+                        move |event: bevy_simplenet::ConnectionThresholdEvent|
+                        threshold_events_clone.lock().unwrap().push(event)
+                    ),
+                }),
+                ..Default::default()
+            }
+        );
+
+    let websocket_url = server.url();
+    let make_client = |client_id: u128| -> DemoClient
+    {
+        client_demo_factory().new_client(
+                client_runtime.clone(),
+                websocket_url.clone(),
+                bevy_simplenet::AuthRequest::None{ client_id },
+                bevy_simplenet::ClientConfig::default(),
+                ()
+            )
+    };
+
+
+    // first client connects: no threshold crossed
+    let mut client1 = make_client(0u128);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client1.next()
+    else { unreachable!() };
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    assert!(threshold_events.lock().unwrap().is_empty());
+
+
+    // second client connects: threshold reached
+    let mut client2 = make_client(1u128);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client2.next()
+    else { unreachable!() };
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    assert_eq!(
+            *threshold_events.lock().unwrap(),
+            vec![bevy_simplenet::ConnectionThresholdEvent::Reached{ threshold: 2u64, connections: 2u64 }]
+        );
+
+
+    // a client disconnects: threshold receded
+    client1.close();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { unreachable!() };
+    assert_eq!(
+            *threshold_events.lock().unwrap(),
+            vec![
+                bevy_simplenet::ConnectionThresholdEvent::Reached{ threshold: 2u64, connections: 2u64 },
+                bevy_simplenet::ConnectionThresholdEvent::Receded{ threshold: 2u64, connections: 1u64 },
+            ]
+        );
+}
+
+//-------------------------------------------------------------------------------------------------------------------
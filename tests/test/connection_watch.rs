@@ -0,0 +1,95 @@
+//local shortcuts
+
+//third-party shortcuts
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = ();
+    type ClientRequest = ();
+    type ServerMsg = ();
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn connection_watch()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default(),
+        );
+
+    let mut connection_watch = server.connection_watch();
+    let mut pending_watch = server.pending_watch();
+    assert_eq!(*connection_watch.borrow_and_update(), 0u64);
+    assert_eq!(*pending_watch.borrow_and_update(), 0u64);
+
+
+    // connect a client
+    let client = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { panic!("server should be connected once client is connected"); };
+
+    // the connection is published
+    assert!(connection_watch.has_changed().unwrap());
+    assert_eq!(*connection_watch.borrow_and_update(), 1u64);
+    assert_eq!(*connection_watch.borrow(), server.num_connections());
+    assert_eq!(*pending_watch.borrow_and_update(), 0u64);
+
+
+    // disconnect the client
+    client.close();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { panic!("server should be disconnected once client is closed"); };
+
+    // the disconnection is published
+    assert!(connection_watch.has_changed().unwrap());
+    assert_eq!(*connection_watch.borrow_and_update(), 0u64);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
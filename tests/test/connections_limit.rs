@@ -30,6 +30,7 @@ impl bevy_simplenet::ChannelPack for DemoChannel
     type ClientRequest = ();
     type ServerMsg = DemoServerMsg;
     type ServerResponse = ();
+    type RejectReason = ();
 }
 
 type _DemoServer = bevy_simplenet::Server<DemoChannel>;
@@ -121,7 +122,7 @@ fn connections_limit_test(max_connections: u32)
 
     // client should fail to connect
     assert!(websocket_client.is_dead());
-    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(_))) = websocket_client.next()
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(..))) = websocket_client.next()
     else { panic!("client should have failed to connect"); };
     let None = websocket_server.next()
     else { panic!("server should not connect to another client"); };
@@ -178,7 +179,7 @@ fn connections_limit_test(max_connections: u32)
 
     // client should not connect
     assert!(websocket_client.is_dead());
-    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(_))) = websocket_client.next()
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(..))) = websocket_client.next()
     else { panic!("client should be closed by server"); };
     let None = websocket_server.next()
     else { panic!("server should not connect to another client"); };
@@ -0,0 +1,122 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Control messages should be delivered in both directions without touching the channel pack.
+#[test]
+fn control_msgs_roundtrip()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+
+    // make client
+    let mut client: DemoClient = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+
+
+    // client sends a control message
+    let flags = bevy_simplenet::ControlMsg::new("feature_flags", vec![1u8, 0u8]);
+    client.send_control(flags.clone());
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Control(received))) = server.next() else { unreachable!() };
+    assert_eq!(received, flags);
+    let None = server.next() else { unreachable!() };
+
+
+    // server sends a control message
+    let caps = bevy_simplenet::ControlMsg::new("capabilities", b"compression".to_vec());
+    server.send_control(client_id, caps.clone()).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Control(received)) = client.next() else { unreachable!() };
+    assert_eq!(received, caps);
+    let None = client.next() else { unreachable!() };
+
+
+    // control messages are ordered with normal messages
+    server.send(client_id, DemoServerMsg(1)).unwrap();
+    server.send_control(client_id, bevy_simplenet::ControlMsg::new("ping", vec![])).unwrap();
+    server.send(client_id, DemoServerMsg(2)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Msg(DemoServerMsg(1))) = client.next() else { unreachable!() };
+    let Some(DemoClientEvent::Control(received)) = client.next() else { unreachable!() };
+    assert_eq!(received.kind, "ping");
+    let Some(DemoClientEvent::Msg(DemoServerMsg(2))) = client.next() else { unreachable!() };
+    let None = client.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,97 @@
+//local shortcuts
+use bevy_simplenet::{DecodeError, DecodeLimits, WireFormat};
+
+//third-party shortcuts
+use bincode::Options;
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::vec::Vec;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum DemoClientMsg
+{
+    Leaf(Vec<u16>),
+    Node(Box<DemoClientMsg>),
+    Blob{ bytes: Vec<u8>, tag: (u16, u16, u16) },
+}
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = ();
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+/// Encodes a client message the way a client would send it to the server.
+fn encode_client_msg(msg: &DemoClientMsg) -> Vec<u8>
+{
+    // the 'message' variant of the server's meta event
+    let mut frame = vec![1u8];
+    frame.extend(bincode::DefaultOptions::new().serialize(msg).unwrap());
+    frame
+}
+
+fn nested_msg(depth: usize, elements: usize) -> DemoClientMsg
+{
+    let mut msg = DemoClientMsg::Leaf(vec![0u16; elements]);
+    for _ in 0..depth { msg = DemoClientMsg::Node(Box::new(msg)); }
+    msg
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn decode_limits()
+{
+    let validate_server_frame = |frame: &[u8], limits: &DecodeLimits|
+        bevy_simplenet::validate_server_frame::<DemoChannel>(WireFormat::Bincode, frame, limits);
+    let validate_client_frame = |frame: &[u8], limits: &DecodeLimits|
+        bevy_simplenet::validate_client_frame::<DemoChannel>(WireFormat::Bincode, frame, limits);
+    let limits = DecodeLimits{ max_depth: 32, max_elements: 100 };
+
+    // valid frame
+    assert_eq!(validate_server_frame(&encode_client_msg(&nested_msg(10, 100)), &limits), Ok(()));
+
+    // too deep
+    assert_eq!(validate_server_frame(&encode_client_msg(&nested_msg(50, 1)), &limits), Err(DecodeError::TooDeep));
+
+    // too many elements
+    assert_eq!(
+        validate_server_frame(&encode_client_msg(&nested_msg(1, 101)), &limits),
+        Err(DecodeError::TooManyElements)
+    );
+
+    // bytes and struct fields are not counted
+    let blob = DemoClientMsg::Blob{ bytes: vec![0u8; 1000], tag: (0u16, 0u16, 0u16) };
+    assert_eq!(validate_server_frame(&encode_client_msg(&blob), &limits), Ok(()));
+
+    // collections are bounded by the frame length even without an element limit
+    assert_eq!(DecodeLimits::default().max_elements, u64::MAX);
+    let mut frame = encode_client_msg(&nested_msg(0, 0));
+    frame.truncate(frame.len() - 1);
+    frame.extend(bincode::DefaultOptions::new().serialize(&1000u64).unwrap());
+    assert_eq!(validate_server_frame(&frame, &DecodeLimits::default()), Err(DecodeError::Malformed));
+
+    // length prefix larger than the frame
+    let mut frame = vec![1u8, 0u8];
+    frame.extend(bincode::DefaultOptions::new().serialize(&u64::MAX).unwrap());
+    let limits = DecodeLimits{ max_depth: 32, max_elements: u64::MAX };
+    assert_eq!(validate_server_frame(&frame, &limits), Err(DecodeError::Malformed));
+
+    // garbage
+    assert_eq!(validate_server_frame(&[255u8; 16], &limits), Err(DecodeError::Malformed));
+    assert_eq!(validate_client_frame(&[255u8; 16], &limits), Err(DecodeError::Malformed));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
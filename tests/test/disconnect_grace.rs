@@ -0,0 +1,133 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Clients that reconnect within the disconnect grace window should be reported as reconnected.
+#[test]
+fn disconnect_grace()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                disconnect_grace: Duration::from_millis(200),
+                ..Default::default()
+            }
+        );
+
+    let websocket_url = server.url();
+    let make_client = ||
+        client_demo_factory().new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+
+    // connect
+    let client = make_client();
+
+    std::thread::sleep(Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
+    else { unreachable!() };
+
+
+    // reconnect within the grace window
+    client.close();
+    std::mem::drop(client);
+
+    std::thread::sleep(Duration::from_millis(25));  //wait for async machinery
+
+    let None = server.next() else { unreachable!() };
+    let mut client = make_client();
+
+    std::thread::sleep(Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Reconnected(_, ())))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+    server.send(0u128, DemoServerMsg(1)).unwrap();
+
+    std::thread::sleep(Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Msg(DemoServerMsg(1))) = client.next() else { unreachable!() };
+
+
+    // the disconnect is reported after the grace window elapses
+    client.close();
+    std::mem::drop(client);
+
+    std::thread::sleep(Duration::from_millis(25));  //wait for async machinery
+
+    let None = server.next() else { unreachable!() };
+
+    std::thread::sleep(Duration::from_millis(250));  //wait for grace window
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { unreachable!() };
+    let Err(bevy_simplenet::ServerError::UnknownClient) = server.send(0u128, DemoServerMsg(2))
+    else { unreachable!() };
+
+
+    // no more events
+    let None = server.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,147 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+/// response from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerResponse(pub u64);
+
+/// request from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientRequest(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = DemoClientRequest;
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = DemoServerResponse;
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+type DemoRecordedEvent = bevy_simplenet::RecordedServerEvent<DemoChannel>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Recorded server event streams should replay the same events, and collect replies made while replaying.
+#[test]
+fn event_recording()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server with a recorder
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+    let recording_path = std::env::temp_dir().join(format!("simplenet_recording_{}.jsonl", std::process::id()));
+    let None = server.set_recorder(bevy_simplenet::ServerRecorder::create(&recording_path).unwrap())
+    else { unreachable!() };
+
+    let websocket_url = server.url();
+
+
+    // make client
+    let mut client = client_demo_factory().new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+
+
+    // message, request, and their replies are recorded
+    let _ = client.send(DemoClientMsg(1));
+    let _ = client.request(DemoClientRequest(2));
+    let _ = client.request(DemoClientRequest(3));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Msg(DemoClientMsg(1)))) = server.next() else { unreachable!() };
+    server.send(client_id, DemoServerMsg(10)).unwrap();
+    let Some((_, DemoServerEvent::Request(token, DemoClientRequest(2)))) = server.next() else { unreachable!() };
+    server.respond(token, DemoServerResponse(20)).unwrap();
+    let Some((_, DemoServerEvent::Request(token, DemoClientRequest(3)))) = server.next() else { unreachable!() };
+    server.reject(token);
+    let None = server.next() else { unreachable!() };
+
+    server.take_recorder().unwrap().flush().unwrap();
+
+
+    // replay the recording
+    let mut replay = bevy_simplenet::ServerReplay::<DemoChannel>::open(&recording_path).unwrap();
+    let _ = std::fs::remove_file(&recording_path);
+
+    let Some((replay_id, DemoServerEvent::Report(DemoServerReport::Connected(meta, ())))) = replay.next()
+    else { unreachable!() };
+    assert_eq!(replay_id, client_id);
+    assert_eq!(meta.env_type, bevy_simplenet::EnvType::Native);
+    let Some((_, DemoServerEvent::Msg(DemoClientMsg(1)))) = replay.next() else { unreachable!() };
+    replay.send(client_id, DemoServerMsg(10));
+    let Some((_, DemoServerEvent::Request(token, DemoClientRequest(2)))) = replay.next() else { unreachable!() };
+    replay.respond(token, DemoServerResponse(20));
+    let Some((_, DemoServerEvent::Request(token, DemoClientRequest(3)))) = replay.next() else { unreachable!() };
+    drop(token);
+    let None = replay.next() else { unreachable!() };
+
+
+    // replies made while replaying match the recording
+    let recorded = format!("{:?}", replay.recorded_outputs());
+    let replayed = format!("{:?}", replay.outputs());
+    assert_eq!(recorded, replayed);
+    let [
+            DemoRecordedEvent::Sent{ msg: DemoServerMsg(10), .. },
+            DemoRecordedEvent::Responded{ response: DemoServerResponse(20), .. },
+            DemoRecordedEvent::Rejected{ .. },
+        ] = replay.recorded_outputs()
+    else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,205 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+/// message from client, defined by a client binary with the same message types
+mod same
+{
+    #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+    pub struct DemoClientMsg(pub u64);
+}
+
+/// message from client, defined by a mismatched client binary
+mod other
+{
+    #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+    pub struct DemoClientMsg(pub u64, pub u64);
+}
+
+/// recursive message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum DemoTree
+{
+    Node(Box<DemoTree>, Box<DemoTree>),
+    Branch{ children: Vec<DemoTree> },
+    Leaf(Option<u32>),
+}
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = ();
+    type ServerResponse = ();
+    type RejectReason = ();
+
+    fn fingerprint() -> Option<u32> { bevy_simplenet::shape_fingerprint::<Self>().ok() }
+}
+
+#[derive(Debug, Clone)]
+pub struct SameChannel;
+impl bevy_simplenet::ChannelPack for SameChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = same::DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = ();
+    type ServerResponse = ();
+    type RejectReason = ();
+
+    fn fingerprint() -> Option<u32> { bevy_simplenet::shape_fingerprint::<Self>().ok() }
+}
+
+#[derive(Debug, Clone)]
+pub struct OtherChannel;
+impl bevy_simplenet::ChannelPack for OtherChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = other::DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = ();
+    type ServerResponse = ();
+    type RejectReason = ();
+
+    fn fingerprint() -> Option<u32> { bevy_simplenet::shape_fingerprint::<Self>().ok() }
+}
+
+#[derive(Debug, Clone)]
+pub struct UncheckedChannel;
+impl bevy_simplenet::ChannelPack for UncheckedChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = ();
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+#[derive(Debug, Clone)]
+pub struct RecursiveChannel;
+impl bevy_simplenet::ChannelPack for RecursiveChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = ();
+    type ClientRequest = ();
+    type ServerMsg = DemoTree;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Servers should reject clients whose message type fingerprint doesn't match.
+#[test]
+fn fingerprint()
+{
+    // fingerprints depend on the shapes of message types, not their paths
+    assert_eq!(
+        bevy_simplenet::shape_fingerprint::<DemoChannel>(),
+        bevy_simplenet::shape_fingerprint::<SameChannel>()
+    );
+    assert_ne!(
+        bevy_simplenet::shape_fingerprint::<DemoChannel>(),
+        bevy_simplenet::shape_fingerprint::<OtherChannel>()
+    );
+    assert!(bevy_simplenet::shape_fingerprint::<RecursiveChannel>().is_ok());
+
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = bevy_simplenet::ServerFactory::<DemoChannel>::new("test").new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+    let websocket_url = server.url();
+
+
+    // client with mismatched message types should fail to connect
+    let mut other_client = bevy_simplenet::ClientFactory::<OtherChannel>::new("test").new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig{
+                max_initial_connect_attempts: 1usize,
+                ..Default::default()
+            },
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    assert!(other_client.is_dead());
+    let Some(bevy_simplenet::ClientEventFrom::<OtherChannel>::Report(
+            bevy_simplenet::ClientReport::IsDead(death_reason, _)
+        )) = other_client.next()
+    else { unreachable!() };
+    assert_eq!(death_reason, bevy_simplenet::DeathReason::ConnectRejected);
+    let None = server.next() else { unreachable!() };
+
+
+    // clients without a fingerprint should fail to connect
+    let mut unchecked_client = bevy_simplenet::ClientFactory::<UncheckedChannel>::new("test").new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 1u128 },
+            bevy_simplenet::ClientConfig{
+                max_initial_connect_attempts: 1usize,
+                ..Default::default()
+            },
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    assert!(unchecked_client.is_dead());
+    let Some(bevy_simplenet::ClientEventFrom::<UncheckedChannel>::Report(
+            bevy_simplenet::ClientReport::IsDead(death_reason, _)
+        )) = unchecked_client.next()
+    else { unreachable!() };
+    assert_eq!(death_reason, bevy_simplenet::DeathReason::ConnectRejected);
+    let None = server.next() else { unreachable!() };
+
+
+    // client with matching message types should connect
+    let mut client = bevy_simplenet::ClientFactory::<DemoChannel>::new("test").new_client(
+            client_runtime,
+            websocket_url,
+            bevy_simplenet::AuthRequest::None{ client_id: 2u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(bevy_simplenet::ClientEventFrom::<DemoChannel>::Report(bevy_simplenet::ClientReport::Connected)) =
+        client.next()
+    else { unreachable!() };
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
+    else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,145 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::sync::Arc;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+/// Closes a client's connection from the server.
+fn disconnect(server: &DemoServer, client_id: bevy_simplenet::ClientId)
+{
+    let closure_frame =
+        ezsockets::CloseFrame{
+            code   : ezsockets::CloseCode::Normal,
+            reason : String::from("test")
+        };
+    server.disconnect_client(client_id, Some(closure_frame)).unwrap();
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Servers should report clients that reconnect too often.
+#[test]
+fn flapping_client()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let time_source = Arc::new(bevy_simplenet::ManualTimeSource::new(Duration::from_secs(100)));
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                flapping: Some(bevy_simplenet::FlappingConfig{ window: Duration::from_secs(60), max_reconnects: 1 }),
+                time_source: time_source.clone(),
+                ..Default::default()
+            }
+        );
+
+    // make client
+    let mut client: DemoClient = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig{
+                reconnect_on_server_close : true,
+                reconnect_interval        : Duration::from_millis(5),
+                ..Default::default()
+            },
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { panic!("server should be connected once client is connected"); };
+
+    // the first reconnect is allowed
+    disconnect(&server, client_id);
+
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { panic!("server should be disconnected after client is disconnected"); };
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { panic!("server should be connected once client reconnects"); };
+    let None = server.next() else { panic!("client should not be flapping yet"); };
+
+    // the second reconnect in the window is reported
+    disconnect(&server, client_id);
+
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { panic!("server should be disconnected after client is disconnected"); };
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { panic!("server should be connected once client reconnects"); };
+    let Some((flapping_id, DemoServerEvent::Report(DemoServerReport::FlappingClient(reported_id, 2)))) = server.next()
+    else { panic!("client should be flapping"); };
+    assert_eq!(flapping_id, client_id);
+    assert_eq!(reported_id, client_id);
+
+    // reconnects outside the window are not counted
+    time_source.advance(Duration::from_secs(60));
+    disconnect(&server, client_id);
+
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { panic!("server should be disconnected after client is disconnected"); };
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { panic!("server should be connected once client reconnects"); };
+    let None = server.next() else { panic!("client should not be flapping after the window"); };
+
+    // the client is unaffected
+    assert!(client.is_connected());
+    while let Some(_) = client.next() {}
+}
+
+//-------------------------------------------------------------------------------------------------------------------
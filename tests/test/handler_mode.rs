@@ -0,0 +1,98 @@
+//local shortcuts
+
+//third-party shortcuts
+use enfync::Handle;
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = ();
+    type ClientRequest = ();
+    type ServerMsg = ();
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Servers without a listener should serve clients through a user-driven listener.
+#[test]
+fn handler_mode()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // prepare our own listener
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let public_url = url::Url::parse(&format!("ws://{}/ws", address)).unwrap();
+
+    // make websocket server without a listener
+    let (mut server, router) = server_demo_factory().new_handler(
+            server_runtime.clone(),
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{ public_url: Some(public_url.clone()), ..Default::default() },
+        );
+    server.wait_until_ready(std::time::Duration::ZERO).unwrap();
+
+    // the server only knows its public url
+    assert_eq!(server.url(), public_url);
+    assert_eq!(server.local_url(), None);
+
+    // serve the server's router with our own listener
+    let make_service = router.into_make_service_with_connect_info::<std::net::SocketAddr>();
+    server_runtime.spawn(
+            async move {
+                axum_server::from_tcp(listener).serve(make_service).await.unwrap();
+            }
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    // connect a client
+    let mut client = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { panic!("server should be connected once client is connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { panic!("client should be connected to server"); };
+
+    // stopping accepting connections only marks the server as draining
+    server.stop_accepting();
+    assert!(server.is_draining());
+    assert!(!server.is_dead());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,156 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type _DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+/// Raw websocket client that never authenticates, so its session stays pending.
+struct IdleClient
+{
+    closed: Arc<AtomicBool>,
+}
+
+#[async_trait::async_trait]
+impl ezsockets::ClientExt for IdleClient
+{
+    type Call = ();
+
+    async fn on_text(&mut self, _text: String) -> Result<(), ezsockets::Error> { Ok(()) }
+    async fn on_binary(&mut self, _bytes: Vec<u8>) -> Result<(), ezsockets::Error> { Ok(()) }
+    async fn on_call(&mut self, _call: ()) -> Result<(), ezsockets::Error> { Ok(()) }
+
+    async fn on_close(
+        &mut self,
+        _close_frame: Option<ezsockets::CloseFrame>
+    ) -> Result<ezsockets::client::ClientCloseMode, ezsockets::Error>
+    {
+        self.closed.store(true, Ordering::Release);
+        Ok(ezsockets::client::ClientCloseMode::Close)
+    }
+}
+
+/// Connects an idle client to the server. Returns a flag that is set when the server closes the client.
+fn connect_idle_client(
+    runtime_handle : enfync::builtin::Handle,
+    url            : url::Url,
+) -> (ezsockets::Client<IdleClient>, Arc<AtomicBool>)
+{
+    let closed = Arc::new(AtomicBool::new(false));
+    let closed_clone = closed.clone();
+    let client_config = ezsockets::ClientConfig::new(url)
+        .query_parameter("v", "test")
+        .query_parameter("t", bevy_simplenet::env_type_as_str(bevy_simplenet::EnvType::Native))
+        .query_parameter("f", "b");
+    let (client, _) = ezsockets::connect_with(
+            move |_| IdleClient{ closed: closed_clone },
+            client_config,
+            ezsockets::ClientConnectorTokio::from(runtime_handle),
+        );
+
+    (client, closed)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Handshake stats should count authenticated sessions and sessions that time out before authenticating.
+#[test]
+fn handshake_stats()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                auth_timeout: std::time::Duration::from_millis(50),
+                ..Default::default()
+            }
+        );
+
+    let websocket_url = server.url();
+    assert_eq!(server.handshake_stats(), bevy_simplenet::HandshakeStats::default());
+
+
+    // client authenticates
+    let _client: DemoClient = client_demo_factory().new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+
+    let stats = server.handshake_stats();
+    assert_eq!(stats.authenticated, 1);
+    assert_eq!(stats.auth_timeouts, 0);
+    assert_eq!(stats.auth_delay_histogram.iter().sum::<u64>(), 1);
+    assert!(stats.total_auth_delay < std::time::Duration::from_millis(50));
+
+
+    // idle client times out
+    let (_idle_client, idle_closed) = connect_idle_client(client_runtime.clone(), websocket_url.clone());
+
+    std::thread::sleep(std::time::Duration::from_millis(100));  //wait for auth timeout
+
+    assert!(idle_closed.load(Ordering::Acquire));
+
+    let stats = server.handshake_stats();
+    assert_eq!(stats.authenticated, 1);
+    assert_eq!(stats.auth_timeouts, 1);
+    assert_eq!(stats.auth_delay_histogram.iter().sum::<u64>(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,116 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::io::{Read, Write};
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+/// Sends an HTTP GET request to the server and returns the response's status line.
+fn http_get_status(address: std::net::SocketAddr, path: &str) -> String
+{
+    let mut stream = std::net::TcpStream::connect(address).unwrap();
+    write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n", path, address).unwrap();
+
+    let mut response = String::default();
+    stream.read_to_string(&mut response).unwrap();
+    response.lines().next().unwrap_or_default().to_string()
+}
+
+/// Gets the socket address of a server.
+fn server_address(server: &bevy_simplenet::Server<DemoChannel>) -> std::net::SocketAddr
+{
+    server.url().socket_addrs(|| None).unwrap()[0]
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Health routes should report the server's liveness and readiness.
+#[test]
+fn health_routes()
+{
+    // prepare tokio runtime for server
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+
+    // launch websocket server
+    let server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{ health_routes: true, ..Default::default() }
+        );
+    let address = server_address(&server);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    // the server is live and ready
+    assert!(http_get_status(address, bevy_simplenet::HEALTH_ROUTE).contains("200"));
+    assert!(http_get_status(address, bevy_simplenet::READY_ROUTE).contains("200"));
+
+    // draining servers are live but not ready
+    server.set_draining(true);
+    assert!(server.is_draining());
+    assert!(http_get_status(address, bevy_simplenet::HEALTH_ROUTE).contains("200"));
+    assert!(http_get_status(address, bevy_simplenet::READY_ROUTE).contains("503"));
+
+    // the server is ready again after draining stops
+    server.set_draining(false);
+    assert!(http_get_status(address, bevy_simplenet::READY_ROUTE).contains("200"));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// Health routes should not be added unless enabled.
+#[test]
+fn health_routes_disabled()
+{
+    // prepare tokio runtime for server
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+
+    // launch websocket server
+    let server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+    let address = server_address(&server);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    assert!(http_get_status(address, bevy_simplenet::HEALTH_ROUTE).contains("404"));
+    assert!(http_get_status(address, bevy_simplenet::READY_ROUTE).contains("404"));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
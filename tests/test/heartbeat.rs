@@ -0,0 +1,300 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::sync::{Arc, Mutex};
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+/// client connect message
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoConnectMsg(pub String);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = DemoConnectMsg;
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Raw websocket client that records text frames from the server, and connects as a WASM client.
+struct WasmTextRecorder
+{
+    texts: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl ezsockets::ClientExt for WasmTextRecorder
+{
+    type Call = ();
+
+    async fn on_text(&mut self, text: String) -> Result<(), ezsockets::Error>
+    {
+        self.texts.lock().unwrap().push(text);
+        Ok(())
+    }
+    async fn on_binary(&mut self, _bytes: Vec<u8>) -> Result<(), ezsockets::Error> { Ok(()) }
+    async fn on_call(&mut self, _call: ()) -> Result<(), ezsockets::Error> { Ok(()) }
+}
+
+/// Connects a WASM text recorder to the server. Returns the text frames received from the server.
+fn connect_wasm_text_recorder(
+    runtime_handle : enfync::builtin::Handle,
+    url            : url::Url,
+) -> (ezsockets::Client<WasmTextRecorder>, Arc<Mutex<Vec<String>>>)
+{
+    let texts = Arc::new(Mutex::new(Vec::new()));
+    let texts_clone = texts.clone();
+    let client_config = ezsockets::ClientConfig::new(url)
+        .query_parameter("v", "test")
+        .query_parameter("t", bevy_simplenet::env_type_as_str(bevy_simplenet::EnvType::Wasm))
+        .query_parameter("f", "b");
+    let (client, _) = ezsockets::connect_with(
+            move |_| WasmTextRecorder{ texts: texts_clone },
+            client_config,
+            ezsockets::ClientConnectorTokio::from(runtime_handle),
+        );
+
+    (client, texts)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn heartbeat_callbacks()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // collect pongs on both sides
+    let server_pongs = Arc::new(Mutex::new(Vec::<(u128, bevy_simplenet::HeartbeatPong)>::new()));
+    let client_pongs = Arc::new(Mutex::new(Vec::<bevy_simplenet::HeartbeatPong>::new()));
+    let server_pongs_clone = server_pongs.clone();
+    let client_pongs_clone = client_pongs.clone();
+
+    // launch websocket server
+    // - the server uses the default ping payload
+    let mut websocket_server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                heartbeat_interval: std::time::Duration::from_millis(10),
+                heartbeat_callback: Some(Arc::new(
+                        move |client_id, pong| server_pongs_clone.lock().unwrap().push((client_id, pong))
+                    )),
+                ..Default::default()
+            }
+        );
+    let websocket_url = websocket_server.url();
+
+
+    // make client
+    // - the client uses a custom ping payload, which should not be mistaken for a timestamp
+    let mut websocket_client = client_demo_factory().new_client(
+            client_runtime,
+            websocket_url,
+            bevy_simplenet::AuthRequest::None{ client_id: 7u128 },
+            bevy_simplenet::ClientConfig{
+                heartbeat_interval: std::time::Duration::from_millis(10),
+                heartbeat_ping_fn: Some(Arc::new(|_| String::from("12345"))),
+                heartbeat_callback: Some(Arc::new(move |pong| client_pongs_clone.lock().unwrap().push(pong))),
+                ..Default::default()
+            },
+            DemoConnectMsg(String::from("hello"))
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = websocket_server.next()
+    else { panic!("server should be connected once client is connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = websocket_client.next()
+    else { panic!("client should be connected to server"); };
+
+
+    // wait for some heartbeats
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    let server_pongs = server_pongs.lock().unwrap();
+    assert!(server_pongs.len() > 0);
+    for (client_id, pong) in server_pongs.iter()
+    {
+        assert_eq!(*client_id, 7u128);
+        assert!(pong.rtt.is_some());
+    }
+
+    let client_pongs = client_pongs.lock().unwrap();
+    assert!(client_pongs.len() > 0);
+    for pong in client_pongs.iter()
+    {
+        assert_eq!(pong.payload, "12345");
+        assert!(pong.rtt.is_none());
+    }
+
+    // heartbeats should not produce events
+    let None = websocket_server.next() else { panic!("server should not receive events"); };
+    let None = websocket_client.next() else { panic!("client should not receive events"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// Heartbeat pings should be replaced with unsolicited pongs while the client is sending messages.
+#[test]
+fn suppress_active_heartbeats()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // collect pongs on the client
+    let client_pongs = Arc::new(Mutex::new(Vec::<bevy_simplenet::HeartbeatPong>::new()));
+    let client_pongs_clone = client_pongs.clone();
+
+    // launch websocket server
+    let mut websocket_server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                rate_limit_config: bevy_simplenet::RateLimitConfig{
+                    period    : std::time::Duration::from_millis(100),
+                    max_count : 1000,
+                },
+                ..Default::default()
+            }
+        );
+    let websocket_url = websocket_server.url();
+
+
+    // make client
+    // - the heartbeat callback makes pongs visible to the client
+    let mut websocket_client = client_demo_factory().new_client(
+            client_runtime,
+            websocket_url,
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig{
+                heartbeat_interval: std::time::Duration::from_millis(20),
+                heartbeat_callback: Some(Arc::new(move |pong| client_pongs_clone.lock().unwrap().push(pong))),
+                suppress_active_heartbeats: true,
+                ..Default::default()
+            },
+            DemoConnectMsg(String::from("hello"))
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = websocket_server.next()
+    else { panic!("server should be connected once client is connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = websocket_client.next()
+    else { panic!("client should be connected to server"); };
+
+
+    // send messages for a while
+    // - pongs for heartbeats sent before the messages started may still arrive, so ignore them
+    let send_for = |duration: std::time::Duration|
+    {
+        let start = std::time::Instant::now();
+        while start.elapsed() < duration
+        {
+            websocket_client.send(DemoClientMsg(0));
+            std::thread::sleep(std::time::Duration::from_millis(3));
+        }
+    };
+    send_for(std::time::Duration::from_millis(30));
+    client_pongs.lock().unwrap().clear();
+    send_for(std::time::Duration::from_millis(100));
+
+    assert_eq!(client_pongs.lock().unwrap().len(), 0);
+
+
+    // heartbeats resume once the client is idle
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    assert!(client_pongs.lock().unwrap().len() > 0);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// WASM clients should receive websocket ping frames instead of text pings if the server allows it.
+#[test]
+fn wasm_ping_frames()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    let make_server = |wasm_ping_frames: bool|
+    {
+        server_demo_factory().new_server(
+                server_runtime.clone(),
+                "127.0.0.1:0",
+                bevy_simplenet::AcceptorConfig::Default,
+                bevy_simplenet::Authenticator::None,
+                bevy_simplenet::ServerConfig{
+                    heartbeat_interval: std::time::Duration::from_millis(10),
+                    wasm_ping_frames,
+                    ..Default::default()
+                }
+            )
+    };
+
+    // text pings by default
+    let text_server = make_server(false);
+    let (_text_client, texts) = connect_wasm_text_recorder(client_runtime.clone(), text_server.url());
+
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for some heartbeats
+
+    let received = texts.lock().unwrap().clone();
+    assert!(received.len() > 0);
+    assert!(received.iter().all(|text| text.starts_with("ping:")));
+
+    // ping frames if enabled
+    let frame_server = make_server(true);
+    let (_frame_client, texts) = connect_wasm_text_recorder(client_runtime.clone(), frame_server.url());
+
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for some heartbeats
+
+    assert_eq!(texts.lock().unwrap().len(), 0);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
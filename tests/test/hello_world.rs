@@ -30,6 +30,7 @@ impl bevy_simplenet::ChannelPack for DemoChannel
     type ClientRequest = ();
     type ServerMsg = DemoServerMsg;
     type ServerResponse = ();
+    type RejectReason = ();
 }
 
 type _DemoServer = bevy_simplenet::Server<DemoChannel>;
@@ -96,11 +97,15 @@ fn hello_world()
 
     std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
 
-    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(_, connect_msg)))) = websocket_server.next()
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(meta, connect_msg)))) =
+        websocket_server.next()
     else { panic!("server should be connected once client is connected"); };
     let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = websocket_client.next()
     else { panic!("client should be connected to server"); };
     assert_eq!(connect_msg.0, connect_msg1.0);
+    assert_eq!(meta.env_type, websocket_client.env_type());
+    assert_eq!(meta.tenant, None);
+    assert_eq!(websocket_client.protocol_version(), "test");
     assert_eq!(websocket_server.num_connections(), 1u64);
 
 
@@ -122,7 +127,7 @@ fn hello_world()
     // send message: server -> client
     tracing::info!("ws hello world test: server sending msg...");
     let server_val = 24;
-    websocket_server.send(client_id, DemoServerMsg(server_val));
+    websocket_server.send(client_id, DemoServerMsg(server_val)).unwrap();
 
     std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
 
@@ -138,7 +143,7 @@ fn hello_world()
             code   : ezsockets::CloseCode::Normal,
             reason : String::from("test")
         };
-    websocket_server.disconnect_client(client_id, Some(closure_frame));
+    websocket_server.disconnect_client(client_id, Some(closure_frame)).unwrap();
 
     std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
 
@@ -190,8 +195,9 @@ fn hello_world()
     else { panic!("server should be disconnected after client is disconnected (by client)"); };
     let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedBySelf)) = websocket_client.next()
     else { panic!("client should have closed itself"); };
-    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(_))) = websocket_client.next()
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(death_reason, _))) = websocket_client.next()
     else { panic!("client should be closed by server"); };
+    assert_eq!(death_reason, bevy_simplenet::DeathReason::ClosedBySelf);
     assert_eq!(client_id, dc_client_id);
 
 
@@ -0,0 +1,145 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Servers should report id collisions, and clients may pick a new id.
+#[test]
+fn id_collision()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+    // make first client
+    let mut client1: DemoClient = client_demo_factory().new_client(
+            client_runtime.clone(),
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { panic!("server should be connected once client is connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client1.next()
+    else { panic!("client should be connected to server"); };
+
+    // make second client with the same id that picks a new id
+    let mut client2: DemoClient = client_demo_factory().new_client(
+            client_runtime.clone(),
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig{
+                reconnect_interval : std::time::Duration::from_millis(5),
+                disambiguate_id    : true,
+                ..Default::default()
+            },
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::IdCollision))) = server.next()
+    else { panic!("server should report the id collision"); };
+    let Some((new_id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { panic!("server should be connected once client reconnects with a new id"); };
+    assert_ne!(new_id, 0u128);
+    assert_eq!(client2.id(), new_id);
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client2.next()
+    else { panic!("client should connect before the server closes it"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_))) = client2.next()
+    else { panic!("client should be closed by server"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IdChanged{ previous: 0u128, current })) =
+        client2.next()
+    else { panic!("client should pick a new id"); };
+    assert_eq!(current, new_id);
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client2.next()
+    else { panic!("client should reconnect with its new id"); };
+
+    // make third client with the same id that doesn't pick a new id
+    let mut client3: DemoClient = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig{ reconnect_on_server_close: false, ..Default::default() },
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::IdCollision))) = server.next()
+    else { panic!("server should report the id collision"); };
+    let None = server.next() else { panic!("server should receive no more events"); };
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client3.next()
+    else { panic!("client should connect before the server closes it"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_))) = client3.next()
+    else { panic!("client should be closed by server"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(death_reason, _))) = client3.next()
+    else { panic!("client should die"); };
+    assert_eq!(death_reason, bevy_simplenet::DeathReason::IdCollision);
+    assert_eq!(client3.id(), 0u128);
+
+    // the first client is unaffected
+    let None = client1.next() else { panic!("first client should receive no more events"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,203 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::sync::{Arc, Mutex};
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Stalls the client's session while handling a message, so heartbeats from the client go unanswered.
+#[derive(Debug)]
+struct Staller;
+
+impl bevy_simplenet::ServerMiddleware<DemoChannel> for Staller
+{
+    fn on_msg(&self, _: bevy_simplenet::ClientId, msg: DemoClientMsg) -> bevy_simplenet::MiddlewareAction<DemoClientMsg>
+    {
+        std::thread::sleep(std::time::Duration::from_millis(msg.0));
+        bevy_simplenet::MiddlewareAction::Continue(msg)
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Clients should report missed heartbeats when the server stops answering.
+#[test]
+fn client_keepalive_miss()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory()
+        .with_middleware(Staller)
+        .new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+    let websocket_url = server.url();
+
+
+    // make client
+    let misses = Arc::new(Mutex::new(Vec::<u32>::new()));
+    let misses_clone = misses.clone();
+    let mut client = client_demo_factory().new_client(
+            client_runtime,
+            websocket_url,
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig{
+                heartbeat_interval: std::time::Duration::from_millis(10),
+                keepalive_miss_threshold: 3,
+                keepalive_miss_callback: Some(Arc::new(move |missed| misses_clone.lock().unwrap().push(missed))),
+                ..Default::default()
+            },
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+
+
+    // healthy connections don't miss heartbeats
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    assert!(misses.lock().unwrap().is_empty());
+
+
+    // stall the server
+    // - the callback is only invoked once per stall
+    let _ = client.send(DemoClientMsg(100));
+    std::thread::sleep(std::time::Duration::from_millis(150));
+
+    let Some((_, DemoServerEvent::Msg(DemoClientMsg(100)))) = server.next() else { unreachable!() };
+    let client_misses = misses.lock().unwrap().clone();
+    assert_eq!(client_misses.len(), 1);
+    assert!(client_misses[0] >= 3);
+
+    // the client is still connected
+    let None = client.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// Servers should report missed heartbeats when a client stops answering.
+#[test]
+fn server_keepalive_miss()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let misses = Arc::new(Mutex::new(Vec::<(u128, u32)>::new()));
+    let misses_clone = misses.clone();
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                heartbeat_interval: std::time::Duration::from_millis(10),
+                keepalive_miss_threshold: 3,
+                keepalive_miss_callback: Some(Arc::new(
+                        move |client_id, missed| misses_clone.lock().unwrap().push((client_id, missed))
+                    )),
+                ..Default::default()
+            }
+        );
+    let websocket_url = server.url();
+
+
+    // make client
+    // - the client stalls while handling server messages
+    let _client = client_demo_factory()
+        .with_event_callback(
+            move |event|
+            {
+                let DemoClientEvent::Msg(DemoServerMsg(stall)) = event else { return; };
+                std::thread::sleep(std::time::Duration::from_millis(stall));
+            }
+        )
+        .new_client(
+            client_runtime,
+            websocket_url,
+            bevy_simplenet::AuthRequest::None{ client_id: 7u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+
+
+    // healthy connections don't miss heartbeats
+    std::thread::sleep(std::time::Duration::from_millis(50));
+    assert!(misses.lock().unwrap().is_empty());
+
+
+    // stall the client
+    // - the callback is only invoked once per stall
+    server.send(client_id, DemoServerMsg(100)).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(150));
+
+    let server_misses = misses.lock().unwrap().clone();
+    assert_eq!(server_misses.len(), 1);
+    assert_eq!(server_misses[0].0, 7u128);
+    assert!(server_misses[0].1 >= 3);
+
+    // the client is still connected
+    let None = server.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,145 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::sync::Arc;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn mailbox()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let time_source = Arc::new(bevy_simplenet::ManualTimeSource::new(Duration::from_secs(100)));
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                mailbox: Some(bevy_simplenet::MailboxConfig{ max_messages: 2, ttl: Duration::from_secs(10) }),
+                time_source: time_source.clone(),
+                ..Default::default()
+            }
+        );
+
+    let websocket_url = server.url();
+
+
+    // send messages to a disconnected client
+    let expired_signal = server.send(0u128, DemoServerMsg(1)).unwrap();
+    time_source.advance(Duration::from_secs(20));
+    let evicted_signal = server.send(0u128, DemoServerMsg(2)).unwrap();
+    let _ = server.send(0u128, DemoServerMsg(3)).unwrap();
+    let delivered_signal = server.send(0u128, DemoServerMsg(4)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    // - the first message expired
+    let Some((0u128, DemoServerEvent::MailboxExpired(message_id))) = server.next() else { unreachable!() };
+    assert_eq!(message_id, expired_signal.id());
+    assert_eq!(
+        expired_signal.outcome(),
+        bevy_simplenet::SendOutcome::Dropped(bevy_simplenet::SendDropReason::MailboxExpired)
+    );
+    // - the second message was evicted from the full mailbox
+    let Some((0u128, DemoServerEvent::MailboxExpired(message_id))) = server.next() else { unreachable!() };
+    assert_eq!(message_id, evicted_signal.id());
+    assert_eq!(
+        evicted_signal.outcome(),
+        bevy_simplenet::SendOutcome::Dropped(bevy_simplenet::SendDropReason::MailboxFull)
+    );
+    assert_eq!(delivered_signal.outcome(), bevy_simplenet::SendOutcome::Queued);
+    let None = server.next() else { unreachable!() };
+
+
+    // make client
+    let mut client = client_demo_factory().new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
+    else { unreachable!() };
+
+
+    // the remaining messages are delivered after connecting
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Msg(DemoServerMsg(3))) = client.next() else { unreachable!() };
+    let Some(DemoClientEvent::Msg(DemoServerMsg(4))) = client.next() else { unreachable!() };
+    assert_eq!(delivered_signal.outcome(), bevy_simplenet::SendOutcome::Sent);
+
+
+    // messages for clients that never reconnect expire on a timer
+    let abandoned_signal = server.send(1u128, DemoServerMsg(5)).unwrap();
+    time_source.advance(Duration::from_secs(20));
+
+    std::thread::sleep(std::time::Duration::from_millis(300));  //wait for the mailbox expiry timer
+
+    let Some((1u128, DemoServerEvent::MailboxExpired(message_id))) = server.next() else { unreachable!() };
+    assert_eq!(message_id, abandoned_signal.id());
+    assert_eq!(
+        abandoned_signal.outcome(),
+        bevy_simplenet::SendOutcome::Dropped(bevy_simplenet::SendDropReason::MailboxExpired)
+    );
+
+
+    // no more events
+    let None = server.next() else { unreachable!() };
+    let None = client.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
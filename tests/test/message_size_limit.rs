@@ -30,6 +30,7 @@ impl bevy_simplenet::ChannelPack for DemoChannel
     type ClientRequest = ();
     type ServerMsg = DemoServerMsg;
     type ServerResponse = ();
+    type RejectReason = ();
 }
 
 type _DemoServer = bevy_simplenet::Server<DemoChannel>;
@@ -100,8 +101,9 @@ fn message_size_limit_test(max_msg_size: u32)
     else { panic!("client should have connected"); };
     let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_))) = websocket_client.next()
     else { panic!("client should be closed by server"); };
-    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(_))) = websocket_client.next()
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(death_reason, _))) = websocket_client.next()
     else { panic!("client should be closed by server"); };
+    assert_eq!(death_reason, bevy_simplenet::DeathReason::ClosedByServer);
     assert_eq!(websocket_server.num_connections(), 0u64);
 
 
@@ -141,8 +143,9 @@ fn message_size_limit_test(max_msg_size: u32)
     else { panic!("client should be disconnected"); };
     let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_))) = websocket_client.next()
     else { panic!("client should be closed by server"); };
-    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(_))) = websocket_client.next()
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(death_reason, _))) = websocket_client.next()
     else { panic!("client should be closed by server"); };
+    assert_eq!(death_reason, bevy_simplenet::DeathReason::ClosedByServer);
     assert_eq!(client_id, dc_client_id);
     assert_eq!(websocket_server.num_connections(), 0u64);
 
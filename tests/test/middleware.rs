@@ -0,0 +1,261 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+/// request from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientRequest(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = DemoClientRequest;
+    type ServerMsg = ();
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+/// Doubles client messages and text messages, and rejects empty text messages.
+#[derive(Debug)]
+struct Doubler;
+
+impl bevy_simplenet::ServerMiddleware<DemoChannel> for Doubler
+{
+    fn on_msg(&self, _: bevy_simplenet::ClientId, msg: DemoClientMsg) -> bevy_simplenet::MiddlewareAction<DemoClientMsg>
+    {
+        bevy_simplenet::MiddlewareAction::Continue(DemoClientMsg(msg.0 * 2))
+    }
+
+    fn on_text(&self, _: bevy_simplenet::ClientId, text: String) -> bevy_simplenet::MiddlewareAction<String>
+    {
+        match text.is_empty()
+        {
+            true  => bevy_simplenet::MiddlewareAction::Reject,
+            false => bevy_simplenet::MiddlewareAction::Continue(text.repeat(2)),
+        }
+    }
+}
+
+/// Rejects odd requests.
+#[derive(Debug)]
+struct OddRejector;
+
+impl bevy_simplenet::ServerMiddleware<DemoChannel> for OddRejector
+{
+    fn on_request(&self, _: bevy_simplenet::ClientId, request: DemoClientRequest) -> bevy_simplenet::MiddlewareAction<DemoClientRequest>
+    {
+        match request.0 % 2
+        {
+            0 => bevy_simplenet::MiddlewareAction::Continue(request),
+            _ => bevy_simplenet::MiddlewareAction::Reject,
+        }
+    }
+}
+
+/// Max request value for a session, stored at auth time.
+struct MaxRequest(u64);
+
+/// Rejects requests that exceed the session's max request value.
+#[derive(Debug)]
+struct MaxRequestChecker;
+
+impl bevy_simplenet::ServerMiddleware<DemoChannel> for MaxRequestChecker
+{
+    fn on_request_with_store(
+        &self,
+        _       : bevy_simplenet::ClientId,
+        store   : &mut bevy_simplenet::SessionStore,
+        request : DemoClientRequest
+    ) -> bevy_simplenet::MiddlewareAction<DemoClientRequest>
+    {
+        let Some(MaxRequest(max)) = store.get::<MaxRequest>()
+        else { return bevy_simplenet::MiddlewareAction::Disconnect; };
+        match request.0 <= *max
+        {
+            true  => bevy_simplenet::MiddlewareAction::Continue(request),
+            false => bevy_simplenet::MiddlewareAction::Reject,
+        }
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn middleware()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory()
+        .with_middleware(Doubler)
+        .with_middleware(OddRejector)
+        .new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                text_messages: true,
+                ..Default::default()
+            }
+        );
+
+    let websocket_url = server.url();
+
+
+    // make client
+    let mut client = client_demo_factory().new_client(
+            client_runtime,
+            websocket_url,
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig{
+                text_messages: true,
+                ..Default::default()
+            },
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+
+
+    // messages are modified by middleware
+    client.send(DemoClientMsg(21));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Msg(DemoClientMsg(42)))) = server.next() else { unreachable!() };
+
+
+    // text messages are modified or rejected by middleware
+    client.send_text("");
+    client.send_text("ab");
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Text(text))) = server.next() else { unreachable!() };
+    assert_eq!(text, "abab");
+    let None = server.next() else { unreachable!() };
+
+
+    // odd requests are rejected by middleware
+    let signal = client.request(DemoClientRequest(1));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Reject(request_id)) = client.next() else { unreachable!() };
+    assert_eq!(request_id, signal.id());
+    let None = server.next() else { unreachable!() };
+
+
+    // even requests pass through
+    let _ = client.request(DemoClientRequest(2));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Request(_, DemoClientRequest(2)))) = server.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn middleware_session_store()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    // - each session's max request value is derived from the client id when the client authenticates
+    let mut server = server_demo_factory()
+        .with_session_init_fn(
+            |auth: &bevy_simplenet::AuthRequest, _: &(), store: &mut bevy_simplenet::SessionStore|
+            {
+                store.insert(MaxRequest(auth.client_id() as u64));
+            }
+        )
+        .with_middleware(MaxRequestChecker)
+        .new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+    let websocket_url = server.url();
+
+
+    // make client
+    let mut client = client_demo_factory().new_client(
+            client_runtime,
+            websocket_url,
+            bevy_simplenet::AuthRequest::None{ client_id: 5u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+
+
+    // requests above the stored max are rejected by middleware
+    let signal = client.request(DemoClientRequest(6));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Reject(request_id)) = client.next() else { unreachable!() };
+    assert_eq!(request_id, signal.id());
+    let None = server.next() else { unreachable!() };
+
+
+    // requests up to the stored max pass through
+    let _ = client.request(DemoClientRequest(5));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Request(_, DemoClientRequest(5)))) = server.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
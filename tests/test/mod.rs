@@ -1,8 +1,66 @@
 //module tree
+mod abort_connect;
+mod account_limit;
 mod authentication;
+mod channel_pack_macro;
+mod client_builder;
+mod client_groups;
+mod client_latest_slots;
+mod client_reconnector;
+mod client_role;
+mod client_stats;
+mod close_reason;
+mod connect_hook;
+mod connect_msg;
+mod connect_progress;
+mod connection_quality;
+mod connection_thresholds;
+mod connection_watch;
 mod connections_limit;
+mod control_msgs;
+mod decode_limits;
+mod disconnect_grace;
+mod event_callback;
+mod event_recording;
+mod fingerprint;
+mod flapping_client;
+mod handler_mode;
+mod handshake_stats;
+mod health_routes;
+mod heartbeat;
 mod hello_world;
+mod id_collision;
+mod keepalive_miss;
+mod latest_slots;
+mod listener_handoff;
+mod mailbox;
 mod message_size_limit;
+mod middleware;
+mod msg_dedup;
+mod pause_accepting;
+#[cfg(feature = "encryption")]
+mod payload_cipher;
+mod pending_policy;
 mod rate_limit;
+mod rate_limit_guard;
+mod reliable_msg;
+mod request_ordering;
+mod request_progress;
 mod request_response;
+mod request_token_store;
+mod request_tracing;
+mod response_streaming;
+mod router_nesting;
+mod scopes;
+mod server_builder;
+mod server_instance;
+mod server_ready;
+mod server_url;
+mod session_epoch;
 mod synchronization;
+mod tenants;
+mod text_messages;
+mod topics;
+mod token_verification;
+mod update_config;
+mod wire_format;
@@ -0,0 +1,129 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+
+    // message 0 is never deduplicated
+    fn dedup_client_msg(msg: &DemoClientMsg) -> bool
+    {
+        msg.0 != 0
+    }
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type _DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// Duplicate client messages should be dropped within the dedup window.
+#[test]
+fn msg_dedup()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                msg_dedup: Some(bevy_simplenet::MsgDedupConfig{
+                    window: std::time::Duration::from_millis(100),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+        );
+
+    // make client
+    let client = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+
+
+    // duplicates are dropped
+    client.send(DemoClientMsg(1));
+    client.send(DemoClientMsg(1));
+    client.send(DemoClientMsg(2));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Msg(DemoClientMsg(1)))) = server.next() else { unreachable!() };
+    let Some((0u128, DemoServerEvent::Msg(DemoClientMsg(2)))) = server.next() else { unreachable!() };
+    let None = server.next() else { unreachable!() };
+    assert_eq!(server.deduplicated_msgs(), 1);
+
+
+    // exempt messages are not dropped
+    client.send(DemoClientMsg(0));
+    client.send(DemoClientMsg(0));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Msg(DemoClientMsg(0)))) = server.next() else { unreachable!() };
+    let Some((0u128, DemoServerEvent::Msg(DemoClientMsg(0)))) = server.next() else { unreachable!() };
+    let None = server.next() else { unreachable!() };
+
+
+    // messages are not dropped after the window
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    client.send(DemoClientMsg(1));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Msg(DemoClientMsg(1)))) = server.next() else { unreachable!() };
+    assert_eq!(server.deduplicated_msgs(), 1);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
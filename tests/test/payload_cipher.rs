@@ -0,0 +1,239 @@
+//local shortcuts
+use bevy_simplenet::PayloadCipher;
+
+//third-party shortcuts
+use bincode::Options;
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::sync::Arc;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+/// Encrypts a client message the way a client would send it to the server.
+fn seal_client_msg(cipher: &dyn PayloadCipher, stream_id: u128, counter: u64, msg: &DemoClientMsg) -> Vec<u8>
+{
+    // the 'message' variant of the server's meta event
+    let mut plaintext = vec![1u8];
+    plaintext.extend(bincode::DefaultOptions::new().serialize(msg).unwrap());
+
+    let frame = bevy_simplenet::CipherFrame{
+            direction : bevy_simplenet::FrameDirection::ClientToServer,
+            stream_id,
+            counter,
+        };
+    let mut bytes = Vec::default();
+    bytes.extend_from_slice(&stream_id.to_le_bytes());
+    bytes.extend_from_slice(&counter.to_le_bytes());
+    bytes.extend(cipher.encrypt(plaintext, &frame).unwrap());
+    bytes
+}
+
+fn server_frame_validator(cipher: Arc<dyn PayloadCipher>) -> bevy_simplenet::ServerFrameValidator<DemoChannel>
+{
+    bevy_simplenet::ServerFrameValidator::new(
+            bevy_simplenet::WireFormat::Bincode,
+            bevy_simplenet::DecodeLimits::default(),
+            Some(cipher),
+            None
+        )
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Frames should only decrypt with the direction, stream id, and counter they were encrypted with.
+#[test]
+fn cipher_frame_binding()
+{
+    let cipher = bevy_simplenet::ChaChaPayloadCipher::new(&[7u8; bevy_simplenet::PAYLOAD_CIPHER_KEY_BYTES]);
+    let frame = bevy_simplenet::CipherFrame{
+            direction : bevy_simplenet::FrameDirection::ClientToServer,
+            stream_id : cipher.new_stream_id(),
+            counter   : 5u64,
+        };
+    assert_ne!(frame.stream_id, cipher.new_stream_id());
+
+    let ciphertext = cipher.encrypt(b"hello".to_vec(), &frame).unwrap();
+    assert_eq!(cipher.decrypt(&ciphertext, &frame).unwrap(), b"hello".to_vec());
+
+    // reflected frames
+    let reflected = bevy_simplenet::CipherFrame{ direction: bevy_simplenet::FrameDirection::ServerToClient, ..frame };
+    assert!(cipher.decrypt(&ciphertext, &reflected).is_err());
+
+    // replayed frames
+    let replayed = bevy_simplenet::CipherFrame{ counter: 6u64, ..frame };
+    assert!(cipher.decrypt(&ciphertext, &replayed).is_err());
+
+    // frames from another stream
+    let other_stream = bevy_simplenet::CipherFrame{ stream_id: frame.stream_id + 1, ..frame };
+    assert!(cipher.decrypt(&ciphertext, &other_stream).is_err());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// Frames received more than once should be rejected.
+#[test]
+fn cipher_repeated_frame()
+{
+    let cipher: Arc<dyn PayloadCipher> =
+        Arc::new(bevy_simplenet::ChaChaPayloadCipher::new(&[5u8; bevy_simplenet::PAYLOAD_CIPHER_KEY_BYTES]));
+    let validator = server_frame_validator(cipher.clone());
+    let stream_id = cipher.new_stream_id();
+
+    let frame = seal_client_msg(&*cipher, stream_id, 0u64, &DemoClientMsg(1));
+    assert_eq!(validator.validate(&frame), Ok(()));
+    assert_eq!(validator.validate(&frame), Err(bevy_simplenet::DecodeError::Replayed));
+
+    // the stream continues after the replay
+    let frame = seal_client_msg(&*cipher, stream_id, 1u64, &DemoClientMsg(2));
+    assert_eq!(validator.validate(&frame), Ok(()));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// Frames received out of order should be rejected.
+#[test]
+fn cipher_out_of_order_frame()
+{
+    let cipher: Arc<dyn PayloadCipher> =
+        Arc::new(bevy_simplenet::ChaChaPayloadCipher::new(&[6u8; bevy_simplenet::PAYLOAD_CIPHER_KEY_BYTES]));
+    let validator = server_frame_validator(cipher.clone());
+    let stream_id = cipher.new_stream_id();
+
+    let frames: Vec<Vec<u8>> = (0..3u64)
+        .map(|counter| seal_client_msg(&*cipher, stream_id, counter, &DemoClientMsg(counter)))
+        .collect();
+    assert_eq!(validator.validate(&frames[0]), Ok(()));
+    assert_eq!(validator.validate(&frames[2]), Ok(()));
+    assert_eq!(validator.validate(&frames[1]), Err(bevy_simplenet::DecodeError::Replayed));
+
+    // frames from other streams are also rejected
+    let other_stream = seal_client_msg(&*cipher, cipher.new_stream_id(), 3u64, &DemoClientMsg(3));
+    assert_eq!(validator.validate(&other_stream), Err(bevy_simplenet::DecodeError::Replayed));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// Encrypted connections should carry messages in both directions across reconnects.
+#[test]
+fn encrypted_connection()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+    let cipher: Arc<dyn PayloadCipher> =
+        Arc::new(bevy_simplenet::ChaChaPayloadCipher::new(&[3u8; bevy_simplenet::PAYLOAD_CIPHER_KEY_BYTES]));
+
+    // launch websocket server
+    let mut websocket_server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                cipher: Some(cipher.clone()),
+                ..Default::default()
+            }
+        );
+
+    // make client
+    let mut websocket_client = client_demo_factory().new_client(
+            client_runtime,
+            websocket_server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig{
+                cipher: Some(cipher),
+                reconnect_interval: std::time::Duration::from_millis(10),
+                ..Default::default()
+            },
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((mut client_id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = websocket_server.next()
+    else { panic!("server should be connected once client is connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = websocket_client.next()
+    else { panic!("client should be connected to server"); };
+
+    for reconnects in 0..2
+    {
+        // send several messages in each direction
+        for i in 0..3u64
+        {
+            websocket_client.send(DemoClientMsg(i));
+            websocket_server.send(client_id, DemoServerMsg(i)).unwrap();
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+        for i in 0..3u64
+        {
+            let Some((_, DemoServerEvent::Msg(DemoClientMsg(msg)))) = websocket_server.next()
+            else { panic!("server did not receive client msg"); };
+            assert_eq!(msg, i);
+            let Some(DemoClientEvent::Msg(DemoServerMsg(msg))) = websocket_client.next()
+            else { panic!("client did not receive server msg"); };
+            assert_eq!(msg, i);
+        }
+        if reconnects == 1 { break; }
+
+        // the server closes the client, which reconnects with a new session
+        let closure_frame =
+            ezsockets::CloseFrame{
+                code   : ezsockets::CloseCode::Normal,
+                reason : String::from("test")
+            };
+        websocket_server.disconnect_client(client_id, Some(closure_frame)).unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+
+        let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_))) = websocket_client.next()
+        else { panic!("client should be closed by the server"); };
+        let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = websocket_client.next()
+        else { panic!("client should reconnect to server"); };
+        let Some((_, DemoServerEvent::Report(DemoServerReport::Disconnected))) = websocket_server.next()
+        else { panic!("server should be disconnected after disconnecting client"); };
+        let Some((id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = websocket_server.next()
+        else { panic!("server should be connected once client reconnects"); };
+        client_id = id;
+    }
+}
+
+//-------------------------------------------------------------------------------------------------------------------
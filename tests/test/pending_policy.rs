@@ -0,0 +1,224 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+/// Raw websocket client that never authenticates, so its session stays pending.
+struct IdleClient
+{
+    closed: Arc<AtomicBool>,
+}
+
+#[async_trait::async_trait]
+impl ezsockets::ClientExt for IdleClient
+{
+    type Call = ();
+
+    async fn on_text(&mut self, _text: String) -> Result<(), ezsockets::Error> { Ok(()) }
+    async fn on_binary(&mut self, _bytes: Vec<u8>) -> Result<(), ezsockets::Error> { Ok(()) }
+    async fn on_call(&mut self, _call: ()) -> Result<(), ezsockets::Error> { Ok(()) }
+
+    async fn on_close(
+        &mut self,
+        _close_frame: Option<ezsockets::CloseFrame>
+    ) -> Result<ezsockets::client::ClientCloseMode, ezsockets::Error>
+    {
+        self.closed.store(true, Ordering::Release);
+        Ok(ezsockets::client::ClientCloseMode::Close)
+    }
+}
+
+/// Connects an idle client to the server. Returns a flag that is set when the server closes the client.
+fn connect_idle_client(
+    runtime_handle : enfync::builtin::Handle,
+    url            : url::Url,
+) -> (ezsockets::Client<IdleClient>, Arc<AtomicBool>)
+{
+    let closed = Arc::new(AtomicBool::new(false));
+    let closed_clone = closed.clone();
+    let client_config = ezsockets::ClientConfig::new(url)
+        .query_parameter("v", "test")
+        .query_parameter("t", bevy_simplenet::env_type_as_str(bevy_simplenet::EnvType::Native))
+        .query_parameter("f", "b");
+    let (client, _) = ezsockets::connect_with(
+            move |_| IdleClient{ closed: closed_clone },
+            client_config,
+            ezsockets::ClientConnectorTokio::from(runtime_handle),
+        );
+
+    (client, closed)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// New connections should evict the oldest pending session when the pending queue is full.
+#[test]
+fn pending_evict_oldest()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                max_pending             : 1u32,
+                pending_overflow_policy : bevy_simplenet::PendingOverflowPolicy::EvictOldest,
+                ..Default::default()
+            }
+        );
+
+    let websocket_url = server.url();
+
+
+    // idle client fills the pending queue
+    let (_idle_client, idle_closed) = connect_idle_client(client_runtime.clone(), websocket_url.clone());
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    assert!(!idle_closed.load(Ordering::Acquire));
+
+
+    // real client evicts the idle client
+    let mut client = client_demo_factory().new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    assert!(idle_closed.load(Ordering::Acquire));
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    assert_eq!(client_id, 0u128);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// Overflow sessions should only be able to authenticate with tokens.
+#[test]
+fn pending_prioritize_tokens()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                max_pending             : 1u32,
+                pending_overflow_policy : bevy_simplenet::PendingOverflowPolicy::PrioritizeTokens,
+                ..Default::default()
+            }
+        );
+
+    let websocket_url = server.url();
+    let make_client = |auth: bevy_simplenet::AuthRequest| -> DemoClient
+    {
+        client_demo_factory().new_client(
+                client_runtime.clone(),
+                websocket_url.clone(),
+                auth,
+                bevy_simplenet::ClientConfig{
+                    reconnect_on_server_close: false,
+                    ..Default::default()
+                },
+                ()
+            )
+    };
+
+
+    // idle client fills the pending queue
+    let (_idle_client, idle_closed) = connect_idle_client(client_runtime.clone(), websocket_url.clone());
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+
+    // overflow client without a token is closed
+    let mut none_client = make_client(bevy_simplenet::AuthRequest::None{ client_id: 0u128 });
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    assert!(none_client.is_dead());
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = none_client.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(Some(close_frame)))) =
+        none_client.next()
+    else { unreachable!() };
+    assert_eq!(close_frame.reason, "server busy");
+    let None = server.next() else { unreachable!() };
+
+
+    // overflow client with a token connects
+    let (token_privkey, _) = bevy_simplenet::generate_auth_token_keys();
+    let token = bevy_simplenet::make_auth_token_from_lifetime(&token_privkey, 10, 1u128);
+    let mut token_client = make_client(bevy_simplenet::AuthRequest::Token{ token });
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = token_client.next()
+    else { unreachable!() };
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    assert_eq!(client_id, 1u128);
+    assert!(!idle_closed.load(Ordering::Acquire));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
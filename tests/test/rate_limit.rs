@@ -30,10 +30,11 @@ impl bevy_simplenet::ChannelPack for DemoChannel
     type ClientRequest = ();
     type ServerMsg = DemoServerMsg;
     type ServerResponse = ();
+    type RejectReason = ();
 }
 
-type _DemoServer = bevy_simplenet::Server<DemoChannel>;
-type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
 type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
 type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
 type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
@@ -51,12 +52,62 @@ fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
 //-------------------------------------------------------------------------------------------------------------------
 //-------------------------------------------------------------------------------------------------------------------
 
+fn rate_limit_policy_setup(policy: bevy_simplenet::RateLimitPolicy)
+    -> (DemoServer, DemoClient, bevy_simplenet::ManualTimeSource, std::time::Duration)
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // prepare a manual clock so rate limit periods don't depend on how long the async machinery takes
+    let rate_limit_period = std::time::Duration::from_millis(15);
+    let time_source = bevy_simplenet::ManualTimeSource::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                rate_limit_config : bevy_simplenet::RateLimitConfig{ period: rate_limit_period, max_count: 2 },
+                rate_limit_policy : policy,
+                time_source       : std::sync::Arc::new(time_source.clone()),
+                ..Default::default()
+            }
+        );
+
+    // make client
+    let mut client = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            DemoConnectMsg(String::from("hello!"))
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+
+    (server, client, time_source, rate_limit_period)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 fn rate_limit_test(max_count_per_period: u32)
 {
     // prepare tokio runtimes for server and client
     let server_runtime = enfync::builtin::native::TokioHandle::default();
     let client_runtime = enfync::builtin::Handle::default();
 
+    // prepare a manual clock so rate limit periods don't depend on how long the async machinery takes
+    let rate_limit_period = std::time::Duration::from_millis(15);
+    let time_source = bevy_simplenet::ManualTimeSource::default();
+
     // launch websocket server
     let mut websocket_server = server_demo_factory().new_server(
             server_runtime,
@@ -65,9 +116,10 @@ fn rate_limit_test(max_count_per_period: u32)
             bevy_simplenet::Authenticator::None,
             bevy_simplenet::ServerConfig{
                 rate_limit_config : bevy_simplenet::RateLimitConfig{
-                    period    : std::time::Duration::from_millis(15),  //15ms to coordinate with async waits
+                    period    : rate_limit_period,
                     max_count : max_count_per_period
                 },
+                time_source : std::sync::Arc::new(time_source.clone()),
                 ..Default::default()
             }
         );
@@ -113,6 +165,7 @@ fn rate_limit_test(max_count_per_period: u32)
 
 
     // send messages to fill up server rate limiter to the brim
+    time_source.advance(rate_limit_period);
     let mut signals = Vec::new();
     for _ in 0..max_count_per_period
     {
@@ -141,6 +194,7 @@ fn rate_limit_test(max_count_per_period: u32)
 
 
     // send messages to fill up server rate limiter past the brim
+    time_source.advance(rate_limit_period);
     let mut signals = Vec::new();
     for _ in 0..(max_count_per_period + 1)
     {
@@ -171,8 +225,9 @@ fn rate_limit_test(max_count_per_period: u32)
     else { panic!("client should be disconnected"); };
     let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_))) = websocket_client.next()
     else { panic!("client should be closed by server"); };
-    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(_))) = websocket_client.next()
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(death_reason, _))) = websocket_client.next()
     else { panic!("client should be closed by server"); };
+    assert_eq!(death_reason, bevy_simplenet::DeathReason::ClosedByServer);
     assert_eq!(client_id, dc_client_id);
 
 
@@ -203,3 +258,61 @@ fn rate_limiter()
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+// Violations should be reported and the violating messages dropped without closing the session.
+#[test]
+fn rate_limit_report_and_drop()
+{
+    let (mut server, client, time_source, rate_limit_period) =
+        rate_limit_policy_setup(bevy_simplenet::RateLimitPolicy::ReportAndDrop);
+
+    // exceed the rate limit
+    // - only the first violation in the period is reported
+    time_source.advance(rate_limit_period);
+    for i in 0..4 { client.send(DemoClientMsg(i)); }
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Msg(DemoClientMsg(0)))) = server.next() else { unreachable!() };
+    let Some((0u128, DemoServerEvent::Msg(DemoClientMsg(1)))) = server.next() else { unreachable!() };
+    let Some((0u128, DemoServerEvent::RateLimitViolation(violation))) = server.next() else { unreachable!() };
+    assert_eq!(violation.count, 3);
+    assert_eq!(violation.config.max_count, 2);
+    let None = server.next() else { unreachable!() };
+
+    // the client can keep sending in the next period
+    time_source.advance(rate_limit_period);
+    client.send(DemoClientMsg(4));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Msg(DemoClientMsg(4)))) = server.next() else { unreachable!() };
+    let None = server.next() else { unreachable!() };
+    assert!(client.is_connected());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// Violations should be reported before the session is closed.
+#[test]
+fn rate_limit_report_and_disconnect()
+{
+    let (mut server, client, time_source, rate_limit_period) =
+        rate_limit_policy_setup(bevy_simplenet::RateLimitPolicy::ReportAndDisconnect);
+
+    // exceed the rate limit
+    time_source.advance(rate_limit_period);
+    for i in 0..3 { client.send(DemoClientMsg(i)); }
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Msg(DemoClientMsg(0)))) = server.next() else { unreachable!() };
+    let Some((0u128, DemoServerEvent::Msg(DemoClientMsg(1)))) = server.next() else { unreachable!() };
+    let Some((0u128, DemoServerEvent::RateLimitViolation(violation))) = server.next() else { unreachable!() };
+    assert_eq!(violation.count, 3);
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { unreachable!() };
+    assert!(client.is_dead());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,167 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerResponse(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientRequest(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = ();
+    type ClientRequest = DemoClientRequest;
+    type ServerMsg = ();
+    type ServerResponse = DemoServerResponse;
+    type RejectReason = ();
+}
+
+type DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+fn connect_demo(config: bevy_simplenet::ServerConfig) -> (DemoServer, DemoClient)
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut websocket_server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            config,
+        );
+
+    // make client
+    let mut websocket_client = client_demo_factory().new_client(
+            client_runtime,
+            websocket_server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = websocket_server.next()
+    else { panic!("server should be connected once client is connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = websocket_client.next()
+    else { panic!("client should be connected to server"); };
+
+    (websocket_server, websocket_client)
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn requests_in_send_order()
+{
+    let (mut websocket_server, websocket_client) = connect_demo(bevy_simplenet::ServerConfig::default());
+
+    // send several requests: client -> server
+    let signals: Vec<_> = (0..10).map(|val| websocket_client.request(DemoClientRequest(val))).collect();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    // all requests are emitted in send order
+    for (val, signal) in signals.iter().enumerate()
+    {
+        let Some((0u128, DemoServerEvent::Request(token, DemoClientRequest(msg_val)))) = websocket_server.next()
+        else { panic!("server did not receive client request"); };
+        assert_eq!(val as u64, msg_val);
+        assert_eq!(signal.id(), token.request_id());
+        websocket_server.ack(token).unwrap();
+    }
+
+    let None = websocket_server.next() else { panic!("server should receive no more requests"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn serialized_requests()
+{
+    let (mut websocket_server, mut websocket_client) = connect_demo(
+            bevy_simplenet::ServerConfig{ serialize_requests: true, ..Default::default() }
+        );
+
+    // send several requests: client -> server
+    let signal1 = websocket_client.request(DemoClientRequest(1));
+    let signal2 = websocket_client.request(DemoClientRequest(2));
+    let signal3 = websocket_client.request(DemoClientRequest(3));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    // only the first request is emitted
+    let Some((0u128, DemoServerEvent::Request(token1, DemoClientRequest(1)))) = websocket_server.next()
+    else { panic!("server did not receive first client request"); };
+    assert_eq!(signal1.id(), token1.request_id());
+    let None = websocket_server.next() else { panic!("server should wait for the first token to be consumed"); };
+
+    // responding to the first request emits the second
+    websocket_server.respond(token1, DemoServerResponse(1)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Request(token2, DemoClientRequest(2)))) = websocket_server.next()
+    else { panic!("server did not receive second client request"); };
+    assert_eq!(signal2.id(), token2.request_id());
+    let None = websocket_server.next() else { panic!("server should wait for the second token to be consumed"); };
+
+    // dropping the second token emits the third
+    drop(token2);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Request(token3, DemoClientRequest(3)))) = websocket_server.next()
+    else { panic!("server did not receive third client request"); };
+    assert_eq!(signal3.id(), token3.request_id());
+    websocket_server.ack(token3).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    // the client received a reply for each request
+    let Some(DemoClientEvent::Response(DemoServerResponse(1), request_id)) = websocket_client.next()
+    else { panic!("client did not receive response"); };
+    assert_eq!(signal1.id(), request_id);
+    let Some(DemoClientEvent::Reject(request_id)) = websocket_client.next()
+    else { panic!("client did not receive rejection"); };
+    assert_eq!(signal2.id(), request_id);
+    let Some(DemoClientEvent::Ack(request_id)) = websocket_client.next()
+    else { panic!("client did not receive ack"); };
+    assert_eq!(signal3.id(), request_id);
+
+    let None = websocket_server.next() else { panic!("server should receive no more requests"); };
+    let None = websocket_client.next() else { panic!("client should receive no more values"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
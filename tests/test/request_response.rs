@@ -17,6 +17,13 @@ pub struct DemoServerResponse(pub u64);
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct DemoClientRequest(pub u64);
 
+/// reason for rejecting a request
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub enum DemoRejectReason
+{
+    Busy,
+}
+
 #[derive(Debug, Clone)]
 pub struct DemoChannel;
 impl bevy_simplenet::ChannelPack for DemoChannel
@@ -26,6 +33,7 @@ impl bevy_simplenet::ChannelPack for DemoChannel
     type ClientRequest = DemoClientRequest;
     type ServerMsg = ();
     type ServerResponse = DemoServerResponse;
+    type RejectReason = DemoRejectReason;
 }
 
 type _DemoServer = bevy_simplenet::Server<DemoChannel>;
@@ -112,11 +120,15 @@ fn request_response()
     assert_eq!(client_val, msg_client_val);
     assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Waiting);
     assert!(!token.destination_is_dead());
+    assert_eq!(
+        websocket_client.pending_requests(),
+        vec![(signal.id(), bevy_simplenet::RequestStatus::Waiting)]
+    );
 
 
     // send response: server -> client
     let server_val = 24;
-    websocket_server.respond(token, DemoServerResponse(server_val));
+    websocket_server.respond(token, DemoServerResponse(server_val)).unwrap();
 
     std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
 
@@ -125,6 +137,7 @@ fn request_response()
     assert_eq!(server_val, msg_server_val);
     assert_eq!(signal.id(), request_id);
     assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Responded);
+    assert!(websocket_client.pending_requests().is_empty());
 
 
     // no more outputs
@@ -203,8 +216,12 @@ fn request_ack()
     assert!(!token.destination_is_dead());
 
 
+    // wait for the request to complete in an async task
+    let completion = enfync::Handle::spawn(&client_runtime, signal.completed());
+
+
     // send ack: server -> client
-    websocket_server.ack(token);
+    websocket_server.ack(token).unwrap();
 
     std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
 
@@ -212,6 +229,74 @@ fn request_ack()
     else { panic!("client did not receive server msg"); };
     assert_eq!(signal.id(), request_id);
     assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Acknowledged);
+    assert_eq!(
+        enfync::blocking::extract(completion).unwrap(),
+        bevy_simplenet::RequestStatusFinal::Acknowledged
+    );
+
+
+    // no more outputs
+    let None = websocket_server.next()
+    else { panic!("server should receive no more connection reports"); };
+    let None = websocket_client.next()
+    else { panic!("client should receive no more values"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn request_ack_with()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut websocket_server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default(),
+        );
+
+
+    // make client
+    let mut websocket_client = client_demo_factory().new_client(
+            client_runtime,
+            websocket_server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 44718u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = websocket_server.next()
+    else { panic!("server should be connected once client is connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = websocket_client.next()
+    else { panic!("client should be connected to server"); };
+
+
+    // send request: client -> server
+    let signal = websocket_client.request(DemoClientRequest(42));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Request(token, DemoClientRequest(42)))) = websocket_server.next()
+    else { panic!("server did not receive client request"); };
+
+
+    // send ack with payload: server -> client
+    websocket_server.ack_with(token, 7u64.to_le_bytes()).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::AckWith(payload, request_id)) = websocket_client.next()
+    else { panic!("client did not receive server ack"); };
+    assert_eq!(signal.id(), request_id);
+    assert_eq!(payload, 7u64.to_le_bytes().to_vec());
+    assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Acknowledged);
 
 
     // no more outputs
@@ -310,6 +395,187 @@ fn request_rejected()
 
 //-------------------------------------------------------------------------------------------------------------------
 
+#[test]
+fn request_rejected_with()
+{
+    // prepare tracing
+    /*
+    let subscriber = tracing_subscriber::FmtSubscriber::builder()
+        .with_max_level(tracing::Level::TRACE)
+        .finish();
+    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+    */
+
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut websocket_server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default(),
+        );
+
+    let websocket_url = websocket_server.url();
+    assert_eq!(websocket_server.num_connections(), 0u64);
+
+
+    // make client
+    let mut websocket_client = client_demo_factory().new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 44718u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+    assert!(!websocket_client.is_dead());
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = websocket_server.next()
+    else { panic!("server should be connected once client is connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = websocket_client.next()
+    else { panic!("client should be connected to server"); };
+    assert_eq!(websocket_server.num_connections(), 1u64);
+
+
+    // send request: client -> server
+    let client_val = 42;
+    let signal = websocket_client.request(DemoClientRequest(client_val));
+    assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Sending);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((
+            msg_client_id,
+            DemoServerEvent::Request(token, DemoClientRequest(msg_client_val))
+        )) = websocket_server.next()
+    else { panic!("server did not receive client msg"); };
+    assert_eq!(client_id, msg_client_id);
+    assert_eq!(client_id, token.client_id());
+    assert_eq!(signal.id(), token.request_id());
+    assert_eq!(client_val, msg_client_val);
+    assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Waiting);
+    assert!(!token.destination_is_dead());
+
+
+    // reject with reason
+    websocket_server.reject_with(token, DemoRejectReason::Busy).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::RejectWith(reason, request_id)) = websocket_client.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(signal.id(), request_id);
+    assert_eq!(reason, DemoRejectReason::Busy);
+    assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Rejected);
+
+
+    // no more outputs
+    let None = websocket_server.next()
+    else { panic!("server should receive no more connection reports"); };
+    let None = websocket_client.next()
+    else { panic!("client should receive no more values"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn request_batch()
+{
+    // prepare tracing
+    /*
+    let subscriber = tracing_subscriber::FmtSubscriber::builder()
+        .with_max_level(tracing::Level::TRACE)
+        .finish();
+    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+    */
+
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut websocket_server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default(),
+        );
+
+    let websocket_url = websocket_server.url();
+    assert_eq!(websocket_server.num_connections(), 0u64);
+
+
+    // make client
+    let mut websocket_client = client_demo_factory().new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 44718u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+    assert!(!websocket_client.is_dead());
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = websocket_server.next()
+    else { panic!("server should be connected once client is connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = websocket_client.next()
+    else { panic!("client should be connected to server"); };
+    assert_eq!(websocket_server.num_connections(), 1u64);
+
+
+    // send request batch: client -> server
+    let signal = websocket_client.request_batch(vec![DemoClientRequest(1), DemoClientRequest(2)]);
+    assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Sending);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((
+            msg_client_id,
+            DemoServerEvent::RequestBatch(token, requests)
+        )) = websocket_server.next()
+    else { panic!("server did not receive client request batch"); };
+    assert_eq!(client_id, msg_client_id);
+    assert_eq!(client_id, token.client_id());
+    assert_eq!(signal.id(), token.request_id());
+    assert_eq!(requests.iter().map(|DemoClientRequest(val)| *val).collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Waiting);
+    assert!(!token.destination_is_dead());
+    assert_eq!(
+        websocket_client.pending_requests(),
+        vec![(signal.id(), bevy_simplenet::RequestStatus::Waiting)]
+    );
+
+
+    // send one response for the whole batch: server -> client
+    let server_val = 24;
+    websocket_server.respond(token, DemoServerResponse(server_val)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Response(DemoServerResponse(msg_server_val), request_id)) = websocket_client.next()
+    else { panic!("client did not receive server msg"); };
+    assert_eq!(server_val, msg_server_val);
+    assert_eq!(signal.id(), request_id);
+    assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Responded);
+    assert!(websocket_client.pending_requests().is_empty());
+
+
+    // no more outputs
+    let None = websocket_server.next()
+    else { panic!("server should receive no more connection reports"); };
+    let None = websocket_client.next()
+    else { panic!("client should receive no more values"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
 #[test]
 fn request_dropped()
 {
@@ -388,7 +654,7 @@ fn request_dropped()
             code   : ezsockets::CloseCode::Normal,
             reason : String::from("test")
         };
-    websocket_server.disconnect_client(client_id, Some(closure_frame));
+    websocket_server.disconnect_client(client_id, Some(closure_frame)).unwrap();
 
     std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
 
@@ -422,7 +688,7 @@ fn request_dropped()
 
 
     // try to acknowledge the token (nothing should happen since the original target session was replaced)
-    websocket_server.ack(token);
+    websocket_server.ack(token).unwrap();
 
     std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
     assert_eq!(signal.status(), bevy_simplenet::RequestStatus::ResponseLost);
@@ -436,3 +702,82 @@ fn request_dropped()
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn request_responder()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut websocket_server = server_demo_factory().new_server(
+            server_runtime.clone(),
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default(),
+        );
+
+    let websocket_url = websocket_server.url();
+
+
+    // make client
+    let mut websocket_client = client_demo_factory().new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 44718u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = websocket_server.next()
+    else { panic!("server should be connected once client is connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = websocket_client.next()
+    else { panic!("client should be connected to server"); };
+
+
+    // send request: client -> server
+    let signal = websocket_client.request(DemoClientRequest(42));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Request(token, DemoClientRequest(42)))) = websocket_server.next()
+    else { panic!("server did not receive client request"); };
+
+
+    // respond from an async task: server -> client
+    let responder = websocket_server.responder(token);
+    assert_eq!(signal.id(), responder.request_id());
+    assert!(!responder.destination_is_dead());
+    let task = enfync::Handle::spawn(&server_runtime, async move { responder.respond(DemoServerResponse(24)) });
+    enfync::blocking::extract(task).unwrap().unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Response(DemoServerResponse(24), request_id)) = websocket_client.next()
+    else { panic!("client did not receive server response"); };
+    assert_eq!(signal.id(), request_id);
+    assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Responded);
+
+
+    // dropping a responder rejects the request
+    let signal = websocket_client.request(DemoClientRequest(43));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Request(token, DemoClientRequest(43)))) = websocket_server.next()
+    else { panic!("server did not receive client request"); };
+    std::mem::drop(websocket_server.responder(token));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Reject(request_id)) = websocket_client.next()
+    else { panic!("client did not receive rejection"); };
+    assert_eq!(signal.id(), request_id);
+    assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Rejected);
+}
+
+//-------------------------------------------------------------------------------------------------------------------
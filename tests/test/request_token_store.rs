@@ -0,0 +1,132 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::sync::Arc;
+use std::time::Duration;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerResponse(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientRequest(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = ();
+    type ClientRequest = DemoClientRequest;
+    type ServerMsg = ();
+    type ServerResponse = DemoServerResponse;
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Stored tokens can be answered later, and are rejected when they expire.
+#[test]
+fn request_token_store()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+    let websocket_url = server.url();
+
+
+    // make client
+    let mut client = client_demo_factory().new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+
+
+    // store two requests
+    let time_source = Arc::new(bevy_simplenet::ManualTimeSource::new(Duration::from_secs(100)));
+    let mut store = bevy_simplenet::RequestTokenStore::new(Duration::from_secs(10))
+        .with_time_source(time_source.clone());
+
+    let answered_signal = client.request(DemoClientRequest(1));
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+    let expired_signal = client.request(DemoClientRequest(2));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Request(token, DemoClientRequest(1)))) = server.next() else { unreachable!() };
+    store.insert(token);
+    time_source.advance(Duration::from_secs(5));
+    let Some((_, DemoServerEvent::Request(token, DemoClientRequest(2)))) = server.next() else { unreachable!() };
+    store.insert(token);
+    assert_eq!(store.len(), 2);
+
+
+    // respond to the first request later
+    let Some(token) = store.take(client_id, answered_signal.id()) else { unreachable!() };
+    server.respond(token, DemoServerResponse(1)).unwrap();
+    assert!(!store.contains(client_id, answered_signal.id()));
+
+
+    // the second request expires
+    time_source.advance(Duration::from_secs(10));
+    store.prune();
+    assert!(store.is_empty());
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Response(DemoServerResponse(1), _)) = client.next() else { unreachable!() };
+    let Some(DemoClientEvent::Reject(_)) = client.next() else { unreachable!() };
+    assert_eq!(answered_signal.status(), bevy_simplenet::RequestStatus::Responded);
+    assert_eq!(expired_signal.status(), bevy_simplenet::RequestStatus::Rejected);
+
+
+    // no more events
+    let None = server.next() else { unreachable!() };
+    let None = client.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,127 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// response from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerResponse(pub u64);
+
+/// request from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientRequest(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = ();
+    type ClientRequest = DemoClientRequest;
+    type ServerMsg = ();
+    type ServerResponse = DemoServerResponse;
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Trace ids attached to requests should be visible on the server and preserved through responses and rejections.
+#[test]
+fn request_tracing()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+    let websocket_url = server.url();
+
+
+    // make client
+    let mut client = client_demo_factory().new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+
+
+    // send requests with and without trace ids
+    let traced_signal = client.request_traced(DemoClientRequest(1), "trace-a");
+    let untraced_signal = client.request(DemoClientRequest(2));
+    let rejected_signal = client.request_traced(DemoClientRequest(3), "trace-b");
+    assert_eq!(traced_signal.trace_id(), Some("trace-a"));
+    assert_eq!(untraced_signal.trace_id(), None);
+    assert_eq!(rejected_signal.trace_id(), Some("trace-b"));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Request(token, DemoClientRequest(1)))) = server.next() else { unreachable!() };
+    assert_eq!(token.trace_id(), Some("trace-a"));
+    let responder = server.responder(token);
+    assert_eq!(responder.trace_id(), Some("trace-a"));
+    responder.respond(DemoServerResponse(1)).unwrap();
+
+    let Some((_, DemoServerEvent::Request(token, DemoClientRequest(2)))) = server.next() else { unreachable!() };
+    assert_eq!(token.trace_id(), None);
+    server.ack(token).unwrap();
+
+    let Some((_, DemoServerEvent::Request(token, DemoClientRequest(3)))) = server.next() else { unreachable!() };
+    assert_eq!(token.trace_id(), Some("trace-b"));
+    server.reject(token);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+
+    // the client receives results for all requests
+    let Some(DemoClientEvent::Response(DemoServerResponse(1), request_id)) = client.next() else { unreachable!() };
+    assert_eq!(request_id, traced_signal.id());
+    assert_eq!(traced_signal.status(), bevy_simplenet::RequestStatus::Responded);
+    let Some(DemoClientEvent::Ack(request_id)) = client.next() else { unreachable!() };
+    assert_eq!(request_id, untraced_signal.id());
+    let Some(DemoClientEvent::Reject(request_id)) = client.next() else { unreachable!() };
+    assert_eq!(request_id, rejected_signal.id());
+    assert_eq!(rejected_signal.status(), bevy_simplenet::RequestStatus::Rejected);
+    let None = client.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,121 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// response from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerResponse(pub u64);
+
+/// request from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientRequest(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = ();
+    type ClientRequest = DemoClientRequest;
+    type ServerMsg = ();
+    type ServerResponse = DemoServerResponse;
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Partial responses should reach the client in order, followed by the final response.
+#[test]
+fn response_streaming()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+
+    // make client
+    let mut client = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+
+
+    // send request
+    let signal = client.request(DemoClientRequest(1));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Request(token, DemoClientRequest(1)))) = server.next() else { unreachable!() };
+
+
+    // stream partial responses
+    server.respond_partial(&token, DemoServerResponse(10)).unwrap();
+    server.respond_partial(&token, DemoServerResponse(11)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::ResponsePart(DemoServerResponse(10), request_id)) = client.next()
+    else { unreachable!() };
+    assert_eq!(request_id, signal.id());
+    let Some(DemoClientEvent::ResponsePart(DemoServerResponse(11), _)) = client.next() else { unreachable!() };
+    assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Waiting);
+
+
+    // responders can also stream partial responses, followed by the final response
+    let responder = server.responder(token);
+    responder.respond_partial(DemoServerResponse(12)).unwrap();
+    responder.respond(DemoServerResponse(13)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::ResponsePart(DemoServerResponse(12), _)) = client.next() else { unreachable!() };
+    let Some(DemoClientEvent::Response(DemoServerResponse(13), request_id)) = client.next() else { unreachable!() };
+    assert_eq!(request_id, signal.id());
+    assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Responded);
+    let None = client.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
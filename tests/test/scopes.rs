@@ -0,0 +1,183 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// scope for sending admin messages
+const ADMIN_MSG: bevy_simplenet::Scopes = bevy_simplenet::Scopes::bit(0);
+/// scope for sending admin requests
+const ADMIN_REQUEST: bevy_simplenet::Scopes = bevy_simplenet::Scopes::bit(1);
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum DemoClientMsg
+{
+    Chat(u64),
+    Admin(u64),
+}
+
+/// request from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum DemoClientRequest
+{
+    Chat(u64),
+    Admin(u64),
+}
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = DemoClientRequest;
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+
+    fn client_msg_scopes(msg: &DemoClientMsg) -> bevy_simplenet::Scopes
+    {
+        match msg
+        {
+            DemoClientMsg::Chat(_)  => bevy_simplenet::Scopes::NONE,
+            DemoClientMsg::Admin(_) => ADMIN_MSG,
+        }
+    }
+
+    fn client_request_scopes(request: &DemoClientRequest) -> bevy_simplenet::Scopes
+    {
+        match request
+        {
+            DemoClientRequest::Chat(_)  => bevy_simplenet::Scopes::NONE,
+            DemoClientRequest::Admin(_) => ADMIN_REQUEST,
+        }
+    }
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    // client 1 is an admin
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test").with_scopes_fn(
+            |auth: &bevy_simplenet::AuthRequest, _: &()|
+            {
+                match auth.client_id()
+                {
+                    1u128 => ADMIN_MSG | ADMIN_REQUEST,
+                    _     => bevy_simplenet::Scopes::NONE,
+                }
+            }
+        )
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Messages and requests should be rejected if the client lacks the required scopes.
+#[test]
+fn scopes_enforced()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+
+    // make clients
+    let mut admin_client: DemoClient = client_demo_factory().new_client(
+            client_runtime.clone(),
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 1u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+    let mut user_client: DemoClient = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 2u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = admin_client.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = user_client.next()
+    else { unreachable!() };
+
+
+    // unscoped messages are allowed for all clients
+    user_client.send(DemoClientMsg::Chat(1));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((2u128, DemoServerEvent::Msg(DemoClientMsg::Chat(1)))) = server.next() else { unreachable!() };
+
+
+    // scoped messages and requests are rejected for clients without the scope
+    user_client.send(DemoClientMsg::Admin(2));
+    let signal = user_client.request(DemoClientRequest::Admin(3));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let None = server.next() else { unreachable!() };
+    let Some(DemoClientEvent::Unauthorized(None, missing)) = user_client.next() else { unreachable!() };
+    assert_eq!(missing, ADMIN_MSG);
+    let Some(DemoClientEvent::Unauthorized(Some(request_id), missing)) = user_client.next() else { unreachable!() };
+    assert_eq!(request_id, signal.id());
+    assert_eq!(missing, ADMIN_REQUEST);
+    assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Rejected);
+    let None = user_client.next() else { unreachable!() };
+
+
+    // scoped messages and requests are allowed for clients with the scope
+    admin_client.send(DemoClientMsg::Admin(4));
+    let signal = admin_client.request(DemoClientRequest::Admin(5));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((1u128, DemoServerEvent::Msg(DemoClientMsg::Admin(4)))) = server.next() else { unreachable!() };
+    let Some((1u128, DemoServerEvent::Request(token, DemoClientRequest::Admin(5)))) = server.next()
+    else { unreachable!() };
+    server.ack(token).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Ack(request_id)) = admin_client.next() else { unreachable!() };
+    assert_eq!(request_id, signal.id());
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,147 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Servers made with builders should accept connections.
+#[test]
+fn server_builder()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // servers need an address
+    let Err(bevy_simplenet::ServerBuildError::MissingAddress) = server_demo_factory().builder().build()
+    else { unreachable!() };
+
+    // servers need a supported wire format
+    #[cfg(not(feature = "msgpack"))]
+    {
+        let Err(bevy_simplenet::ServerBuildError::UnsupportedWireFormat(_)) = server_demo_factory()
+            .builder()
+            .address("127.0.0.1:0")
+            .config(bevy_simplenet::ServerConfig{
+                wire_format: bevy_simplenet::WireFormat::MsgPack,
+                ..Default::default()
+            })
+            .build()
+        else { unreachable!() };
+    }
+
+    // servers can't encrypt text messages
+    #[cfg(feature = "encryption")]
+    {
+        let key = [0u8; bevy_simplenet::PAYLOAD_CIPHER_KEY_BYTES];
+        let Err(bevy_simplenet::ServerBuildError::TextWithCipher) = server_demo_factory()
+            .builder()
+            .address("127.0.0.1:0")
+            .config(bevy_simplenet::ServerConfig{
+                text_messages: true,
+                cipher: Some(std::sync::Arc::new(bevy_simplenet::ChaChaPayloadCipher::new(&key))),
+                ..Default::default()
+            })
+            .build()
+        else { unreachable!() };
+    }
+
+    // simulated delays can reorder encrypted frames
+    #[cfg(all(feature = "netsim", feature = "encryption"))]
+    {
+        let key = [0u8; bevy_simplenet::PAYLOAD_CIPHER_KEY_BYTES];
+        let Err(bevy_simplenet::ServerBuildError::NetsimWithCipher) = server_demo_factory()
+            .builder()
+            .address("127.0.0.1:0")
+            .config(bevy_simplenet::ServerConfig{
+                netsim: Some(bevy_simplenet::NetsimConfig::default()),
+                cipher: Some(std::sync::Arc::new(bevy_simplenet::ChaChaPayloadCipher::new(&key))),
+                ..Default::default()
+            })
+            .build()
+        else { unreachable!() };
+    }
+
+    // launch websocket server
+    let mut server = server_demo_factory()
+        .builder()
+        .runtime(server_runtime)
+        .address("127.0.0.1:0")
+        .authenticator(bevy_simplenet::Authenticator::None)
+        .rate_limit(bevy_simplenet::RateLimitConfig{
+            period    : std::time::Duration::from_millis(100),
+            max_count : 10u32,
+        })
+        .build()
+        .unwrap();
+
+    // make client
+    let mut client: DemoClient = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    assert_eq!(client_id, 0u128);
+
+    // the client can send messages
+    let _ = client.send(DemoClientMsg(1));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Msg(DemoClientMsg(1)))) = server.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
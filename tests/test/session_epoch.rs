@@ -0,0 +1,113 @@
+//local shortcuts
+
+//third-party shortcuts
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = u64;
+    type ClientRequest = ();
+    type ServerMsg = ();
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn session_epochs()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default(),
+        );
+
+
+    // connect a client
+    let client = client_demo_factory().new_client(
+            client_runtime.clone(),
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, epoch1, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next_with_epoch()
+    else { panic!("server should be connected once client is connected"); };
+    assert_ne!(epoch1, 0u64);
+    assert!(server.is_current(0u128, epoch1));
+
+
+    // messages are tagged with the client's session epoch
+    client.send(1u64);
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, msg_epoch, DemoServerEvent::Msg(1u64))) = server.next_with_epoch()
+    else { panic!("server should receive client message"); };
+    assert_eq!(msg_epoch, epoch1);
+
+
+    // the epoch ends when the disconnect is consumed
+    client.close();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, dc_epoch, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next_with_epoch()
+    else { panic!("server should be disconnected once client is closed"); };
+    assert_eq!(dc_epoch, epoch1);
+    assert!(!server.is_current(0u128, epoch1));
+
+
+    // reconnecting starts a new epoch
+    let _client = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, epoch2, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next_with_epoch()
+    else { panic!("server should be connected once client is connected"); };
+    assert_ne!(epoch1, epoch2);
+    assert!(!server.is_current(0u128, epoch1));
+    assert!(server.is_current(0u128, epoch2));
+    assert!(!server.is_current(1u128, epoch2));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
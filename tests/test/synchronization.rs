@@ -26,6 +26,7 @@ impl bevy_simplenet::ChannelPack for DemoChannel
     type ClientRequest = ();
     type ServerMsg = DemoServerMsg;
     type ServerResponse = ();
+    type RejectReason = ();
 }
 
 type _DemoServer = bevy_simplenet::Server<DemoChannel>;
@@ -195,7 +196,7 @@ fn client_send_sync_request()
 
     let Some((_, DemoServerEvent::Request(token, ()))) = server.next() else { unreachable!() };
     assert_eq!(signal.status(), bevy_simplenet::RequestStatus::Waiting);
-    server.ack(token);
+    server.ack(token).unwrap();
 
     std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
 
@@ -257,21 +258,31 @@ fn server_send_sync_msg_single()
 
     // sending a server message before the server report is consumed should fail
     let server_val = 24;
-    server.send(client.id(), DemoServerMsg(server_val));
+    let Err(bevy_simplenet::ServerError::UnknownClient) = server.send(client.id(), DemoServerMsg(server_val))
+    else { unreachable!() };
 
     std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
 
     let None = client.next() else { unreachable!() };
 
+    // the connection event was produced but not consumed yet
+    let sync_state = server.sync_state();
+    assert_eq!(sync_state.unconsumed_connection_events(), 1);
+    assert_eq!(sync_state.registered_clients, 1);
+    assert_eq!(sync_state.connected_clients, 0);
+
 
     // consume server connected report
     let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
     else { unreachable!() };
+    let sync_state = server.sync_state();
+    assert_eq!(sync_state.unconsumed_connection_events(), 0);
+    assert_eq!(sync_state.connected_clients, 1);
 
 
     // sending a message after the server report is consumed should succeed
     let server_val = 42;
-    server.send(client.id(), DemoServerMsg(server_val));
+    server.send(client.id(), DemoServerMsg(server_val)).unwrap();
 
     std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
 
@@ -349,7 +360,8 @@ fn server_send_sync_msg_multiple()
 
     // sending a server message before the server report is consumed should fail
     let server_val = 24;
-    server.send(client2.id(), DemoServerMsg(server_val));
+    let Err(bevy_simplenet::ServerError::UnknownClient) = server.send(client2.id(), DemoServerMsg(server_val))
+    else { unreachable!() };
 
     std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
 
@@ -365,7 +377,8 @@ fn server_send_sync_msg_multiple()
 
     // sending a server message before the server report for client 2 is consumed should still fail
     let server_val = 24;
-    server.send(client2.id(), DemoServerMsg(server_val));
+    let Err(bevy_simplenet::ServerError::UnknownClient) = server.send(client2.id(), DemoServerMsg(server_val))
+    else { unreachable!() };
 
     std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
 
@@ -381,7 +394,7 @@ fn server_send_sync_msg_multiple()
 
     // sending a message after the server report is consumed should succeed
     let server_val = 42;
-    server.send(client2.id(), DemoServerMsg(server_val));
+    server.send(client2.id(), DemoServerMsg(server_val)).unwrap();
 
     std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
 
@@ -396,3 +409,62 @@ fn server_send_sync_msg_multiple()
 }
 
 //-------------------------------------------------------------------------------------------------------------------
+
+// Unordered server message sends should not synchronize with processing of connection events
+#[test]
+fn server_send_unordered_msg()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+    let websocket_url = server.url();
+
+
+    // make client
+    let mut client = client_demo_factory().new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+
+    // sending an unordered message before the server report is consumed should succeed
+    let server_val = 24;
+    let Err(bevy_simplenet::ServerError::UnknownClient) = server.send(client.id(), DemoServerMsg(0))
+    else { unreachable!() };
+    let unordered_signal = server.send_unordered(client.id(), DemoServerMsg(server_val)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Msg(DemoServerMsg(msg_server_val))) = client.next() else { unreachable!() };
+    assert_eq!(server_val, msg_server_val);
+    assert_eq!(unordered_signal.outcome(), bevy_simplenet::SendOutcome::Sent);
+
+
+    // consume server connected report
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(_, ())))) = server.next()
+    else { unreachable!() };
+
+
+    // no more events
+    let None = server.next() else { unreachable!() };
+    let None = client.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,162 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+use std::sync::Arc;
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Clients should be validated against the server's tenant registry and limited by their tenant's config.
+#[test]
+fn tenants()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let tenants = bevy_simplenet::TenantRegistry::default()
+        .with_tenant("a", bevy_simplenet::TenantConfig{ max_connections: 1u32, ..Default::default() })
+        .with_tenant("b", bevy_simplenet::TenantConfig::default());
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                tenants: Some(Arc::new(tenants)),
+                ..Default::default()
+            }
+        );
+
+    let websocket_url = server.url();
+    let make_client = |client_id: u128, tenant: Option<&str>| -> DemoClient
+    {
+        client_demo_factory().new_client(
+                client_runtime.clone(),
+                websocket_url.clone(),
+                bevy_simplenet::AuthRequest::None{ client_id },
+                bevy_simplenet::ClientConfig{
+                    tenant: tenant.map(String::from),
+                    ..Default::default()
+                },
+                ()
+            )
+    };
+
+
+    // client with a registered tenant connects
+    let mut client_a1 = make_client(0u128, Some("a"));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client_a1.next()
+    else { unreachable!() };
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Connected(meta, ())))) = server.next()
+    else { unreachable!() };
+    assert_eq!(meta.tenant.as_deref(), Some("a"));
+
+
+    // client for a tenant at capacity is closed
+    let mut client_a2 = make_client(1u128, Some("a"));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    assert!(client_a2.is_dead());
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client_a2.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_))) = client_a2.next()
+    else { unreachable!() };
+    let None = server.next() else { unreachable!() };
+
+
+    // clients with missing or unknown tenants are closed
+    for tenant in [None, Some("c")]
+    {
+        let mut client = make_client(2u128, tenant);
+
+        std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+        assert!(client.is_dead());
+        let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+        else { unreachable!() };
+        let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::ClosedByServer(_))) = client.next()
+        else { unreachable!() };
+        let None = server.next() else { unreachable!() };
+    }
+
+
+    // tenants are limited independently
+    let mut client_b = make_client(3u128, Some("b"));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client_b.next()
+    else { unreachable!() };
+    let Some((3u128, DemoServerEvent::Report(DemoServerReport::Connected(meta, ())))) = server.next()
+    else { unreachable!() };
+    assert_eq!(meta.tenant.as_deref(), Some("b"));
+
+
+    // tenant capacity is released when clients disconnect
+    client_a1.close();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { unreachable!() };
+
+    let mut client_a3 = make_client(4u128, Some("a"));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client_a3.next()
+    else { unreachable!() };
+    let Some((4u128, DemoServerEvent::Report(DemoServerReport::Connected(meta, ())))) = server.next()
+    else { unreachable!() };
+    assert_eq!(meta.tenant.as_deref(), Some("a"));
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,127 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type _DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+fn token_verification_client(url: url::Url, token: bevy_simplenet::AuthToken) -> DemoClient
+{
+    client_demo_factory().new_client(
+            enfync::builtin::Handle::default(),
+            url,
+            bevy_simplenet::AuthRequest::Token{ token },
+            bevy_simplenet::ClientConfig{
+                max_initial_connect_attempts: 1usize,
+                ..Default::default()
+            },
+            ()
+        )
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Clients should be able to reconnect with cached tokens, and tokens with forged payloads should be rejected.
+#[test]
+fn token_verification()
+{
+    // prepare tokio runtime for server
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+
+    // prep token
+    let (token_privkey, token_pubkey) = bevy_simplenet::generate_auth_token_keys();
+    let token = bevy_simplenet::make_auth_token_from_lifetime(&token_privkey, 10, 0u128);
+
+    // launch websocket server that verifies tokens on the blocking thread pool
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::Token{ pubkey: token_pubkey },
+            bevy_simplenet::ServerConfig{
+                token_verification: bevy_simplenet::TokenVerificationConfig{
+                    offload: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        );
+
+
+    // connect client (the token is verified and cached)
+    let client = token_verification_client(server.url(), token);
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+
+    assert!(client.is_connected());
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+
+    client.close();
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Disconnected))) = server.next()
+    else { unreachable!() };
+
+
+    // reconnect with the same token (the token is found in the cache)
+    let client = token_verification_client(server.url(), token);
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+
+    assert!(client.is_connected());
+    let Some((0u128, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+
+
+    // a token with a cached signature but a different client id is rejected
+    let mut forged_token = token;
+    forged_token.client_id = 1u128;
+    let forged_client = token_verification_client(server.url(), forged_token);
+    std::thread::sleep(std::time::Duration::from_millis(50));  //wait for async machinery
+
+    assert!(forged_client.is_dead());
+    let None = server.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
@@ -0,0 +1,207 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = ();
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+// Clients should receive messages published to topics they subscribed to.
+#[test]
+fn topics_publish()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig::default()
+        );
+
+
+    // make clients
+    let mut client1: DemoClient = client_demo_factory().new_client(
+            client_runtime.clone(),
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 1u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+    let mut client2: DemoClient = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 2u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client1.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client2.next()
+    else { unreachable!() };
+
+
+    // clients subscribe to different topics
+    let signal1 = client1.subscribe("news");
+    let signal2 = client2.subscribe("weather");
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Ack(request_id)) = client1.next() else { unreachable!() };
+    assert_eq!(request_id, signal1.id());
+    assert_eq!(signal1.status(), bevy_simplenet::RequestStatus::Acknowledged);
+    let Some(DemoClientEvent::Ack(request_id)) = client2.next() else { unreachable!() };
+    assert_eq!(request_id, signal2.id());
+
+
+    // server publishes to each topic
+    server.publish("news", DemoServerMsg(1)).unwrap();
+    server.publish("weather", DemoServerMsg(2)).unwrap();
+    server.publish("sports", DemoServerMsg(3)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::TopicMsg(topic, DemoServerMsg(1))) = client1.next() else { unreachable!() };
+    assert_eq!(topic, "news");
+    let None = client1.next() else { unreachable!() };
+    let Some(DemoClientEvent::TopicMsg(topic, DemoServerMsg(2))) = client2.next() else { unreachable!() };
+    assert_eq!(topic, "weather");
+    let None = client2.next() else { unreachable!() };
+
+
+    // client unsubscribes
+    client1.unsubscribe("news");
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    server.publish("news", DemoServerMsg(4)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let None = client1.next() else { unreachable!() };
+    let None = client2.next() else { unreachable!() };
+    let None = server.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
+
+// Subscriptions denied by the server's topic permission callback should be rejected.
+#[test]
+fn topics_permission()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    // - only client 1 may subscribe to private topics
+    let mut server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                topic_permission: Some(std::sync::Arc::new(
+                    |client_id: u128, topic: &str| client_id == 1u128 || !topic.starts_with("private")
+                )),
+                ..Default::default()
+            }
+        );
+
+
+    // make client
+    let mut client: DemoClient = client_demo_factory().new_client(
+            client_runtime,
+            server.url(),
+            bevy_simplenet::AuthRequest::None{ client_id: 2u128 },
+            bevy_simplenet::ClientConfig::default(),
+            ()
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((_, DemoServerEvent::Report(DemoServerReport::Connected(..)))) = server.next()
+    else { unreachable!() };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = client.next()
+    else { unreachable!() };
+
+
+    // the client may subscribe to public topics but not private topics
+    let public_signal = client.subscribe("public");
+    let private_signal = client.subscribe("private");
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Ack(request_id)) = client.next() else { unreachable!() };
+    assert_eq!(request_id, public_signal.id());
+    let Some(DemoClientEvent::Reject(request_id)) = client.next() else { unreachable!() };
+    assert_eq!(request_id, private_signal.id());
+    assert_eq!(private_signal.status(), bevy_simplenet::RequestStatus::Rejected);
+
+
+    // the client only receives messages published to its topics
+    server.publish("private", DemoServerMsg(1)).unwrap();
+    server.publish("public", DemoServerMsg(2)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::TopicMsg(topic, DemoServerMsg(2))) = client.next() else { unreachable!() };
+    assert_eq!(topic, "public");
+    let None = client.next() else { unreachable!() };
+}
+
+//-------------------------------------------------------------------------------------------------------------------
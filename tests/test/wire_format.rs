@@ -0,0 +1,144 @@
+//local shortcuts
+
+//third-party shortcuts
+use serde::{Serialize, Deserialize};
+
+//standard shortcuts
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+/// message from server
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoServerMsg(pub u64);
+
+/// message from client
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoClientMsg(pub u64);
+
+/// client connect message
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DemoConnectMsg(pub String);
+
+#[derive(Debug, Clone)]
+pub struct DemoChannel;
+impl bevy_simplenet::ChannelPack for DemoChannel
+{
+    type ConnectMsg = DemoConnectMsg;
+    type ClientMsg = DemoClientMsg;
+    type ClientRequest = ();
+    type ServerMsg = DemoServerMsg;
+    type ServerResponse = ();
+    type RejectReason = ();
+}
+
+type _DemoServer = bevy_simplenet::Server<DemoChannel>;
+type _DemoClient = bevy_simplenet::Client<DemoChannel>;
+type DemoClientEvent = bevy_simplenet::ClientEventFrom<DemoChannel>;
+type DemoServerEvent = bevy_simplenet::ServerEventFrom<DemoChannel>;
+type DemoServerReport = bevy_simplenet::ServerReport<<DemoChannel as bevy_simplenet::ChannelPack>::ConnectMsg>;
+
+fn server_demo_factory() -> bevy_simplenet::ServerFactory<DemoChannel>
+{
+    bevy_simplenet::ServerFactory::<DemoChannel>::new("test")
+}
+
+fn client_demo_factory() -> bevy_simplenet::ClientFactory<DemoChannel>
+{
+    bevy_simplenet::ClientFactory::<DemoChannel>::new("test")
+}
+
+
+//-------------------------------------------------------------------------------------------------------------------
+//-------------------------------------------------------------------------------------------------------------------
+
+#[test]
+fn wire_format()
+{
+    // prepare tokio runtimes for server and client
+    let server_runtime = enfync::builtin::native::TokioHandle::default();
+    let client_runtime = enfync::builtin::Handle::default();
+
+    // launch websocket server
+    let mut websocket_server = server_demo_factory().new_server(
+            server_runtime,
+            "127.0.0.1:0",
+            bevy_simplenet::AcceptorConfig::Default,
+            bevy_simplenet::Authenticator::None,
+            bevy_simplenet::ServerConfig{
+                wire_format: bevy_simplenet::WireFormat::Json,
+                ..Default::default()
+            }
+        );
+    let websocket_url = websocket_server.url();
+
+
+    // the default wire format doesn't depend on enabled features
+    assert_eq!(bevy_simplenet::ClientConfig::default().wire_format, bevy_simplenet::WireFormat::Bincode);
+    assert_eq!(bevy_simplenet::ServerConfig::default().wire_format, bevy_simplenet::WireFormat::Bincode);
+
+
+    // client with a different wire format should fail to connect
+    let mut websocket_client = client_demo_factory().new_client(
+            client_runtime.clone(),
+            websocket_url.clone(),
+            bevy_simplenet::AuthRequest::None{ client_id: 0u128 },
+            bevy_simplenet::ClientConfig{
+                max_initial_connect_attempts: 1usize,
+                ..Default::default()
+            },
+            DemoConnectMsg(String::from("hello"))
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    assert!(websocket_client.is_dead());
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::IsDead(death_reason, _))) = websocket_client.next()
+    else { panic!("client should have failed to connect"); };
+    assert_eq!(death_reason, bevy_simplenet::DeathReason::ConnectRejected);
+    let None = websocket_server.next()
+    else { panic!("server should not connect to the client"); };
+
+
+    // client with the same wire format should connect
+    let mut websocket_client = client_demo_factory().new_client(
+            client_runtime,
+            websocket_url,
+            bevy_simplenet::AuthRequest::None{ client_id: 1u128 },
+            bevy_simplenet::ClientConfig{
+                wire_format: bevy_simplenet::WireFormat::Json,
+                ..Default::default()
+            },
+            DemoConnectMsg(String::from("hello"))
+        );
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((client_id, DemoServerEvent::Report(DemoServerReport::Connected(_, connect_msg)))) = websocket_server.next()
+    else { panic!("server should be connected once client is connected"); };
+    let Some(DemoClientEvent::Report(bevy_simplenet::ClientReport::Connected)) = websocket_client.next()
+    else { panic!("client should be connected to server"); };
+    assert_eq!(connect_msg.0, "hello");
+
+
+    // send message: client -> server
+    websocket_client.send(DemoClientMsg(42));
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some((msg_client_id, DemoServerEvent::Msg(DemoClientMsg(42)))) = websocket_server.next()
+    else { panic!("server did not receive client msg"); };
+    assert_eq!(client_id, msg_client_id);
+
+
+    // send message: server -> client
+    websocket_server.send(client_id, DemoServerMsg(24)).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(25));  //wait for async machinery
+
+    let Some(DemoClientEvent::Msg(DemoServerMsg(24))) = websocket_client.next()
+    else { panic!("client did not receive server msg"); };
+}
+
+//-------------------------------------------------------------------------------------------------------------------